@@ -0,0 +1,79 @@
+// watch 모드 테스트용 로컬 웹훅 서버
+//
+// 사용법: cargo run --example webhook_stub -- [포트] [처음 실패시킬 요청 수]
+// WEBHOOK_SECRET 환경 변수가 있으면 X-Webhook-Signature 헤더를 검증합니다.
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::env;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let port = args.get(1).map(|p| p.as_str()).unwrap_or("8080");
+    let mut fail_remaining: u32 = args.get(2).and_then(|n| n.parse().ok()).unwrap_or(0);
+    let secret = env::var("WEBHOOK_SECRET").ok();
+
+    let listener = TcpListener::bind(format!("127.0.0.1:{}", port)).expect("포트 바인딩 실패");
+    println!("웹훅 스텁 서버 대기 중: http://127.0.0.1:{}", port);
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        let mut reader = BufReader::new(stream.try_clone().expect("스트림 복제 실패"));
+
+        // 요청 줄과 헤더 읽기
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).ok();
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+            }
+        }
+
+        let length: usize = headers.get("content-length").and_then(|l| l.parse().ok()).unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).ok();
+        let body = String::from_utf8_lossy(&body);
+
+        println!("\n{}", request_line.trim());
+        for name in ["x-webhook-id", "x-webhook-timestamp", "x-webhook-signature"] {
+            println!("  {}: {}", name, headers.get(name).map(|v| v.as_str()).unwrap_or("-"));
+        }
+        println!("  본문: {}", body);
+
+        if let (Some(secret), Some(timestamp)) = (&secret, headers.get("x-webhook-timestamp")) {
+            let expected = sign(secret, timestamp, &body);
+            let valid = headers.get("x-webhook-signature") == Some(&expected);
+            println!("  서명 검증: {}", if valid { "성공" } else { "실패" });
+        }
+
+        let status = if fail_remaining > 0 {
+            fail_remaining -= 1;
+            "503 Service Unavailable"
+        } else {
+            "200 OK"
+        };
+        println!("  응답: {}", status);
+
+        let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+        stream.write_all(response.as_bytes()).ok();
+    }
+}
+
+fn sign(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC 키 길이 제한 없음");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    let hex: String = mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
@@ -0,0 +1,179 @@
+//! 교체 가능한 RPC 백엔드
+//!
+//! 전송과 벤치마크 로직은 구체적인 클라이언트 대신 [`RpcBackend`] 트레이트를 통해 RPC를 호출합니다.
+//! 실제 네트워크에는 비동기(`nonblocking`) `RpcClient` 구현을, 오프라인 테스트에는
+//! [`crate::fake_rpc::FakeRpc`]를 사용합니다.
+//!
+//! 모든 메서드는 `Send` 퓨처를 반환하므로 `tokio::spawn`으로 여러 요청을 동시에 실행할 수 있습니다.
+
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::Result as ClientResult,
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcBlockConfig, RpcProgramAccountsConfig},
+    rpc_filter::RpcFilterType,
+    rpc_response::{RpcPrioritizationFee, RpcResult, RpcSimulateTransactionResult, RpcVersionInfo},
+};
+use solana_sdk::{
+    account::Account,
+    clock::Slot,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::Transaction,
+};
+use solana_transaction_status::{TransactionDetails, TransactionStatus, UiConfirmedBlock};
+use std::future::Future;
+
+/// 전송/벤치마크에 필요한 RPC 메서드 모음
+pub trait RpcBackend: Send + Sync {
+    /// 계정 잔액 조회 (lamports)
+    fn get_balance(&self, pubkey: &Pubkey) -> impl Future<Output = ClientResult<u64>> + Send;
+
+    /// 최근 블록해시 조회
+    fn get_latest_blockhash(&self) -> impl Future<Output = ClientResult<Hash>> + Send;
+
+    /// 주어진 커미트먼트의 최근 블록해시와 그 블록해시로 서명한 트랜잭션이 처리될 수 있는 마지막 블록 높이
+    fn get_latest_blockhash_with_commitment(
+        &self,
+        commitment: CommitmentConfig,
+    ) -> impl Future<Output = ClientResult<(Hash, u64)>> + Send;
+
+    /// 블록해시가 아직 유효한지 확인 (만료되면 그 블록해시로 서명한 트랜잭션은 더 이상 처리될 수 없음)
+    fn is_blockhash_valid(&self, blockhash: &Hash) -> impl Future<Output = ClientResult<bool>> + Send;
+
+    /// 서명된 트랜잭션 전송 (확인을 기다리지 않음)
+    fn send_transaction(&self, transaction: &Transaction) -> impl Future<Output = ClientResult<Signature>> + Send;
+
+    /// 트랜잭션 시뮬레이션
+    fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> impl Future<Output = RpcResult<RpcSimulateTransactionResult>> + Send;
+
+    /// 서명 상태 조회 (알 수 없는 서명은 `None`)
+    fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> impl Future<Output = RpcResult<Vec<Option<TransactionStatus>>>> + Send;
+
+    /// 최근 상태 캐시에서 밀려난 트랜잭션까지 원장 기록에서 찾는 서명 상태 조회 (`searchTransactionHistory`)
+    ///
+    /// 캐시 조회보다 느리므로 확인 대기 중의 반복 조회가 아니라, 이미 처리되었는지 판단해야 할 때 사용합니다.
+    fn get_signature_statuses_with_history(
+        &self,
+        signatures: &[Signature],
+    ) -> impl Future<Output = RpcResult<Vec<Option<TransactionStatus>>>> + Send;
+
+    /// 주어진 커미트먼트의 현재 슬롯 조회
+    fn get_slot(&self, commitment: CommitmentConfig) -> impl Future<Output = ClientResult<Slot>> + Send;
+
+    /// 주어진 커미트먼트의 현재 블록 높이 조회
+    fn get_block_height(&self, commitment: CommitmentConfig) -> impl Future<Output = ClientResult<u64>> + Send;
+
+    /// 노드 버전 조회
+    fn get_version(&self) -> impl Future<Output = ClientResult<RpcVersionInfo>> + Send;
+
+    /// 계정 조회 (없는 계정은 `None`)
+    fn get_account(&self, pubkey: &Pubkey) -> impl Future<Output = ClientResult<Option<Account>>> + Send;
+
+    /// 여러 계정을 한 번에 조회 (요청 순서대로, 없는 계정은 `None`)
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> impl Future<Output = ClientResult<Vec<Option<Account>>>> + Send;
+
+    /// 프로그램이 소유한 계정 중 필터를 모두 만족하는 계정 조회
+    fn get_program_accounts(
+        &self,
+        program: &Pubkey,
+        filters: &[RpcFilterType],
+    ) -> impl Future<Output = ClientResult<Vec<(Pubkey, Account)>>> + Send;
+
+    /// 블록 조회 (트랜잭션과 보상 정보 제외)
+    fn get_block(&self, slot: Slot) -> impl Future<Output = ClientResult<UiConfirmedBlock>> + Send;
+
+    /// 최근 슬롯들의 우선순위 수수료 (주소를 지정하면 그 계정에 쓰기 잠금을 건 트랜잭션 기준)
+    fn get_recent_prioritization_fees(
+        &self,
+        addresses: &[Pubkey],
+    ) -> impl Future<Output = ClientResult<Vec<RpcPrioritizationFee>>> + Send;
+}
+
+impl RpcBackend for RpcClient {
+    async fn get_balance(&self, pubkey: &Pubkey) -> ClientResult<u64> {
+        RpcClient::get_balance(self, pubkey).await
+    }
+
+    async fn get_latest_blockhash(&self) -> ClientResult<Hash> {
+        RpcClient::get_latest_blockhash(self).await
+    }
+
+    async fn get_latest_blockhash_with_commitment(&self, commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        RpcClient::get_latest_blockhash_with_commitment(self, commitment).await
+    }
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash) -> ClientResult<bool> {
+        RpcClient::is_blockhash_valid(self, blockhash, CommitmentConfig::processed()).await
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> ClientResult<Signature> {
+        RpcClient::send_transaction(self, transaction).await
+    }
+
+    async fn simulate_transaction(&self, transaction: &Transaction) -> RpcResult<RpcSimulateTransactionResult> {
+        RpcClient::simulate_transaction(self, transaction).await
+    }
+
+    async fn get_signature_statuses(&self, signatures: &[Signature]) -> RpcResult<Vec<Option<TransactionStatus>>> {
+        RpcClient::get_signature_statuses(self, signatures).await
+    }
+
+    async fn get_signature_statuses_with_history(&self, signatures: &[Signature]) -> RpcResult<Vec<Option<TransactionStatus>>> {
+        RpcClient::get_signature_statuses_with_history(self, signatures).await
+    }
+
+    async fn get_slot(&self, commitment: CommitmentConfig) -> ClientResult<Slot> {
+        RpcClient::get_slot_with_commitment(self, commitment).await
+    }
+
+    async fn get_block_height(&self, commitment: CommitmentConfig) -> ClientResult<u64> {
+        RpcClient::get_block_height_with_commitment(self, commitment).await
+    }
+
+    async fn get_version(&self) -> ClientResult<RpcVersionInfo> {
+        RpcClient::get_version(self).await
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> ClientResult<Option<Account>> {
+        Ok(RpcClient::get_account_with_commitment(self, pubkey, self.commitment()).await?.value)
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        RpcClient::get_multiple_accounts(self, pubkeys).await
+    }
+
+    async fn get_program_accounts(&self, program: &Pubkey, filters: &[RpcFilterType]) -> ClientResult<Vec<(Pubkey, Account)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters.to_vec()),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        RpcClient::get_program_accounts_with_config(self, program, config).await
+    }
+
+    async fn get_block(&self, slot: Slot) -> ClientResult<UiConfirmedBlock> {
+        let config = RpcBlockConfig {
+            transaction_details: Some(TransactionDetails::None),
+            rewards: Some(false),
+            max_supported_transaction_version: Some(0),
+            ..RpcBlockConfig::default()
+        };
+        RpcClient::get_block_with_config(self, slot, config).await
+    }
+
+    async fn get_recent_prioritization_fees(&self, addresses: &[Pubkey]) -> ClientResult<Vec<RpcPrioritizationFee>> {
+        RpcClient::get_recent_prioritization_fees(self, addresses).await
+    }
+}
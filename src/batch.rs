@@ -0,0 +1,623 @@
+//! 대량 SOL 지급 (`batch` 하위 명령)
+//!
+//! CSV 목록의 지급을 정해진 동시 실행 수만큼 병렬로 전송합니다. 각 지급은 전송 전에 서명과
+//! 블록해시를 저널에 기록하므로, Ctrl-C로 중단되거나 프로세스가 죽더라도 다시 실행하면
+//! 저널을 보고 이미 처리된 지급을 건너뛰고 결과를 알 수 없는 지급은 서명 상태로 확인합니다.
+//! 블록해시가 만료되어 처리될 수 없음이 확인된 경우에만 다시 전송하므로 중복 지급이 생기지 않습니다.
+
+use crate::backend::RpcBackend;
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::output::{OutputFormat, TransferRecord};
+use crate::transfer::{self, ConfirmOptions};
+use crate::{rpc, runtime};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    native_token::{lamports_to_sol, sol_to_lamports},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    system_instruction,
+};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::{watch, Semaphore};
+use tokio::task::JoinSet;
+
+/// 사용법 안내 (연결 없이 출력할 수 있도록 공개)
+pub const USAGE: &str = "사용법:
+  batch <지급 목록.csv> [옵션]
+CSV 형식: 수신 주소,SOL 양[,ID]  (첫 줄 헤더와 # 주석 줄은 무시, ID가 없으면 줄 번호 사용)
+옵션:
+  --concurrency <N>         동시에 진행할 전송 수 (기본값: 4)
+  --fee <값>                우선순위 수수료 (lamports/compute unit 또는 recent[:백분위], 기본값: 프로필 설정)
+  --journal <파일>          진행 상태 저널 (기본값: <목록 파일>.journal.jsonl)
+  --retry-failed            이전 실행에서 실패한 지급도 다시 전송
+  --output <형식>           결과 형식: table, json, csv (기본값: table)
+Ctrl-C를 한 번 누르면 새 전송을 멈추고 진행 중인 전송을 기다리며, 두 번 누르면 즉시 중단합니다.";
+
+/// 기본 동시 실행 수
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// 지급 하나
+#[derive(Debug, Clone)]
+pub struct Payout {
+    /// 저널에서 지급을 식별하는 키 (CSV의 ID 열 또는 줄 번호)
+    pub key: String,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+}
+
+/// 저널에 기록되는 지급 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayoutStatus {
+    /// 서명 후 전송됨 (또는 전송 직전) - 결과는 서명으로 확인해야 함
+    Signed,
+    /// confirmed 커미트먼트로 확인됨
+    Confirmed,
+    /// 처리되지 않았거나 온체인에서 실패함
+    Failed,
+    /// 블록해시가 만료되어 처리될 수 없음 (다시 전송 가능)
+    Expired,
+}
+
+impl PayoutStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PayoutStatus::Signed => "signed",
+            PayoutStatus::Confirmed => "confirmed",
+            PayoutStatus::Failed => "failed",
+            PayoutStatus::Expired => "expired",
+        }
+    }
+}
+
+/// 저널 항목 (같은 키의 마지막 항목이 현재 상태)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub key: String,
+    pub recipient: String,
+    pub lamports: u64,
+    pub status: PayoutStatus,
+    pub signature: Option<String>,
+    pub blockhash: Option<String>,
+    pub error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 추가 전용 JSONL 저널
+pub struct Journal {
+    path: PathBuf,
+    file: Mutex<File>,
+    entries: Mutex<HashMap<String, JournalEntry>>,
+}
+
+impl Journal {
+    /// 저널 파일을 열고 기존 항목을 불러옴 (없으면 새로 생성)
+    pub fn open(path: &Path) -> Result<Journal> {
+        let mut entries = HashMap::new();
+        if path.exists() {
+            for line in fs::read_to_string(path)?.lines().filter(|l| !l.trim().is_empty()) {
+                let entry: JournalEntry = serde_json::from_str(line)
+                    .map_err(|e| Error::Input(format!("저널 형식 오류 ({}): {}", path.display(), e)))?;
+                entries.insert(entry.key.clone(), entry);
+            }
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Journal {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+            entries: Mutex::new(entries),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 키의 현재 상태
+    pub fn get(&self, key: &str) -> Option<JournalEntry> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    /// 모든 키의 현재 상태
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries.lock().unwrap().values().cloned().collect()
+    }
+
+    /// 항목을 기록하고 디스크에 동기화
+    pub fn append(&self, entry: JournalEntry) -> Result<()> {
+        let line = serde_json::to_string(&entry).map_err(|e| Error::Io(e.into()))?;
+        {
+            let mut file = self.file.lock().unwrap();
+            writeln!(file, "{}", line).and_then(|_| file.sync_data())?;
+        }
+        self.entries.lock().unwrap().insert(entry.key.clone(), entry);
+        Ok(())
+    }
+}
+
+/// 배치 실행 설정
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    pub concurrency: usize,
+    pub fee: u64,
+    pub retry_failed: bool,
+    pub confirm: ConfirmOptions,
+    /// 진행 메시지 출력 위치 (표 형식이 아니면 표준 오류)
+    pub output: OutputFormat,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        BatchOptions {
+            concurrency: DEFAULT_CONCURRENCY,
+            fee: transfer::DEFAULT_PRIORITY_FEE,
+            retry_failed: false,
+            confirm: ConfirmOptions::default(),
+            output: OutputFormat::Table,
+        }
+    }
+}
+
+/// 중단 요청 단계
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interrupt {
+    Running,
+    /// 새 전송을 시작하지 않고 진행 중인 전송은 끝까지 기다림
+    Drain,
+    /// 진행 중인 전송도 즉시 중단 (저널에는 서명까지 기록된 상태로 남음)
+    Abort,
+}
+
+/// 배치 실행 결과
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BatchSummary {
+    /// 이번 실행에서 확인된 지급
+    pub confirmed: usize,
+    /// 이전 실행에서 이미 확인되어 건너뛴 지급
+    pub skipped: usize,
+    pub failed: usize,
+    /// 결과를 알 수 없는 지급 (다시 실행하면 서명으로 확인)
+    pub unresolved: usize,
+    /// 중단되어 시작하지 않은 지급
+    pub not_started: usize,
+}
+
+// 지급 하나의 처리 결과
+enum Outcome {
+    Confirmed,
+    Skipped,
+    Failed,
+    Unresolved,
+}
+
+// 이전에 서명된 트랜잭션의 상태 확인 결과
+enum Resolution {
+    Confirmed,
+    Failed(String),
+    Expired,
+    Pending,
+}
+
+/// batch 하위 명령 실행
+pub fn run(rpc_url: &str, sender: Keypair, fee: u64, args: &Args) -> Result<()> {
+    let csv_path = match args.positional(1) {
+        Some(path) if path != "help" => path,
+        _ => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+    };
+
+    let payouts = parse_payouts(&fs::read_to_string(csv_path)?)?;
+    let options = BatchOptions {
+        concurrency: args.parse_or("concurrency", DEFAULT_CONCURRENCY)?.max(1),
+        fee,
+        retry_failed: args.has("retry-failed"),
+        output: OutputFormat::from_args(args)?,
+        ..BatchOptions::default()
+    };
+    let journal_path = match args.value("journal") {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(format!("{}.journal.jsonl", csv_path)),
+    };
+    let journal = Arc::new(Journal::open(&journal_path)?);
+
+    let output = options.output;
+
+    let total: u64 = payouts.iter().map(|p| p.lamports).sum();
+    output.progress(format_args!(
+        "지급 {}건, 총 {} SOL (동시 실행 {}개, 저널: {})",
+        payouts.len(),
+        lamports_to_sol(total),
+        options.concurrency,
+        journal.path().display()
+    ));
+
+    let backend = Arc::new(rpc::async_client(rpc_url));
+    let (summary, records) = runtime::block_on(async {
+        let (interrupt_tx, interrupt_rx) = watch::channel(Interrupt::Running);
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                output.progress("\n중단 요청: 새 전송을 시작하지 않고 진행 중인 전송을 기다립니다. (한 번 더 누르면 즉시 중단)");
+                let _ = interrupt_tx.send(Interrupt::Drain);
+            }
+            if tokio::signal::ctrl_c().await.is_ok() {
+                output.progress("\n즉시 중단합니다. 진행 중이던 전송은 저널에 서명과 함께 기록되어 있습니다.");
+                let _ = interrupt_tx.send(Interrupt::Abort);
+            }
+        });
+        let summary = run_payouts(backend.clone(), Arc::new(sender), payouts.clone(), journal.clone(), &options, interrupt_rx).await;
+        let records = match output.is_table() {
+            true => vec![],
+            false => transfer_records(backend.as_ref(), &payouts, &journal, options.fee).await,
+        };
+        (summary, records)
+    });
+
+    output.progress("\n===== 지급 결과 =====");
+    output.progress(format_args!("확인됨: {}건", summary.confirmed));
+    output.progress(format_args!("이전 실행에서 완료: {}건", summary.skipped));
+    output.progress(format_args!("실패: {}건", summary.failed));
+    output.progress(format_args!("결과 미확인: {}건", summary.unresolved));
+    output.progress(format_args!("시작하지 않음: {}건", summary.not_started));
+    if summary.unresolved > 0 || summary.not_started > 0 {
+        output.progress(format_args!("같은 명령을 다시 실행하면 저널({})을 확인해 이어서 진행합니다.", journal.path().display()));
+    }
+    if summary.failed > 0 && !options.retry_failed {
+        output.progress("실패한 지급을 다시 보내려면 --retry-failed 옵션을 사용하세요.");
+    }
+    output.print(&records);
+    Ok(())
+}
+
+/// CSV 지급 목록 파싱 (`수신 주소,SOL 양[,ID]`)
+pub fn parse_payouts(content: &str) -> Result<Vec<Payout>> {
+    let mut payouts: Vec<Payout> = vec![];
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        // 헤더 줄 건너뛰기
+        if payouts.is_empty() && fields[0].eq_ignore_ascii_case("recipient") {
+            continue;
+        }
+        if fields.len() < 2 {
+            return Err(Error::Input(format!("{}번째 줄: 수신 주소와 SOL 양이 필요합니다", line_number)));
+        }
+
+        let recipient = Pubkey::from_str(fields[0])
+            .map_err(|_| Error::Input(format!("{}번째 줄: 유효하지 않은 솔라나 주소입니다: {}", line_number, fields[0])))?;
+        let amount: f64 = fields[1]
+            .parse()
+            .map_err(|_| Error::Input(format!("{}번째 줄: 유효한 SOL 양이 아닙니다: {}", line_number, fields[1])))?;
+        let lamports = sol_to_lamports(amount);
+        if lamports == 0 {
+            return Err(Error::Input(format!("{}번째 줄: 전송 금액은 0보다 커야 합니다", line_number)));
+        }
+        let key = match fields.get(2).filter(|id| !id.is_empty()) {
+            Some(id) => id.to_string(),
+            None => format!("line-{}", line_number),
+        };
+        if payouts.iter().any(|p| p.key == key) {
+            return Err(Error::Input(format!("{}번째 줄: 중복된 ID입니다: {}", line_number, key)));
+        }
+        payouts.push(Payout { key, recipient, lamports });
+    }
+    Ok(payouts)
+}
+
+/// 지급 목록을 최대 `options.concurrency`개씩 동시에 처리
+///
+/// `interrupt`가 [`Interrupt::Drain`]이 되면 새 지급을 시작하지 않고, [`Interrupt::Abort`]가 되면
+/// 진행 중인 작업도 취소합니다. 취소된 지급은 저널에 `signed` 상태(또는 기록 없음)로 남습니다.
+pub async fn run_payouts<B: RpcBackend + 'static>(
+    backend: Arc<B>,
+    payer: Arc<Keypair>,
+    payouts: Vec<Payout>,
+    journal: Arc<Journal>,
+    options: &BatchOptions,
+    mut interrupt: watch::Receiver<Interrupt>,
+) -> BatchSummary {
+    let semaphore = Arc::new(Semaphore::new(options.concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+    let mut summary = BatchSummary::default();
+    let total = payouts.len();
+    let mut started = 0;
+
+    for payout in payouts {
+        if *interrupt.borrow() != Interrupt::Running {
+            break;
+        }
+        let permit = tokio::select! {
+            permit = semaphore.clone().acquire_owned() => permit.expect("세마포어는 닫히지 않음"),
+            _ = wait_for(&mut interrupt, Interrupt::Drain) => break,
+        };
+
+        started += 1;
+        let backend = backend.clone();
+        let payer = payer.clone();
+        let journal = journal.clone();
+        let options = options.clone();
+        tasks.spawn(async move {
+            let _permit = permit;
+            process(backend.as_ref(), &payer, &journal, payout, &options).await
+        });
+    }
+    summary.not_started = total - started;
+
+    let mut aborted = false;
+    loop {
+        tokio::select! {
+            joined = tasks.join_next() => match joined {
+                Some(Ok(outcome)) => summary.record(outcome),
+                // 중단으로 취소된 작업
+                Some(Err(_)) => summary.unresolved += 1,
+                None => break,
+            },
+            _ = wait_for(&mut interrupt, Interrupt::Abort), if !aborted => {
+                tasks.abort_all();
+                aborted = true;
+            }
+        }
+    }
+    summary
+}
+
+impl BatchSummary {
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Confirmed => self.confirmed += 1,
+            Outcome::Skipped => self.skipped += 1,
+            Outcome::Failed => self.failed += 1,
+            Outcome::Unresolved => self.unresolved += 1,
+        }
+    }
+}
+
+/// 저널의 현재 상태를 지급 목록 순서대로 출력 레코드로 변환
+///
+/// 확인된 지급은 서명 상태를 조회하여 처리된 슬롯을 채웁니다 (조회에 실패하면 슬롯 없음).
+/// 저널에 기록이 없는 지급(시작하지 않음)은 제외합니다.
+pub async fn transfer_records<B: RpcBackend + ?Sized>(
+    backend: &B,
+    payouts: &[Payout],
+    journal: &Journal,
+    fee: u64,
+) -> Vec<TransferRecord> {
+    let entries: Vec<JournalEntry> = payouts.iter().filter_map(|payout| journal.get(&payout.key)).collect();
+
+    let confirmed: Vec<Signature> = entries
+        .iter()
+        .filter(|entry| entry.status == PayoutStatus::Confirmed)
+        .filter_map(|entry| entry.signature.as_deref().and_then(|s| Signature::from_str(s).ok()))
+        .collect();
+    let mut slots = HashMap::new();
+    // getSignatureStatuses는 한 번에 최대 256개 (오래된 전송도 슬롯을 찾도록 기록까지 조회)
+    for chunk in confirmed.chunks(256) {
+        if let Ok(response) = backend.get_signature_statuses_with_history(chunk).await {
+            for (signature, status) in chunk.iter().zip(response.value) {
+                if let Some(status) = status {
+                    slots.insert(signature.to_string(), status.slot);
+                }
+            }
+        }
+    }
+
+    entries
+        .into_iter()
+        .map(|entry| TransferRecord {
+            slot: entry.signature.as_ref().and_then(|s| slots.get(s).copied()),
+            id: entry.key,
+            signature: entry.signature,
+            status: entry.status.as_str().to_string(),
+            fee,
+            error: entry.error,
+        })
+        .collect()
+}
+
+// 중단 단계가 `level` 이상이 될 때까지 대기 (송신자가 사라지면 영원히 대기)
+async fn wait_for(interrupt: &mut watch::Receiver<Interrupt>, level: Interrupt) {
+    loop {
+        let current = *interrupt.borrow_and_update();
+        if current == Interrupt::Abort || (level == Interrupt::Drain && current == Interrupt::Drain) {
+            return;
+        }
+        if interrupt.changed().await.is_err() {
+            future::pending::<()>().await;
+        }
+    }
+}
+
+// 지급 하나 처리: 저널 확인 → (필요하면) 이전 서명 확인 → 서명/기록 → 전송 및 확인
+async fn process<B: RpcBackend + ?Sized>(
+    backend: &B,
+    payer: &Keypair,
+    journal: &Journal,
+    payout: Payout,
+    options: &BatchOptions,
+) -> Outcome {
+    let key = payout.key.clone();
+
+    if let Some(previous) = journal.get(&key) {
+        if previous.recipient != payout.recipient.to_string() || previous.lamports != payout.lamports {
+            options.output.progress(format_args!("[{}] 저널의 기록(수신자/금액)과 목록이 다릅니다. 건너뜁니다.", key));
+            return Outcome::Failed;
+        }
+        match previous.status {
+            PayoutStatus::Confirmed => return Outcome::Skipped,
+            PayoutStatus::Failed if !options.retry_failed => {
+                options.output.progress(format_args!("[{}] 이전 실행에서 실패: {}", key, previous.error.as_deref().unwrap_or("-")));
+                return Outcome::Failed;
+            }
+            PayoutStatus::Signed => match resolve_previous(backend, &previous, options).await {
+                Resolution::Confirmed => {
+                    options.output.progress(format_args!("[{}] 이전 실행의 전송이 확인되었습니다: {}", key, previous.signature.as_deref().unwrap_or("-")));
+                    return finish(journal, options.output, previous, PayoutStatus::Confirmed, None);
+                }
+                Resolution::Failed(error) => {
+                    options.output.progress(format_args!("[{}] 이전 실행의 전송이 실패했습니다: {}", key, error));
+                    return finish(journal, options.output, previous, PayoutStatus::Failed, Some(error));
+                }
+                Resolution::Pending => {
+                    options.output.progress(format_args!("[{}] 이전 실행의 전송 결과를 아직 확인할 수 없습니다.", key));
+                    return Outcome::Unresolved;
+                }
+                Resolution::Expired => {
+                    if let Err(e) = journal.append(JournalEntry {
+                        status: PayoutStatus::Expired,
+                        updated_at: Utc::now(),
+                        ..previous
+                    }) {
+                        options.output.progress(format_args!("[{}] 저널 기록 실패: {}", key, e));
+                        return Outcome::Unresolved;
+                    }
+                }
+            },
+            PayoutStatus::Failed | PayoutStatus::Expired => {}
+        }
+    }
+
+    // 서명 후 전송 전에 서명과 블록해시를 기록
+    let instruction = system_instruction::transfer(&payer.pubkey(), &payout.recipient, payout.lamports);
+    let built = transfer::build_transaction(backend, payer, &[], vec![instruction], options.fee).await;
+    let transaction = match built {
+        Ok(transaction) => transaction,
+        Err(error) => {
+            options.output.progress(format_args!("[{}] 트랜잭션 생성 실패: {}", key, error));
+            return Outcome::Failed;
+        }
+    };
+    let entry = JournalEntry {
+        key: key.clone(),
+        recipient: payout.recipient.to_string(),
+        lamports: payout.lamports,
+        status: PayoutStatus::Signed,
+        signature: Some(transaction.signatures[0].to_string()),
+        blockhash: Some(transaction.message.recent_blockhash.to_string()),
+        error: None,
+        updated_at: Utc::now(),
+    };
+    if let Err(e) = journal.append(entry.clone()) {
+        options.output.progress(format_args!("[{}] 저널 기록 실패로 전송하지 않았습니다: {}", key, e));
+        return Outcome::Failed;
+    }
+
+    let start = Instant::now();
+    let sent = transfer::send_and_confirm(backend, &transaction, &options.confirm).await;
+    match sent {
+        Ok(signature) => {
+            options.output.progress(format_args!(
+                "[{}] {} SOL → {} 전송 완료 ({:?}): {}",
+                key,
+                lamports_to_sol(payout.lamports),
+                payout.recipient,
+                start.elapsed(),
+                signature
+            ));
+            finish(journal, options.output, entry, PayoutStatus::Confirmed, None)
+        }
+        Err(error) => {
+            // 실패하더라도 트랜잭션이 처리되었을 수 있으므로 한 번 더 확인
+            let mut options = options.clone();
+            options.confirm.timeout = options.confirm.poll_interval;
+            match resolve_previous(backend, &entry, &options).await {
+                Resolution::Confirmed => finish(journal, options.output, entry, PayoutStatus::Confirmed, None),
+                Resolution::Failed(onchain) => {
+                    options.output.progress(format_args!("[{}] 전송 실패: {}", key, onchain));
+                    finish(journal, options.output, entry, PayoutStatus::Failed, Some(onchain))
+                }
+                Resolution::Expired => {
+                    options.output.progress(format_args!("[{}] 전송 실패: {}", key, error));
+                    finish(journal, options.output, entry, PayoutStatus::Failed, Some(error.to_string()))
+                }
+                Resolution::Pending => {
+                    options.output.progress(format_args!("[{}] 전송 결과 미확인 (서명 {}): {}", key, transaction.signatures[0], error));
+                    let _ = journal.append(JournalEntry {
+                        error: Some(error.to_string()),
+                        updated_at: Utc::now(),
+                        ..entry
+                    });
+                    Outcome::Unresolved
+                }
+            }
+        }
+    }
+}
+
+// 최종 상태 기록
+fn finish(journal: &Journal, output: OutputFormat, entry: JournalEntry, status: PayoutStatus, error: Option<String>) -> Outcome {
+    let key = entry.key.clone();
+    let result = journal.append(JournalEntry {
+        status,
+        error,
+        updated_at: Utc::now(),
+        ..entry
+    });
+    match (result, status) {
+        (Err(e), _) => {
+            output.progress(format_args!("[{}] 저널 기록 실패: {}", key, e));
+            Outcome::Unresolved
+        }
+        (Ok(()), PayoutStatus::Confirmed) => Outcome::Confirmed,
+        (Ok(()), _) => Outcome::Failed,
+    }
+}
+
+// 서명된 트랜잭션이 처리되었는지, 또는 블록해시 만료로 더 이상 처리될 수 없는지 확인
+//
+// 이전 실행이 오래전일 수 있으므로 최근 상태 캐시가 아니라 원장 기록까지 조회합니다.
+// 상태가 보이지 않고 블록해시가 아직 유효하면 `options.confirm.timeout`까지 기다립니다.
+async fn resolve_previous<B: RpcBackend + ?Sized>(backend: &B, entry: &JournalEntry, options: &BatchOptions) -> Resolution {
+    let parsed = entry
+        .signature
+        .as_deref()
+        .and_then(|s| Signature::from_str(s).ok())
+        .zip(entry.blockhash.as_deref().and_then(|b| Hash::from_str(b).ok()));
+    let (signature, blockhash) = match parsed {
+        Some(parsed) => parsed,
+        None => return Resolution::Expired,
+    };
+
+    let start = Instant::now();
+    loop {
+        if let Ok(response) = backend.get_signature_statuses_with_history(&[signature]).await {
+            match response.value.into_iter().next().flatten() {
+                Some(status) if status.err.is_some() => {
+                    return Resolution::Failed(status.err.map(|e| e.to_string()).unwrap_or_default())
+                }
+                Some(status) if status.satisfies_commitment(CommitmentConfig::confirmed()) => {
+                    return Resolution::Confirmed
+                }
+                Some(_) => {}
+                None => {
+                    if let Ok(false) = backend.is_blockhash_valid(&blockhash).await {
+                        // 만료 직전에 처리되었을 수 있으므로 상태를 한 번 더 확인 (조회 실패는 미확정으로 다시 시도)
+                        let recheck = backend.get_signature_statuses_with_history(&[signature]).await;
+                        if matches!(recheck, Ok(response) if response.value.first().is_some_and(Option::is_none)) {
+                            return Resolution::Expired;
+                        }
+                    }
+                }
+            }
+        }
+        if start.elapsed() >= options.confirm.timeout {
+            return Resolution::Pending;
+        }
+        tokio::time::sleep(options.confirm.poll_interval).await;
+    }
+}
@@ -0,0 +1,271 @@
+//! RPC 응답 시간 측정 작업
+//!
+//! `rpc_benchmark`가 엔드포인트마다 실행하는 작업 모음입니다. [`RpcBackend`]를 거치므로
+//! 가짜 백엔드로 지연과 오류를 주입해 측정 로직을 검증할 수 있습니다.
+//! 엔드포인트끼리는 동시에 측정하고, 한 엔드포인트의 작업은 [`run_suite`]로 순서대로 측정합니다.
+//! [`run_rounds`]는 예열 라운드 후 작업 목록을 정해진 횟수만큼 반복하여 작업별 히스토그램을 만듭니다.
+//!
+//! 측정할 작업은 `--methods` 옵션으로 고릅니다 ([`suite_from_args`]). 기본값은 가벼운 4개 메서드인
+//! [`standard_suite`]이고, `all`은 계정/블록/시뮬레이션 등 모든 메서드인 [`full_suite`]입니다.
+
+use crate::backend::RpcBackend;
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::failure::{Failure, FailureCounts};
+use crate::stats::LatencyHistogram;
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, pubkey, pubkey::Pubkey, signature::Signature, system_instruction,
+    sysvar, transaction::Transaction,
+};
+use std::time::{Duration, Instant};
+
+/// 잔액 조회 벤치마크에 사용하는 대형 지갑
+pub const LARGE_WALLET: Pubkey = pubkey!("4Rf9mGD7FeYknun5JczX5nGLTfQuS1GRjwA3iseBQxP4");
+
+// SPL 토큰 계정 크기와 계정 데이터 안의 소유자 위치
+const TOKEN_ACCOUNT_SIZE: u64 = 165;
+const TOKEN_OWNER_OFFSET: usize = 32;
+
+/// 측정할 RPC 작업
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operation {
+    /// 연결 테스트 (getVersion)
+    Version,
+    /// 최근 블록해시 가져오기 (getLatestBlockhash)
+    LatestBlockhash,
+    /// 현재 슬롯 가져오기 (getSlot, confirmed)
+    Slot,
+    /// 지갑 잔액 확인 (getBalance)
+    Balance(Pubkey),
+    /// 계정 정보 조회 (getAccountInfo)
+    AccountInfo(Pubkey),
+    /// 여러 계정 한 번에 조회 (getMultipleAccounts)
+    MultipleAccounts(Vec<Pubkey>),
+    /// 필터를 적용한 프로그램 계정 조회 (getProgramAccounts)
+    ProgramAccounts { program: Pubkey, filters: Vec<RpcFilterType> },
+    /// 서명 상태 조회 (getSignatureStatuses)
+    SignatureStatuses(Vec<Signature>),
+    /// 최신 finalized 블록 조회 (getBlock, 트랜잭션 제외)
+    Block,
+    /// 서명하지 않은 자기 전송 시뮬레이션 (simulateTransaction)
+    SimulateTransaction(Pubkey),
+    /// 최근 우선순위 수수료 조회 (getRecentPrioritizationFees)
+    RecentPrioritizationFees(Vec<Pubkey>),
+}
+
+impl Operation {
+    /// 출력용 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            Operation::Version => "버전 확인",
+            Operation::LatestBlockhash => "최근 블록해시 가져오기",
+            Operation::Slot => "현재 슬롯 가져오기",
+            Operation::Balance(_) => "대형 지갑 잔액 확인",
+            Operation::AccountInfo(_) => "계정 정보 조회",
+            Operation::MultipleAccounts(_) => "여러 계정 조회",
+            Operation::ProgramAccounts { .. } => "프로그램 계정 조회 (필터)",
+            Operation::SignatureStatuses(_) => "서명 상태 조회",
+            Operation::Block => "블록 조회",
+            Operation::SimulateTransaction(_) => "트랜잭션 시뮬레이션",
+            Operation::RecentPrioritizationFees(_) => "최근 우선순위 수수료 조회",
+        }
+    }
+
+    /// RPC 메서드 이름 (기계가 읽는 출력에 사용)
+    pub fn method(&self) -> &'static str {
+        match self {
+            Operation::Version => "getVersion",
+            Operation::LatestBlockhash => "getLatestBlockhash",
+            Operation::Slot => "getSlot",
+            Operation::Balance(_) => "getBalance",
+            Operation::AccountInfo(_) => "getAccountInfo",
+            Operation::MultipleAccounts(_) => "getMultipleAccounts",
+            Operation::ProgramAccounts { .. } => "getProgramAccounts",
+            Operation::SignatureStatuses(_) => "getSignatureStatuses",
+            Operation::Block => "getBlock",
+            Operation::SimulateTransaction(_) => "simulateTransaction",
+            Operation::RecentPrioritizationFees(_) => "getRecentPrioritizationFees",
+        }
+    }
+
+    /// RPC 메서드 이름으로 기본 인자를 채운 작업 찾기 ([`full_suite`] 기준)
+    pub fn from_method(method: &str) -> Option<Operation> {
+        full_suite().into_iter().find(|operation| operation.method() == method)
+    }
+}
+
+/// 기본 벤치마크 작업 목록
+pub fn standard_suite() -> Vec<Operation> {
+    vec![
+        Operation::Version,
+        Operation::LatestBlockhash,
+        Operation::Slot,
+        Operation::Balance(LARGE_WALLET),
+    ]
+}
+
+/// 지원하는 모든 메서드의 작업 목록 (기본 작업 뒤에 무거운 조회 작업)
+pub fn full_suite() -> Vec<Operation> {
+    let mut suite = standard_suite();
+    suite.extend([
+        Operation::AccountInfo(LARGE_WALLET),
+        Operation::MultipleAccounts(vec![LARGE_WALLET, sysvar::clock::id(), spl_token::id()]),
+        // 대형 지갑이 소유한 SPL 토큰 계정
+        Operation::ProgramAccounts {
+            program: spl_token::id(),
+            filters: vec![
+                RpcFilterType::DataSize(TOKEN_ACCOUNT_SIZE),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(TOKEN_OWNER_OFFSET, LARGE_WALLET.as_ref())),
+            ],
+        },
+        // 존재하지 않는 서명 (상태 캐시와 기록 조회 비용)
+        Operation::SignatureStatuses(vec![Signature::default()]),
+        Operation::Block,
+        Operation::SimulateTransaction(LARGE_WALLET),
+        Operation::RecentPrioritizationFees(vec![]),
+    ]);
+    suite
+}
+
+/// 쉼표로 구분한 RPC 메서드 이름 목록을 작업 목록으로 (`all`은 [`full_suite`])
+pub fn parse_suite(spec: &str) -> Result<Vec<Operation>> {
+    if spec.trim() == "all" {
+        return Ok(full_suite());
+    }
+    let mut suite: Vec<Operation> = Vec::new();
+    for method in spec.split(',').map(str::trim).filter(|m| !m.is_empty()) {
+        let operation = Operation::from_method(method).ok_or_else(|| {
+            let methods: Vec<&str> = full_suite().iter().map(Operation::method).collect();
+            Error::Input(format!("지원하지 않는 메서드: {} (지원: {}, all)", method, methods.join(", ")))
+        })?;
+        if !suite.contains(&operation) {
+            suite.push(operation);
+        }
+    }
+    if suite.is_empty() {
+        return Err(Error::Input("측정할 메서드가 없습니다".to_string()));
+    }
+    Ok(suite)
+}
+
+/// `--methods` 옵션의 작업 목록 (없으면 [`standard_suite`])
+pub fn suite_from_args(args: &Args) -> Result<Vec<Operation>> {
+    args.value("methods").map_or_else(|| Ok(standard_suite()), parse_suite)
+}
+
+/// 작업 하나를 실행하고 응답 시간을 반환 (실패하면 RPC 오류)
+///
+/// 블록 조회의 슬롯과 시뮬레이션의 블록해시처럼 요청에 필요한 값은 측정 시작 전에 조회합니다.
+pub async fn measure<B: RpcBackend + ?Sized>(backend: &B, operation: &Operation) -> ClientResult<Duration> {
+    let block_slot = match operation {
+        Operation::Block => Some(backend.get_slot(CommitmentConfig::finalized()).await?),
+        _ => None,
+    };
+    let simulation = match operation {
+        Operation::SimulateTransaction(payer) => {
+            let instruction = system_instruction::transfer(payer, payer, 1);
+            let mut transaction = Transaction::new_with_payer(&[instruction], Some(payer));
+            transaction.message.recent_blockhash = backend.get_latest_blockhash().await?;
+            Some(transaction)
+        }
+        _ => None,
+    };
+
+    let start = Instant::now();
+    match operation {
+        Operation::Version => backend.get_version().await.map(drop)?,
+        Operation::LatestBlockhash => backend.get_latest_blockhash().await.map(drop)?,
+        Operation::Slot => backend.get_slot(CommitmentConfig::confirmed()).await.map(drop)?,
+        Operation::Balance(pubkey) => backend.get_balance(pubkey).await.map(drop)?,
+        Operation::AccountInfo(pubkey) => backend.get_account(pubkey).await.map(drop)?,
+        Operation::MultipleAccounts(pubkeys) => backend.get_multiple_accounts(pubkeys).await.map(drop)?,
+        Operation::ProgramAccounts { program, filters } => backend.get_program_accounts(program, filters).await.map(drop)?,
+        Operation::SignatureStatuses(signatures) => backend.get_signature_statuses(signatures).await.map(drop)?,
+        Operation::Block => backend.get_block(block_slot.unwrap_or_default()).await.map(drop)?,
+        Operation::SimulateTransaction(_) => {
+            let transaction = simulation.as_ref().expect("시뮬레이션 트랜잭션은 측정 전에 준비됨");
+            backend.simulate_transaction(transaction).await.map(drop)?
+        }
+        Operation::RecentPrioritizationFees(addresses) => backend.get_recent_prioritization_fees(addresses).await.map(drop)?,
+    }
+    Ok(start.elapsed())
+}
+
+/// 작업 목록을 순서대로 측정 (엔드포인트 하나 안에서는 요청이 겹치지 않도록 순차 실행)
+pub async fn run_suite<B: RpcBackend + ?Sized>(
+    backend: &B,
+    operations: &[Operation],
+) -> Vec<(Operation, ClientResult<Duration>)> {
+    let mut results = Vec::with_capacity(operations.len());
+    for operation in operations {
+        results.push((operation.clone(), measure(backend, operation).await));
+    }
+    results
+}
+
+/// 반복 측정 횟수 (`--iterations`, `--warmup`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rounds {
+    /// 통계에 포함하는 측정 횟수
+    pub iterations: usize,
+    /// 통계에서 제외하는 예열 횟수 (연결 수립, DNS 조회 등 첫 요청 비용 제외)
+    pub warmup: usize,
+}
+
+impl Rounds {
+    /// 명령줄 옵션으로 반복 횟수 결정 (지정하지 않으면 도구별 기본값)
+    pub fn from_args(args: &Args, default: Rounds) -> Result<Rounds> {
+        Ok(Rounds {
+            iterations: args.parse_or("iterations", default.iterations)?.max(1),
+            warmup: args.parse_or("warmup", default.warmup)?,
+        })
+    }
+}
+
+/// 작업 하나의 반복 측정 결과
+#[derive(Debug, Clone)]
+pub struct OperationStats {
+    pub operation: Operation,
+    /// 성공한 측정의 응답 시간
+    pub latency: LatencyHistogram,
+    /// 종류별 실패 수
+    pub failures: FailureCounts,
+}
+
+/// 예열 후 작업 목록을 `rounds.iterations`번 반복 측정 (작업 순서대로 결과 반환)
+///
+/// 요청 한도를 넘으면 다음 요청 전에 `Retry-After`만큼 기다립니다 ([`Failure::backoff`]).
+/// 예열 라운드의 결과는 버립니다.
+pub async fn run_rounds<B: RpcBackend + ?Sized>(backend: &B, operations: &[Operation], rounds: Rounds) -> Vec<OperationStats> {
+    for _ in 0..rounds.warmup {
+        run_suite(backend, operations).await;
+    }
+
+    let mut stats: Vec<OperationStats> = operations
+        .iter()
+        .map(|operation| OperationStats {
+            operation: operation.clone(),
+            latency: LatencyHistogram::new(),
+            failures: FailureCounts::new(),
+        })
+        .collect();
+    for _ in 0..rounds.iterations {
+        for stat in stats.iter_mut() {
+            match measure(backend, &stat.operation).await {
+                Ok(elapsed) => stat.latency.record(elapsed),
+                Err(e) => {
+                    let failure = Failure::from_client_error(&e);
+                    stat.failures.record(&failure);
+                    if let Some(backoff) = failure.backoff() {
+                        tokio::time::sleep(backoff).await;
+                    }
+                }
+            }
+        }
+    }
+    stats
+}
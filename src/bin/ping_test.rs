@@ -1,131 +1,364 @@
+use solana_transfer_bot::benchmark::Rounds;
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::config::Settings;
+use solana_transfer_bot::failure::{Failure, FailureCounts, FailureKind};
+use solana_transfer_bot::output::{self, LatencyRecord, OutputFormat, ProbeRecord};
+use solana_transfer_bot::probe::{self, IpFamily, PingOptions, ProbeSample, ProbeStats, ProbeTarget, ProbeTiming, Timeline};
+use solana_transfer_bot::reporting::{self, TableLabels};
+use solana_transfer_bot::stats::{LatencyHistogram, LatencySummary};
+use solana_transfer_bot::{rpc, runtime};
 use std::process::Command;
 use std::time::{Duration, Instant};
 use std::collections::HashMap;
-use std::thread;
 use std::env;
+use std::thread;
 use url::Url;
-use dotenv::dotenv;
 
-// 공식 RPC 엔드포인트
-const OFFICIAL_RPC: &str = "https://api.mainnet-beta.solana.com";
+// 기본 반복 횟수 (--count 또는 --iterations, --warmup)
+const DEFAULT_ROUNDS: Rounds = Rounds { iterations: 10, warmup: 1 };
 
 fn main() {
     // .env 파일에서 환경 변수 로드
-    dotenv().ok();
-    
-    println!("솔라나 RPC 서버 Ping 테스트를 시작합니다...");
-    println!("각 RPC 서버의 네트워크 지연 시간을 측정합니다.\n");
-
-    // 환경 변수에서 RPC URL 불러오기
-    let custom_rpc_url = env::var("SOLANA_RPC_URL").unwrap_or_else(|_| {
-        println!("환경변수 SOLANA_RPC_URL이 설정되지 않았습니다. 기본 RPC URL만 테스트합니다.");
-        String::new()
-    });
-    
-    // RPC 엔드포인트 목록 생성
-    let mut rpc_endpoints = vec![OFFICIAL_RPC];
-    if !custom_rpc_url.is_empty() {
-        rpc_endpoints.push(&custom_rpc_url);
+    rpc::load_env();
+
+    // 출력 형식 (--output json|csv|table)과 측정 옵션 (--count, --warmup, --interval, --timeout)
+    let args = Args::parse(env::args().skip(1));
+    // 측정할 RPC는 설정(--profile, --rpc-url, 설정 파일)에서 결정
+    let parsed = OutputFormat::from_args(&args)
+        .and_then(|output| Ok((output, PingOptions::from_args(&args, DEFAULT_ROUNDS)?, Settings::load(&args)?)));
+    let (output, options, settings) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    // 요약 대신 측정 하나하나를 출력 (--timeline, JSON/CSV에서만)
+    let timeline = args.has("timeline");
+
+    // RPC 엔드포인트 목록 생성 (프로필의 공식 RPC + 설정된 RPC)
+    let rpc_endpoints = settings.benchmark_endpoints(rpc::skip_official_rpc());
+
+    // 시스템 ping 명령으로 ICMP 왕복 시간 측정 (--icmp)
+    if args.has("icmp") {
+        run_icmp(output, &options, &rpc_endpoints, timeline);
+        return;
     }
-    
-    // 결과를 저장할 맵
-    let mut results: HashMap<&str, Vec<Duration>> = HashMap::new();
+
+    // 측정할 주소 종류 (--ipv4, --ipv6 중 하나만 주면 그 종류만, 아니면 둘 다)
+    let families = match (args.has("ipv4"), args.has("ipv6")) {
+        (true, false) => vec![IpFamily::V4],
+        (false, true) => vec![IpFamily::V6],
+        _ => IpFamily::ALL.to_vec(),
+    };
+
+    output.progress("솔라나 RPC 서버 Ping 테스트를 시작합니다...");
+    output.progress(format_args!(
+        "각 RPC 서버에 직접 연결해 DNS 조회, TCP 연결, TLS 핸드셰이크, getHealth 응답 시간을 측정합니다 (예열 {}회 후 {}회, 간격 {:?}, 타임아웃 {:?}).\n",
+        options.rounds.warmup, options.rounds.iterations, options.interval, options.timeout
+    ));
+
+    // (엔드포인트, 주소 종류)별 결과 (측정 순서대로)
+    let mut results: Vec<(&str, IpFamily, ProbeStats, Timeline)> = Vec::new();
 
     // 각 RPC 엔드포인트에 대해 테스트 실행
-    for &endpoint in &rpc_endpoints {
-        println!("테스트 중: {}", endpoint);
-        
+    for endpoint in rpc_endpoints.iter().map(|e| e.as_str()) {
+        output.progress(format_args!("테스트 중: {}", endpoint));
+
+        let target = match ProbeTarget::parse(endpoint) {
+            Ok(target) => target,
+            Err(e) => {
+                output.progress(format_args!("  {}", e));
+                continue;
+            }
+        };
+
+        // 호스트에 주소가 있는 종류만 측정 (조회에 실패하면 측정마다 실패로 집계)
+        let available: Vec<IpFamily> = match runtime::block_on(probe::resolve(&target)) {
+            Ok(addresses) => families
+                .iter()
+                .copied()
+                .filter(|family| {
+                    let found = addresses.iter().any(|address| family.matches(address));
+                    if !found {
+                        output.progress(format_args!("  {} 주소 없음 (건너뜀)", family.label()));
+                    }
+                    found
+                })
+                .collect(),
+            Err(failure) => {
+                output.progress(format_args!("  {}", failure.message));
+                families.clone()
+            }
+        };
+
+        for family in available {
+            output.progress(format_args!("  {}", family.label()));
+
+            // 예열 (통계에서 제외)
+            for _ in 0..options.rounds.warmup {
+                let _ = runtime::block_on(probe::probe(&target, family, options.timeout));
+                thread::sleep(options.interval);
+            }
+
+            let mut stats = ProbeStats::new();
+            let mut timeline = Timeline::new();
+            let start = Instant::now();
+            for _ in 0..options.rounds.iterations {
+                let offset = start.elapsed();
+                let result = runtime::block_on(probe::probe(&target, family, options.timeout));
+                let sample = timeline.record(offset, result.as_ref().map(|timing| timing.total).map_err(|failure| failure.kind));
+                match &result {
+                    Ok(timing) => output.progress(format_args!("    {}: {}", position(sample), describe(timing))),
+                    Err(failure) => output.progress(format_args!("    {}: 손실 - {}", position(sample), failure.message)),
+                }
+                stats.record(&result);
+
+                // 다음 측정 전 대기
+                thread::sleep(options.interval);
+            }
+
+            if output.is_table() {
+                if let Some(address) = stats.address {
+                    println!("  주소: {}", address);
+                }
+                let phases: Vec<(&str, &LatencyHistogram)> =
+                    stats.phases().into_iter().map(|(_, label, histogram)| (label, histogram)).collect();
+                reporting::print_phases(&phases);
+                print_loss_and_jitter(&timeline);
+                reporting::print_failures("실패", stats.total.len(), &stats.failures);
+                if stats.unhealthy > 0 {
+                    println!("  ⚠️ getHealth가 ok가 아닌 응답: {}회 (노드 지연 등)", stats.unhealthy);
+                }
+            }
+            results.push((endpoint, family, stats, timeline));
+        }
+        output.progress("");
+    }
+
+    if !output.is_table() {
+        if timeline {
+            let records: Vec<ProbeRecord> = results
+                .iter()
+                .flat_map(|(endpoint, family, _, timeline)| {
+                    timeline.samples().iter().map(|sample| ProbeRecord::new(endpoint, Some(family.as_str()), sample))
+                })
+                .collect();
+            output.print(&records);
+            return;
+        }
+        // 단계마다 레코드 하나 (실패와 지터는 측정 전체에 대한 값이므로 total 레코드에만)
+        let no_failures = FailureCounts::new();
+        let records: Vec<LatencyRecord> = results
+            .iter()
+            .flat_map(|(endpoint, family, stats, timeline)| {
+                stats.phases().into_iter().map(|(operation, _, histogram)| {
+                    let total = operation == "total";
+                    let failures = if total { &stats.failures } else { &no_failures };
+                    LatencyRecord {
+                        ip_family: Some(family.as_str().to_string()),
+                        jitter_ms: timeline.jitter().filter(|_| total).map(output::millis),
+                        ..LatencyRecord::new(endpoint, operation, histogram.summary().as_ref(), failures)
+                    }
+                })
+            })
+            .collect();
+        output.print(&records);
+        return;
+    }
+
+    // 종합 결과 출력 (전체 시간 기준, 손실된 측정은 제외)
+    let summaries: Vec<(String, Option<LatencySummary>)> = results
+        .iter()
+        .map(|(endpoint, family, stats, _)| (format!("{} ({})", endpoint, family.label()), stats.total.summary()))
+        .collect();
+
+    reporting::print_latency_table(
+        &TableLabels {
+            title: "Ping 테스트 결과",
+            metric: "전체시간",
+            failure: "응답 없음",
+            fastest: "가장 빠른 RPC 서버",
+        },
+        &summaries,
+    );
+    let stability: Vec<(String, &Timeline)> = results
+        .iter()
+        .map(|(endpoint, family, _, timeline)| (format!("{} ({})", endpoint, family.label()), timeline))
+        .collect();
+    reporting::print_loss_and_jitter(&stability);
+}
+
+// 측정 번호와 시작 시각 (예: "#3 +0.41s")
+fn position(sample: &ProbeSample) -> String {
+    format!("#{} +{:.2}s", sample.seq, sample.offset.as_secs_f64())
+}
+
+// 측정 한 번의 단계별 시간
+fn describe(timing: &ProbeTiming) -> String {
+    let tls = match timing.tls {
+        Some(tls) => format!(", TLS {:?}", tls),
+        None => String::new(),
+    };
+    format!(
+        "전체 {:?} (DNS {:?}, TCP {:?}{}, 첫 바이트 {:?}){}",
+        timing.total,
+        timing.dns,
+        timing.connect,
+        tls,
+        timing.ttfb,
+        if timing.healthy { "" } else { " - getHealth 비정상" }
+    )
+}
+
+// 엔드포인트 하나의 손실률과 지터
+fn print_loss_and_jitter(timeline: &Timeline) {
+    let jitter = match timeline.jitter() {
+        Some(jitter) => format!("{:?}", jitter),
+        None => "-".to_string(),
+    };
+    println!(
+        "  손실: {}/{} ({:.1}%), 지터: {}",
+        timeline.lost(),
+        timeline.sent(),
+        timeline.loss() * 100.0,
+        jitter
+    );
+}
+
+// 시스템 ping 명령으로 호스트별 ICMP 왕복 시간 측정 (ICMP가 허용된 환경에서만 동작)
+fn run_icmp(output: OutputFormat, options: &PingOptions, rpc_endpoints: &[String], timeline: bool) {
+    output.progress("솔라나 RPC 서버 ICMP Ping 테스트를 시작합니다...");
+    output.progress(format_args!(
+        "시스템 ping 명령으로 각 RPC 서버 호스트의 왕복 시간을 측정합니다 (예열 {}회 후 {}회, 간격 {:?}, 타임아웃 {:?}).\n",
+        options.rounds.warmup, options.rounds.iterations, options.interval, options.timeout
+    ));
+
+    // 엔드포인트별 지연 시간, 실패 (응답 없음, 실행 실패), 측정 순서
+    let mut results: HashMap<&str, (LatencyHistogram, FailureCounts, Timeline)> = HashMap::new();
+
+    for endpoint in rpc_endpoints.iter().map(|e| e.as_str()) {
+        output.progress(format_args!("테스트 중: {}", endpoint));
+
         // URL에서 호스트 추출
         let host = match extract_host(endpoint) {
             Some(host) => host,
             None => {
-                println!("  유효하지 않은 URL: {}", endpoint);
+                output.progress(format_args!("  유효하지 않은 URL: {}", endpoint));
                 continue;
             }
         };
-        
-        println!("  호스트: {}", host);
-        
-        // 결과 벡터 초기화
-        results.insert(endpoint, Vec::new());
-        
-        // Ping 테스트 실행 (5회)
-        for i in 1..=5 {
-            print!("  Ping #{}: ", i);
-            
-            let start = Instant::now();
-            
-            #[cfg(target_os = "windows")]
-            let ping_result = Command::new("ping")
-                .args(&["-n", "1", &host])
-                .output();
-                
-            #[cfg(not(target_os = "windows"))]
-            let ping_result = Command::new("ping")
-                .args(&["-c", "1", &host])
-                .output();
-            
-            match ping_result {
-                Ok(output) => {
-                    let _elapsed = start.elapsed();
-                    let output_str = String::from_utf8_lossy(&output.stdout);
-                    
-                    // Ping 시간 추출
-                    if let Some(time) = extract_ping_time(&output_str) {
-                        println!("{:?}", time);
-                        results.get_mut(endpoint).unwrap().push(time);
-                    } else {
-                        println!("응답 없음");
-                        results.get_mut(endpoint).unwrap().push(Duration::from_secs(999));
-                    }
-                },
-                Err(e) => {
-                    println!("오류: {:?}", e);
-                    results.get_mut(endpoint).unwrap().push(Duration::from_secs(999));
+
+        output.progress(format_args!("  호스트: {}", host));
+
+        // 예열 (통계에서 제외)
+        for _ in 0..options.rounds.warmup {
+            let _ = ping_once(&host, options.timeout);
+            thread::sleep(options.interval);
+        }
+
+        let (latency, failures, samples) = results.entry(endpoint).or_default();
+
+        let start = Instant::now();
+        for _ in 0..options.rounds.iterations {
+            let offset = start.elapsed();
+            let result = ping_once(&host, options.timeout);
+            let sample = samples.record(offset, result.as_ref().copied().map_err(|failure| failure.kind));
+            match result {
+                Ok(time) => {
+                    output.progress(format_args!("  Ping {}: {:?}", position(sample), time));
+                    latency.record(time);
+                }
+                Err(failure) => {
+                    output.progress(format_args!("  Ping {}: 손실 - {}", position(sample), failure.message));
+                    failures.record(&failure);
                 }
             }
-            
-            // 다음 ping 전 약간의 지연
-            thread::sleep(Duration::from_millis(200));
+
+            // 다음 ping 전 대기
+            thread::sleep(options.interval);
         }
-        
-        println!();
+
+        if output.is_table() {
+            reporting::print_histogram("Ping", latency, failures.total());
+            print_loss_and_jitter(samples);
+            reporting::print_failures("실패", latency.len(), failures);
+        }
+        output.progress("");
     }
-    
-    // 종합 결과 출력
-    println!("\n===== Ping 테스트 결과 =====");
-    println!("| RPC 엔드포인트 | 평균 지연시간 | 최소 지연시간 | 최대 지연시간 |");
-    println!("|----------------|--------------|--------------|--------------|");
-    
-    let mut avg_times: Vec<(String, Duration)> = Vec::new();
-    
-    for (&endpoint, durations) in &results {
-        // 유효한 응답만 필터링 (오류가 발생한 경우 제외)
-        let valid_durations: Vec<&Duration> = durations.iter()
-            .filter(|&&d| d != Duration::from_secs(999))
+
+    if !output.is_table() {
+        // 유효하지 않은 URL은 목록에서 제외
+        let tested: Vec<(&str, &(LatencyHistogram, FailureCounts, Timeline))> = rpc_endpoints
+            .iter()
+            .filter_map(|endpoint| Some((endpoint.as_str(), results.get(endpoint.as_str())?)))
             .collect();
-            
-        if valid_durations.is_empty() {
-            println!("| {:<14} | 응답 없음     | -            | -            |", endpoint);
-            continue;
+        if timeline {
+            let records: Vec<ProbeRecord> = tested
+                .iter()
+                .flat_map(|(endpoint, (_, _, samples))| samples.samples().iter().map(|sample| ProbeRecord::new(endpoint, None, sample)))
+                .collect();
+            output.print(&records);
+        } else {
+            let records: Vec<LatencyRecord> = tested
+                .iter()
+                .map(|(endpoint, (latency, failures, samples))| LatencyRecord {
+                    jitter_ms: samples.jitter().map(output::millis),
+                    ..LatencyRecord::new(endpoint, "ping", latency.summary().as_ref(), failures)
+                })
+                .collect();
+            output.print(&records);
         }
-        
-        // 통계 계산
-        let sum: Duration = valid_durations.iter().fold(Duration::from_secs(0), |acc, &x| acc + *x);
-        let avg = sum / valid_durations.len() as u32;
-        let min = valid_durations.iter().min().unwrap();
-        let max = valid_durations.iter().max().unwrap();
-        
-        println!("| {:<14} | {:?} | {:?} | {:?} |", 
-            endpoint, avg, min, max);
-            
-        avg_times.push((endpoint.to_string(), avg));
+        return;
     }
-    
-    // 가장 빠른 RPC 서버 확인
-    if !avg_times.is_empty() {
-        avg_times.sort_by_key(|(_, duration)| *duration);
-        println!("\n🏆 가장 낮은 지연시간 RPC 서버: {} (평균: {:?})", avg_times[0].0, avg_times[0].1);
+
+    // 종합 결과 출력 (응답이 없었던 측정은 제외, 유효하지 않은 URL은 목록에서 제외)
+    let tested: Vec<(&String, &(LatencyHistogram, FailureCounts, Timeline))> = rpc_endpoints
+        .iter()
+        .filter_map(|endpoint| Some((endpoint, results.get(endpoint.as_str())?)))
+        .collect();
+    let summaries: Vec<(String, Option<LatencySummary>)> = tested
+        .iter()
+        .map(|(endpoint, (latency, _, _))| (endpoint.to_string(), latency.summary()))
+        .collect();
+
+    reporting::print_latency_table(
+        &TableLabels {
+            title: "ICMP Ping 테스트 결과",
+            metric: "지연시간",
+            failure: "응답 없음",
+            fastest: "가장 낮은 지연시간 RPC 서버",
+        },
+        &summaries,
+    );
+    let stability: Vec<(String, &Timeline)> =
+        tested.iter().map(|(endpoint, (_, _, samples))| (endpoint.to_string(), samples)).collect();
+    reporting::print_loss_and_jitter(&stability);
+}
+
+// 시스템 ping 명령으로 한 번 측정 (응답이 없으면 타임아웃, 실행에 실패하면 기타 오류)
+fn ping_once(host: &str, timeout: Duration) -> Result<Duration, Failure> {
+    // 응답 대기 시간 (Windows와 macOS는 밀리초, 그 밖의 Unix는 초)
+    #[cfg(target_os = "windows")]
+    let ping_result = Command::new("ping")
+        .args(["-n", "1", "-w", &timeout.as_millis().to_string(), host])
+        .output();
+
+    #[cfg(target_os = "macos")]
+    let ping_result = Command::new("ping")
+        .args(["-c", "1", "-W", &timeout.as_millis().to_string(), host])
+        .output();
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let ping_result = Command::new("ping")
+        .args(["-c", "1", "-W", &timeout.as_secs().max(1).to_string(), host])
+        .output();
+
+    match ping_result {
+        // Ping 시간 추출 (1ms 미만도 그대로)
+        Ok(ping_output) => probe::parse_ping_time(&String::from_utf8_lossy(&ping_output.stdout))
+            .ok_or_else(|| Failure::new(FailureKind::Timeout, "응답 없음")),
+        Err(e) => Err(Failure::new(FailureKind::Other, format!("오류: {:?}", e))),
     }
 }
 
@@ -136,32 +369,3 @@ fn extract_host(url_str: &str) -> Option<String> {
         Err(_) => None,
     }
 }
-
-// Ping 결과에서 시간 추출
-fn extract_ping_time(ping_output: &str) -> Option<Duration> {
-    // Windows 형식의 ping 출력에서 시간 추출
-    #[cfg(target_os = "windows")]
-    {
-        let time_str = ping_output.lines()
-            .find(|line| line.contains("시간=") || line.contains("time="))
-            .and_then(|line| {
-                line.split("=").nth(3).or_else(|| line.split("=").nth(2))
-            })
-            .and_then(|s| s.trim_end_matches("ms").trim().parse::<u64>().ok());
-            
-        time_str.map(|ms| Duration::from_millis(ms))
-    }
-    
-    // Unix 형식의 ping 출력에서 시간 추출
-    #[cfg(not(target_os = "windows"))]
-    {
-        let time_str = ping_output.lines()
-            .find(|line| line.contains("time="))
-            .and_then(|line| {
-                line.split("time=").nth(1)
-            })
-            .and_then(|s| s.trim_end_matches(" ms").trim().parse::<f64>().ok());
-            
-        time_str.map(|ms| Duration::from_millis(ms as u64))
-    }
-} 
\ No newline at end of file
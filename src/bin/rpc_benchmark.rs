@@ -1,145 +1,264 @@
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::commitment_config::CommitmentConfig;
-use std::time::{Duration, Instant};
+use solana_transfer_bot::benchmark::{self, OperationStats, Rounds};
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::failure::FailureCounts;
+use solana_transfer_bot::freshness::{self, FreshnessOptions};
+use solana_transfer_bot::landing::TestRun;
+use solana_transfer_bot::load::{self, LoadOptions, LoadShape};
+use solana_transfer_bot::output::{FreshnessRecord, LatencyRecord, LoadRecord, OutputFormat, SubscriptionRecord};
+use solana_transfer_bot::reporting::{self, MethodRow};
+use solana_transfer_bot::subscription::{self, SubscriptionOptions, TestTransfers};
+use solana_transfer_bot::cluster::{self, Cluster};
+use solana_transfer_bot::config::Settings;
+use solana_transfer_bot::rpc;
 use std::collections::HashMap;
 use std::env;
-use dotenv::dotenv;
+use std::sync::Arc;
 
-// 공식 RPC 엔드포인트
-const OFFICIAL_RPC: &str = "https://api.mainnet-beta.solana.com";
+// 기본 반복 횟수 (--iterations, --warmup)
+const DEFAULT_ROUNDS: Rounds = Rounds { iterations: 20, warmup: 2 };
 
-fn main() {
+#[tokio::main]
+async fn main() {
     // .env 파일에서 환경 변수 로드
-    dotenv().ok();
+    rpc::load_env();
     
-    println!("솔라나 RPC 서버 성능 벤치마크 테스트를 시작합니다...");
-    println!("각 RPC 서버에 대해 다양한 작업의 응답 시간을 측정합니다.\n");
-
-    // 환경 변수에서 RPC URL 불러오기
-    let custom_rpc_url = env::var("SOLANA_RPC_URL").unwrap_or_else(|_| {
-        println!("환경변수 SOLANA_RPC_URL이 설정되지 않았습니다. 기본 RPC URL만 테스트합니다.");
-        String::new()
+    // 출력 형식 (--output json|csv|table), 반복 횟수 (--iterations, --warmup), 측정할 메서드 (--methods)
+    // 측정할 RPC와 테스트 지갑은 설정(--profile, --rpc-url, --keypair, 설정 파일)에서 결정
+    let args = Args::parse(env::args().skip(1));
+    let parsed = OutputFormat::from_args(&args).and_then(|output| {
+        Ok((output, Rounds::from_args(&args, DEFAULT_ROUNDS)?, benchmark::suite_from_args(&args)?, Settings::load(&args)?))
     });
-    
-    // RPC 엔드포인트 목록 생성
-    let mut rpc_endpoints = vec![OFFICIAL_RPC];
-    if !custom_rpc_url.is_empty() {
-        rpc_endpoints.push(&custom_rpc_url);
-    }
-    
-    // 결과를 저장할 맵
-    let mut results: HashMap<&str, Vec<Duration>> = HashMap::new();
+    let (output, rounds, suite, settings) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
 
-    // 각 RPC 엔드포인트에 대해 테스트 실행
-    for &endpoint in &rpc_endpoints {
-        println!("테스트 중: {}", endpoint);
-        
-        // RPC 클라이언트 생성
-        let client = match RpcClient::new_with_timeout(endpoint.to_string(), Duration::from_secs(30)) {
-            client => client,
-        };
+    // RPC 엔드포인트 목록 생성 (프로필의 공식 RPC + 설정된 RPC)
+    let rpc_endpoints = settings.benchmark_endpoints(rpc::skip_official_rpc());
 
-        // 결과 벡터 초기화
-        results.insert(endpoint, Vec::new());
-        
-        // 테스트 1: 연결 테스트 (getVersion)
-        print!("  - 버전 확인: ");
-        let start = Instant::now();
-        match client.get_version() {
-            Ok(_) => {
-                let elapsed = start.elapsed();
-                println!("{:?}", elapsed);
-                results.get_mut(endpoint).unwrap().push(elapsed);
-            },
+    // 부하 테스트 모드 (--load)
+    if args.has("load") {
+        match LoadOptions::from_args(&args) {
+            Ok(options) => run_load(output, &options, &rpc_endpoints).await,
             Err(e) => {
-                println!("오류: {:?}", e);
-                results.get_mut(endpoint).unwrap().push(Duration::from_secs(999));
+                eprintln!("{}", e);
+                std::process::exit(1);
             }
         }
-        
-        // 테스트 2: 블록 해시 가져오기
-        print!("  - 최근 블록해시 가져오기: ");
-        let start = Instant::now();
-        match client.get_latest_blockhash() {
-            Ok(_) => {
-                let elapsed = start.elapsed();
-                println!("{:?}", elapsed);
-                results.get_mut(endpoint).unwrap().push(elapsed);
-            },
+        return;
+    }
+
+    // 최신성 비교 모드 (--freshness)
+    if args.has("freshness") {
+        match FreshnessOptions::from_args(&args) {
+            Ok(options) => run_freshness(output, &options, &rpc_endpoints).await,
             Err(e) => {
-                println!("오류: {:?}", e);
-                results.get_mut(endpoint).unwrap().push(Duration::from_secs(999));
+                eprintln!("{}", e);
+                std::process::exit(1);
             }
         }
-        
-        // 테스트 3: 슬롯 정보 가져오기
-        print!("  - 현재 슬롯 가져오기: ");
-        let start = Instant::now();
-        match client.get_slot_with_commitment(CommitmentConfig::confirmed()) {
-            Ok(_) => {
-                let elapsed = start.elapsed();
-                println!("{:?}", elapsed);
-                results.get_mut(endpoint).unwrap().push(elapsed);
-            },
+        return;
+    }
+
+    // 웹소켓 구독 알림 지연 비교 모드 (--pubsub)
+    if args.has("pubsub") {
+        match SubscriptionOptions::from_args(&args) {
+            Ok(options) => run_subscriptions(output, &options, &args, &settings, &rpc_endpoints).await,
             Err(e) => {
-                println!("오류: {:?}", e);
-                results.get_mut(endpoint).unwrap().push(Duration::from_secs(999));
+                eprintln!("{}", e);
+                std::process::exit(1);
             }
         }
+        return;
+    }
+
+    output.progress("솔라나 RPC 서버 성능 벤치마크 테스트를 시작합니다...");
+    let methods: Vec<&str> = suite.iter().map(|operation| operation.method()).collect();
+    output.progress(format_args!(
+        "각 RPC 서버에 대해 메서드별 응답 시간을 측정합니다 (예열 {}회 후 {}회 반복): {}\n",
+        rounds.warmup,
+        rounds.iterations,
+        methods.join(", ")
+    ));
+
+    // 엔드포인트들을 동시에 측정 (한 엔드포인트 안의 작업은 순서대로 실행)
+    // 벤치마크용 클라이언트는 요청 한도 초과를 재시도하지 않고 그대로 돌려줌
+    let tasks: Vec<_> = rpc_endpoints
+        .iter()
+        .map(|endpoint| {
+            let client = rpc::benchmark_client(endpoint);
+            let suite = suite.clone();
+            tokio::spawn(async move { benchmark::run_rounds(&client, &suite, rounds).await })
+        })
+        .collect();
+    
+    // (엔드포인트, 메서드)별 측정 결과
+    let mut results: HashMap<(&str, &str), OperationStats> = HashMap::new();
+    // 작업별 결과 (기계가 읽는 출력용)
+    let mut records: Vec<LatencyRecord> = Vec::new();
+
+    // 엔드포인트 순서대로 측정 결과 출력
+    for (endpoint, task) in rpc_endpoints.iter().map(|e| e.as_str()).zip(tasks) {
+        output.progress(format_args!("테스트 중: {}", endpoint));
+        let measurements = task.await.expect("벤치마크 작업 실패");
         
-        // 테스트 4: 지갑 잔액 확인 (큰 지갑 사용)
-        print!("  - 대형 지갑 잔액 확인: ");
-        let start = Instant::now();
-        match client.get_balance(&"4Rf9mGD7FeYknun5JczX5nGLTfQuS1GRjwA3iseBQxP4".parse().unwrap()) {
-            Ok(_) => {
-                let elapsed = start.elapsed();
-                println!("{:?}", elapsed);
-                results.get_mut(endpoint).unwrap().push(elapsed);
-            },
-            Err(e) => {
-                println!("오류: {:?}", e);
-                results.get_mut(endpoint).unwrap().push(Duration::from_secs(999));
+        for stats in measurements {
+            if output.is_table() {
+                reporting::print_histogram(stats.operation.label(), &stats.latency, stats.failures.total());
+                reporting::print_failures("실패", stats.latency.len(), &stats.failures);
+            } else if let Some(error) = &stats.failures.last_error {
+                output.progress(format_args!("    마지막 오류: {}", error));
             }
+            let summary = stats.latency.summary();
+            records.push(LatencyRecord::new(endpoint, stats.operation.method(), summary.as_ref(), &stats.failures));
+            results.insert((endpoint, stats.operation.method()), stats);
         }
         
-        println!();
+        output.progress("");
+    }
+
+    if !output.is_table() {
+        output.print(&records);
+        return;
     }
     
-    // 종합 결과 출력
-    println!("\n===== 종합 결과 =====");
-    println!("| RPC 엔드포인트 | 평균 응답시간 | 최소 응답시간 | 최대 응답시간 |");
-    println!("|----------------|--------------|--------------|--------------|");
-    
-    // 평균 시간을 저장할 벡터 (문자열로 저장하여 참조 문제 해결)
-    let mut avg_times: Vec<(String, Duration)> = Vec::new();
+    // 메서드별로 엔드포인트를 비교 (오류가 발생한 측정은 통계에서 제외)
+    let rows: Vec<MethodRow> = methods
+        .iter()
+        .map(|&method| {
+            let cells = rpc_endpoints
+                .iter()
+                .map(|endpoint| {
+                    let stats = &results[&(endpoint.as_str(), method)];
+                    (stats.latency.summary(), stats.failures.total())
+                })
+                .collect();
+            (method, cells)
+        })
+        .collect();
     
-    for (&endpoint, durations) in &results {
-        // 유효한 응답만 필터링 (오류가 발생한 경우 제외)
-        let valid_durations: Vec<&Duration> = durations.iter()
-            .filter(|&&d| d != Duration::from_secs(999))
-            .collect();
-            
-        if valid_durations.is_empty() {
-            println!("| {:<14} | 연결 오류     | -            | -            |", endpoint);
-            continue;
+    reporting::print_method_matrix(&rpc_endpoints, &rows);
+
+    // 엔드포인트별 실패 종류 (모든 메서드 합계)
+    println!("\n===== 엔드포인트별 실패 =====");
+    for endpoint in &rpc_endpoints {
+        let mut failures = FailureCounts::new();
+        let mut successes = 0;
+        for method in &methods {
+            let stats = &results[&(endpoint.as_str(), *method)];
+            failures.merge(&stats.failures);
+            successes += stats.latency.len();
+        }
+        match failures.is_empty() {
+            true => println!("{}: 실패 없음", endpoint),
+            false => {
+                println!("{}", endpoint);
+                reporting::print_failures("실패", successes, &failures);
+            }
         }
-        
-        // 통계 계산
-        let sum: Duration = valid_durations.iter().fold(Duration::from_secs(0), |acc, &x| acc + *x);
-        let avg = sum / valid_durations.len() as u32;
-        let min = valid_durations.iter().min().unwrap();
-        let max = valid_durations.iter().max().unwrap();
-        
-        println!("| {:<14} | {:?} | {:?} | {:?} |", 
-            endpoint, avg, min, max);
-            
-        // 문자열로 변환하여 저장
-        avg_times.push((endpoint.to_string(), avg));
     }
-    
-    // 가장 빠른 RPC 서버 확인
-    if !avg_times.is_empty() {
-        avg_times.sort_by_key(|(_, duration)| *duration);
-        println!("\n🏆 가장 빠른 RPC 서버: {} (평균: {:?})", avg_times[0].0, avg_times[0].1);
+}
+
+// 엔드포인트마다 차례로 부하를 걸고 결과 출력 (동시에 걸면 클라이언트 쪽 자원을 나눠 쓰므로 순서대로)
+async fn run_load(output: OutputFormat, options: &LoadOptions, endpoints: &[String]) {
+    let shape = match options.shape {
+        LoadShape::Rate { from, to } if from == to => format!("초당 {}개 요청", to),
+        LoadShape::Rate { from, to } => format!("초당 {}개에서 {}개까지 늘리며 요청", from, to),
+        LoadShape::Concurrency(workers) => format!("동시 요청 {}개", workers),
+    };
+    let mix: Vec<String> = options
+        .mix
+        .iter()
+        .map(|(operation, weight)| format!("{}:{}", operation.method(), weight))
+        .collect();
+    output.progress(format_args!(
+        "솔라나 RPC 서버 부하 테스트를 시작합니다: {}, {:?} 동안 (타임아웃 {:?}, 메서드 {})\n",
+        shape,
+        options.duration,
+        options.timeout,
+        mix.join(",")
+    ));
+
+    let mut records: Vec<LoadRecord> = Vec::new();
+    for endpoint in endpoints {
+        output.progress(format_args!("부하 테스트 중: {}", endpoint));
+        let report = load::run_load(Arc::new(rpc::benchmark_client(endpoint)), options).await;
+        if output.is_table() {
+            reporting::print_load_report(endpoint, &report);
+            println!();
+        }
+        records.extend(LoadRecord::from_report(endpoint, &report));
+    }
+    output.print(&records);
+}
+
+// 모든 엔드포인트를 같은 시점에 조회해 뒤처진 정도 비교
+async fn run_freshness(output: OutputFormat, options: &FreshnessOptions, endpoints: &[String]) {
+    output.progress(format_args!(
+        "솔라나 RPC 서버 최신성 비교를 시작합니다: {:?} 동안 {:?}마다 슬롯, 블록 높이, 블록해시 조회 (표본 {}개)\n",
+        options.duration,
+        options.interval,
+        options.samples()
+    ));
+
+    let clients: Vec<_> = endpoints.iter().map(|endpoint| Arc::new(rpc::benchmark_client(endpoint))).collect();
+    let report = freshness::run_freshness(&clients, options).await;
+    if output.is_table() {
+        reporting::print_freshness(endpoints, &report);
+    }
+    output.print(&FreshnessRecord::from_report(endpoints, &report));
+}
+
+// 모든 엔드포인트의 웹소켓에 같은 구독을 열고 알림이 도착하는 시각 비교
+async fn run_subscriptions(output: OutputFormat, options: &SubscriptionOptions, args: &Args, settings: &Settings, endpoints: &[String]) {
+    let ws_urls: Vec<String> = endpoints.iter().map(|endpoint| settings.benchmark_ws_url(endpoint)).collect();
+
+    // signatureSubscribe는 테스트 지갑이 설정되었을 때만 (설정된 RPC로 전송)
+    let payer = match settings.configured_keypair() {
+        Some(Ok(payer)) => Some(payer),
+        Some(Err(e)) => {
+            output.progress(format_args!("테스트 지갑을 불러올 수 없습니다: {}", e));
+            None
+        }
+        None => None,
+    };
+    let transfers = match payer {
+        Some(payer) if options.signatures > 0 => {
+            let backend = rpc::async_client(&settings.rpc_url.value);
+            // 실제 전송이므로 메인넷이면 확인받고, 클러스터를 알 수 없거나 확인하지 않으면 전송하지 않음
+            let confirmed = Cluster::detect_async(&backend)
+                .await
+                .and_then(|cluster| cluster::confirm_mainnet(&cluster, args));
+            match confirmed {
+                Ok(()) => {
+                    let run = TestRun::new();
+                    output.progress(format_args!("signatureSubscribe 실행 ID: {}", run.id()));
+                    Some(TestTransfers { backend, payer, run })
+                }
+                Err(e) => {
+                    output.progress(format_args!("signatureSubscribe 전송을 건너뜁니다: {}", e));
+                    None
+                }
+            }
+        }
+        _ => {
+            output.progress("테스트 지갑(--keypair, SOLANA_KEYPAIR, SOLANA_PRIVATE_KEY)이 없거나 --signatures가 0이므로 signatureSubscribe는 건너뜁니다.");
+            None
+        }
+    };
+    output.progress(format_args!(
+        "솔라나 RPC 웹소켓 구독 알림 지연 비교를 시작합니다: {:?} 동안 slotSubscribe, accountSubscribe({}){}\n",
+        options.duration,
+        options.account,
+        if transfers.is_some() { format!(", signatureSubscribe(전송 {}건)", options.signatures) } else { String::new() }
+    ));
+
+    let report = subscription::run_subscriptions(&ws_urls, options, transfers).await;
+    if output.is_table() {
+        reporting::print_subscriptions(endpoints, &report);
     }
-} 
\ No newline at end of file
+    output.print(&SubscriptionRecord::from_report(endpoints, &report));
+}
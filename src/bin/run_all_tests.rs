@@ -1,36 +1,51 @@
-use std::process::Command;
-use std::io::{self, Write};
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::config::{self, Settings, Source};
+use solana_transfer_bot::output::{self, LatencyRecord, OutputFormat};
+use solana_transfer_bot::rpc;
 use std::env;
-use dotenv::dotenv;
+use std::process::{Command, Stdio};
+use std::io::{self, Write};
 
 fn main() {
     // .env 파일에서 환경 변수 로드
-    dotenv().ok();
+    rpc::load_env();
+    
+    // 출력 형식 (--output json|csv|table)과 설정 (각 테스트에 같은 설정 옵션을 넘김)
+    let args = Args::parse(env::args().skip(1));
+    let (output, settings) = match OutputFormat::from_args(&args).and_then(|output| Ok((output, Settings::load(&args)?))) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let forwarded = settings_args(&args);
+    let has_keypair = settings.configured_keypair().is_some();
+    if !output.is_table() {
+        run_unattended(output, &forwarded, has_keypair);
+        return;
+    }
     
     println!("===============================================");
     println!("🚀 솔라나 RPC 종합 성능 테스트를 시작합니다 🚀");
     println!("===============================================\n");
     
-    // 환경 변수 확인
-    let rpc_url = env::var("SOLANA_RPC_URL").unwrap_or_else(|_| {
-        println!("⚠️ 환경변수 SOLANA_RPC_URL이 설정되지 않았습니다. 기본 RPC URL만 테스트합니다.");
-        String::new()
-    });
-    
-    if !rpc_url.is_empty() {
-        println!("🔍 테스트할 커스텀 RPC URL: {}\n", rpc_url);
+    // 설정 확인
+    println!("🔍 프로필: {}", settings.profile.value);
+    match settings.rpc_url.source {
+        Source::Default => println!("⚠️ RPC URL이 설정되지 않았습니다. 프로필 기본 RPC URL만 테스트합니다."),
+        _ => println!("🔍 테스트할 RPC URL: {}\n", config::redact_url(&settings.rpc_url.value)),
     }
     
-    let has_private_key = env::var("SOLANA_PRIVATE_KEY").is_ok();
-    if !has_private_key {
-        println!("⚠️ 환경변수 SOLANA_PRIVATE_KEY가 설정되지 않았습니다. 트랜잭션 테스트는 건너뛰게 됩니다.\n");
+    if !has_keypair {
+        println!("⚠️ 테스트 지갑(--keypair, SOLANA_KEYPAIR, SOLANA_PRIVATE_KEY)이 설정되지 않았습니다. 트랜잭션 테스트는 건너뛰게 됩니다.\n");
     }
     
     // 테스트 순서 안내
     println!("다음 순서로 테스트가 진행됩니다:");
     println!("1. Ping 테스트 (네트워크 지연 시간)");
     println!("2. RPC API 벤치마크 (API 응답 시간)");
-    if has_private_key {
+    if has_keypair {
         println!("3. 트랜잭션 속도 테스트 (실제 거래 처리 시간)\n");
     } else {
         println!("3. 트랜잭션 속도 테스트 (건너뜀 - 개인키 없음)\n");
@@ -45,7 +60,8 @@ fn main() {
     // 1. Ping 테스트 실행
     println!("\n\n===== 1️⃣ Ping 테스트 시작 =====\n");
     let status = Command::new("cargo")
-        .args(["run", "--release", "--bin", "ping_test"])
+        .args(["run", "--release", "--bin", "ping_test", "--"])
+        .args(&forwarded)
         .status()
         .expect("Ping 테스트 실행 실패");
     
@@ -62,7 +78,8 @@ fn main() {
     // 2. RPC 벤치마크 테스트 실행
     println!("\n\n===== 2️⃣ RPC API 벤치마크 테스트 시작 =====\n");
     let status = Command::new("cargo")
-        .args(["run", "--release", "--bin", "rpc_benchmark"])
+        .args(["run", "--release", "--bin", "rpc_benchmark", "--"])
+        .args(&forwarded)
         .status()
         .expect("RPC 벤치마크 테스트 실행 실패");
     
@@ -71,7 +88,7 @@ fn main() {
     }
     
     // 개인키가 있는 경우에만 트랜잭션 테스트 실행
-    if has_private_key {
+    if has_keypair {
         // 다음 테스트 전 잠시 대기
         println!("\n계속하려면 Enter 키를 누르세요...");
         io::stdout().flush().unwrap();
@@ -81,7 +98,8 @@ fn main() {
         // 3. 트랜잭션 속도 테스트 실행
         println!("\n\n===== 3️⃣ 트랜잭션 속도 테스트 시작 =====\n");
         let status = Command::new("cargo")
-            .args(["run", "--release", "--bin", "tx_speed_test"])
+            .args(["run", "--release", "--bin", "tx_speed_test", "--"])
+            .args(&forwarded)
             .status()
             .expect("트랜잭션 속도 테스트 실행 실패");
         
@@ -89,11 +107,58 @@ fn main() {
             println!("⚠️ 트랜잭션 속도 테스트가 비정상적으로 종료되었습니다.");
         }
     } else {
-        println!("\n\n❌ 환경변수 SOLANA_PRIVATE_KEY가 설정되지 않아 트랜잭션 속도 테스트를 건너뜁니다.");
+        println!("\n\n❌ 테스트 지갑이 설정되지 않아 트랜잭션 속도 테스트를 건너뜁니다.");
     }
     
     // 테스트 종료
     println!("\n===============================================");
     println!("🎉 모든 RPC 성능 테스트가 완료되었습니다 🎉");
     println!("===============================================");
-} 
\ No newline at end of file
+} 
+
+// JSON/CSV 출력: 확인 없이 각 테스트를 JSON 출력으로 실행하고 결과를 하나로 합쳐 출력
+// (안내 문구와 각 테스트의 진행 상황은 표준 오류로 출력)
+fn run_unattended(output: OutputFormat, forwarded: &[String], has_keypair: bool) {
+    let mut tests = vec![("ping_test", "Ping 테스트"), ("rpc_benchmark", "RPC 벤치마크 테스트")];
+    if has_keypair {
+        tests.push(("tx_speed_test", "트랜잭션 속도 테스트"));
+    } else {
+        eprintln!("테스트 지갑이 설정되지 않아 트랜잭션 속도 테스트를 건너뜁니다.");
+    }
+
+    let mut records: Vec<LatencyRecord> = Vec::new();
+    let mut failed = false;
+    for (bin, name) in tests {
+        eprintln!("===== {} =====", name);
+        let result = Command::new("cargo")
+            .args(["run", "--release", "--bin", bin, "--", "--output", "json"])
+            .args(forwarded)
+            .stderr(Stdio::inherit())
+            .output();
+        let parsed = match result {
+            Ok(child) => output::parse_json::<LatencyRecord>(&String::from_utf8_lossy(&child.stdout)),
+            Err(e) => Err(e.into()),
+        };
+        match parsed {
+            Ok(child_records) => records.extend(child_records),
+            Err(e) => {
+                eprintln!("{} 결과를 읽을 수 없습니다: {}", name, e);
+                failed = true;
+            }
+        }
+    }
+
+    output.print(&records);
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+// 각 테스트에 그대로 넘길 설정 옵션 (--profile, --config, --rpc-url ...)
+fn settings_args(args: &Args) -> Vec<String> {
+    config::OPTIONS
+        .iter()
+        .filter_map(|name| Some(vec![format!("--{}", name), args.value(name)?.to_string()]))
+        .flatten()
+        .collect()
+}
@@ -1,180 +1,382 @@
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::{
-    commitment_config::CommitmentConfig,
-    pubkey::Pubkey,
-    signature::{Keypair, Signer},
-    system_instruction,
-    transaction::Transaction,
-    compute_budget::ComputeBudgetInstruction,
-};
-use std::env;
-use std::time::{Duration, Instant};
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_transfer_bot::benchmark::Rounds;
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::cluster::{self, Cluster};
+use solana_transfer_bot::config::Settings;
+use solana_transfer_bot::failure::FailureCounts;
+use solana_transfer_bot::fee_sweep::{self, SweepOptions};
+use solana_transfer_bot::landing::{self, Landing, LandingStats, TestRun};
+use solana_transfer_bot::output::{FeeSweepRecord, LandingRecord, LatencyRecord, OutputFormat, ThroughputRecord};
+use solana_transfer_bot::reporting::{self, TableLabels};
+use solana_transfer_bot::stats::{LatencyHistogram, LatencySummary};
+use solana_transfer_bot::throughput::{self, ThroughputOptions, ThroughputReport};
+use solana_transfer_bot::{rpc, transfer, Error};
 use std::collections::HashMap;
-use dotenv::dotenv;
-
-// 테스트할 RPC 엔드포인트 목록
-const OFFICIAL_RPC: &str = "https://api.mainnet-beta.solana.com";
+use std::env;
+use std::sync::Arc;
 
-// 테스트 트랜잭션 수
-const TEST_TX_COUNT: usize = 3;
+// 기본 테스트 트랜잭션 수 (--iterations, --warmup; 예열 트랜잭션도 실제로 전송되어 수수료가 듭니다)
+const DEFAULT_ROUNDS: Rounds = Rounds { iterations: 3, warmup: 0 };
 
 // 테스트에 사용할 SOL 금액 (0.000001 SOL = 1000 lamports)
 const TEST_LAMPORTS: u64 = 1000;
 
-fn main() {
+// 빠른 확인을 위한 우선순위 수수료
+const TEST_PRIORITY_FEE: u64 = 5;
+
+#[tokio::main]
+async fn main() {
     // .env 파일에서 환경 변수 로드
-    dotenv().ok();
+    rpc::load_env();
     
-    println!("솔라나 RPC 서버 트랜잭션 속도 테스트를 시작합니다...");
-    println!("각 RPC 서버의 트랜잭션 처리 시간을 측정합니다.\n");
+    // 출력 형식 (--output json|csv|table)과 반복 횟수 (--iterations, --warmup)
+    // 측정할 RPC와 테스트 지갑은 설정(--profile, --rpc-url, --keypair, 설정 파일)에서 결정
+    let args = Args::parse(env::args().skip(1));
+    // 요약 대신 트랜잭션 하나하나를 출력 (--transactions, JSON/CSV에서만)
+    let per_transaction = args.has("transactions");
+    let parsed = OutputFormat::from_args(&args)
+        .and_then(|output| Ok((output, Rounds::from_args(&args, DEFAULT_ROUNDS)?, Settings::load(&args)?)));
+    let (output, rounds, settings) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
     
-    // 환경 변수에서 RPC URL 불러오기
-    let custom_rpc_url = env::var("SOLANA_RPC_URL").unwrap_or_else(|_| {
-        println!("환경변수 SOLANA_RPC_URL이 설정되지 않았습니다. 기본 RPC URL만 테스트합니다.");
-        String::new()
-    });
+    output.progress("솔라나 RPC 서버 트랜잭션 속도 테스트를 시작합니다...");
+    output.progress("각 RPC 서버로 보낸 트랜잭션이 포함되기까지의 슬롯 수와 커미트먼트별 시간을 측정합니다.\n");
     
-    // RPC 엔드포인트 목록 생성
-    let mut rpc_endpoints = vec![OFFICIAL_RPC];
-    if !custom_rpc_url.is_empty() {
-        rpc_endpoints.push(&custom_rpc_url);
-    }
+    // RPC 엔드포인트 목록 생성 (프로필의 공식 RPC + 설정된 RPC)
+    let rpc_endpoints = settings.benchmark_endpoints(rpc::skip_official_rpc());
     
-    // 환경 변수에서 개인키 불러오기
-    let private_key = env::var("SOLANA_PRIVATE_KEY").unwrap_or_else(|_| {
-        panic!("환경변수 SOLANA_PRIVATE_KEY가 설정되지 않았습니다.");
-    });
+    // 설정된 테스트 지갑 불러오기
+    let sender_keypair = match load_keypair(&settings) {
+        Ok(keypair) => keypair,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    output.progress(format_args!("테스트 지갑 주소: {}", sender_keypair.pubkey()));
+    
+    // 측정 트랜잭션마다 실행 ID와 순번을 담은 메모를 붙여 서로 다른 트랜잭션이 되도록 함
+    let run = TestRun::new();
+    output.progress(format_args!("실행 ID: {}", run.id()));
+    
+    // compute unit 가격 격자별 포함률과 비용 비교 (--fee-sweep)
+    if args.has("fee-sweep") {
+        run_fee_sweep(output, &args, &settings, &sender_keypair, &run).await;
+        return;
+    }
     
-    let sender_keypair = create_keypair_from_base58(&private_key);
-    println!("테스트 지갑 주소: {}", sender_keypair.pubkey());
+    // 미리 서명한 트랜잭션을 목표 속도로 동시에 보내 초당 수락/포함 수와 드롭률 측정 (--throughput)
+    if args.has("throughput") {
+        run_throughput_test(output, &args, &rpc_endpoints, &sender_keypair, &run).await;
+        return;
+    }
     
-    // 결과를 저장할 맵
-    let mut results: HashMap<&str, Vec<Duration>> = HashMap::new();
+    // 엔드포인트별 포함 측정 결과
+    let mut results: HashMap<&str, LandingStats> = HashMap::new();
+    // 트랜잭션별 레코드 (--transactions)
+    let mut transactions: Vec<LandingRecord> = Vec::new();
     
     // 각 RPC 엔드포인트에 대해 테스트 실행
-    for &endpoint in &rpc_endpoints {
-        println!("\n테스트 중: {}", endpoint);
+    // 엔드포인트끼리 같은 지갑의 쓰기 잠금을 두고 경쟁하지 않도록 순서대로 실행한다
+    for endpoint in rpc_endpoints.iter().map(|e| e.as_str()) {
+        output.progress(format_args!("\n테스트 중: {}", endpoint));
         
         // RPC 클라이언트 생성
-        let client = RpcClient::new_with_timeout(endpoint.to_string(), Duration::from_secs(30));
+        let client = rpc::async_client(endpoint);
         
         // 지갑 잔액 확인
-        match client.get_balance(&sender_keypair.pubkey()) {
+        match client.get_balance(&sender_keypair.pubkey()).await {
             Ok(balance) => {
-                println!("  현재 잔액: {} SOL", balance as f64 / 1_000_000_000.0);
-                if balance < TEST_LAMPORTS * TEST_TX_COUNT as u64 + 10000 {
-                    println!("  경고: 잔액이 부족합니다. 테스트를 위해 최소 0.00001 SOL이 필요합니다.");
+                output.progress(format_args!("  현재 잔액: {} SOL", balance as f64 / 1_000_000_000.0));
+                if balance < TEST_LAMPORTS * (rounds.iterations + rounds.warmup) as u64 + 10000 {
+                    output.progress("  경고: 잔액이 부족합니다. 테스트를 위해 최소 0.00001 SOL이 필요합니다.");
                     continue;
                 }
             },
             Err(e) => {
-                println!("  잔액 확인 실패: {:?}", e);
+                output.progress(format_args!("  잔액 확인 실패: {}", Error::from(e)));
                 continue;
             }
         }
         
-        // 결과 벡터 초기화
-        results.insert(endpoint, Vec::new());
+        let stats = results.entry(endpoint).or_default();
         
-        // 테스트 수신자 생성 (자기 자신에게 전송)
-        let recipient = sender_keypair.pubkey();
+        // 예열 트랜잭션 (통계에서 제외)
+        for i in 1..=rounds.warmup {
+            output.progress(format_args!("  예열 트랜잭션 #{}", i));
+            let (_, instructions) = run.next_transfer(&sender_keypair.pubkey(), TEST_LAMPORTS);
+            if let Err(e) = transfer::send_instructions(&client, &sender_keypair, &[], instructions, TEST_PRIORITY_FEE).await {
+                output.progress(format_args!("    오류: {}", e));
+            }
+        }
         
         // 여러 번 트랜잭션 전송 테스트
-        for i in 1..=TEST_TX_COUNT {
-            println!("  트랜잭션 테스트 #{}", i);
+        for i in 1..=rounds.iterations {
+            output.progress(format_args!("  트랜잭션 테스트 #{}", i));
             
-            // 트랜잭션 전송 및 시간 측정
-            match send_sol_and_measure_time(&client, &sender_keypair, &recipient, TEST_LAMPORTS) {
-                Ok(elapsed) => {
-                    println!("    처리 시간: {:?}", elapsed);
-                    results.get_mut(endpoint).unwrap().push(elapsed);
+            // 트랜잭션 전송 후 finalized까지 상태 추적 (시간은 전송 요청부터)
+            output.progress("    트랜잭션 전송 중...");
+            // 자기 자신에게 보내는 전송 (메모: rpc-bench:<실행 ID>:<순번>)
+            let (sequence, instructions) = run.next_transfer(&sender_keypair.pubkey(), TEST_LAMPORTS);
+            let result = match transfer::build_transaction(&client, &sender_keypair, &[], instructions, TEST_PRIORITY_FEE).await {
+                Ok(transaction) => landing::track(&client, &transaction, &landing::TRACK_OPTIONS).await,
+                Err(e) => Err(e),
+            };
+            match &result {
+                Ok(landing) => {
+                    output.progress(format_args!("    서명: {}", landing.signature));
+                    output.progress(format_args!("    {}", describe(landing)));
                 },
-                Err(e) => {
-                    println!("    오류: {:?}", e);
-                }
+                Err(e) => output.progress(format_args!("    오류: {}", e)),
+            }
+            stats.record(&result);
+            if per_transaction {
+                transactions.push(LandingRecord {
+                    run_id: Some(run.id().to_string()),
+                    memo: Some(landing::memo(run.id(), sequence)),
+                    ..LandingRecord::new(endpoint, i, &result)
+                });
             }
         }
     }
     
-    // 종합 결과 출력
-    println!("\n===== 트랜잭션 처리 속도 결과 =====");
-    println!("| RPC 엔드포인트 | 평균 처리시간 | 최소 처리시간 | 최대 처리시간 |");
-    println!("|----------------|--------------|--------------|--------------|");
+    let tested: Vec<(&String, &LandingStats)> = rpc_endpoints
+        .iter()
+        .filter_map(|endpoint| Some((endpoint, results.get(endpoint.as_str())?)))
+        .collect();
+    
+    if !output.is_table() {
+        if per_transaction {
+            output.print(&transactions);
+            return;
+        }
+        // 커미트먼트마다 레코드 하나 (실패와 포함률은 전송 전체에 대한 값이므로 confirmed 레코드에만)
+        // 잔액 부족 등으로 테스트하지 않은 엔드포인트는 제외
+        let no_failures = FailureCounts::new();
+        let records: Vec<LatencyRecord> = tested
+            .iter()
+            .flat_map(|(endpoint, stats)| {
+                stats.phases().into_iter().map(|(operation, _, histogram)| {
+                    let confirmed = operation == "confirmed";
+                    let failures = if confirmed { &stats.failures } else { &no_failures };
+                    LatencyRecord {
+                        landing_rate: Some(stats.landing_rate()).filter(|_| confirmed),
+                        slots_to_land: stats.average_slots().filter(|_| confirmed),
+                        run_id: Some(run.id().to_string()),
+                        ..LatencyRecord::new(endpoint, operation, histogram.summary().as_ref(), failures)
+                    }
+                })
+            })
+            .collect();
+        output.print(&records);
+        return;
+    }
     
-    let mut avg_times: Vec<(String, Duration)> = Vec::new();
+    // 종합 결과 출력 (confirmed까지 시간 기준)
+    let summaries: Vec<(String, Option<LatencySummary>)> = tested
+        .iter()
+        .map(|(endpoint, stats)| {
+            println!("\n{}", endpoint);
+            let phases: Vec<(&str, &LatencyHistogram)> =
+                stats.phases().into_iter().map(|(_, label, histogram)| (label, histogram)).collect();
+            reporting::print_phases(&phases);
+            reporting::print_failures("실패한 전송", stats.sent - stats.failures.total(), &stats.failures);
+            (endpoint.to_string(), stats.confirmed.summary())
+        })
+        .collect();
     
-    for (&endpoint, durations) in &results {
-        if durations.is_empty() {
-            println!("| {:<14} | 테스트 실패   | -            | -            |", endpoint);
-            continue;
+    reporting::print_latency_table(
+        &TableLabels {
+            title: "트랜잭션 처리 속도 결과",
+            metric: "확인시간",
+            failure: "테스트 실패",
+            fastest: "가장 빠른 트랜잭션 처리 RPC 서버",
+        },
+        &summaries,
+    );
+    let landing: Vec<(String, &LandingStats)> = tested.iter().map(|(endpoint, stats)| (endpoint.to_string(), *stats)).collect();
+    reporting::print_landing(&landing);
+    println!("\n실행 ID: {} (트랜잭션 메모 {})", run.id(), landing::memo(run.id(), 1));
+}
+
+// 우선순위 수수료 스윕: 설정된 RPC 하나로 가격 단계마다 트랜잭션을 보내 비교
+async fn run_fee_sweep(output: OutputFormat, args: &Args, settings: &Settings, sender_keypair: &Keypair, run: &TestRun) {
+    let options = match SweepOptions::from_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let endpoint = &settings.rpc_url.value;
+    let client = rpc::async_client(endpoint);
+    // 단계마다 수수료를 내는 실제 전송이므로 메인넷이면 확인받음
+    confirm_cluster(&client, args).await;
+    
+    output.progress(format_args!(
+        "\n우선순위 수수료 스윕: {} ({}단계 × {}회, 목표 confirmed p50 {:?})",
+        endpoint,
+        options.levels.len(),
+        options.repeat,
+        options.target
+    ));
+    
+    // 모든 트랜잭션이 가장 비싼 단계의 수수료를 낸다고 보고 잔액 확인
+    let most_expensive = options.levels.iter().map(|level| level.cost()).max().unwrap_or(0);
+    let required = (most_expensive + TEST_LAMPORTS) * (options.levels.len() * options.repeat) as u64;
+    match client.get_balance(&sender_keypair.pubkey()).await {
+        Ok(balance) if balance < required => {
+            eprintln!(
+                "잔액이 부족합니다: {} SOL (최대 {} SOL 필요)",
+                balance as f64 / 1_000_000_000.0,
+                required as f64 / 1_000_000_000.0
+            );
+            std::process::exit(1);
+        },
+        Ok(_) => {},
+        Err(e) => {
+            eprintln!("잔액 확인 실패: {}", Error::from(e));
+            std::process::exit(1);
         }
-        
-        // 통계 계산
-        let sum: Duration = durations.iter().sum();
-        let avg = sum / durations.len() as u32;
-        let min = durations.iter().min().unwrap();
-        let max = durations.iter().max().unwrap();
-        
-        println!("| {:<14} | {:?} | {:?} | {:?} |", 
-            endpoint, avg, min, max);
-            
-        avg_times.push((endpoint.to_string(), avg));
     }
     
-    // 가장 빠른 RPC 서버 확인
-    if !avg_times.is_empty() {
-        avg_times.sort_by_key(|(_, duration)| *duration);
-        println!("\n🏆 가장 빠른 트랜잭션 처리 RPC 서버: {} (평균: {:?})", avg_times[0].0, avg_times[0].1);
+    let report = fee_sweep::run_sweep(&client, sender_keypair, run, TEST_LAMPORTS, &options, &landing::TRACK_OPTIONS, output).await;
+    if output.is_table() {
+        reporting::print_fee_sweep(&report);
+        println!("실행 ID: {}", run.id());
+    } else {
+        output.print(&FeeSweepRecord::from_report(endpoint, &report));
     }
 }
 
-// Base58 인코딩된 개인키로부터 Keypair 생성
-fn create_keypair_from_base58(private_key: &str) -> Keypair {
-    let bytes = bs58::decode(private_key)
-        .into_vec()
-        .expect("유효하지 않은 개인키");
+// 병렬 전송 처리량: 테스트 계정을 채운 뒤 엔드포인트마다 미리 서명한 트랜잭션 묶음을 목표 속도로 전송
+async fn run_throughput_test(output: OutputFormat, args: &Args, endpoints: &[String], sender_keypair: &Keypair, run: &TestRun) {
+    let options = match ThroughputOptions::from_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    // 지갑 키에서 만든 테스트 계정 (지난 실행에서 남은 잔액은 그대로 사용)
+    let accounts = throughput::test_accounts(sender_keypair, options.accounts);
+    let funding = rpc::async_client(endpoints.last().expect("벤치마크 엔드포인트는 항상 하나 이상"));
+    
+    // 테스트 계정의 남은 잔액을 지갑으로 돌려받고 종료 (--reclaim)
+    if args.has("reclaim") {
+        match throughput::reclaim_accounts(&funding, sender_keypair, &accounts, TEST_PRIORITY_FEE).await {
+            Ok(reclaimed) => output.progress(format_args!(
+                "테스트 계정 {}개에서 {} SOL을 돌려받았습니다.",
+                accounts.len(),
+                reclaimed as f64 / 1_000_000_000.0
+            )),
+            Err(e) => {
+                eprintln!("테스트 계정 잔액 회수 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    
+    let minimum = throughput::required_balance(options.per_account() * endpoints.len(), options.fee);
+    output.progress(format_args!(
+        "\n병렬 전송 처리량 테스트: 엔드포인트마다 {}개, 초당 {}개, 테스트 계정 {}개",
+        options.count, options.rate, accounts.len()
+    ));
+    // 지갑에서 테스트 계정을 채우고 수수료를 내는 실제 전송이므로 메인넷이면 확인받음
+    confirm_cluster(&funding, args).await;
+    match throughput::fund_accounts(&funding, sender_keypair, &accounts, minimum, TEST_PRIORITY_FEE).await {
+        Ok(0) => output.progress("  테스트 계정 잔액 충분"),
+        Ok(funded) => output.progress(format_args!("  테스트 계정 충전: {} SOL", funded as f64 / 1_000_000_000.0)),
+        Err(e) => {
+            eprintln!("테스트 계정 충전 실패: {}", e);
+            std::process::exit(1);
+        }
+    }
+    
+    let mut results: Vec<(String, ThroughputReport)> = Vec::new();
+    for endpoint in endpoints {
+        output.progress(format_args!("\n테스트 중: {}", endpoint));
+        let client = Arc::new(rpc::async_client(endpoint));
+        let result = throughput::run_throughput(
+            client,
+            &accounts,
+            run,
+            TEST_LAMPORTS,
+            &options,
+            &landing::TRACK_OPTIONS,
+            output,
+        )
+        .await;
+        match result {
+            Ok(report) => {
+                output.progress(format_args!(
+                    "  포함 {}개 (초당 {:.1}개, 드롭률 {:.1}%)",
+                    report.landed,
+                    report.landed_per_second(),
+                    report.drop_rate() * 100.0
+                ));
+                results.push((endpoint.clone(), report));
+            },
+            Err(e) => output.progress(format_args!("  오류: {}", e)),
+        }
+    }
     
-    Keypair::from_bytes(&bytes).expect("키페어 생성 실패")
+    if output.is_table() {
+        let rows: Vec<(String, &ThroughputReport)> = results.iter().map(|(endpoint, report)| (endpoint.clone(), report)).collect();
+        reporting::print_throughput(&rows);
+        println!("실행 ID: {}", run.id());
+    } else {
+        let records: Vec<ThroughputRecord> =
+            results.iter().map(|(endpoint, report)| ThroughputRecord::new(endpoint, report)).collect();
+        output.print(&records);
+    }
+}
+
+// 전송할 RPC의 클러스터를 감지해 메인넷이면 확인받고, 감지하지 못하거나 확인하지 않으면 종료
+async fn confirm_cluster(client: &AsyncRpcClient, args: &Args) {
+    let confirmed = Cluster::detect_async(client)
+        .await
+        .and_then(|cluster| cluster::confirm_mainnet(&cluster, args));
+    if let Err(e) = confirmed {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+// 트랜잭션 하나의 포함 슬롯과 커미트먼트별 시간 (예: "슬롯 100 → 102 (2슬롯), processed 612ms, ...")
+fn describe(landing: &Landing) -> String {
+    let slot = |slot: Option<u64>| slot.map_or("?".to_string(), |slot| slot.to_string());
+    let time = |elapsed: Option<std::time::Duration>| elapsed.map_or("-".to_string(), |elapsed| format!("{:?}", elapsed));
+    if !landing.landed() {
+        return format!("슬롯 {}에 전송, 블록에 포함되지 않음", slot(landing.sent_slot));
+    }
+    let mut line = format!(
+        "슬롯 {} → {} ({}슬롯), processed {}, confirmed {}, finalized {}",
+        slot(landing.sent_slot),
+        slot(landing.landed_slot),
+        slot(landing.slots_to_land()),
+        time(landing.processed),
+        time(landing.confirmed),
+        time(landing.finalized)
+    );
+    if let Some(error) = &landing.err {
+        line.push_str(&format!(" (실행 실패: {})", error));
+    }
+    line
 }
 
-// SOL 전송 및 시간 측정 함수
-fn send_sol_and_measure_time(
-    rpc_client: &RpcClient,
-    sender: &Keypair,
-    recipient: &Pubkey,
-    lamports: u64,
-) -> Result<Duration, Box<dyn std::error::Error>> {
-    // 시작 시간 기록
-    let start_time = Instant::now();
-    
-    // 최근 블록해시 가져오기
-    let recent_blockhash = rpc_client.get_latest_blockhash()?;
-    
-    // 명령어 벡터 생성
-    let mut instructions = vec![];
-    
-    // 우선순위 수수료 설정 (빠른 확인을 위해)
-    let priority_fee_instruction = ComputeBudgetInstruction::set_compute_unit_price(5);
-    instructions.push(priority_fee_instruction);
-    
-    // 전송 명령 추가
-    let transfer_instruction = system_instruction::transfer(&sender.pubkey(), recipient, lamports);
-    instructions.push(transfer_instruction);
-    
-    // 트랜잭션 생성 및 서명
-    let mut transaction = Transaction::new_with_payer(&instructions, Some(&sender.pubkey()));
-    transaction.sign(&[sender], recent_blockhash);
-    
-    // 트랜잭션 전송 및 확인
-    println!("    트랜잭션 전송 중...");
-    let signature = rpc_client.send_and_confirm_transaction_with_spinner_and_commitment(
-        &transaction,
-        CommitmentConfig::confirmed(),
-    )?;
-    
-    // 종료 시간 기록 및 소요 시간 계산
-    let elapsed = start_time.elapsed();
-    println!("    트랜잭션 확인: {}", signature);
-    
-    Ok(elapsed)
-} 
\ No newline at end of file
+// 테스트 지갑 키페어 (입력받지 않으므로 설정에서 지정 필수)
+fn load_keypair(settings: &Settings) -> Result<Keypair, Error> {
+    settings.configured_keypair().unwrap_or_else(|| {
+        Err(Error::Config(
+            "테스트 지갑이 설정되지 않았습니다. --keypair, SOLANA_KEYPAIR, SOLANA_PRIVATE_KEY 또는 설정 파일의 keypair를 지정하세요.".to_string(),
+        ))
+    })
+}
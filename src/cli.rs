@@ -0,0 +1,122 @@
+//! 명령줄 인자 파싱과 대화형 입력
+
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// 값을 받지 않는 옵션 (다음 인자를 값으로 가져가지 않으므로 위치 인자 앞에 써도 됨)
+///
+/// 새 불리언 옵션을 추가하면 여기에도 추가합니다. `--이름=값` 형식으로는 값을 줄 수 있습니다.
+pub const FLAGS: &[&str] = &[
+    "yes",
+    "once",
+    "no-ws",
+    "retry-failed",
+    "only-matched",
+    "transactions",
+    "fee-sweep",
+    "throughput",
+    "reclaim",
+    "load",
+    "freshness",
+    "pubsub",
+    "timeline",
+    "icmp",
+    "ipv4",
+    "ipv6",
+];
+
+/// 명령줄 인자 (위치 인자 + `--이름 값` 형식의 옵션)
+#[derive(Debug, Default, Clone)]
+pub struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, Vec<String>>,
+}
+
+impl Args {
+    /// 인자 목록 파싱
+    /// 값 없이 쓰인 옵션과 [`FLAGS`]의 옵션(`--yes`)은 "true" 값으로 저장된다
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Self {
+        let mut parsed = Args::default();
+        let mut iter = args.into_iter().peekable();
+
+        while let Some(arg) = iter.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                // `--이름=값` 형식 지원
+                if let Some((name, value)) = name.split_once('=') {
+                    parsed.push_option(name, value.to_string());
+                    continue;
+                }
+
+                let value = match iter.peek() {
+                    Some(next) if !next.starts_with("--") && !FLAGS.contains(&name) => iter.next().unwrap(),
+                    _ => "true".to_string(),
+                };
+                parsed.push_option(name, value);
+            } else {
+                parsed.positional.push(arg);
+            }
+        }
+
+        parsed
+    }
+
+    fn push_option(&mut self, name: &str, value: String) {
+        self.options.entry(name.to_string()).or_default().push(value);
+    }
+
+    /// n번째 위치 인자
+    pub fn positional(&self, index: usize) -> Option<&str> {
+        self.positional.get(index).map(|s| s.as_str())
+    }
+
+    /// n번째 위치 인자 (없으면 오류)
+    pub fn require_positional(&self, index: usize, name: &str) -> Result<&str> {
+        self.positional(index)
+            .ok_or_else(|| Error::Input(format!("{} 인자가 필요합니다", name)))
+    }
+
+    /// 옵션 값 (여러 번 지정된 경우 마지막 값)
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .get(name)
+            .and_then(|values| values.last())
+            .map(|s| s.as_str())
+    }
+
+    /// 여러 번 지정 가능한 옵션의 모든 값
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.options
+            .get(name)
+            .map(|values| values.iter().map(|s| s.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// 옵션 지정 여부
+    pub fn has(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    /// 옵션 값을 파싱 (없으면 기본값)
+    pub fn parse_or<T: FromStr>(&self, name: &str, default: T) -> Result<T> {
+        match self.value(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| Error::Input(format!("--{} 값이 올바르지 않습니다: {}", name, value))),
+            None => Ok(default),
+        }
+    }
+}
+
+/// 사용자 입력을 받는 함수
+///
+/// 안내 문구는 표준 오류로 출력합니다 (`--output json|csv`의 결과가 있는 표준 출력과 섞이지 않도록).
+pub fn get_input(prompt: &str) -> String {
+    eprint!("{}", prompt);
+    io::stderr().flush().expect("출력 실패");
+    
+    let mut input = String::new();
+    io::stdin().lock().read_line(&mut input).expect("입력 읽기 실패");
+    input
+}
@@ -0,0 +1,195 @@
+//! 클러스터 감지와 익스플로러 링크
+//!
+//! RPC 노드의 제네시스 해시(`getGenesisHash`)로 연결된 클러스터(mainnet-beta, devnet, testnet,
+//! 그 밖의 커스텀 클러스터)를 판별하고, 클러스터에 맞는 익스플로러(Solana Explorer, Solscan,
+//! SolanaFM) 트랜잭션 링크를 만듭니다. RPC URL만으로는 클러스터를 알 수 없으므로(RPC 제공자 URL 등)
+//! 링크와 메인넷 전송 확인은 항상 감지된 클러스터를 기준으로 합니다.
+
+use crate::cli::{self, Args};
+use crate::error::{Error, Result};
+use solana_client::{nonblocking::rpc_client::RpcClient as AsyncRpcClient, rpc_client::RpcClient};
+use std::fmt;
+use std::str::FromStr;
+
+/// mainnet-beta 제네시스 해시
+pub const MAINNET_BETA_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+
+/// devnet 제네시스 해시
+pub const DEVNET_GENESIS_HASH: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
+
+/// testnet 제네시스 해시
+pub const TESTNET_GENESIS_HASH: &str = "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY";
+
+/// 연결된 클러스터
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cluster {
+    MainnetBeta,
+    Devnet,
+    Testnet,
+    /// 로컬 검증인 등 알려지지 않은 클러스터 (제네시스 해시)
+    Custom(String),
+}
+
+impl Cluster {
+    /// 제네시스 해시로 클러스터 판별
+    pub fn from_genesis_hash(genesis_hash: &str) -> Cluster {
+        match genesis_hash {
+            MAINNET_BETA_GENESIS_HASH => Cluster::MainnetBeta,
+            DEVNET_GENESIS_HASH => Cluster::Devnet,
+            TESTNET_GENESIS_HASH => Cluster::Testnet,
+            other => Cluster::Custom(other.to_string()),
+        }
+    }
+
+    /// RPC 노드에 제네시스 해시를 조회하여 클러스터 감지
+    pub fn detect(rpc_client: &RpcClient) -> Result<Cluster> {
+        let genesis_hash = rpc_client.get_genesis_hash()?;
+        Ok(Cluster::from_genesis_hash(&genesis_hash.to_string()))
+    }
+
+    /// [`Cluster::detect`]의 비동기 버전 (tokio 런타임 안에서 사용)
+    pub async fn detect_async(rpc_client: &AsyncRpcClient) -> Result<Cluster> {
+        let genesis_hash = rpc_client.get_genesis_hash().await?;
+        Ok(Cluster::from_genesis_hash(&genesis_hash.to_string()))
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        *self == Cluster::MainnetBeta
+    }
+
+    /// 기본 제공 프로필 이름과 같은 클러스터인지 여부 (커스텀 클러스터는 localnet/사용자 프로필로 간주)
+    pub fn matches_profile(&self, profile: &str) -> bool {
+        match self {
+            Cluster::MainnetBeta => profile == "mainnet",
+            Cluster::Devnet => profile == "devnet",
+            Cluster::Testnet => profile == "testnet",
+            Cluster::Custom(_) => !matches!(profile, "mainnet" | "devnet" | "testnet"),
+        }
+    }
+}
+
+impl fmt::Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cluster::MainnetBeta => write!(f, "mainnet-beta"),
+            Cluster::Devnet => write!(f, "devnet"),
+            Cluster::Testnet => write!(f, "testnet"),
+            Cluster::Custom(genesis_hash) => write!(f, "custom (제네시스 해시 {})", genesis_hash),
+        }
+    }
+}
+
+/// 트랜잭션 링크에 사용할 익스플로러
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Explorer {
+    /// explorer.solana.com
+    Solana,
+    /// solscan.io
+    Solscan,
+    /// solana.fm
+    SolanaFm,
+    /// 사용자 지정 URL 템플릿 (`{signature}` 자리에 트랜잭션 서명)
+    Template(String),
+}
+
+impl FromStr for Explorer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "solana" | "explorer" => Ok(Explorer::Solana),
+            "solscan" => Ok(Explorer::Solscan),
+            "solanafm" | "solana.fm" => Ok(Explorer::SolanaFm),
+            template if template.contains("{signature}") => Ok(Explorer::Template(template.to_string())),
+            other => Err(Error::Config(format!(
+                "익스플로러 형식 오류: {} (solana, solscan, solanafm 또는 {{signature}}를 포함한 URL 템플릿)",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Explorer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Explorer::Solana => write!(f, "solana"),
+            Explorer::Solscan => write!(f, "solscan"),
+            Explorer::SolanaFm => write!(f, "solanafm"),
+            Explorer::Template(template) => write!(f, "{}", template),
+        }
+    }
+}
+
+impl Explorer {
+    /// 클러스터에 맞는 트랜잭션 링크
+    ///
+    /// 커스텀 클러스터는 익스플로러가 RPC URL로 직접 조회하도록 `customUrl`을 붙입니다
+    /// (로컬 검증인처럼 브라우저에서 접근할 수 있는 RPC만 조회됩니다).
+    /// RPC URL에 API 키로 보이는 값이 있으면 링크가 공유되어도 노출되지 않도록 `customUrl`을 생략합니다.
+    pub fn tx_url(&self, signature: &str, cluster: &Cluster, rpc_url: &str) -> String {
+        let custom = match shareable_url(rpc_url) {
+            Some(url) => {
+                let encoded: String = url::form_urlencoded::byte_serialize(url.as_bytes()).collect();
+                format!("?cluster=custom&customUrl={}", encoded)
+            }
+            None => "?cluster=custom".to_string(),
+        };
+        match self {
+            Explorer::Solana => {
+                let query = match cluster {
+                    Cluster::MainnetBeta => String::new(),
+                    Cluster::Devnet => "?cluster=devnet".to_string(),
+                    Cluster::Testnet => "?cluster=testnet".to_string(),
+                    Cluster::Custom(_) => custom,
+                };
+                format!("https://explorer.solana.com/tx/{}{}", signature, query)
+            }
+            Explorer::Solscan => {
+                let query = match cluster {
+                    Cluster::MainnetBeta => String::new(),
+                    Cluster::Devnet => "?cluster=devnet".to_string(),
+                    Cluster::Testnet => "?cluster=testnet".to_string(),
+                    Cluster::Custom(_) => custom,
+                };
+                format!("https://solscan.io/tx/{}{}", signature, query)
+            }
+            Explorer::SolanaFm => {
+                let cluster = match cluster {
+                    Cluster::MainnetBeta => "mainnet-alpha",
+                    Cluster::Devnet => "devnet-solana",
+                    Cluster::Testnet => "testnet-solana",
+                    Cluster::Custom(_) => "localnet-solana",
+                };
+                format!("https://solana.fm/tx/{}?cluster={}", signature, cluster)
+            }
+            Explorer::Template(template) => template.replace("{signature}", signature),
+        }
+    }
+}
+
+// 링크에 넣어도 되는 RPC URL (사용자 정보, 쿼리, 긴 경로 조각처럼 API 키일 수 있는 값이 있으면 `None`)
+fn shareable_url(rpc_url: &str) -> Option<&str> {
+    let url = url::Url::parse(rpc_url).ok()?;
+    let has_secret = !url.username().is_empty()
+        || url.password().is_some()
+        || url.query().is_some()
+        || url.path_segments().is_some_and(|mut segments| segments.any(|segment| segment.len() >= 20));
+    (!has_secret).then_some(rpc_url)
+}
+
+/// 메인넷이면 실제 자금이 움직인다는 것을 확인받기 (`--yes`로 생략)
+///
+/// 확인하지 않으면 [`Error::Input`]을 반환합니다.
+pub fn confirm_mainnet(cluster: &Cluster, args: &Args) -> Result<()> {
+    if !cluster.is_mainnet() || args.has("yes") {
+        return Ok(());
+    }
+
+    eprintln!("경고: 메인넷(mainnet-beta)에 연결되어 있습니다. 실제 자금이 전송됩니다.");
+    let input = cli::get_input("계속하려면 'mainnet'을 입력하세요: ");
+    if input.trim() == "mainnet" {
+        Ok(())
+    } else {
+        Err(Error::Input("메인넷 확인이 취소되었습니다 (확인 없이 실행하려면 --yes 옵션 사용)".to_string()))
+    }
+}
@@ -0,0 +1,654 @@
+//! 설정 파일과 클러스터 프로필
+//!
+//! 설정은 명령줄 옵션 > 환경 변수 > 설정 파일 > 기본값 순서로 적용됩니다. 설정 파일(TOML)에는
+//! 이름 붙은 프로필을 여러 개 둘 수 있고, `--profile`로 하나를 선택합니다. `mainnet`, `devnet`,
+//! `testnet`, `localnet` 프로필은 설정 파일이 없어도 기본 RPC/웹소켓 URL을 갖습니다. 익스플로러 링크는
+//! 프로필 이름이 아니라 RPC 노드에서 감지한 클러스터([`crate::cluster`])를 기준으로 만듭니다.
+//!
+//! ```toml
+//! default_profile = "devnet"
+//!
+//! [profiles.devnet]
+//! rpc_url = "https://api.devnet.solana.com"
+//! keypair = "file:~/.config/solana/devnet.json"
+//! commitment = "confirmed"
+//! fee = "recent:75"
+//!
+//! [profiles.mainnet]
+//! rpc_url = "https://my-rpc.example.com/?api-key=secret"
+//! keypair = "env:MAINNET_PRIVATE_KEY"
+//! explorer = "solscan"
+//! ```
+
+use crate::cli::{self, Args};
+use crate::cluster::{Cluster, Explorer};
+use crate::error::{Error, Result};
+use crate::{keys, rpc, transfer};
+use serde::Deserialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+    signature::Keypair,
+};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// 기본 설정 파일 경로 (현재 디렉토리)
+pub const DEFAULT_CONFIG_FILE: &str = "solana_transfer_bot.toml";
+
+/// 설정 파일 경로 환경 변수 이름
+pub const CONFIG_ENV: &str = "SOLANA_BOT_CONFIG";
+
+/// 프로필 환경 변수 이름
+pub const PROFILE_ENV: &str = "SOLANA_PROFILE";
+
+/// 웹소켓 URL 환경 변수 이름
+pub const WS_URL_ENV: &str = "SOLANA_WS_URL";
+
+/// 키페어 소스 환경 변수 이름 (`env:이름`, `file:경로`, `prompt`)
+pub const KEYPAIR_ENV: &str = "SOLANA_KEYPAIR";
+
+/// 커미트먼트 환경 변수 이름
+pub const COMMITMENT_ENV: &str = "SOLANA_COMMITMENT";
+
+/// 우선순위 수수료 전략 환경 변수 이름
+pub const FEE_ENV: &str = "SOLANA_PRIORITY_FEE";
+
+/// 익스플로러 환경 변수 이름
+pub const EXPLORER_ENV: &str = "SOLANA_EXPLORER";
+
+/// 설정을 정하는 명령줄 옵션 이름 (`--<이름> <값>`, 다른 도구를 실행할 때 그대로 넘길 수 있음)
+pub const OPTIONS: [&str; 8] = ["config", "profile", "rpc-url", "ws-url", "keypair", "commitment", "fee", "explorer"];
+
+/// 프로필을 지정하지 않았을 때 사용하는 프로필
+pub const DEFAULT_PROFILE: &str = "mainnet";
+
+/// 기본 제공 프로필 이름
+pub const BUILTIN_PROFILES: [&str; 4] = ["mainnet", "devnet", "testnet", "localnet"];
+
+// URL에서 가린 부분 표시 (퍼센트 인코딩되지 않는 문자만 사용)
+const URL_MASK: &str = "***";
+
+// 최근 수수료 전략의 기본 백분위
+const DEFAULT_FEE_PERCENTILE: u8 = 50;
+
+/// 설정 파일 내용
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    /// `--profile`을 지정하지 않았을 때 사용할 프로필
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+/// 설정 파일의 프로필 하나 (지정하지 않은 값은 기본값 사용)
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileConfig {
+    pub rpc_url: Option<String>,
+    pub ws_url: Option<String>,
+    /// 키페어 소스 (`env:이름`, `file:경로`, `base58:개인키`, `prompt`)
+    pub keypair: Option<String>,
+    /// `processed` | `confirmed` | `finalized`
+    pub commitment: Option<String>,
+    /// 우선순위 수수료 전략 (`5`, `fixed:5`, `recent`, `recent:75`)
+    pub fee: Option<String>,
+    /// 익스플로러 (`solana`, `solscan`, `solanafm` 또는 `{signature}`를 포함한 URL 템플릿)
+    pub explorer: Option<String>,
+}
+
+impl ConfigFile {
+    /// TOML 문자열 파싱
+    pub fn parse(content: &str) -> Result<ConfigFile> {
+        toml::from_str(content).map_err(|e| Error::Config(format!("설정 파일 형식 오류: {}", e)))
+    }
+
+    /// 설정 파일 읽기
+    pub fn load(path: &Path) -> Result<ConfigFile> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("설정 파일을 읽을 수 없습니다 ({}): {}", path.display(), e)))?;
+        ConfigFile::parse(&content).map_err(|e| Error::Config(format!("{} ({})", e, path.display())))
+    }
+}
+
+/// 값이 정해진 단계
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Cli,
+    Env,
+    File,
+    Default,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Source::Cli => "명령줄",
+            Source::Env => "환경 변수",
+            Source::File => "설정 파일",
+            Source::Default => "기본값",
+        })
+    }
+}
+
+/// 값과 그 값이 정해진 단계
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+/// 보내는 지갑 키페어를 가져오는 방법
+#[derive(Clone, PartialEq, Eq)]
+pub enum KeypairSource {
+    /// 환경 변수에 든 Base58 개인키
+    Env(String),
+    /// Solana CLI 형식(JSON 바이트 배열) 키페어 파일
+    File(PathBuf),
+    /// Base58 개인키 (설정 파일에 직접 기록 - 권장하지 않음)
+    Base58(String),
+    /// 실행 시 입력
+    Prompt,
+}
+
+impl FromStr for KeypairSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        match s.split_once(':') {
+            Some(("env", name)) if !name.is_empty() => Ok(KeypairSource::Env(name.to_string())),
+            Some(("file", path)) if !path.is_empty() => Ok(KeypairSource::File(expand_home(path))),
+            Some(("base58", key)) if !key.is_empty() => Ok(KeypairSource::Base58(key.to_string())),
+            _ if s == "prompt" => Ok(KeypairSource::Prompt),
+            _ => Err(Error::Config(format!(
+                "키페어 소스 형식 오류: {} (env:이름, file:경로, base58:개인키, prompt 중 하나)",
+                redact_secret(s)
+            ))),
+        }
+    }
+}
+
+// 개인키가 Debug 출력에 노출되지 않도록 Display(가림 처리)를 그대로 사용
+impl fmt::Debug for KeypairSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for KeypairSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeypairSource::Env(name) => write!(f, "env:{}", name),
+            KeypairSource::File(path) => write!(f, "file:{}", path.display()),
+            KeypairSource::Base58(_) => write!(f, "base58:<가림>"),
+            KeypairSource::Prompt => write!(f, "prompt"),
+        }
+    }
+}
+
+impl KeypairSource {
+    /// 키페어 불러오기
+    pub fn load(&self) -> Result<Keypair> {
+        match self {
+            KeypairSource::Env(name) => {
+                let private_key = env::var(name)
+                    .ok()
+                    .filter(|key| !key.trim().is_empty())
+                    .ok_or_else(|| Error::Config(format!("환경변수 {}가 설정되지 않았습니다", name)))?;
+                keys::create_keypair_from_base58(&private_key)
+            }
+            KeypairSource::File(path) => {
+                let content = fs::read_to_string(path)
+                    .map_err(|e| Error::Key(format!("키페어 파일을 읽을 수 없습니다 ({}): {}", path.display(), e)))?;
+                let bytes: Vec<u8> = serde_json::from_str(&content)
+                    .map_err(|e| Error::Key(format!("키페어 파일 형식 오류 ({}): {}", path.display(), e)))?;
+                Keypair::from_bytes(&bytes).map_err(|e| Error::Key(format!("키페어 생성 실패 ({}): {}", path.display(), e)))
+            }
+            KeypairSource::Base58(private_key) => keys::create_keypair_from_base58(private_key),
+            KeypairSource::Prompt => {
+                let input = cli::get_input("보내는 지갑의 개인키를 입력하세요: ");
+                if input.trim().is_empty() {
+                    return Err(Error::Config(format!(
+                        "개인키가 없습니다. {} 환경 변수나 설정 파일의 keypair 항목을 설정하세요",
+                        keys::PRIVATE_KEY_ENV
+                    )));
+                }
+                keys::create_keypair_from_base58(&input)
+            }
+        }
+    }
+}
+
+/// 우선순위 수수료 전략
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeStrategy {
+    /// 고정 값 (lamports per compute unit)
+    Fixed(u64),
+    /// 최근 블록들의 우선순위 수수료(getRecentPrioritizationFees) 중 주어진 백분위 값
+    Recent { percentile: u8 },
+}
+
+impl FromStr for FeeStrategy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let invalid = || Error::Config(format!("수수료 전략 형식 오류: {} (예: 5, fixed:5, recent, recent:75)", s));
+        match s.split_once(':') {
+            None if s == "recent" => Ok(FeeStrategy::Recent { percentile: DEFAULT_FEE_PERCENTILE }),
+            None => s.parse().map(FeeStrategy::Fixed).map_err(|_| invalid()),
+            Some(("fixed", value)) => value.trim().parse().map(FeeStrategy::Fixed).map_err(|_| invalid()),
+            Some(("recent", percentile)) => match percentile.trim().parse() {
+                Ok(percentile) if percentile <= 100 => Ok(FeeStrategy::Recent { percentile }),
+                _ => Err(invalid()),
+            },
+            Some(_) => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for FeeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeeStrategy::Fixed(fee) => write!(f, "fixed:{}", fee),
+            FeeStrategy::Recent { percentile } => write!(f, "recent:{}", percentile),
+        }
+    }
+}
+
+impl FeeStrategy {
+    /// 실제로 사용할 우선순위 수수료 계산 (최근 수수료 정보가 없으면 기본값)
+    pub fn resolve(&self, rpc_client: &RpcClient) -> Result<u64> {
+        match self {
+            FeeStrategy::Fixed(fee) => Ok(*fee),
+            FeeStrategy::Recent { percentile } => {
+                let mut fees: Vec<u64> = rpc_client
+                    .get_recent_prioritization_fees(&[])?
+                    .into_iter()
+                    .map(|fee| fee.prioritization_fee)
+                    .collect();
+                if fees.is_empty() {
+                    return Ok(transfer::DEFAULT_PRIORITY_FEE);
+                }
+                fees.sort_unstable();
+                let index = (fees.len() - 1) * usize::from(*percentile) / 100;
+                Ok(fees[index])
+            }
+        }
+    }
+}
+
+/// 적용된 설정 (프로필과 각 값의 출처 포함)
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub profile: Setting<String>,
+    /// 읽은 설정 파일 (없으면 `None`)
+    pub config_path: Option<PathBuf>,
+    pub rpc_url: Setting<String>,
+    pub ws_url: Setting<String>,
+    pub keypair: Setting<KeypairSource>,
+    pub commitment: Setting<CommitmentLevel>,
+    pub fee: Setting<FeeStrategy>,
+    pub explorer: Setting<Explorer>,
+}
+
+impl Settings {
+    /// 명령줄 옵션, 환경 변수, 설정 파일로 설정 결정
+    ///
+    /// 설정 파일은 `--config`, `SOLANA_BOT_CONFIG`, 현재 디렉토리의 `solana_transfer_bot.toml` 순서로 찾습니다.
+    /// 앞의 두 방법으로 지정한 파일이 없으면 오류입니다.
+    pub fn load(args: &Args) -> Result<Settings> {
+        let explicit = args
+            .value("config")
+            .map(PathBuf::from)
+            .or_else(|| env::var(CONFIG_ENV).ok().filter(|p| !p.is_empty()).map(PathBuf::from));
+        let file = match explicit {
+            Some(path) => Some((ConfigFile::load(&path)?, path)),
+            None => {
+                let path = PathBuf::from(DEFAULT_CONFIG_FILE);
+                match path.exists() {
+                    true => Some((ConfigFile::load(&path)?, path)),
+                    false => None,
+                }
+            }
+        };
+
+        Settings::resolve(args, &|name| env::var(name).ok(), file)
+    }
+
+    /// 주어진 소스들로 설정 결정 (환경 변수 조회 함수를 받아 테스트에서 교체 가능)
+    pub fn resolve(
+        args: &Args,
+        env: &dyn Fn(&str) -> Option<String>,
+        file: Option<(ConfigFile, PathBuf)>,
+    ) -> Result<Settings> {
+        let env = |name: &str| env(name).filter(|value| !value.trim().is_empty());
+        let (file, config_path) = match file {
+            Some((file, path)) => (file, Some(path)),
+            None => (ConfigFile::default(), None),
+        };
+
+        let profile = pick(
+            args.value("profile").map(String::from),
+            env(PROFILE_ENV),
+            file.default_profile.clone(),
+            DEFAULT_PROFILE.to_string(),
+        );
+        let profile_config = match file.profiles.get(&profile.value) {
+            Some(config) => config.clone(),
+            None if BUILTIN_PROFILES.contains(&profile.value.as_str()) => ProfileConfig::default(),
+            None => {
+                let mut available: Vec<&str> = BUILTIN_PROFILES.to_vec();
+                available.extend(file.profiles.keys().map(String::as_str).filter(|p| !BUILTIN_PROFILES.contains(p)));
+                return Err(Error::Config(format!(
+                    "알 수 없는 프로필입니다: {} (사용 가능: {})",
+                    profile.value,
+                    available.join(", ")
+                )));
+            }
+        };
+        let builtin = builtin_profile(&profile.value);
+
+        let rpc_url = match builtin.map(|b| b.rpc_url.to_string()) {
+            Some(default) => pick(
+                args.value("rpc-url").map(String::from),
+                env(rpc::RPC_URL_ENV),
+                profile_config.rpc_url.clone(),
+                default,
+            ),
+            None => pick_required(
+                args.value("rpc-url").map(String::from),
+                env(rpc::RPC_URL_ENV),
+                profile_config.rpc_url.clone(),
+            )
+            .ok_or_else(|| Error::Config(format!("[{}] 프로필에 rpc_url이 없습니다", profile.value)))?,
+        };
+        let ws_url = pick(
+            args.value("ws-url").map(String::from),
+            env(WS_URL_ENV),
+            profile_config.ws_url.clone(),
+            websocket_url(&rpc_url.value),
+        );
+
+        // SOLANA_PRIVATE_KEY가 설정되어 있으면 환경 변수 단계의 키페어 소스로 취급
+        let keypair_env = env(KEYPAIR_ENV)
+            .or_else(|| env(keys::PRIVATE_KEY_ENV).map(|_| format!("env:{}", keys::PRIVATE_KEY_ENV)));
+        let keypair = parse_setting(
+            pick(
+                args.value("keypair").map(String::from),
+                keypair_env,
+                profile_config.keypair.clone(),
+                format!("env:{}", keys::PRIVATE_KEY_ENV),
+            ),
+            |s| s.parse(),
+        )?;
+        // 기본값(env:SOLANA_PRIVATE_KEY)인데 환경 변수가 없으면 실행 시 입력
+        let keypair = match keypair {
+            Setting { source: Source::Default, .. } => Setting {
+                value: KeypairSource::Prompt,
+                source: Source::Default,
+            },
+            keypair => keypair,
+        };
+
+        let commitment = parse_setting(
+            pick(
+                args.value("commitment").map(String::from),
+                env(COMMITMENT_ENV),
+                profile_config.commitment.clone(),
+                "confirmed".to_string(),
+            ),
+            parse_commitment,
+        )?;
+        let fee = parse_setting(
+            pick(
+                args.value("fee").map(String::from),
+                env(FEE_ENV),
+                profile_config.fee.clone(),
+                FeeStrategy::Fixed(transfer::DEFAULT_PRIORITY_FEE).to_string(),
+            ),
+            |s| s.parse(),
+        )?;
+        let explorer = parse_setting(
+            pick(
+                args.value("explorer").map(String::from),
+                env(EXPLORER_ENV),
+                profile_config.explorer.clone(),
+                Explorer::Solana.to_string(),
+            ),
+            |s| s.parse(),
+        )?;
+
+        Ok(Settings {
+            profile,
+            config_path,
+            rpc_url,
+            ws_url,
+            keypair,
+            commitment,
+            fee,
+            explorer,
+        })
+    }
+
+    /// 설정된 커미트먼트로 RPC 클라이언트 생성
+    pub fn client(&self) -> RpcClient {
+        rpc::client_with_commitment(&self.rpc_url.value, self.commitment_config())
+    }
+
+    pub fn commitment_config(&self) -> CommitmentConfig {
+        CommitmentConfig { commitment: self.commitment.value }
+    }
+
+    /// 보내는 지갑 키페어 불러오기
+    pub fn load_keypair(&self) -> Result<Keypair> {
+        self.keypair.value.load()
+    }
+
+    /// 따로 지정한 키페어 (지정하지 않아 실행 시 입력받는 경우는 `None`)
+    ///
+    /// 입력을 기다리지 않아야 하는 벤치마크 도구가 테스트 지갑 유무를 판단할 때 사용합니다.
+    pub fn configured_keypair(&self) -> Option<Result<Keypair>> {
+        match self.keypair.value {
+            KeypairSource::Prompt => None,
+            _ => Some(self.load_keypair()),
+        }
+    }
+
+    /// 벤치마크 대상 엔드포인트 (프로필의 기본 RPC + 설정된 RPC)
+    ///
+    /// 설정된 RPC가 기본 RPC와 같거나 기본 RPC가 없는 프로필이면 설정된 RPC만 반환합니다.
+    /// `skip_default`이면 기본 RPC를 빼고 설정된 RPC만 반환합니다 (로컬 검증인 테스트처럼 공개 RPC에 연결하지 않아야 할 때).
+    pub fn benchmark_endpoints(&self, skip_default: bool) -> Vec<String> {
+        let configured = self.rpc_url.value.clone();
+        match builtin_profile(&self.profile.value).map(|builtin| builtin.rpc_url) {
+            Some(default) if default != configured && !skip_default => vec![default.to_string(), configured],
+            _ => vec![configured],
+        }
+    }
+
+    /// 벤치마크 엔드포인트의 웹소켓 URL (설정된 RPC면 설정된 웹소켓 URL, 아니면 RPC URL에서 유도)
+    pub fn benchmark_ws_url(&self, endpoint: &str) -> String {
+        match endpoint == self.rpc_url.value {
+            true => self.ws_url.value.clone(),
+            false => websocket_url(endpoint),
+        }
+    }
+
+    /// 수수료 전략에 따른 우선순위 수수료 (lamports per compute unit)
+    pub fn priority_fee(&self, rpc_client: &RpcClient) -> Result<u64> {
+        self.fee.value.resolve(rpc_client)
+    }
+
+    /// 감지된 클러스터에 맞는 트랜잭션 익스플로러 링크
+    pub fn explorer_url(&self, signature: &str, cluster: &Cluster) -> String {
+        self.explorer.value.tx_url(signature, cluster, &self.rpc_url.value)
+    }
+
+    /// 적용된 설정을 출력용 줄 목록으로 (URL의 인증 정보와 개인키는 가림)
+    pub fn describe(&self) -> Vec<String> {
+        let config_path = match &self.config_path {
+            Some(path) => path.display().to_string(),
+            None => "없음".to_string(),
+        };
+        vec![
+            format!("설정 파일: {}", config_path),
+            line("profile", &self.profile.value, self.profile.source),
+            line("rpc_url", &redact_url(&self.rpc_url.value), self.rpc_url.source),
+            line("ws_url", &redact_url(&self.ws_url.value), self.ws_url.source),
+            line("keypair", &self.keypair.value.to_string(), self.keypair.source),
+            line("commitment", &format!("{:?}", self.commitment.value).to_lowercase(), self.commitment.source),
+            line("fee", &self.fee.value.to_string(), self.fee.source),
+            line("explorer", &self.explorer.value.to_string(), self.explorer.source),
+        ]
+    }
+}
+
+fn line(name: &str, value: &str, source: Source) -> String {
+    format!("{:<11} {}  ({})", name, value, source)
+}
+
+/// config 하위 명령 실행 (`config show`)
+pub fn run(settings: &Settings, args: &Args) -> Result<()> {
+    match args.positional(1) {
+        Some("show") => {
+            for line in settings.describe() {
+                println!("{}", line);
+            }
+            Ok(())
+        }
+        _ => {
+            println!("사용법:
+  config show [--profile <이름>] [--config <파일>]   적용된 설정과 각 값의 출처 표시 (비밀 값은 가림)");
+            Ok(())
+        }
+    }
+}
+
+// 기본 제공 프로필 값
+struct BuiltinProfile {
+    name: &'static str,
+    rpc_url: &'static str,
+}
+
+const BUILTINS: [BuiltinProfile; 4] = [
+    BuiltinProfile {
+        name: "mainnet",
+        rpc_url: rpc::OFFICIAL_RPC,
+    },
+    BuiltinProfile {
+        name: "devnet",
+        rpc_url: "https://api.devnet.solana.com",
+    },
+    BuiltinProfile {
+        name: "testnet",
+        rpc_url: "https://api.testnet.solana.com",
+    },
+    BuiltinProfile {
+        name: "localnet",
+        rpc_url: "http://127.0.0.1:8899",
+    },
+];
+
+fn builtin_profile(name: &str) -> Option<&'static BuiltinProfile> {
+    BUILTINS.iter().find(|b| b.name == name)
+}
+
+// 명령줄 > 환경 변수 > 설정 파일 > 기본값
+fn pick(cli: Option<String>, env: Option<String>, file: Option<String>, default: String) -> Setting<String> {
+    pick_required(cli, env, file).unwrap_or(Setting {
+        value: default,
+        source: Source::Default,
+    })
+}
+
+fn pick_required(cli: Option<String>, env: Option<String>, file: Option<String>) -> Option<Setting<String>> {
+    [(cli, Source::Cli), (env, Source::Env), (file, Source::File)]
+        .into_iter()
+        .find_map(|(value, source)| value.map(|value| Setting { value, source }))
+}
+
+fn parse_setting<T>(setting: Setting<String>, parse: impl Fn(&str) -> Result<T>) -> Result<Setting<T>> {
+    let value = parse(&setting.value).map_err(|e| match e {
+        Error::Config(message) => Error::Config(format!("{} ({})", message, setting.source)),
+        other => other,
+    })?;
+    Ok(Setting {
+        value,
+        source: setting.source,
+    })
+}
+
+fn parse_commitment(input: &str) -> Result<CommitmentLevel> {
+    match input.trim() {
+        "processed" => Ok(CommitmentLevel::Processed),
+        "confirmed" => Ok(CommitmentLevel::Confirmed),
+        "finalized" => Ok(CommitmentLevel::Finalized),
+        other => Err(Error::Config(format!(
+            "커미트먼트 형식 오류: {} (processed, confirmed, finalized 중 하나)",
+            other
+        ))),
+    }
+}
+
+/// RPC URL에서 웹소켓 URL 유추 (http -> ws, https -> wss)
+pub fn websocket_url(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        // solana-test-validator는 RPC 포트 + 1에서 웹소켓을 제공
+        match rest.split_once(":8899") {
+            Some((host, path)) => format!("ws://{}:8900{}", host, path),
+            None => format!("ws://{}", rest),
+        }
+    } else {
+        rpc_url.to_string()
+    }
+}
+
+/// URL의 사용자 정보와 쿼리 값 가리기 (RPC 제공자 API 키 노출 방지)
+pub fn redact_url(input: &str) -> String {
+    let mut url = match url::Url::parse(input) {
+        Ok(url) => url,
+        Err(_) => return input.to_string(),
+    };
+    if !url.username().is_empty() || url.password().is_some() {
+        let _ = url.set_username(URL_MASK);
+        let _ = url.set_password(None);
+    }
+    if url.query().is_some() {
+        let pairs: Vec<(String, String)> = url.query_pairs().map(|(k, _)| (k.into_owned(), URL_MASK.to_string())).collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    // 경로에 키를 넣는 제공자도 있으므로 긴 경로 조각은 가림
+    let segments: Vec<String> = url
+        .path_segments()
+        .map(|segments| segments.map(|s| if s.len() >= 20 { URL_MASK.to_string() } else { s.to_string() }).collect())
+        .unwrap_or_default();
+    if !segments.is_empty() {
+        url.set_path(&segments.join("/"));
+    }
+    url.to_string()
+}
+
+// 오류 메시지에 들어가는 값에서 개인키로 보이는 부분 가리기
+fn redact_secret(input: &str) -> String {
+    match input.split_once(':') {
+        Some((kind, _)) => format!("{}:<가림>", kind),
+        None if input.len() > 20 => "<가림>".to_string(),
+        None => input.to_string(),
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
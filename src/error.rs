@@ -0,0 +1,248 @@
+//! 오류 타입
+//!
+//! 라이브러리의 모든 공개 함수는 [`Error`]를 반환합니다. 호출자(와 알림)는 문자열 대신 오류 종류로
+//! 분기할 수 있습니다. 예를 들어 요청 한도 초과는 잠시 후 재시도하고, 블록해시 만료는 다시 서명해
+//! 전송하며, 설정/입력 오류는 사용자에게 바로 알립니다.
+//!
+//! RPC 클라이언트 오류([`ClientError`])는 `From` 변환에서 전송 계층 오류, 요청 한도 초과,
+//! 시뮬레이션(preflight) 실패, 블록해시 만료, 온체인 실패로 분류됩니다.
+
+use crate::failure::HttpStatus;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::{RpcError, RpcResponseErrorData},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    instruction::InstructionError,
+    program_error::ProgramError,
+    signature::Signature,
+    transaction::TransactionError,
+};
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+/// 라이브러리 결과 타입
+pub type Result<T> = std::result::Result<T, Error>;
+
+// 요청 한도 초과를 뜻하는 JSON-RPC 오류 코드 (HTTP 429를 본문으로 전달하는 RPC 제공자 포함)
+pub(crate) const RATE_LIMIT_CODES: [i64; 2] = [429, -32429];
+
+/// 오류 종류
+#[derive(Debug)]
+pub enum Error {
+    /// 설정 오류 (필수 환경 변수 누락, 설정/정의 파일 형식 등)
+    Config(String),
+    /// 개인키 오류 (Base58 디코딩, 키페어 형식)
+    Key(String),
+    /// 사용자 입력 오류 (주소, 금액, 명령줄 인자, CSV 등)
+    Input(String),
+    /// 파일 입출력 오류
+    Io(io::Error),
+    /// RPC 연결 오류 (연결 거부, 타임아웃, 5xx 응답, 응답 파싱 실패 등)
+    Transport(String),
+    /// RPC 요청 한도 초과
+    RateLimited(String),
+    /// 그 밖의 RPC 오류 응답 (노드 지연 등)
+    Rpc { code: Option<i64>, message: String },
+    /// 전송 전 시뮬레이션(preflight) 실패 (프로그램 로그 포함)
+    Simulation {
+        message: String,
+        error: Option<TransactionError>,
+        logs: Vec<String>,
+    },
+    /// 블록해시 만료 (새 블록해시로 다시 서명해 전송해야 함)
+    BlockhashExpired,
+    /// 트랜잭션이 거부되었거나 온체인에서 실패함 (서명은 전송 후 실패가 확인된 경우에만 있음)
+    Transaction {
+        signature: Option<Signature>,
+        error: TransactionError,
+    },
+    /// 확인 대기 시간 초과 (트랜잭션이 처리되었는지 알 수 없음)
+    ConfirmationTimeout {
+        signature: Signature,
+        timeout: Duration,
+        last_error: Option<String>,
+    },
+    /// 웹훅 전달 실패 (재시도 횟수 초과)
+    Webhook(String),
+}
+
+impl Error {
+    /// 알림/로그에 쓰는 짧은 오류 종류 이름
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Config(_) => "config",
+            Error::Key(_) => "key",
+            Error::Input(_) => "input",
+            Error::Io(_) => "io",
+            Error::Transport(_) => "rpc_transport",
+            Error::RateLimited(_) => "rate_limited",
+            Error::Rpc { .. } => "rpc",
+            Error::Simulation { .. } => "simulation",
+            Error::BlockhashExpired => "blockhash_expired",
+            Error::Transaction { .. } => "transaction",
+            Error::ConfirmationTimeout { .. } => "confirmation_timeout",
+            Error::Webhook(_) => "webhook",
+        }
+    }
+
+    /// 같은 요청을 (잠시 후) 다시 시도해 볼 만한 오류인지 여부
+    ///
+    /// 블록해시 만료는 같은 트랜잭션으로는 재시도할 수 없고 다시 서명해야 하므로 포함하지 않습니다.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Error::Transport(_) | Error::RateLimited(_))
+    }
+
+    /// 전송한 트랜잭션이 자금을 옮기지 않았음이 확실한 오류인지 여부
+    ///
+    /// 노드가 거부했거나, 시뮬레이션 또는 온체인에서 실패했거나, 처리되지 않은 채 블록해시가 만료된 경우입니다.
+    /// 이때는 새로 서명해 다시 보내도 중복 전송이 되지 않습니다. 연결 오류와 확인 시간 초과는 처리 여부를 알 수 없으므로 포함하지 않습니다.
+    pub fn is_safe_to_resend(&self) -> bool {
+        matches!(
+            self,
+            Error::RateLimited(_)
+                | Error::Rpc { .. }
+                | Error::Simulation { .. }
+                | Error::BlockhashExpired
+                | Error::Transaction { .. }
+        )
+    }
+
+    /// 실패한 명령의 위치와 명령 오류 (온체인 실패 또는 시뮬레이션 실패인 경우)
+    pub fn instruction_error(&self) -> Option<(u8, &InstructionError)> {
+        let error = match self {
+            Error::Transaction { error, .. } => error,
+            Error::Simulation { error: Some(error), .. } => error,
+            _ => return None,
+        };
+        match error {
+            TransactionError::InstructionError(index, error) => Some((*index, error)),
+            _ => None,
+        }
+    }
+
+    /// 시뮬레이션 결과를 오류로 변환 (성공한 시뮬레이션이면 `None`)
+    pub fn from_simulation(result: &RpcSimulateTransactionResult) -> Option<Error> {
+        let error = result.err.clone()?;
+        Some(simulation_error(format!("트랜잭션 시뮬레이션 실패: {}", error), Some(error), result.logs.clone()))
+    }
+}
+
+// 시뮬레이션 실패 중 블록해시 만료는 별도 종류로 분류
+fn simulation_error(message: String, error: Option<TransactionError>, logs: Option<Vec<String>>) -> Error {
+    match error {
+        Some(TransactionError::BlockhashNotFound) => Error::BlockhashExpired,
+        error => Error::Simulation {
+            message,
+            error,
+            logs: logs.unwrap_or_default(),
+        },
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Config(message) => write!(f, "설정 오류: {}", message),
+            Error::Key(message) => write!(f, "개인키 오류: {} (Base58로 인코딩된 64바이트 키페어인지 확인하세요)", message),
+            Error::Input(message) => write!(f, "{}", message),
+            Error::Io(error) => write!(f, "파일 입출력 오류: {}", error),
+            Error::Transport(message) => write!(f, "RPC 연결 오류: {} (RPC URL과 네트워크 상태를 확인하세요)", message),
+            Error::RateLimited(message) => {
+                write!(f, "RPC 요청 한도 초과: {} (잠시 후 다시 시도하거나 다른 RPC를 사용하세요)", message)
+            }
+            Error::Rpc { code: Some(code), message } => write!(f, "RPC 오류 ({}): {}", code, message),
+            Error::Rpc { code: None, message } => write!(f, "RPC 오류: {}", message),
+            Error::Simulation { message, logs, .. } => {
+                write!(f, "{}", message)?;
+                if !logs.is_empty() {
+                    write!(f, "\n프로그램 로그:")?;
+                    for log in logs {
+                        write!(f, "\n  {}", log)?;
+                    }
+                }
+                Ok(())
+            }
+            Error::BlockhashExpired => write!(f, "블록해시가 만료되었습니다 (새 블록해시로 다시 서명해 전송하세요)"),
+            Error::Transaction { signature: Some(signature), error } => {
+                write!(f, "트랜잭션 실패 ({}): {}", signature, error)
+            }
+            Error::Transaction { signature: None, error } => write!(f, "트랜잭션 거부: {}", error),
+            Error::ConfirmationTimeout { signature, timeout, last_error } => {
+                write!(f, "트랜잭션 확인 시간 초과 ({:?}): {}", timeout, signature)?;
+                if let Some(error) = last_error {
+                    write!(f, " (마지막 상태 조회 오류: {})", error)?;
+                }
+                write!(f, " - 처리 여부를 서명으로 확인한 뒤 다시 전송하세요")
+            }
+            Error::Webhook(message) => write!(f, "웹훅 전송 실패: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::Simulation { error: Some(error), .. } | Error::Transaction { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ClientError> for Error {
+    fn from(error: ClientError) -> Self {
+        // 벤치마크용 클라이언트의 HTTP 오류 상태
+        if let Some(http) = HttpStatus::from_client_error(&error) {
+            return match http.status {
+                429 => Error::RateLimited(http.to_string()),
+                _ => Error::Transport(http.to_string()),
+            };
+        }
+        match error.kind {
+            ClientErrorKind::Io(error) => Error::Transport(error.to_string()),
+            ClientErrorKind::Reqwest(error) => match error.status() {
+                Some(status) if status.as_u16() == 429 => Error::RateLimited(error.to_string()),
+                _ => Error::Transport(error.to_string()),
+            },
+            ClientErrorKind::SerdeJson(error) => Error::Transport(format!("응답 파싱 실패: {}", error)),
+            ClientErrorKind::SigningError(error) => Error::Key(error.to_string()),
+            ClientErrorKind::TransactionError(TransactionError::BlockhashNotFound) => Error::BlockhashExpired,
+            ClientErrorKind::TransactionError(error) => Error::Transaction { signature: None, error },
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, data }) => match data {
+                RpcResponseErrorData::SendTransactionPreflightFailure(result) => {
+                    simulation_error(message, result.err, result.logs)
+                }
+                _ if RATE_LIMIT_CODES.contains(&code) => Error::RateLimited(message),
+                _ => Error::Rpc { code: Some(code), message },
+            },
+            ClientErrorKind::RpcError(RpcError::RpcRequestError(message)) => Error::Transport(message),
+            ClientErrorKind::RpcError(RpcError::ParseError(message)) => {
+                Error::Transport(format!("응답 파싱 실패: {}", message))
+            }
+            ClientErrorKind::RpcError(RpcError::ForUser(message)) => Error::Rpc { code: None, message },
+            ClientErrorKind::Custom(message) => Error::Rpc { code: None, message },
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+// 상태/커서/저널 파일의 JSON 오류 (형식 오류는 `InvalidData` 입출력 오류가 됨)
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Io(error.into())
+    }
+}
+
+impl From<ProgramError> for Error {
+    fn from(error: ProgramError) -> Self {
+        Error::Input(format!("계정 데이터 오류: {}", error))
+    }
+}
@@ -0,0 +1,340 @@
+//! 벤치마크 실패 분류
+//!
+//! 측정 도구는 실패한 요청을 버리지 않고 [`FailureKind`]로 분류해 [`FailureCounts`]에 셉니다.
+//! 통계에는 성공한 측정만 들어가고, 실패는 종류별 횟수와 비율로 따로 보고합니다.
+//!
+//! 벤치마크용 RPC 클라이언트([`crate::rpc::benchmark_client`])는 HTTP 오류 상태를 재시도하지 않고
+//! [`HttpStatus`]로 돌려주므로 429 응답의 `Retry-After`를 측정 도구가 직접 따를 수 있습니다
+//! ([`Failure::backoff`]). 기다린 시간은 응답 시간에 포함되지 않습니다.
+
+use crate::error::{Error, RATE_LIMIT_CODES};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::RpcError,
+};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+/// `Retry-After` 없이 요청 한도를 넘었을 때 다음 요청까지 기다리는 시간
+pub const RATE_LIMIT_PAUSE: Duration = Duration::from_millis(500);
+
+/// `Retry-After`를 따르는 최대 대기 시간
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// 실패 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FailureKind {
+    /// 요청 한도 초과 (HTTP 429 또는 JSON-RPC 429/-32429)
+    RateLimited,
+    /// 429가 아닌 4xx 응답 (인증 실패, 차단된 메서드 등)
+    HttpStatus,
+    /// 5xx 응답
+    ServerError,
+    /// 응답 시간 초과
+    Timeout,
+    /// 연결 거부
+    ConnectionRefused,
+    /// TLS 핸드셰이크/인증서 오류
+    Tls,
+    /// 그 밖의 연결 오류 (DNS, 연결 끊김 등)
+    Connection,
+    /// JSON-RPC 오류 응답
+    JsonRpc,
+    /// 잘못된 응답 등 그 밖의 오류
+    Other,
+}
+
+impl FailureKind {
+    /// 모든 종류 (출력 순서)
+    pub const ALL: [FailureKind; 9] = [
+        FailureKind::RateLimited,
+        FailureKind::HttpStatus,
+        FailureKind::ServerError,
+        FailureKind::Timeout,
+        FailureKind::ConnectionRefused,
+        FailureKind::Tls,
+        FailureKind::Connection,
+        FailureKind::JsonRpc,
+        FailureKind::Other,
+    ];
+
+    /// 기계가 읽는 출력의 이름
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FailureKind::RateLimited => "rate_limited",
+            FailureKind::HttpStatus => "http_4xx",
+            FailureKind::ServerError => "http_5xx",
+            FailureKind::Timeout => "timeout",
+            FailureKind::ConnectionRefused => "connection_refused",
+            FailureKind::Tls => "tls",
+            FailureKind::Connection => "connection",
+            FailureKind::JsonRpc => "json_rpc",
+            FailureKind::Other => "other",
+        }
+    }
+
+    /// 출력용 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            FailureKind::RateLimited => "요청 한도 초과",
+            FailureKind::HttpStatus => "HTTP 4xx",
+            FailureKind::ServerError => "HTTP 5xx",
+            FailureKind::Timeout => "타임아웃",
+            FailureKind::ConnectionRefused => "연결 거부",
+            FailureKind::Tls => "TLS 오류",
+            FailureKind::Connection => "연결 오류",
+            FailureKind::JsonRpc => "JSON-RPC 오류",
+            FailureKind::Other => "기타 오류",
+        }
+    }
+
+    // HTTP 오류 상태의 종류
+    fn from_status(status: u16) -> FailureKind {
+        match status {
+            429 => FailureKind::RateLimited,
+            500..=599 => FailureKind::ServerError,
+            _ => FailureKind::HttpStatus,
+        }
+    }
+}
+
+/// 성공하지 못한 HTTP 응답 (상태 코드와 `Retry-After`)
+///
+/// 벤치마크용 RPC 클라이언트가 반환하며, [`ClientError`]로 변환하면 입출력 오류 안에 담깁니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpStatus {
+    pub status: u16,
+    pub retry_after: Option<Duration>,
+}
+
+impl fmt::Display for HttpStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HTTP {}", self.status)?;
+        if let Some(retry_after) = self.retry_after {
+            write!(f, " (Retry-After: {:?})", retry_after)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for HttpStatus {}
+
+impl From<HttpStatus> for ClientError {
+    fn from(status: HttpStatus) -> Self {
+        io::Error::other(status).into()
+    }
+}
+
+impl HttpStatus {
+    /// RPC 클라이언트 오류 안의 HTTP 상태 (벤치마크용 클라이언트가 반환한 경우)
+    pub fn from_client_error(error: &ClientError) -> Option<HttpStatus> {
+        match error.kind() {
+            ClientErrorKind::Io(error) => error.get_ref()?.downcast_ref::<HttpStatus>().copied(),
+            _ => None,
+        }
+    }
+}
+
+/// 분류된 실패 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub kind: FailureKind,
+    /// HTTP 상태 코드 (HTTP 오류인 경우)
+    pub status: Option<u16>,
+    /// JSON-RPC 오류 코드 (JSON-RPC 오류 응답인 경우)
+    pub code: Option<i64>,
+    /// 다시 요청하기 전에 기다려야 하는 시간 (`Retry-After`)
+    pub retry_after: Option<Duration>,
+    pub message: String,
+}
+
+impl Failure {
+    pub fn new(kind: FailureKind, message: impl Into<String>) -> Failure {
+        Failure {
+            kind,
+            status: None,
+            code: None,
+            retry_after: None,
+            message: message.into(),
+        }
+    }
+
+    /// 다음 요청 전에 기다릴 시간 (요청 한도 초과면 `Retry-After`, 없으면 [`RATE_LIMIT_PAUSE`])
+    pub fn backoff(&self) -> Option<Duration> {
+        match self.kind {
+            FailureKind::RateLimited => Some(self.retry_after.unwrap_or(RATE_LIMIT_PAUSE).min(MAX_RETRY_AFTER)),
+            _ => None,
+        }
+    }
+
+    /// 측정 도구가 정한 시간 안에 응답이 없음
+    pub fn timeout(after: Duration) -> Failure {
+        Failure::new(FailureKind::Timeout, format!("{:?} 안에 응답 없음", after))
+    }
+
+    /// 성공하지 못한 HTTP 응답
+    pub fn from_status(http: HttpStatus, message: impl Into<String>) -> Failure {
+        Failure {
+            status: Some(http.status),
+            retry_after: http.retry_after,
+            ..Failure::new(FailureKind::from_status(http.status), message)
+        }
+    }
+
+    /// 연결 단계의 입출력 오류 (`context`는 실패한 단계, 예: "TCP 연결 실패")
+    pub fn from_io(error: &io::Error, context: &str) -> Failure {
+        Failure::new(connection_kind(error), format!("{}: {}", context, error))
+    }
+
+    /// RPC 클라이언트 오류 분류
+    pub fn from_client_error(error: &ClientError) -> Failure {
+        let message = error.to_string();
+        if let Some(http) = HttpStatus::from_client_error(error) {
+            return Failure::from_status(http, message);
+        }
+        match error.kind() {
+            ClientErrorKind::Reqwest(reqwest) => match reqwest.status() {
+                Some(status) => Failure {
+                    status: Some(status.as_u16()),
+                    ..Failure::new(FailureKind::from_status(status.as_u16()), message)
+                },
+                None if reqwest.is_timeout() => Failure::new(FailureKind::Timeout, message),
+                None if reqwest.is_decode() || reqwest.is_body() => Failure::new(FailureKind::Other, message),
+                None => Failure::new(connection_kind(reqwest), message),
+            },
+            ClientErrorKind::Io(io) => Failure::new(connection_kind(io), message),
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
+                let kind = match RATE_LIMIT_CODES.contains(code) {
+                    true => FailureKind::RateLimited,
+                    false => FailureKind::JsonRpc,
+                };
+                Failure {
+                    code: Some(*code),
+                    ..Failure::new(kind, message)
+                }
+            }
+            _ => Failure::new(FailureKind::Other, message),
+        }
+    }
+
+    /// 라이브러리 오류 분류 (전송처럼 [`Error`]를 반환하는 작업)
+    pub fn from_error(error: &Error) -> Failure {
+        let kind = match error {
+            Error::RateLimited(_) => FailureKind::RateLimited,
+            Error::Transport(_) => FailureKind::Connection,
+            Error::Rpc { .. } => FailureKind::JsonRpc,
+            Error::ConfirmationTimeout { .. } => FailureKind::Timeout,
+            _ => FailureKind::Other,
+        };
+        Failure {
+            code: match error {
+                Error::Rpc { code, .. } => *code,
+                _ => None,
+            },
+            ..Failure::new(kind, error.to_string())
+        }
+    }
+}
+
+// 연결 단계 오류의 원인 (오류 체인에서 연결 거부, 타임아웃, TLS 오류를 찾음)
+fn connection_kind(error: &(dyn std::error::Error + 'static)) -> FailureKind {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(io) = error.downcast_ref::<io::Error>() {
+            match io.kind() {
+                io::ErrorKind::ConnectionRefused => return FailureKind::ConnectionRefused,
+                io::ErrorKind::TimedOut => return FailureKind::Timeout,
+                _ => {}
+            }
+        }
+        let message = error.to_string().to_lowercase();
+        if message.contains("certificate") || message.contains("tls") || message.contains("handshake") {
+            return FailureKind::Tls;
+        }
+        current = error.source();
+    }
+    FailureKind::Connection
+}
+
+/// 종류별 실패 횟수
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FailureCounts {
+    kinds: BTreeMap<FailureKind, usize>,
+    statuses: BTreeMap<u16, usize>,
+    codes: BTreeMap<i64, usize>,
+    /// 마지막 실패 메시지
+    pub last_error: Option<String>,
+}
+
+impl FailureCounts {
+    pub fn new() -> FailureCounts {
+        FailureCounts::default()
+    }
+
+    /// 실패 하나 기록
+    pub fn record(&mut self, failure: &Failure) {
+        *self.kinds.entry(failure.kind).or_default() += 1;
+        if let Some(status) = failure.status {
+            *self.statuses.entry(status).or_default() += 1;
+        }
+        if let Some(code) = failure.code {
+            *self.codes.entry(code).or_default() += 1;
+        }
+        self.last_error = Some(failure.message.clone());
+    }
+
+    /// 다른 집계를 합침 (마지막 메시지는 `other`에 있으면 그 값)
+    pub fn merge(&mut self, other: &FailureCounts) {
+        for (kind, count) in &other.kinds {
+            *self.kinds.entry(*kind).or_default() += count;
+        }
+        for (status, count) in &other.statuses {
+            *self.statuses.entry(*status).or_default() += count;
+        }
+        for (code, count) in &other.codes {
+            *self.codes.entry(*code).or_default() += count;
+        }
+        if other.last_error.is_some() {
+            self.last_error.clone_from(&other.last_error);
+        }
+    }
+
+    /// 전체 실패 수
+    pub fn total(&self) -> usize {
+        self.kinds.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// 한 종류의 실패 수
+    pub fn count(&self, kind: FailureKind) -> usize {
+        self.kinds.get(&kind).copied().unwrap_or(0)
+    }
+
+    /// 실패가 있었던 종류와 횟수 ([`FailureKind::ALL`] 순서)
+    pub fn kinds(&self) -> impl Iterator<Item = (FailureKind, usize)> + '_ {
+        self.kinds.iter().map(|(kind, count)| (*kind, *count))
+    }
+
+    /// HTTP 상태 코드별 횟수
+    pub fn statuses(&self) -> impl Iterator<Item = (u16, usize)> + '_ {
+        self.statuses.iter().map(|(status, count)| (*status, *count))
+    }
+
+    /// JSON-RPC 오류 코드별 횟수
+    pub fn codes(&self) -> impl Iterator<Item = (i64, usize)> + '_ {
+        self.codes.iter().map(|(code, count)| (*code, *count))
+    }
+
+    /// 전체 시도 중 실패 비율 (`successes`는 성공한 측정 수)
+    pub fn rate(&self, successes: usize) -> f64 {
+        match successes + self.total() {
+            0 => 0.0,
+            attempts => self.total() as f64 / attempts as f64,
+        }
+    }
+}
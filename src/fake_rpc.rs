@@ -0,0 +1,536 @@
+//! 테스트용 인메모리 RPC 백엔드
+//!
+//! 네트워크 없이 [`RpcBackend`]를 사용하는 로직을 검증하기 위한 가짜 구현입니다.
+//! 잔액을 메모리에 보관하고 시스템 프로그램의 SOL 전송 명령만 실제로 반영하며,
+//! 메서드별 지연 시간, 오류, 트랜잭션 드롭을 스크립트로 주입할 수 있습니다.
+//!
+//! ```
+//! use solana_transfer_bot::fake_rpc::{self, FakeRpc, Method};
+//! use solana_transfer_bot::transfer;
+//! use solana_sdk::{pubkey::Pubkey, signature::{Keypair, Signer}};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let sender = Keypair::new();
+//! let recipient = Pubkey::new_unique();
+//! let fake = FakeRpc::new().with_balance(&sender.pubkey(), 1_000_000);
+//!
+//! // 첫 블록해시 조회는 실패, 두 번째 시도는 성공
+//! fake.fail_next(Method::GetLatestBlockhash, fake_rpc::transport_error("연결 거부"));
+//! assert!(transfer::send_sol(&fake, &sender, &recipient, 1_000, 0).await.is_err());
+//! transfer::send_sol(&fake, &sender, &recipient, 1_000, 0).await.unwrap();
+//! assert_eq!(fake.balance(&recipient), 1_000);
+//! # }
+//! ```
+
+use crate::backend::RpcBackend;
+use solana_client::{
+    client_error::{ClientError, Result as ClientResult},
+    rpc_filter::RpcFilterType,
+    rpc_request::{RpcError, RpcResponseErrorData},
+    rpc_response::{
+        Response, RpcPrioritizationFee, RpcResponseContext, RpcResult, RpcSimulateTransactionResult, RpcVersionInfo,
+    },
+};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    clock::{Slot, MAX_PROCESSING_AGE},
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+    hash::Hash,
+    instruction::InstructionError,
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    signature::Signature,
+    system_instruction::SystemInstruction,
+    system_program,
+    transaction::{Transaction, TransactionError},
+};
+use solana_transaction_status::{TransactionConfirmationStatus, TransactionStatus, UiConfirmedBlock};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 서명 하나당 기본 수수료 (lamports)
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+// 시스템 프로그램의 "잔액 부족" 오류 코드 (SystemError::ResultWithNegativeLamports)
+const INSUFFICIENT_LAMPORTS_ERROR: u32 = 1;
+
+// 실제 노드가 반환하는 JSON-RPC 오류 코드
+const PREFLIGHT_FAILURE_CODE: i64 = -32002;
+
+/// 스크립트 대상 RPC 메서드
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Method {
+    GetBalance,
+    GetLatestBlockhash,
+    IsBlockhashValid,
+    SendTransaction,
+    SimulateTransaction,
+    GetSignatureStatuses,
+    GetSlot,
+    GetBlockHeight,
+    GetVersion,
+    GetAccountInfo,
+    GetMultipleAccounts,
+    GetProgramAccounts,
+    GetBlock,
+    GetRecentPrioritizationFees,
+}
+
+/// 네트워크 연결 오류 생성 (타임아웃, 연결 거부 등)
+pub fn transport_error(message: &str) -> ClientError {
+    io::Error::new(io::ErrorKind::ConnectionRefused, message.to_string()).into()
+}
+
+/// JSON-RPC 오류 응답 생성 (예: 429 요청 한도 초과)
+pub fn rpc_error(code: i64, message: &str) -> ClientError {
+    RpcError::RpcResponseError {
+        code,
+        message: message.to_string(),
+        data: RpcResponseErrorData::Empty,
+    }
+    .into()
+}
+
+// 전송되어 블록에 포함된 트랜잭션
+struct Landed {
+    slot: Slot,
+    err: Option<TransactionError>,
+    // confirmed 상태가 되기까지 남은 상태 조회 횟수
+    polls_until_confirmed: usize,
+    // confirmed 다음 finalized 상태가 되기까지 남은 상태 조회 횟수
+    polls_until_finalized: usize,
+    // 최근 상태 캐시에서 밀려나 기록 조회로만 보임
+    evicted: bool,
+}
+
+#[derive(Default)]
+struct State {
+    balances: HashMap<Pubkey, u64>,
+    slot: Slot,
+    // processed 슬롯보다 confirmed, finalized 슬롯이 뒤처진 정도
+    commitment_lag: (Slot, Slot),
+    blockhash: Hash,
+    // 현재 블록해시를 발급한 블록 높이
+    blockhash_height: u64,
+    valid_blockhashes: HashSet<Hash>,
+    latency: HashMap<Method, Duration>,
+    errors: HashMap<Method, VecDeque<ClientError>>,
+    drop_sends: usize,
+    preflight: bool,
+    confirmation_polls: usize,
+    finalization_polls: usize,
+    landed: HashMap<Signature, Landed>,
+    sent: Vec<Transaction>,
+    calls: HashMap<Method, usize>,
+}
+
+/// 스크립트 가능한 인메모리 RPC 백엔드
+///
+/// 모든 메서드가 `&self`로 동작하므로 테스트 도중에도 스크립트를 추가할 수 있습니다.
+pub struct FakeRpc {
+    state: Mutex<State>,
+}
+
+impl Default for FakeRpc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeRpc {
+    /// 빈 원장으로 시작하는 가짜 백엔드 (프리플라이트 검사 활성화, 전송 즉시 확인)
+    pub fn new() -> Self {
+        let blockhash = Hash::new_unique();
+        let state = State {
+            slot: 1,
+            blockhash,
+            blockhash_height: 1,
+            valid_blockhashes: HashSet::from([blockhash]),
+            preflight: true,
+            ..State::default()
+        };
+        FakeRpc { state: Mutex::new(state) }
+    }
+
+    /// 계정 잔액 설정
+    pub fn with_balance(self, pubkey: &Pubkey, lamports: u64) -> Self {
+        self.set_balance(pubkey, lamports);
+        self
+    }
+
+    /// 계정 잔액 설정
+    pub fn set_balance(&self, pubkey: &Pubkey, lamports: u64) {
+        self.state().balances.insert(*pubkey, lamports);
+    }
+
+    /// 현재 잔액 (없는 계정은 0)
+    pub fn balance(&self, pubkey: &Pubkey) -> u64 {
+        self.state().balances.get(pubkey).copied().unwrap_or(0)
+    }
+
+    /// 메서드 호출마다 적용할 지연 시간 (동시에 실행되는 호출끼리는 서로 기다리지 않음)
+    pub fn set_latency(&self, method: Method, latency: Duration) {
+        self.state().latency.insert(method, latency);
+    }
+
+    /// 다음 호출이 주어진 오류로 실패하도록 예약 (여러 번 호출하면 순서대로 소비)
+    pub fn fail_next(&self, method: Method, error: ClientError) {
+        self.state().errors.entry(method).or_default().push_back(error);
+    }
+
+    /// 다음 `count`번의 전송을 받아들이되 블록에 포함시키지 않음
+    pub fn drop_next_sends(&self, count: usize) {
+        self.state().drop_sends += count;
+    }
+
+    /// 프리플라이트 검사 여부 (끄면 실패하는 트랜잭션도 오류와 함께 블록에 포함됨)
+    pub fn set_preflight(&self, enabled: bool) {
+        self.state().preflight = enabled;
+    }
+
+    /// 포함된 트랜잭션이 confirmed 상태가 되기까지 필요한 상태 조회 횟수
+    pub fn set_confirmation_polls(&self, polls: usize) {
+        self.state().confirmation_polls = polls;
+    }
+
+    /// confirmed 상태가 된 트랜잭션이 finalized 상태가 되기까지 필요한 상태 조회 횟수 (기본값 0은 바로 finalized)
+    pub fn set_finalization_polls(&self, polls: usize) {
+        self.state().finalization_polls = polls;
+    }
+
+    /// 새 블록해시를 발급하고 이전 블록해시를 모두 만료시킴
+    pub fn expire_blockhashes(&self) {
+        let mut state = self.state();
+        let blockhash = Hash::new_unique();
+        state.blockhash = blockhash;
+        state.blockhash_height = state.slot;
+        state.valid_blockhashes = HashSet::from([blockhash]);
+    }
+
+    /// 지금까지 포함된 트랜잭션을 최근 상태 캐시에서 밀어냄
+    ///
+    /// 이후 `get_signature_statuses`는 이 트랜잭션들을 `None`으로 답하고,
+    /// `get_signature_statuses_with_history`만 상태를 돌려줍니다.
+    pub fn evict_status_cache(&self) {
+        for landed in self.state().landed.values_mut() {
+            landed.evicted = true;
+        }
+    }
+
+    /// 슬롯 진행
+    pub fn advance_slot(&self, slots: Slot) {
+        self.state().slot += slots;
+    }
+
+    /// confirmed, finalized 슬롯이 processed 슬롯보다 뒤처진 정도 (기본값은 모두 0)
+    pub fn set_commitment_lag(&self, confirmed: Slot, finalized: Slot) {
+        self.state().commitment_lag = (confirmed, finalized);
+    }
+
+    /// 드롭된 것을 포함해 받아들인 모든 트랜잭션
+    pub fn sent_transactions(&self) -> Vec<Transaction> {
+        self.state().sent.clone()
+    }
+
+    /// 블록에 포함된 트랜잭션 수
+    pub fn landed_count(&self) -> usize {
+        self.state().landed.len()
+    }
+
+    /// 메서드 호출 횟수 (실패한 호출 포함)
+    pub fn call_count(&self, method: Method) -> usize {
+        self.state().calls.get(&method).copied().unwrap_or(0)
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 호출 기록, 지연 적용, 예약된 오류 반환
+    async fn begin(&self, method: Method) -> ClientResult<()> {
+        let latency = {
+            let mut state = self.state();
+            *state.calls.entry(method).or_default() += 1;
+            state.latency.get(&method).copied()
+        };
+        if let Some(latency) = latency {
+            tokio::time::sleep(latency).await;
+        }
+        match self.state().errors.get_mut(&method).and_then(|queue| queue.pop_front()) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    // 잔액이 있는 계정은 데이터가 없는 시스템 계정
+    fn account(&self, pubkey: &Pubkey) -> Option<Account> {
+        let lamports = *self.state().balances.get(pubkey)?;
+        Some(Account::new(lamports, 0, &system_program::id()))
+    }
+
+    // 커미트먼트별 슬롯 (가짜 원장에서는 블록 높이도 같은 값)
+    fn slot_at(&self, commitment: CommitmentConfig) -> Slot {
+        let state = self.state();
+        let lag = match commitment.commitment {
+            CommitmentLevel::Finalized => state.commitment_lag.1,
+            CommitmentLevel::Confirmed => state.commitment_lag.0,
+            _ => 0,
+        };
+        state.slot.saturating_sub(lag)
+    }
+
+    fn context(&self) -> RpcResponseContext {
+        RpcResponseContext {
+            slot: self.state().slot,
+            api_version: None,
+        }
+    }
+
+    // 서명 상태 (조회할 때마다 confirmed/finalized까지 남은 횟수를 하나씩 소비)
+    fn signature_statuses(&self, signatures: &[Signature], search_history: bool) -> Response<Vec<Option<TransactionStatus>>> {
+        let value = {
+            let mut state = self.state();
+            signatures
+                .iter()
+                .map(|signature| {
+                    let landed = state.landed.get_mut(signature).filter(|landed| search_history || !landed.evicted)?;
+                    let confirmation_status = if landed.polls_until_confirmed > 0 {
+                        landed.polls_until_confirmed -= 1;
+                        TransactionConfirmationStatus::Processed
+                    } else if landed.polls_until_finalized > 0 {
+                        landed.polls_until_finalized -= 1;
+                        TransactionConfirmationStatus::Confirmed
+                    } else {
+                        TransactionConfirmationStatus::Finalized
+                    };
+                    // 실제 노드처럼 finalized 트랜잭션은 confirmations가 없음
+                    let finalized = confirmation_status == TransactionConfirmationStatus::Finalized;
+                    Some(TransactionStatus {
+                        slot: landed.slot,
+                        confirmations: (!finalized).then_some(0),
+                        status: landed.err.clone().map_or(Ok(()), Err),
+                        err: landed.err.clone(),
+                        confirmation_status: Some(confirmation_status),
+                    })
+                })
+                .collect()
+        };
+        Response {
+            context: self.context(),
+            value,
+        }
+    }
+}
+
+// 서명 수수료를 차감하고 시스템 전송 명령을 적용한 잔액 계산 (원장은 변경하지 않음)
+fn execute(balances: &HashMap<Pubkey, u64>, transaction: &Transaction) -> (HashMap<Pubkey, u64>, Option<TransactionError>) {
+    let mut after = balances.clone();
+    let keys = &transaction.message.account_keys;
+    let payer = keys[0];
+    let fee = LAMPORTS_PER_SIGNATURE * transaction.signatures.len() as u64;
+
+    let payer_balance = after.get(&payer).copied().unwrap_or(0);
+    if payer_balance < fee {
+        return (balances.clone(), Some(TransactionError::InsufficientFundsForFee));
+    }
+    after.insert(payer, payer_balance - fee);
+    let fee_only = after.clone();
+
+    for (index, instruction) in transaction.message.instructions.iter().enumerate() {
+        if keys[instruction.program_id_index as usize] != system_program::id() {
+            continue;
+        }
+        let decoded = limited_deserialize::<SystemInstruction>(&instruction.data);
+        if let Ok(SystemInstruction::Transfer { lamports }) = decoded {
+            let from = keys[instruction.accounts[0] as usize];
+            let to = keys[instruction.accounts[1] as usize];
+            let from_balance = after.get(&from).copied().unwrap_or(0);
+            if from_balance < lamports {
+                let error = InstructionError::Custom(INSUFFICIENT_LAMPORTS_ERROR);
+                return (fee_only, Some(TransactionError::InstructionError(index as u8, error)));
+            }
+            after.insert(from, from_balance - lamports);
+            *after.entry(to).or_default() += lamports;
+        }
+    }
+    (after, None)
+}
+
+fn simulation_result(err: Option<TransactionError>) -> RpcSimulateTransactionResult {
+    let logs = match &err {
+        Some(err) => vec![format!("Program failed: {}", err)],
+        None => vec!["Program 11111111111111111111111111111111 success".to_string()],
+    };
+    RpcSimulateTransactionResult {
+        err,
+        logs: Some(logs),
+        accounts: None,
+        units_consumed: Some(150),
+        return_data: None,
+        inner_instructions: None,
+    }
+}
+
+impl RpcBackend for FakeRpc {
+    async fn get_balance(&self, pubkey: &Pubkey) -> ClientResult<u64> {
+        self.begin(Method::GetBalance).await?;
+        Ok(self.balance(pubkey))
+    }
+
+    async fn get_latest_blockhash(&self) -> ClientResult<Hash> {
+        self.begin(Method::GetLatestBlockhash).await?;
+        Ok(self.state().blockhash)
+    }
+
+    async fn get_latest_blockhash_with_commitment(&self, _commitment: CommitmentConfig) -> ClientResult<(Hash, u64)> {
+        self.begin(Method::GetLatestBlockhash).await?;
+        let state = self.state();
+        Ok((state.blockhash, state.blockhash_height + MAX_PROCESSING_AGE as u64))
+    }
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash) -> ClientResult<bool> {
+        self.begin(Method::IsBlockhashValid).await?;
+        Ok(self.state().valid_blockhashes.contains(blockhash))
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> ClientResult<Signature> {
+        self.begin(Method::SendTransaction).await?;
+        transaction.verify().map_err(ClientError::from)?;
+        let signature = transaction.signatures[0];
+
+        let mut state = self.state();
+        if !state.valid_blockhashes.contains(&transaction.message.recent_blockhash) {
+            return Err(TransactionError::BlockhashNotFound.into());
+        }
+        if state.landed.contains_key(&signature) {
+            return Err(TransactionError::AlreadyProcessed.into());
+        }
+
+        let (after, err) = execute(&state.balances, transaction);
+        if let (Some(error), true) = (&err, state.preflight) {
+            return Err(RpcError::RpcResponseError {
+                code: PREFLIGHT_FAILURE_CODE,
+                message: format!("Transaction simulation failed: {}", error),
+                data: RpcResponseErrorData::SendTransactionPreflightFailure(simulation_result(err.clone())),
+            }
+            .into());
+        }
+
+        state.sent.push(transaction.clone());
+        if state.drop_sends > 0 {
+            state.drop_sends -= 1;
+            return Ok(signature);
+        }
+
+        state.balances = after;
+        state.slot += 1;
+        let landed = Landed {
+            slot: state.slot,
+            err,
+            polls_until_confirmed: state.confirmation_polls,
+            polls_until_finalized: state.finalization_polls,
+            evicted: false,
+        };
+        state.landed.insert(signature, landed);
+        Ok(signature)
+    }
+
+    async fn simulate_transaction(&self, transaction: &Transaction) -> RpcResult<RpcSimulateTransactionResult> {
+        self.begin(Method::SimulateTransaction).await?;
+        let err = {
+            let state = self.state();
+            if state.valid_blockhashes.contains(&transaction.message.recent_blockhash) {
+                execute(&state.balances, transaction).1
+            } else {
+                Some(TransactionError::BlockhashNotFound)
+            }
+        };
+        Ok(Response {
+            context: self.context(),
+            value: simulation_result(err),
+        })
+    }
+
+    async fn get_signature_statuses(&self, signatures: &[Signature]) -> RpcResult<Vec<Option<TransactionStatus>>> {
+        self.begin(Method::GetSignatureStatuses).await?;
+        Ok(self.signature_statuses(signatures, false))
+    }
+
+    async fn get_signature_statuses_with_history(&self, signatures: &[Signature]) -> RpcResult<Vec<Option<TransactionStatus>>> {
+        self.begin(Method::GetSignatureStatuses).await?;
+        Ok(self.signature_statuses(signatures, true))
+    }
+
+    async fn get_slot(&self, commitment: CommitmentConfig) -> ClientResult<Slot> {
+        self.begin(Method::GetSlot).await?;
+        Ok(self.slot_at(commitment))
+    }
+
+    async fn get_block_height(&self, commitment: CommitmentConfig) -> ClientResult<u64> {
+        self.begin(Method::GetBlockHeight).await?;
+        Ok(self.slot_at(commitment))
+    }
+
+    async fn get_version(&self) -> ClientResult<RpcVersionInfo> {
+        self.begin(Method::GetVersion).await?;
+        Ok(RpcVersionInfo {
+            solana_core: "fake".to_string(),
+            feature_set: None,
+        })
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> ClientResult<Option<Account>> {
+        self.begin(Method::GetAccountInfo).await?;
+        Ok(self.account(pubkey))
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        self.begin(Method::GetMultipleAccounts).await?;
+        Ok(pubkeys.iter().map(|pubkey| self.account(pubkey)).collect())
+    }
+
+    async fn get_program_accounts(&self, program: &Pubkey, filters: &[RpcFilterType]) -> ClientResult<Vec<(Pubkey, Account)>> {
+        self.begin(Method::GetProgramAccounts).await?;
+        if *program != system_program::id() {
+            return Ok(vec![]);
+        }
+        let pubkeys: Vec<Pubkey> = self.state().balances.keys().copied().collect();
+        Ok(pubkeys
+            .into_iter()
+            .filter_map(|pubkey| Some((pubkey, self.account(&pubkey)?)))
+            .filter(|(_, account)| {
+                let shared = AccountSharedData::from(account.clone());
+                filters.iter().all(|filter| filter.allows(&shared))
+            })
+            .collect())
+    }
+
+    async fn get_block(&self, slot: Slot) -> ClientResult<UiConfirmedBlock> {
+        self.begin(Method::GetBlock).await?;
+        if slot > self.state().slot {
+            return Err(rpc_error(-32004, &format!("Block not available for slot {}", slot)));
+        }
+        Ok(UiConfirmedBlock {
+            previous_blockhash: Hash::default().to_string(),
+            blockhash: self.state().blockhash.to_string(),
+            parent_slot: slot.saturating_sub(1),
+            transactions: None,
+            signatures: None,
+            rewards: None,
+            block_time: None,
+            block_height: Some(slot),
+        })
+    }
+
+    async fn get_recent_prioritization_fees(&self, _addresses: &[Pubkey]) -> ClientResult<Vec<RpcPrioritizationFee>> {
+        self.begin(Method::GetRecentPrioritizationFees).await?;
+        Ok(vec![RpcPrioritizationFee {
+            slot: self.state().slot,
+            prioritization_fee: 0,
+        }])
+    }
+}
+
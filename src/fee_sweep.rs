@@ -0,0 +1,252 @@
+//! 우선순위 수수료 스윕 (`tx_speed_test --fee-sweep`)
+//!
+//! compute unit 가격(과 선택적으로 CU 한도) 격자의 단계마다 트랜잭션을 여러 번 보내고, 단계별 포함률,
+//! 포함까지 걸린 슬롯 수, confirmed까지 시간, 비용을 비교해 목표 시간 안에 포함되는 가장 싼 가격을 추천합니다.
+//!
+//! 모든 트랜잭션이 같은 지갑에서 보내지므로 한 라운드 안에서는 단계마다 하나씩 차례로 보내고 포함을 기다립니다.
+//! 네트워크 상태 변화가 특정 단계에만 영향을 주지 않도록 라운드마다 시작 단계를 하나씩 돌려 가며
+//! `--repeat` 라운드를 반복합니다.
+
+use crate::backend::RpcBackend;
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::landing::{self, Landing, LandingStats, TestRun};
+use crate::output::OutputFormat;
+use crate::transfer::{self, ConfirmOptions};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signature::{Keypair, Signer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// 기본 compute unit 가격 격자 (micro-lamports per CU)
+pub const DEFAULT_PRICES: [u64; 5] = [0, 1_000, 10_000, 100_000, 1_000_000];
+
+/// 단계마다 보내는 기본 트랜잭션 수
+pub const DEFAULT_REPEAT: usize = 3;
+
+/// 추천 기준이 되는 기본 목표 시간 (confirmed까지 p50)
+pub const DEFAULT_TARGET: Duration = Duration::from_secs(2);
+
+/// 추천하려면 필요한 최소 포함률
+pub const MIN_LANDING_RATE: f64 = 0.9;
+
+/// CU 한도 명령이 없을 때 적용되는 한도 (명령 하나당)
+pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+
+// 측정용 트랜잭션의 compute budget 외 명령 수 (메모와 전송)
+const TRANSFER_INSTRUCTIONS: u32 = 2;
+
+/// 서명 하나의 기본 수수료 (lamports)
+pub const BASE_FEE: u64 = 5_000;
+
+/// 격자의 한 단계
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeLevel {
+    /// compute unit 가격 (micro-lamports per CU)
+    pub price: u64,
+    /// CU 한도 (`None`이면 한도 명령을 붙이지 않음)
+    pub cu_limit: Option<u32>,
+}
+
+impl FeeLevel {
+    /// 우선순위 수수료 계산에 쓰이는 CU 한도 (한도 명령이 없으면 측정용 트랜잭션의 기본 한도)
+    pub fn compute_units(&self) -> u32 {
+        self.cu_limit.unwrap_or(DEFAULT_COMPUTE_UNIT_LIMIT * TRANSFER_INSTRUCTIONS)
+    }
+
+    /// 트랜잭션 하나의 우선순위 수수료 (lamports, 가격 × CU 한도를 올림)
+    pub fn priority_fee(&self) -> u64 {
+        (self.price as u128 * self.compute_units() as u128).div_ceil(1_000_000) as u64
+    }
+
+    /// 트랜잭션 하나의 총 수수료 (기본 수수료 + 우선순위 수수료)
+    pub fn cost(&self) -> u64 {
+        BASE_FEE + self.priority_fee()
+    }
+}
+
+impl fmt::Display for FeeLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.cu_limit {
+            Some(limit) => write!(f, "{} µL/CU, 한도 {} CU", self.price, limit),
+            None => write!(f, "{} µL/CU", self.price),
+        }
+    }
+}
+
+/// 수수료 스윕 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepOptions {
+    /// 가격 순서대로, 같은 가격 안에서는 CU 한도 순서대로
+    pub levels: Vec<FeeLevel>,
+    /// 단계마다 보내는 트랜잭션 수 (라운드 수)
+    pub repeat: usize,
+    /// confirmed까지 p50 목표
+    pub target: Duration,
+}
+
+impl Default for SweepOptions {
+    fn default() -> Self {
+        SweepOptions {
+            levels: grid(&DEFAULT_PRICES, &[]),
+            repeat: DEFAULT_REPEAT,
+            target: DEFAULT_TARGET,
+        }
+    }
+}
+
+impl SweepOptions {
+    /// 명령줄 옵션 (`--prices`, `--cu-limits` 쉼표 구분 목록, `--repeat`, `--target` 밀리초)
+    pub fn from_args(args: &Args) -> Result<SweepOptions> {
+        let prices = match args.value("prices") {
+            Some(spec) => parse_list("prices", spec)?,
+            None => DEFAULT_PRICES.to_vec(),
+        };
+        let limits = match args.value("cu-limits") {
+            Some(spec) => parse_list("cu-limits", spec)?,
+            None => Vec::new(),
+        };
+        if limits.contains(&0) {
+            return Err(Error::Input("--cu-limits는 0보다 커야 합니다".to_string()));
+        }
+        let repeat: usize = args.parse_or("repeat", DEFAULT_REPEAT)?;
+        let target: u64 = args.parse_or("target", DEFAULT_TARGET.as_millis() as u64)?;
+        Ok(SweepOptions {
+            levels: grid(&prices, &limits),
+            repeat: repeat.max(1),
+            target: Duration::from_millis(target),
+        })
+    }
+}
+
+// 가격과 CU 한도의 모든 조합 (한도 목록이 비어 있으면 한도 없음)
+fn grid(prices: &[u64], limits: &[u32]) -> Vec<FeeLevel> {
+    let limits: Vec<Option<u32>> = if limits.is_empty() { vec![None] } else { limits.iter().copied().map(Some).collect() };
+    prices
+        .iter()
+        .flat_map(|&price| limits.iter().map(move |&cu_limit| FeeLevel { price, cu_limit }))
+        .collect()
+}
+
+// 쉼표로 구분한 숫자 목록 (중복은 한 번만)
+fn parse_list<T: FromStr + PartialEq>(name: &str, spec: &str) -> Result<Vec<T>> {
+    let mut values: Vec<T> = Vec::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let value = entry.parse().map_err(|_| Error::Input(format!("--{} 값 오류: {}", name, entry)))?;
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+    if values.is_empty() {
+        return Err(Error::Input(format!("--{}에 값이 없습니다", name)));
+    }
+    Ok(values)
+}
+
+/// 한 단계의 결과
+#[derive(Debug, Clone)]
+pub struct LevelResult {
+    pub level: FeeLevel,
+    pub stats: LandingStats,
+}
+
+impl LevelResult {
+    /// 포함된 트랜잭션이 낸 수수료 합계 (lamports, 포함되지 않은 트랜잭션은 수수료가 없음)
+    pub fn total_cost(&self) -> u64 {
+        self.level.cost() * self.stats.landed as u64
+    }
+
+    /// 포함률이 [`MIN_LANDING_RATE`] 이상이고 confirmed까지 p50이 `target` 이내인지
+    pub fn meets(&self, target: Duration) -> bool {
+        self.stats.landing_rate() >= MIN_LANDING_RATE
+            && self.stats.confirmed.summary().is_some_and(|summary| summary.p50 <= target)
+    }
+}
+
+/// 수수료 스윕 결과
+#[derive(Debug, Clone)]
+pub struct SweepReport {
+    /// 측정 트랜잭션 메모에 담긴 실행 ID
+    pub run_id: String,
+    pub target: Duration,
+    /// [`SweepOptions::levels`] 순서
+    pub levels: Vec<LevelResult>,
+}
+
+impl SweepReport {
+    /// 목표를 만족하는 단계 중 트랜잭션 하나의 비용이 가장 싼 단계 (없으면 `None`)
+    pub fn recommended(&self) -> Option<&LevelResult> {
+        self.levels
+            .iter()
+            .filter(|result| result.meets(self.target))
+            .min_by_key(|result| (result.level.cost(), result.level.price))
+    }
+}
+
+/// 라운드마다 모든 단계의 트랜잭션(`run`의 메모를 붙여 `payer`가 자기 자신에게 `lamports` 전송)을 차례로 보내고 포함 측정
+///
+/// 같은 지갑의 트랜잭션끼리 경쟁하지 않도록 한 번에 하나씩 보내며, 라운드마다 시작 단계를 하나씩 돌림
+pub async fn run_sweep<B: RpcBackend + ?Sized>(
+    backend: &B,
+    payer: &Keypair,
+    run: &TestRun,
+    lamports: u64,
+    options: &SweepOptions,
+    track: &ConfirmOptions,
+    output: OutputFormat,
+) -> SweepReport {
+    let mut levels: Vec<LevelResult> = options
+        .levels
+        .iter()
+        .map(|&level| LevelResult {
+            level,
+            stats: LandingStats::new(),
+        })
+        .collect();
+
+    for round in 1..=options.repeat {
+        output.progress(format_args!("  라운드 {}/{}", round, options.repeat));
+        let count = levels.len();
+        for offset in 0..count {
+            let result = &mut levels[(round - 1 + offset) % count];
+            let landing = send(backend, payer, run, lamports, &result.level, track).await;
+            match &landing {
+                Ok(landing) => match (landing.slots_to_land(), landing.confirmed) {
+                    (Some(slots), Some(confirmed)) => {
+                        output.progress(format_args!("    {}: {}슬롯, confirmed {:?}", result.level, slots, confirmed))
+                    }
+                    _ if landing.landed() => output.progress(format_args!("    {}: 포함됨 (confirmed 전 종료)", result.level)),
+                    _ => output.progress(format_args!("    {}: 포함되지 않음", result.level)),
+                },
+                Err(e) => output.progress(format_args!("    {}: 오류: {}", result.level, e)),
+            }
+            result.stats.record(&landing);
+        }
+    }
+
+    SweepReport {
+        run_id: run.id().to_string(),
+        target: options.target,
+        levels,
+    }
+}
+
+// 단계 하나의 트랜잭션을 만들어 보내고 포함 측정
+async fn send<B: RpcBackend + ?Sized>(
+    backend: &B,
+    payer: &Keypair,
+    run: &TestRun,
+    lamports: u64,
+    level: &FeeLevel,
+    track: &ConfirmOptions,
+) -> Result<Landing> {
+    let (_, transfer) = run.next_transfer(&payer.pubkey(), lamports);
+    let mut instructions = Vec::new();
+    if let Some(limit) = level.cu_limit {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    }
+    instructions.extend(transfer);
+    let transaction = transfer::build_transaction(backend, payer, &[], instructions, level.price).await?;
+    landing::track(backend, &transaction, track).await
+}
@@ -0,0 +1,248 @@
+//! 엔드포인트 최신성 비교 (`rpc_benchmark --freshness`)
+//!
+//! 응답이 빨라도 뒤처진 상태를 돌려주는 RPC는 쓸모가 없습니다. 표본 간격(`--interval`)마다 모든
+//! 엔드포인트에 동시에 커미트먼트별 `getSlot`, `getBlockHeight`, `getLatestBlockhash`를 요청하고,
+//! 각 엔드포인트가 같은 표본에서 가장 앞선 값보다 얼마나 뒤처졌는지를 측정 시간(`--duration`) 동안
+//! 집계합니다.
+//!
+//! 블록해시의 최신성은 `getLatestBlockhash`가 함께 돌려주는 `lastValidBlockHeight`로 비교합니다.
+//! 같은 시점에 발급된 블록해시라면 이 값도 같으므로, 다른 엔드포인트보다 평균
+//! [`STALE_BLOCKHASH_LAG`]블록 넘게 작은 엔드포인트는 오래된 블록해시를 돌려준다고 표시합니다.
+
+use crate::backend::RpcBackend;
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::failure::{Failure, FailureCounts};
+use solana_client::client_error::Result as ClientResult;
+use solana_sdk::clock::Slot;
+use solana_sdk::commitment_config::{CommitmentConfig, CommitmentLevel};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 기본 측정 시간
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(10);
+
+/// 기본 표본 간격
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 기본 요청 타임아웃
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 블록해시가 평균 이 블록 수보다 많이 뒤처지면 오래된 블록해시로 표시 (약 4초)
+pub const STALE_BLOCKHASH_LAG: f64 = 10.0;
+
+/// 슬롯을 비교하는 커미트먼트 ([`Observation::slots`] 순서)
+pub const COMMITMENTS: [CommitmentLevel; 3] = [CommitmentLevel::Processed, CommitmentLevel::Confirmed, CommitmentLevel::Finalized];
+
+// 블록 높이와 블록해시를 조회하는 커미트먼트
+const COMMITMENT: CommitmentConfig = CommitmentConfig::confirmed();
+
+/// 최신성 비교 설정
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FreshnessOptions {
+    /// 표본을 모으는 시간
+    pub duration: Duration,
+    /// 표본 사이 간격
+    pub interval: Duration,
+    /// 요청 하나의 타임아웃 (넘으면 그 값은 비교에서 빠짐)
+    pub timeout: Duration,
+}
+
+impl Default for FreshnessOptions {
+    fn default() -> Self {
+        FreshnessOptions {
+            duration: DEFAULT_DURATION,
+            interval: DEFAULT_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl FreshnessOptions {
+    /// 명령줄 옵션 (`--duration` 초, `--interval`/`--timeout` 밀리초)
+    pub fn from_args(args: &Args) -> Result<FreshnessOptions> {
+        let duration: f64 = args.parse_or("duration", DEFAULT_DURATION.as_secs_f64())?;
+        let interval: u64 = args.parse_or("interval", DEFAULT_INTERVAL.as_millis() as u64)?;
+        let timeout: u64 = args.parse_or("timeout", DEFAULT_TIMEOUT.as_millis() as u64)?;
+        if !duration.is_finite() || duration <= 0.0 || interval == 0 {
+            return Err(Error::Input("--duration과 --interval은 0보다 커야 합니다".to_string()));
+        }
+        Ok(FreshnessOptions {
+            duration: Duration::from_secs_f64(duration),
+            interval: Duration::from_millis(interval),
+            timeout: Duration::from_millis(timeout.max(1)),
+        })
+    }
+
+    /// 측정 시간 동안 모을 표본 수 (최소 하나)
+    pub fn samples(&self) -> usize {
+        (self.duration.as_secs_f64() / self.interval.as_secs_f64()).ceil().max(1.0) as usize
+    }
+}
+
+/// 한 표본에서 엔드포인트 하나가 돌려준 값 (실패한 요청은 `None`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Observation {
+    /// [`COMMITMENTS`] 순서의 슬롯
+    pub slots: [Option<Slot>; 3],
+    /// confirmed 블록 높이
+    pub block_height: Option<u64>,
+    /// confirmed 최근 블록해시의 `lastValidBlockHeight` (블록해시가 새로울수록 큼)
+    pub last_valid_block_height: Option<u64>,
+}
+
+/// 엔드포인트 하나에 표본 요청을 동시에 보냄
+pub async fn observe<B: RpcBackend>(backend: &B, timeout: Duration) -> (Observation, Vec<Failure>) {
+    let slot = |level: CommitmentLevel| backend.get_slot(CommitmentConfig { commitment: level });
+    let (processed, confirmed, finalized, block_height, blockhash) = tokio::join!(
+        timed(timeout, slot(COMMITMENTS[0])),
+        timed(timeout, slot(COMMITMENTS[1])),
+        timed(timeout, slot(COMMITMENTS[2])),
+        timed(timeout, backend.get_block_height(COMMITMENT)),
+        timed(timeout, backend.get_latest_blockhash_with_commitment(COMMITMENT)),
+    );
+
+    let mut failures = Vec::new();
+    let mut keep = |result: std::result::Result<u64, Failure>| match result {
+        Ok(value) => Some(value),
+        Err(failure) => {
+            failures.push(failure);
+            None
+        }
+    };
+    let observation = Observation {
+        slots: [keep(processed), keep(confirmed), keep(finalized)],
+        block_height: keep(block_height),
+        last_valid_block_height: keep(blockhash.map(|(_, last_valid)| last_valid)),
+    };
+    (observation, failures)
+}
+
+// 타임아웃을 적용한 요청 하나
+async fn timed<T>(timeout: Duration, request: impl Future<Output = ClientResult<T>>) -> std::result::Result<T, Failure> {
+    match tokio::time::timeout(timeout, request).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(Failure::from_client_error(&e)),
+        Err(_) => Err(Failure::timeout(timeout)),
+    }
+}
+
+/// 뒤처진 정도 (표본마다 그 표본에서 가장 앞선 값과의 차이)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Lag {
+    /// 비교한 표본 수
+    pub samples: usize,
+    /// 차이의 합
+    pub total: u64,
+    pub max: u64,
+}
+
+impl Lag {
+    pub fn record(&mut self, lag: u64) {
+        self.samples += 1;
+        self.total += lag;
+        self.max = self.max.max(lag);
+    }
+
+    /// 평균 (비교한 표본이 없으면 `None`)
+    pub fn average(&self) -> Option<f64> {
+        (self.samples > 0).then(|| self.total as f64 / self.samples as f64)
+    }
+}
+
+/// 엔드포인트 하나의 최신성
+#[derive(Debug, Clone, Default)]
+pub struct EndpointFreshness {
+    /// [`COMMITMENTS`] 순서의 슬롯 차이
+    pub slot_lag: [Lag; 3],
+    /// 블록 높이 차이
+    pub block_height_lag: Lag,
+    /// 최근 블록해시의 `lastValidBlockHeight` 차이 (블록)
+    pub blockhash_lag: Lag,
+    /// 종류별 실패 수
+    pub failures: FailureCounts,
+}
+
+impl EndpointFreshness {
+    /// 다른 엔드포인트보다 평균 [`STALE_BLOCKHASH_LAG`]블록 넘게 오래된 블록해시를 돌려주는지
+    pub fn stale_blockhash(&self) -> bool {
+        self.blockhash_lag.average().is_some_and(|lag| lag > STALE_BLOCKHASH_LAG)
+    }
+}
+
+/// 모든 엔드포인트의 최신성 비교 결과
+#[derive(Debug, Clone, Default)]
+pub struct FreshnessReport {
+    /// 모은 표본 수
+    pub samples: usize,
+    /// 측정 동안 관찰한 가장 높은 processed 슬롯
+    pub highest_slot: Option<Slot>,
+    /// 엔드포인트별 결과 (설정한 엔드포인트 순서)
+    pub endpoints: Vec<EndpointFreshness>,
+}
+
+impl FreshnessReport {
+    /// 엔드포인트 수만큼 빈 결과
+    pub fn new(endpoints: usize) -> FreshnessReport {
+        FreshnessReport {
+            endpoints: vec![EndpointFreshness::default(); endpoints],
+            ..FreshnessReport::default()
+        }
+    }
+
+    /// 같은 시점에 모은 표본 하나를 반영 (엔드포인트 순서, 값이 없는 엔드포인트는 그 항목의 비교에서 빠짐)
+    pub fn record(&mut self, observations: &[Observation]) {
+        self.samples += 1;
+        for (index, _) in COMMITMENTS.iter().enumerate() {
+            let values: Vec<Option<u64>> = observations.iter().map(|o| o.slots[index]).collect();
+            self.compare(&values, |endpoint| &mut endpoint.slot_lag[index]);
+        }
+        let values: Vec<Option<u64>> = observations.iter().map(|o| o.block_height).collect();
+        self.compare(&values, |endpoint| &mut endpoint.block_height_lag);
+        let values: Vec<Option<u64>> = observations.iter().map(|o| o.last_valid_block_height).collect();
+        self.compare(&values, |endpoint| &mut endpoint.blockhash_lag);
+
+        let highest = observations.iter().filter_map(|o| o.slots[0]).max();
+        self.highest_slot = self.highest_slot.max(highest);
+    }
+
+    // 표본의 최댓값과 각 엔드포인트 값의 차이 기록
+    fn compare(&mut self, values: &[Option<u64>], lag: impl Fn(&mut EndpointFreshness) -> &mut Lag) {
+        let Some(highest) = values.iter().flatten().max().copied() else {
+            return;
+        };
+        for (endpoint, value) in self.endpoints.iter_mut().zip(values) {
+            if let Some(value) = value {
+                lag(endpoint).record(highest.saturating_sub(*value));
+            }
+        }
+    }
+}
+
+/// 표본 간격마다 모든 엔드포인트를 동시에 조회하고 최신성 비교 (엔드포인트 순서대로 결과)
+pub async fn run_freshness<B: RpcBackend + 'static>(backends: &[Arc<B>], options: &FreshnessOptions) -> FreshnessReport {
+    let mut report = FreshnessReport::new(backends.len());
+    let started = Instant::now();
+    for sample in 0..options.samples() {
+        tokio::time::sleep_until((started + options.interval * sample as u32).into()).await;
+        let tasks: Vec<_> = backends
+            .iter()
+            .map(|backend| {
+                let (backend, timeout) = (backend.clone(), options.timeout);
+                tokio::spawn(async move { observe(backend.as_ref(), timeout).await })
+            })
+            .collect();
+
+        let mut observations = Vec::with_capacity(tasks.len());
+        for (endpoint, task) in report.endpoints.iter_mut().zip(tasks) {
+            let (observation, failures) = task.await.unwrap_or_default();
+            for failure in &failures {
+                endpoint.failures.record(failure);
+            }
+            observations.push(observation);
+        }
+        report.record(&observations);
+    }
+    report
+}
@@ -0,0 +1,444 @@
+//! 지갑 트랜잭션 내역 내보내기 (`history` 하위 명령)
+
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::output::{csv_escape, OutputFormat};
+use crate::state_file;
+use serde::{Deserialize, Serialize};
+use solana_client::{
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::RpcTransactionConfig,
+    rpc_response::RpcConfirmedTransactionStatusWithSignature,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::Signature,
+};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta,
+    UiLoadedAddresses,
+    UiTransactionEncoding,
+    UiTransactionTokenBalance,
+};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+// getSignaturesForAddress 한 번에 가져올 최대 서명 수 (RPC 상한 1000)
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+// CSV 헤더 (HistoryRecord 필드 순서와 동일)
+const CSV_HEADER: &str = "signature,slot,block_time,status,asset,change,fee_lamports,fee_payer,memo,counterparty";
+
+/// 사용법 안내 (연결 없이 출력할 수 있도록 공개)
+pub const USAGE: &str = "사용법:
+  history <지갑 주소> [옵션]
+옵션:
+  --output table|json|csv 출력 형식 (기본값: 표, --file을 지정하면 csv, json은 한 줄에 하나의 JSON 객체)
+  --file <파일>          내보낼 파일 (기본값: 표준 출력, 표 형식은 지원하지 않음)
+  --limit <개수>         가져올 최대 트랜잭션 수 (기본값: 전체)
+  --page-size <개수>     페이지당 서명 수 (기본값: 100)
+  --until <서명>         이 서명까지만 가져오기 (이전 내보내기 이후의 내역만)
+  --cursor <파일>        마지막으로 가져온 서명을 기록하고, 재실행 시 그 이후부터 이어서 가져오기
+  --delay-ms <밀리초>    트랜잭션 조회 사이 대기 시간 (기본값: 0)";
+
+/// 지갑 하나의 트랜잭션에서 발생한 자산 변동 하나
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub status: String,
+    // "SOL" 또는 토큰 민트 주소
+    pub asset: String,
+    // 부호 있는 변동량 (UI 단위, 수수료 제외)
+    pub change: String,
+    pub fee_lamports: u64,
+    pub fee_payer: bool,
+    pub memo: Option<String>,
+    pub counterparty: Option<String>,
+}
+
+// 중단된 내보내기를 이어서 진행하기 위한 커서 (트랜잭션 하나를 기록할 때마다 갱신)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cursor {
+    last_signature: Option<String>,
+    // 커서를 저장한 시점의 출력 파일 길이 (이후에 기록된 줄은 재실행 시 잘라낸다)
+    #[serde(default)]
+    output_len: Option<u64>,
+}
+
+// 내보내기 출력 대상
+enum Writer {
+    File(BufWriter<File>),
+    Stdout(std::io::Stdout),
+}
+
+impl Writer {
+    fn inner(&mut self) -> &mut dyn Write {
+        match self {
+            Writer::File(file) => file,
+            Writer::Stdout(stdout) => stdout,
+        }
+    }
+
+    // 지금까지 기록한 내용을 내보내고 파일 길이 반환 (표준 출력이면 `None`)
+    fn flush(&mut self) -> Result<Option<u64>> {
+        self.inner().flush()?;
+        match self {
+            Writer::File(file) => Ok(Some(file.get_ref().metadata()?.len())),
+            Writer::Stdout(_) => Ok(None),
+        }
+    }
+}
+
+/// 공통 `--output` 옵션의 출력 형식
+///
+/// 지정하지 않으면 `--file`로 내보낼 때는 csv, 표준 출력이면 표입니다. 표는 파일로 내보낼 수 없습니다.
+pub fn format(args: &Args) -> Result<OutputFormat> {
+    let file = args.value("file");
+    let format = match args.value("output") {
+        Some(value) => value.parse()?,
+        None if file.is_some() => OutputFormat::Csv,
+        None => OutputFormat::Table,
+    };
+    if format.is_table() && file.is_some() {
+        return Err(Error::Input("표 형식은 파일로 내보낼 수 없습니다 (--output json 또는 csv)".to_string()));
+    }
+    Ok(format)
+}
+
+/// history 하위 명령 실행
+pub fn run(rpc_client: &RpcClient, args: &Args) -> Result<()> {
+    let wallet = match args.positional(1) {
+        Some(address) => Pubkey::from_str(address)
+            .map_err(|_| Error::Input(format!("유효하지 않은 솔라나 주소입니다: {}", address)))?,
+        None => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+    };
+
+    let format = format(args)?;
+    let limit: usize = args.parse_or("limit", usize::MAX)?;
+    let page_size: usize = args.parse_or("page-size", DEFAULT_PAGE_SIZE)?;
+    let delay = Duration::from_millis(args.parse_or("delay-ms", 0)?);
+    let until = args.value("until").map(parse_signature).transpose()?;
+    let cursor_path = args.value("cursor");
+
+    // 커서가 있으면 마지막으로 가져온 서명 이전부터 이어서 진행
+    let cursor = match cursor_path {
+        Some(path) => state_file::load(path)?,
+        None => Cursor::default(),
+    };
+    let mut before = cursor.last_signature.as_deref().map(parse_signature).transpose()?;
+    if let Some(signature) = &before {
+        eprintln!("이전 실행에 이어서 {} 이전의 내역을 가져옵니다.", signature);
+    }
+
+    let file = args.value("file");
+    // 커서 저장 이후에 기록된 줄은 다시 가져오므로 잘라내어 중복을 막는다
+    if let (Some(path), Some(len)) = (file, cursor.output_len) {
+        truncate_output(path, len)?;
+    }
+    let mut writer = open_writer(file, format)?;
+    let mut fetched = 0;
+
+    while fetched < limit {
+        let page = rpc_client.get_signatures_for_address_with_config(
+            &wallet,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit: Some(page_size.min(limit - fetched)),
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )?;
+
+        if page.is_empty() {
+            break;
+        }
+
+        for status in &page {
+            for record in fetch_records(rpc_client, &wallet, status)? {
+                write_record(writer.inner(), format, &record)?;
+            }
+            fetched += 1;
+
+            // 트랜잭션 하나를 모두 기록한 뒤에 커서 갱신 (페이지 중간에 중단되어도 중복 없이 이어서 진행)
+            let output_len = writer.flush()?;
+            if let Some(path) = cursor_path {
+                let cursor = Cursor {
+                    last_signature: Some(status.signature.clone()),
+                    output_len,
+                };
+                state_file::save(path, &cursor)?;
+            }
+
+            if !delay.is_zero() {
+                thread::sleep(delay);
+            }
+        }
+
+        let last = &page[page.len() - 1].signature;
+        before = Some(parse_signature(last)?);
+        eprintln!("{}개 트랜잭션 처리 (마지막 서명: {})", fetched, last);
+    }
+
+    eprintln!("내보내기 완료: 총 {}개 트랜잭션", fetched);
+    Ok(())
+}
+
+/// 서명 하나의 트랜잭션을 조회하여 지갑 기준 자산 변동 기록으로 변환
+pub fn fetch_records(
+    rpc_client: &RpcClient,
+    wallet: &Pubkey,
+    status: &RpcConfirmedTransactionStatusWithSignature,
+) -> Result<Vec<HistoryRecord>> {
+    let transaction = fetch_transaction(rpc_client, &status.signature)?;
+    Ok(decode_transaction(wallet, status.memo.clone(), &transaction))
+}
+
+/// 서명으로 트랜잭션 조회 (버전 0 트랜잭션 포함)
+pub fn fetch_transaction(
+    rpc_client: &RpcClient,
+    signature: &str,
+) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let signature = parse_signature(signature)?;
+    let transaction = rpc_client.get_transaction_with_config(
+        &signature,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+    Ok(transaction)
+}
+
+/// 트랜잭션 메타데이터의 잔액 전후 값으로 SOL/토큰 변동 계산
+pub fn decode_transaction(
+    wallet: &Pubkey,
+    memo: Option<String>,
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Vec<HistoryRecord> {
+    let wallet = wallet.to_string();
+    let account_keys = account_keys(transaction);
+    let fee_payer = account_keys.first() == Some(&wallet);
+
+    let meta = match &transaction.transaction.meta {
+        Some(meta) => meta,
+        None => return vec![],
+    };
+    let status = if meta.err.is_some() { "failed" } else { "success" };
+    let fee = meta.fee;
+
+    let record = |asset: String, change: String, counterparty: Option<String>| HistoryRecord {
+        signature: signature_of(transaction),
+        slot: transaction.slot,
+        block_time: transaction.block_time,
+        status: status.to_string(),
+        asset,
+        change,
+        fee_lamports: if fee_payer { fee } else { 0 },
+        fee_payer,
+        memo: memo.clone(),
+        counterparty,
+    };
+
+    let mut records = vec![];
+
+    // SOL 변동 (수수료 지불자인 경우 수수료는 별도 필드로 분리)
+    let deltas: Vec<i128> = meta
+        .pre_balances
+        .iter()
+        .zip(&meta.post_balances)
+        .enumerate()
+        .map(|(i, (pre, post))| {
+            let delta = *post as i128 - *pre as i128;
+            if i == 0 { delta + fee as i128 } else { delta }
+        })
+        .collect();
+
+    if let Some(index) = account_keys.iter().position(|key| *key == wallet) {
+        let delta = deltas.get(index).copied().unwrap_or(0);
+        if delta != 0 {
+            let counterparty = largest_opposite(&deltas, index, delta).and_then(|i| account_keys.get(i).cloned());
+            records.push(record("SOL".to_string(), format_amount(delta, 9), counterparty));
+        }
+    }
+
+    // 토큰 변동 (지갑이 소유한 토큰 계정 기준)
+    let pre: Vec<UiTransactionTokenBalance> = Option::from(meta.pre_token_balances.clone()).unwrap_or_default();
+    let post: Vec<UiTransactionTokenBalance> = Option::from(meta.post_token_balances.clone()).unwrap_or_default();
+    let changes = token_changes(&pre, &post);
+
+    for change in changes.iter().filter(|c| c.owner.as_deref() == Some(wallet.as_str()) && c.delta != 0) {
+        let counterparty = changes
+            .iter()
+            .filter(|other| other.mint == change.mint && other.owner != change.owner && other.delta.signum() == -change.delta.signum())
+            .max_by_key(|other| other.delta.abs())
+            .and_then(|other| other.owner.clone());
+        records.push(record(change.mint.clone(), format_amount(change.delta, change.decimals), counterparty));
+    }
+
+    // 자산 변동이 없는 트랜잭션도 수수료 정산을 위해 한 줄 남긴다
+    if records.is_empty() {
+        records.push(record("SOL".to_string(), format_amount(0, 9), None));
+    }
+
+    records
+}
+
+// 토큰 계정 하나의 전후 변동
+struct TokenChange {
+    mint: String,
+    owner: Option<String>,
+    decimals: u8,
+    delta: i128,
+}
+
+fn token_changes(pre: &[UiTransactionTokenBalance], post: &[UiTransactionTokenBalance]) -> Vec<TokenChange> {
+    let mut by_account: HashMap<u8, TokenChange> = HashMap::new();
+
+    for (balances, sign) in [(pre, -1), (post, 1)] {
+        for balance in balances {
+            let amount: i128 = balance.ui_token_amount.amount.parse().unwrap_or(0);
+            let entry = by_account.entry(balance.account_index).or_insert_with(|| TokenChange {
+                mint: balance.mint.clone(),
+                owner: Option::from(balance.owner.clone()),
+                decimals: balance.ui_token_amount.decimals,
+                delta: 0,
+            });
+            entry.delta += sign * amount;
+        }
+    }
+
+    let mut changes: Vec<TokenChange> = by_account.into_values().collect();
+    changes.sort_by(|a, b| a.mint.cmp(&b.mint));
+    changes
+}
+
+// 주어진 변동과 반대 방향으로 가장 크게 움직인 계정의 인덱스
+fn largest_opposite(deltas: &[i128], index: usize, delta: i128) -> Option<usize> {
+    deltas
+        .iter()
+        .enumerate()
+        .filter(|(i, d)| *i != index && d.signum() == -delta.signum())
+        .max_by_key(|(_, d)| d.abs())
+        .map(|(i, _)| i)
+}
+
+/// 트랜잭션의 전체 계정 키 (주소 조회 테이블 포함)
+pub fn account_keys(transaction: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<String> {
+    let mut keys: Vec<String> = transaction
+        .transaction
+        .transaction
+        .decode()
+        .map(|tx| tx.message.static_account_keys().iter().map(|k| k.to_string()).collect())
+        .unwrap_or_default();
+
+    if let Some(meta) = &transaction.transaction.meta {
+        let loaded: Option<UiLoadedAddresses> = meta.loaded_addresses.clone().into();
+        if let Some(loaded) = loaded {
+            keys.extend(loaded.writable);
+            keys.extend(loaded.readonly);
+        }
+    }
+
+    keys
+}
+
+fn signature_of(transaction: &EncodedConfirmedTransactionWithStatusMeta) -> String {
+    transaction
+        .transaction
+        .transaction
+        .decode()
+        .and_then(|tx| tx.signatures.first().map(|s| s.to_string()))
+        .unwrap_or_default()
+}
+
+/// 정수 금액을 소수점 문자열로 변환 (예: -1500000, 9 -> "-0.0015")
+pub fn format_amount(raw: i128, decimals: u8) -> String {
+    if decimals == 0 {
+        return raw.to_string();
+    }
+
+    let sign = if raw < 0 { "-" } else { "" };
+    let raw = raw.unsigned_abs();
+    let scale = 10u128.pow(decimals as u32);
+    let fraction = format!("{:0width$}", raw % scale, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        format!("{}{}", sign, raw / scale)
+    } else {
+        format!("{}{}.{}", sign, raw / scale, fraction)
+    }
+}
+
+// 출력 파일을 커서에 기록된 길이로 자르기 (파일이 없거나 더 짧으면 그대로)
+fn truncate_output(path: &str, len: u64) -> Result<()> {
+    let current = match Path::new(path).metadata() {
+        Ok(metadata) => metadata.len(),
+        Err(_) => return Ok(()),
+    };
+    if current > len {
+        eprintln!("마지막 커서 이후에 기록된 {}바이트를 잘라냅니다: {}", current - len, path);
+        OpenOptions::new().write(true).open(path)?.set_len(len)?;
+    }
+    Ok(())
+}
+
+// 출력 대상 열기 (파일이 이미 있으면 이어쓰기)
+fn open_writer(file: Option<&str>, format: OutputFormat) -> Result<Writer> {
+    let (mut writer, needs_header) = match file {
+        Some(path) => {
+            let exists = Path::new(path).metadata().map(|m| m.len() > 0).unwrap_or(false);
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            (Writer::File(BufWriter::new(file)), !exists)
+        }
+        None => (Writer::Stdout(std::io::stdout()), true),
+    };
+
+    if format == OutputFormat::Csv && needs_header {
+        writeln!(writer.inner(), "{}", CSV_HEADER)?;
+    }
+    Ok(writer)
+}
+
+fn write_record(writer: &mut dyn Write, format: OutputFormat, record: &HistoryRecord) -> Result<()> {
+    match format {
+        // 표는 한 줄에 하나씩 바로 출력 (내역이 길어도 모두 모으지 않음)
+        OutputFormat::Table => writeln!(
+            writer,
+            "{:>10}  {:<7}  {:>20} {}  수수료 {} lamports  {}",
+            record.slot, record.status, record.change, record.asset, record.fee_lamports, record.signature
+        )?,
+        OutputFormat::Json => writeln!(writer, "{}", serde_json::to_string(record)?)?,
+        OutputFormat::Csv => writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{},{}",
+            record.signature,
+            record.slot,
+            record.block_time.map(|t| t.to_string()).unwrap_or_default(),
+            record.status,
+            record.asset,
+            record.change,
+            record.fee_lamports,
+            record.fee_payer,
+            csv_escape(record.memo.as_deref().unwrap_or("")),
+            record.counterparty.as_deref().unwrap_or(""),
+        )?,
+    }
+    Ok(())
+}
+
+fn parse_signature(input: &str) -> Result<Signature> {
+    Signature::from_str(input).map_err(|_| Error::Input(format!("유효하지 않은 트랜잭션 서명입니다: {}", input)))
+}
@@ -0,0 +1,26 @@
+//! 개인키 로딩
+//!
+//! 보내는 지갑은 Base58로 인코딩된 64바이트 키페어(`SOLANA_PRIVATE_KEY`)로 지정합니다.
+
+use crate::error::{Error, Result};
+use solana_sdk::signature::Keypair;
+use std::env;
+
+/// 개인키 환경 변수 이름
+pub const PRIVATE_KEY_ENV: &str = "SOLANA_PRIVATE_KEY";
+
+/// Base58 인코딩된 개인키로부터 Keypair 생성
+///
+/// 개인키가 올바른 Base58 문자열이 아니거나 키페어 형식이 아니면 [`Error::Key`]를 반환합니다.
+pub fn create_keypair_from_base58(private_key: &str) -> Result<Keypair> {
+    let bytes = bs58::decode(private_key.trim())
+        .into_vec()
+        .map_err(|e| Error::Key(format!("유효하지 않은 Base58 문자열입니다: {}", e)))?;
+    
+    Keypair::from_bytes(&bytes).map_err(|e| Error::Key(format!("키페어 생성 실패: {}", e)))
+}
+
+/// 환경 변수에 설정된 개인키 (없으면 `None`)
+pub fn private_key_from_env() -> Option<String> {
+    env::var(PRIVATE_KEY_ENV).ok()
+}
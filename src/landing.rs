@@ -0,0 +1,259 @@
+//! 트랜잭션 포함 측정 (`tx_speed_test`)
+//!
+//! 확인까지 걸린 시간만으로는 엔드포인트가 트랜잭션을 리더에게 얼마나 빨리 전달하는지 알기 어렵습니다.
+//! 전송 직전의 processed 슬롯과 트랜잭션이 포함된 슬롯을 기록해 "포함까지 걸린 슬롯 수"를 구하고,
+//! `getSignatureStatuses`를 폴링해 processed, confirmed, finalized에 처음 도달한 시간을 따로 측정합니다.
+//!
+//! 블록에 포함되지 않은 트랜잭션은 오류가 아니라 측정 결과이므로, 전송 실패와 구분해 포함률로 집계합니다.
+//!
+//! 측정용 트랜잭션은 자기 자신에게 보내는 전송에 실행 ID와 순번을 담은 메모를 붙여 만듭니다 ([`TestRun`]).
+//! 같은 블록해시를 받더라도 트랜잭션이 모두 달라 "already processed"로 거부되지 않고, 실행 ID가 무작위이므로
+//! 여러 기기에서 같은 지갑으로 동시에 실행해도 겹치지 않습니다. 남는 계정이 없으므로 정리할 것도 없습니다.
+
+use crate::backend::RpcBackend;
+use crate::error::{Error, Result};
+use crate::failure::{Failure, FailureCounts, FailureKind};
+use crate::stats::LatencyHistogram;
+use crate::transfer::ConfirmOptions;
+use solana_sdk::clock::Slot;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 포함 측정의 상태 조회 설정 (시간 해상도를 위해 [`ConfirmOptions::default`]보다 자주 조회)
+pub const TRACK_OPTIONS: ConfirmOptions = ConfirmOptions {
+    poll_interval: Duration::from_millis(200),
+    resend_interval: Duration::from_secs(2),
+    timeout: Duration::from_secs(60),
+};
+
+/// 측정용 트랜잭션 메모의 접두사 (`rpc-bench:<실행 ID>:<순번>`)
+pub const MEMO_PREFIX: &str = "rpc-bench";
+
+/// 측정 실행 하나 (실행 ID와 트랜잭션 순번)
+#[derive(Debug)]
+pub struct TestRun {
+    id: String,
+    sequence: AtomicU64,
+}
+
+impl Default for TestRun {
+    fn default() -> Self {
+        TestRun::new()
+    }
+}
+
+impl TestRun {
+    /// 시작 시각과 무작위 값으로 만든 실행 ID (예: `20240501093000-1a2b3c4d`)
+    pub fn new() -> TestRun {
+        let id = format!("{}-{:08x}", chrono::Utc::now().format("%Y%m%d%H%M%S"), rand::random::<u32>());
+        TestRun::with_id(id)
+    }
+
+    /// 주어진 실행 ID
+    pub fn with_id(id: impl Into<String>) -> TestRun {
+        TestRun {
+            id: id.into(),
+            sequence: AtomicU64::new(0),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 다음 순번(1부터)과 그 순번의 메모를 붙인 자기 자신에게 보내는 전송 명령
+    pub fn next_transfer(&self, payer: &Pubkey, lamports: u64) -> (u64, Vec<Instruction>) {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        let memo = memo(&self.id, sequence);
+        let instructions = vec![
+            spl_memo::build_memo(memo.as_bytes(), &[]),
+            system_instruction::transfer(payer, payer, lamports),
+        ];
+        (sequence, instructions)
+    }
+}
+
+/// 실행 ID와 순번의 메모
+pub fn memo(run_id: &str, sequence: u64) -> String {
+    format!("{}:{}:{}", MEMO_PREFIX, run_id, sequence)
+}
+
+/// 트랜잭션 하나의 포함 측정 결과 (시간은 전송 요청을 보낸 시점부터)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Landing {
+    pub signature: Signature,
+    /// 전송 직전의 processed 슬롯 (조회에 실패하면 `None`)
+    pub sent_slot: Option<Slot>,
+    /// 트랜잭션이 포함된 슬롯 (타임아웃까지 포함되지 않으면 `None`)
+    pub landed_slot: Option<Slot>,
+    pub processed: Option<Duration>,
+    pub confirmed: Option<Duration>,
+    pub finalized: Option<Duration>,
+    /// 블록에 포함되었지만 실행에 실패한 경우의 오류
+    pub err: Option<TransactionError>,
+}
+
+impl Landing {
+    /// 블록에 포함되었는지 (실행 실패 포함)
+    pub fn landed(&self) -> bool {
+        self.landed_slot.is_some()
+    }
+
+    /// 전송 슬롯부터 포함된 슬롯까지의 슬롯 수
+    pub fn slots_to_land(&self) -> Option<u64> {
+        Some(self.landed_slot?.saturating_sub(self.sent_slot?))
+    }
+
+    /// 포함되지 않았거나(타임아웃) 실행에 실패했으면 그 실패
+    pub fn failure(&self) -> Option<Failure> {
+        if !self.landed() {
+            return Some(Failure::new(FailureKind::Timeout, "블록에 포함되지 않음"));
+        }
+        let error = Error::Transaction {
+            signature: Some(self.signature),
+            error: self.err.clone()?,
+        };
+        Some(Failure::from_error(&error))
+    }
+}
+
+/// 서명된 트랜잭션을 전송하고 finalized가 되거나 `timeout`이 지날 때까지 커미트먼트별 도달 시간 측정
+///
+/// 상태가 보이지 않으면 `resend_interval`마다 다시 전송하고, 블록해시가 만료되어 더 이상 포함될 수 없으면
+/// 바로 멈춥니다. 전송 요청 자체가 실패한 경우만 오류를 반환합니다.
+pub async fn track<B: RpcBackend + ?Sized>(backend: &B, transaction: &Transaction, options: &ConfirmOptions) -> Result<Landing> {
+    let sent_slot = backend.get_slot(CommitmentConfig::processed()).await.ok();
+    let start = Instant::now();
+    let signature = backend.send_transaction(transaction).await?;
+    let mut landing = Landing {
+        signature,
+        sent_slot,
+        landed_slot: None,
+        processed: None,
+        confirmed: None,
+        finalized: None,
+        err: None,
+    };
+    let mut last_sent = start;
+
+    loop {
+        // 상태 조회 중의 일시적인 오류는 무시하고 다음 조회에서 다시 확인
+        if let Ok(response) = backend.get_signature_statuses(&[signature]).await {
+            match response.value.into_iter().next().flatten() {
+                Some(status) => {
+                    let elapsed = start.elapsed();
+                    landing.landed_slot = Some(status.slot);
+                    landing.err = status.err.clone();
+                    landing.processed.get_or_insert(elapsed);
+                    if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+                        landing.confirmed.get_or_insert(elapsed);
+                    }
+                    if status.satisfies_commitment(CommitmentConfig::finalized()) {
+                        landing.finalized = Some(elapsed);
+                        return Ok(landing);
+                    }
+                }
+                None if landing.landed_slot.is_none() && last_sent.elapsed() >= options.resend_interval => {
+                    let blockhash = &transaction.message.recent_blockhash;
+                    if let Ok(false) = backend.is_blockhash_valid(blockhash).await {
+                        return Ok(landing);
+                    }
+                    // 드롭되었을 수 있으므로 다시 전송 (이미 처리된 경우의 오류는 무시)
+                    let _ = backend.send_transaction(transaction).await;
+                    last_sent = Instant::now();
+                }
+                None => {}
+            }
+        }
+
+        if start.elapsed() >= options.timeout {
+            return Ok(landing);
+        }
+        tokio::time::sleep(options.poll_interval).await;
+    }
+}
+
+/// 엔드포인트 하나의 포함 측정 집계
+#[derive(Debug, Clone, Default)]
+pub struct LandingStats {
+    /// 전송을 시도한 트랜잭션 수 (전송 요청이 실패한 것 포함)
+    pub sent: usize,
+    /// 블록에 포함된 트랜잭션 수 (실행에 실패한 것 포함)
+    pub landed: usize,
+    /// 포함된 트랜잭션의 포함까지 걸린 슬롯 수 (전송 순서)
+    pub slots_to_land: Vec<u64>,
+    /// 실행에 성공한 트랜잭션의 커미트먼트별 도달 시간
+    pub processed: LatencyHistogram,
+    pub confirmed: LatencyHistogram,
+    pub finalized: LatencyHistogram,
+    /// 전송 실패, 실행 실패, 포함되지 않음(타임아웃)
+    pub failures: FailureCounts,
+}
+
+impl LandingStats {
+    pub fn new() -> LandingStats {
+        LandingStats::default()
+    }
+
+    /// 측정 결과 하나 기록
+    pub fn record(&mut self, result: &Result<Landing>) {
+        self.sent += 1;
+        let landing = match result {
+            Ok(landing) => landing,
+            Err(e) => {
+                self.failures.record(&Failure::from_error(e));
+                return;
+            }
+        };
+        self.slots_to_land.extend(landing.slots_to_land());
+        if landing.landed() {
+            self.landed += 1;
+        }
+        if let Some(failure) = landing.failure() {
+            self.failures.record(&failure);
+            return;
+        }
+        for (histogram, elapsed) in [
+            (&mut self.processed, landing.processed),
+            (&mut self.confirmed, landing.confirmed),
+            (&mut self.finalized, landing.finalized),
+        ] {
+            if let Some(elapsed) = elapsed {
+                histogram.record(elapsed);
+            }
+        }
+    }
+
+    /// 전송을 시도한 트랜잭션 중 블록에 포함된 비율 (0~1, 시도가 없으면 0)
+    pub fn landing_rate(&self) -> f64 {
+        if self.sent == 0 {
+            return 0.0;
+        }
+        self.landed as f64 / self.sent as f64
+    }
+
+    /// 포함까지 걸린 평균 슬롯 수 (포함된 트랜잭션이 없으면 `None`)
+    pub fn average_slots(&self) -> Option<f64> {
+        let count = self.slots_to_land.len();
+        (count > 0).then(|| self.slots_to_land.iter().sum::<u64>() as f64 / count as f64)
+    }
+
+    pub fn max_slots(&self) -> Option<u64> {
+        self.slots_to_land.iter().max().copied()
+    }
+
+    /// 커미트먼트별 (작업 이름, 출력용 이름, 히스토그램)
+    pub fn phases(&self) -> [(&'static str, &'static str, &LatencyHistogram); 3] {
+        [
+            ("processed", "processed까지", &self.processed),
+            ("confirmed", "confirmed까지", &self.confirmed),
+            ("finalized", "finalized까지", &self.finalized),
+        ]
+    }
+}
@@ -0,0 +1,80 @@
+//! 솔라나 토큰 전송 봇 라이브러리
+//!
+//! 전송 봇(`solana_transfer_bot`)과 RPC 성능 테스트 도구들(`rpc_benchmark`, `ping_test`,
+//! `tx_speed_test`)이 공유하는 기능을 제공합니다. 다른 서비스에서도 바이너리를 실행하는 대신
+//! 이 크레이트에 의존하여 전송과 벤치마크 기능을 사용할 수 있습니다.
+//!
+//! - [`error`]: 오류 종류 ([`Error`]) - 설정, 개인키, 입력, RPC 연결/한도, 시뮬레이션, 블록해시 만료, 온체인 실패
+//! - [`keys`]: Base58 개인키로 키페어 생성
+//! - [`transfer`]: SOL/SPL 토큰 전송 (비동기, 동기 코드용 [`transfer::blocking`] 래퍼 포함)
+//! - [`runtime`]: 동기 코드에서 비동기 전송을 실행하는 공유 tokio 런타임
+//! - [`rpc`]: RPC 엔드포인트와 환경 변수 로딩
+//! - [`cluster`]: 제네시스 해시로 클러스터 감지, 익스플로러 링크, 메인넷 전송 확인
+//! - [`config`]: 설정 파일과 클러스터 프로필 (명령줄 > 환경 변수 > 설정 파일 > 기본값)
+//! - [`backend`]: 전송/벤치마크가 사용하는 RPC 백엔드 트레이트
+//! - [`fake_rpc`]: 오프라인 테스트용 인메모리 백엔드
+//! - [`benchmark`]: RPC 응답 시간 측정 작업
+//! - [`fee_sweep`]: 우선순위 수수료 스윕 (compute unit 가격/한도 단계별 포함률, 슬롯 수, 비용과 추천 가격)
+//! - [`freshness`]: 엔드포인트 최신성 비교 (커미트먼트별 슬롯, 블록 높이, 블록해시가 뒤처진 정도)
+//! - [`landing`]: 트랜잭션 포함 측정 (전송/포함 슬롯, processed/confirmed/finalized 도달 시간, 포함률)
+//! - [`load`]: 동시 부하 테스트 (목표 초당 요청 수 또는 고정 동시 요청 수)
+//! - [`probe`]: DNS/TCP/TLS/첫 바이트 시간을 나눠 재는 네트워크 프로브 (IPv4/IPv6)
+//! - [`subscription`]: 웹소켓 구독(slot, account, signature) 알림이 엔드포인트마다 늦게 도착하는 정도
+//! - [`throughput`]: 병렬 전송 처리량 테스트 (미리 서명한 트랜잭션을 여러 테스트 계정으로 목표 속도에 맞춰 전송, 초당 수락/포함 수와 드롭률)
+//! - [`stats`]: 지연 시간 통계
+//! - [`failure`]: 벤치마크 실패 분류 (요청 한도 초과, 5xx, 타임아웃, 연결 거부, TLS, JSON-RPC 오류)
+//! - [`reporting`]: 벤치마크 결과 표 출력
+//! - [`output`]: 기계가 읽는 JSON/CSV 결과 (`--output`, 버전이 붙은 스키마)
+//! - [`state_file`]: 재시작 후에도 이어서 진행하기 위한 JSON 상태 파일 (임시 파일에 쓴 뒤 교체)
+//!
+//! 전송 봇의 하위 명령 구현([`stake`], [`history`], [`watch`], [`schedule`], [`serve`], [`batch`])도
+//! 함께 공개됩니다.
+//!
+//! ```no_run
+//! use solana_transfer_bot::{keys, rpc, transfer};
+//! use solana_sdk::signature::Signer;
+//!
+//! # async fn example() -> solana_transfer_bot::Result<()> {
+//! rpc::load_env();
+//! let sender = keys::create_keypair_from_base58(&keys::private_key_from_env().unwrap())?;
+//! let client = rpc::async_client(&rpc::custom_rpc_url().unwrap());
+//! let signature = transfer::send_sol(&client, &sender, &sender.pubkey(), 1_000, transfer::DEFAULT_PRIORITY_FEE).await?;
+//! println!("{}", signature);
+//! # Ok(())
+//! # }
+//! ```
+
+// RpcBackend는 RpcClient와 같은 (큰) ClientError를 그대로 반환한다
+#![allow(clippy::result_large_err)]
+
+pub mod backend;
+pub mod batch;
+pub mod benchmark;
+pub mod cli;
+pub mod cluster;
+pub mod config;
+pub mod error;
+pub mod failure;
+pub mod fake_rpc;
+pub mod fee_sweep;
+pub mod freshness;
+pub mod history;
+pub mod keys;
+pub mod landing;
+pub mod load;
+pub mod output;
+pub mod probe;
+pub mod reporting;
+pub mod rpc;
+pub mod runtime;
+pub mod schedule;
+pub mod serve;
+pub mod stake;
+pub mod state_file;
+pub mod stats;
+pub mod subscription;
+pub mod throughput;
+pub mod transfer;
+pub mod watch;
+
+pub use error::{Error, Result};
@@ -0,0 +1,388 @@
+//! 동시 부하 테스트 (`rpc_benchmark --load`)
+//!
+//! 정해진 시간 동안 메서드 조합을 두 가지 형태 중 하나로 호출합니다.
+//!
+//! - 목표 초당 요청 수 (`--rps`): 응답을 기다리지 않고 일정한 간격으로 요청을 시작합니다.
+//!   `--ramp-from`을 주면 그 값에서 목표까지 실행 시간 동안 선형으로 늘립니다.
+//! - 고정 동시 요청 수 (`--concurrency`): 워커마다 응답을 받으면 바로 다음 요청을 보냅니다.
+//!
+//! 결과는 요청을 시작한 시각 기준 구간(`--window`)으로 나누어 처리량, 지연 시간 백분위,
+//! 종류별 실패 수를 집계하고, 지연 시간이나 실패율이 처음 구간보다 크게 나빠진 첫 구간을
+//! 성능 저하 지점으로 보고합니다 ([`LoadReport::degradation`]).
+//!
+//! 동시 요청 수 형태에서는 요청 한도를 넘은 워커가 `Retry-After`만큼 쉬고, 목표 초당 요청 수
+//! 형태에서는 부하를 그대로 유지합니다 (요청 한도 초과도 결과의 일부이므로).
+
+use crate::backend::RpcBackend;
+use crate::benchmark::{self, Operation};
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::failure::{Failure, FailureCounts, FailureKind};
+use crate::stats::LatencyHistogram;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// 기본 동시 요청 수 (`--rps`와 `--concurrency`를 모두 지정하지 않은 경우)
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// 기본 실행 시간
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(30);
+
+/// 기본 요청 타임아웃
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 기본 집계 구간
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(1);
+
+/// 기준 구간보다 p50이 이 배수를 넘으면 성능 저하
+pub const DEGRADATION_FACTOR: f64 = 2.0;
+
+/// 구간의 실패(오류 + 타임아웃) 비율이 이 값을 넘으면 성능 저하
+pub const FAILURE_RATE_LIMIT: f64 = 0.05;
+
+// 기준과 비교하려면 구간에 필요한 최소 성공 수
+const MIN_WINDOW_SAMPLES: usize = 5;
+
+/// 부하 형태
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadShape {
+    /// 초당 요청 수 (`from`에서 `to`까지 선형 증가, 고정 부하면 같은 값)
+    Rate { from: f64, to: f64 },
+    /// 동시 요청 수
+    Concurrency(usize),
+}
+
+/// 부하 테스트 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadOptions {
+    pub shape: LoadShape,
+    /// 요청을 보내는 시간
+    pub duration: Duration,
+    /// 요청 하나의 타임아웃 (넘으면 타임아웃으로 집계)
+    pub timeout: Duration,
+    /// 시간별 통계를 나누는 구간 길이
+    pub window: Duration,
+    /// (작업, 가중치) 목록 - 가중치 비율대로 순환하며 호출
+    pub mix: Vec<(Operation, u32)>,
+}
+
+impl LoadOptions {
+    /// 명령줄 옵션 (`--rps`, `--ramp-from`, `--concurrency`, `--duration`, `--timeout`, `--window`, `--methods`)
+    pub fn from_args(args: &Args) -> Result<LoadOptions> {
+        let shape = match (args.value("rps"), args.value("concurrency")) {
+            (Some(_), Some(_)) => {
+                return Err(Error::Input("--rps와 --concurrency는 함께 지정할 수 없습니다".to_string()));
+            }
+            (Some(_), None) => {
+                let to: f64 = args.parse_or("rps", 0.0)?;
+                let from: f64 = args.parse_or("ramp-from", to)?;
+                if !(to > 0.0 && from > 0.0) {
+                    return Err(Error::Input("--rps와 --ramp-from은 0보다 커야 합니다".to_string()));
+                }
+                LoadShape::Rate { from, to }
+            }
+            (None, _) => LoadShape::Concurrency(args.parse_or("concurrency", DEFAULT_CONCURRENCY)?.max(1)),
+        };
+        let duration = Duration::from_secs_f64(args.parse_or("duration", DEFAULT_DURATION.as_secs_f64())?.max(0.0));
+        let timeout = Duration::from_millis(args.parse_or("timeout", DEFAULT_TIMEOUT.as_millis() as u64)?.max(1));
+        let window = Duration::from_secs_f64(args.parse_or("window", DEFAULT_WINDOW.as_secs_f64())?.max(0.001));
+        let mix = match args.value("methods") {
+            Some(spec) => parse_mix(spec)?,
+            None => benchmark::standard_suite().into_iter().map(|operation| (operation, 1)).collect(),
+        };
+        Ok(LoadOptions {
+            shape,
+            duration,
+            timeout,
+            window,
+            mix,
+        })
+    }
+}
+
+/// `메서드[:가중치]`를 쉼표로 구분한 메서드 조합 (예: `getSlot:3,getBalance`, `all`은 모든 메서드 같은 비율)
+pub fn parse_mix(spec: &str) -> Result<Vec<(Operation, u32)>> {
+    let mut mix: Vec<(Operation, u32)> = Vec::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (method, weight) = match entry.split_once(':') {
+            Some((method, weight)) => {
+                let weight = weight
+                    .trim()
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&w| w > 0)
+                    .ok_or_else(|| Error::Input(format!("메서드 가중치 오류: {}", entry)))?;
+                (method.trim(), weight)
+            }
+            None => (entry, 1),
+        };
+        for operation in benchmark::parse_suite(method)? {
+            match mix.iter_mut().find(|(existing, _)| *existing == operation) {
+                Some((_, existing)) => *existing += weight,
+                None => mix.push((operation, weight)),
+            }
+        }
+    }
+    if mix.is_empty() {
+        return Err(Error::Input("측정할 메서드가 없습니다".to_string()));
+    }
+    Ok(mix)
+}
+
+/// 구간 하나의 결과
+#[derive(Debug, Clone, Default)]
+pub struct LoadWindow {
+    /// 실행 시작부터 구간 시작까지의 시간
+    pub start: Duration,
+    /// 구간 길이 (마지막 구간은 짧을 수 있음)
+    pub length: Duration,
+    /// 구간 안에서 시작한 요청 수
+    pub requests: usize,
+    /// 성공한 요청의 응답 시간
+    pub latency: LatencyHistogram,
+    /// 종류별 실패 수 (타임아웃 포함)
+    pub failures: FailureCounts,
+}
+
+impl LoadWindow {
+    /// 초당 보낸 요청 수
+    pub fn offered_rps(&self) -> f64 {
+        per_second(self.requests, self.length)
+    }
+
+    /// 초당 성공한 요청 수
+    pub fn throughput(&self) -> f64 {
+        per_second(self.latency.len(), self.length)
+    }
+
+    /// 타임아웃 수
+    pub fn timeouts(&self) -> usize {
+        self.failures.count(FailureKind::Timeout)
+    }
+
+    /// 타임아웃이 아닌 실패 수
+    pub fn errors(&self) -> usize {
+        self.failures.total() - self.timeouts()
+    }
+
+    /// 실패(오류 + 타임아웃) 비율
+    pub fn failure_rate(&self) -> f64 {
+        ratio(self.failures.total(), self.requests)
+    }
+}
+
+/// 성능 저하 원인
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DegradationCause {
+    /// p50이 기준의 [`DEGRADATION_FACTOR`]배를 넘음
+    Latency { baseline: Duration, p50: Duration },
+    /// 실패율이 [`FAILURE_RATE_LIMIT`]을 넘음
+    Failures { rate: f64 },
+}
+
+/// 처음으로 성능이 나빠진 구간
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Degradation {
+    /// [`LoadReport::windows`]의 위치
+    pub window: usize,
+    pub start: Duration,
+    /// 그 구간에서 보낸 초당 요청 수
+    pub offered_rps: f64,
+    pub cause: DegradationCause,
+}
+
+/// 엔드포인트 하나의 부하 테스트 결과
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    /// 요청을 보낸 시간
+    pub duration: Duration,
+    /// 구간별 결과 (시간 순)
+    pub windows: Vec<LoadWindow>,
+}
+
+impl LoadReport {
+    /// 모든 구간을 합친 실행 전체의 결과 (길이는 실행 시간)
+    pub fn total(&self) -> LoadWindow {
+        let mut total = LoadWindow {
+            length: self.duration,
+            ..LoadWindow::default()
+        };
+        for window in &self.windows {
+            total.requests += window.requests;
+            total.latency.merge(&window.latency);
+            total.failures.merge(&window.failures);
+        }
+        total
+    }
+
+    /// 성능 저하 지점 (없으면 `None`)
+    ///
+    /// 성공이 충분한 첫 구간의 p50을 기준으로, 이후 구간의 p50이 기준의 [`DEGRADATION_FACTOR`]배를 넘거나
+    /// 실패율이 [`FAILURE_RATE_LIMIT`]을 넘는 첫 구간입니다. 첫 구간부터 실패율이 높으면 그 구간입니다.
+    pub fn degradation(&self) -> Option<Degradation> {
+        let mut baseline: Option<Duration> = None;
+        for (index, window) in self.windows.iter().enumerate() {
+            if window.requests == 0 {
+                continue;
+            }
+            let cause = if window.failure_rate() > FAILURE_RATE_LIMIT {
+                Some(DegradationCause::Failures {
+                    rate: window.failure_rate(),
+                })
+            } else if window.latency.len() < MIN_WINDOW_SAMPLES {
+                None
+            } else {
+                let p50 = window.latency.percentile(50.0);
+                match baseline {
+                    None => {
+                        baseline = Some(p50);
+                        None
+                    }
+                    Some(baseline) if p50.as_secs_f64() > baseline.as_secs_f64() * DEGRADATION_FACTOR => {
+                        Some(DegradationCause::Latency { baseline, p50 })
+                    }
+                    Some(_) => None,
+                }
+            };
+            if let Some(cause) = cause {
+                return Some(Degradation {
+                    window: index,
+                    start: window.start,
+                    offered_rps: window.offered_rps(),
+                    cause,
+                });
+            }
+        }
+        None
+    }
+}
+
+// 요청 하나의 결과
+struct Outcome {
+    // 실행 시작부터 요청 시작까지
+    started: Duration,
+    result: std::result::Result<Duration, Failure>,
+}
+
+/// 설정대로 부하를 걸고 결과를 구간별로 집계 (보낸 요청이 모두 끝나거나 타임아웃될 때까지 기다림)
+pub async fn run_load<B: RpcBackend + 'static>(backend: Arc<B>, options: &LoadOptions) -> LoadReport {
+    // 가중치만큼 반복한 호출 순서
+    let schedule: Arc<Vec<Operation>> = Arc::new(
+        options
+            .mix
+            .iter()
+            .flat_map(|(operation, weight)| std::iter::repeat_n(operation.clone(), *weight as usize))
+            .collect(),
+    );
+    let (sender, mut receiver) = mpsc::unbounded_channel::<Outcome>();
+    let started = Instant::now();
+
+    match options.shape {
+        LoadShape::Rate { from, to } => {
+            let (sender, schedule, backend) = (sender.clone(), schedule.clone(), backend.clone());
+            let (duration, timeout) = (options.duration, options.timeout);
+            tokio::spawn(async move {
+                let mut offset = Duration::ZERO;
+                let mut sequence = 0;
+                while offset < duration && !schedule.is_empty() {
+                    tokio::time::sleep_until((started + offset).into()).await;
+                    let operation = schedule[sequence % schedule.len()].clone();
+                    let (sender, backend) = (sender.clone(), backend.clone());
+                    tokio::spawn(async move {
+                        let result = request(backend.as_ref(), &operation, timeout).await;
+                        let _ = sender.send(Outcome { started: offset, result });
+                    });
+                    sequence += 1;
+                    let progress = offset.as_secs_f64() / duration.as_secs_f64();
+                    offset += Duration::from_secs_f64(1.0 / (from + (to - from) * progress));
+                }
+            });
+        }
+        LoadShape::Concurrency(workers) => {
+            // 워커들이 호출 순서를 나눠 가져 조합 비율을 유지
+            let sequence = Arc::new(AtomicUsize::new(0));
+            for _ in 0..workers {
+                let (sender, schedule, backend, sequence) = (sender.clone(), schedule.clone(), backend.clone(), sequence.clone());
+                let (duration, timeout) = (options.duration, options.timeout);
+                tokio::spawn(async move {
+                    loop {
+                        let offset = started.elapsed();
+                        if offset >= duration || schedule.is_empty() {
+                            break;
+                        }
+                        let operation = &schedule[sequence.fetch_add(1, Ordering::Relaxed) % schedule.len()];
+                        let result = request(backend.as_ref(), operation, timeout).await;
+                        let backoff = result.as_ref().err().and_then(Failure::backoff);
+                        if sender.send(Outcome { started: offset, result }).is_err() {
+                            break;
+                        }
+                        if let Some(backoff) = backoff {
+                            tokio::time::sleep(backoff).await;
+                        }
+                    }
+                });
+            }
+        }
+    }
+    drop(sender);
+
+    let mut report = LoadReport {
+        duration: options.duration,
+        windows: windows(options.duration, options.window),
+    };
+    while let Some(outcome) = receiver.recv().await {
+        let last = report.windows.len() - 1;
+        let index = (outcome.started.as_secs_f64() / options.window.as_secs_f64()) as usize;
+        let window = &mut report.windows[index.min(last)];
+        window.requests += 1;
+        match outcome.result {
+            Ok(latency) => window.latency.record(latency),
+            Err(failure) => window.failures.record(&failure),
+        }
+    }
+    report
+}
+
+// 타임아웃을 적용한 요청 하나
+async fn request<B: RpcBackend>(backend: &B, operation: &Operation, timeout: Duration) -> std::result::Result<Duration, Failure> {
+    match tokio::time::timeout(timeout, benchmark::measure(backend, operation)).await {
+        Ok(Ok(latency)) => Ok(latency),
+        Ok(Err(e)) => Err(Failure::from_client_error(&e)),
+        Err(_) => Err(Failure::timeout(timeout)),
+    }
+}
+
+// 실행 시간을 구간 길이로 나눈 빈 구간 목록 (최소 하나)
+fn windows(duration: Duration, length: Duration) -> Vec<LoadWindow> {
+    let mut windows = Vec::new();
+    let mut start = Duration::ZERO;
+    loop {
+        windows.push(LoadWindow {
+            start,
+            length: length.min(duration.saturating_sub(start)).max(Duration::from_millis(1)),
+            ..LoadWindow::default()
+        });
+        start += length;
+        if start >= duration {
+            return windows;
+        }
+    }
+}
+
+fn per_second(count: usize, duration: Duration) -> f64 {
+    if duration.is_zero() {
+        0.0
+    } else {
+        count as f64 / duration.as_secs_f64()
+    }
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
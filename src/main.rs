@@ -1,64 +1,168 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::{
-    commitment_config::CommitmentConfig,
-    pubkey::Pubkey,
-    signature::{Keypair, Signer},
-    system_instruction,
-    transaction::Transaction,
-    compute_budget::ComputeBudgetInstruction,
-};
-use std::io::{self, BufRead, Write};
+use solana_transfer_bot::cli::{self, get_input};
+use solana_transfer_bot::cluster::{self, Cluster};
+use solana_transfer_bot::config::{self, Settings};
+use solana_transfer_bot::output::{OutputFormat, TransferRecord};
+use solana_transfer_bot::stake::StakeCommand;
+use solana_transfer_bot::{batch, history, rpc, schedule, serve, stake, transfer, watch, Error, Result};
 use std::str::FromStr;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 use std::env;
-use dotenv::dotenv;
 
 fn main() {
     // .env 파일에서 환경 변수 로드
-    dotenv().ok();
+    rpc::load_env();
     
-    println!("솔라나 토큰 전송 봇이 시작되었습니다.");
-    
-    // 환경 변수에서 RPC URL 불러오기
-    let rpc_url = env::var("SOLANA_RPC_URL").unwrap_or_else(|_| {
-        let input = get_input("환경변수 SOLANA_RPC_URL이 설정되지 않았습니다. RPC URL을 입력하세요: ");
-        input.trim().to_string()
-    });
+    // 하위 명령 처리 (stake, history, schedule, serve, watch, batch, config ...)
+    let args = cli::Args::parse(env::args().skip(1));
+    let result = match args.positional(0) {
+        Some(command) if is_command(command) => {
+            Settings::load(&args).and_then(|settings| run_command(command, &settings, &args)).map_err(|err| format!("명령 실패: {}", err))
+        }
+        _ => Settings::load(&args).and_then(|settings| interactive(&settings, &args)).map_err(|err| format!("오류: {}", err)),
+    };
     
-    println!("사용 중인 RPC URL: {}", rpc_url);
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+}
+
+fn is_command(command: &str) -> bool {
+    matches!(command, "stake" | "history" | "schedule" | "serve" | "batch" | "watch" | "config")
+}
+
+fn run_command(command: &str, settings: &Settings, args: &cli::Args) -> Result<()> {
+    if command == "config" {
+        return config::run(settings, args);
+    }
     
-    // 환경 변수에서 개인키 불러오기
-    let private_key = env::var("SOLANA_PRIVATE_KEY").unwrap_or_else(|_| {
-        let input = get_input("환경변수 SOLANA_PRIVATE_KEY가 설정되지 않았습니다. 개인키를 입력하세요: ");
-        input.trim().to_string()
-    });
+    // batch와 history만 --output 형식을 지원 (json/csv이면 안내 문구는 표준 오류로)
+    let output = match command {
+        "batch" => OutputFormat::from_args(args)?,
+        "history" => history::format(args)?,
+        _ => OutputFormat::Table,
+    };
+    // 사용법 출력과 저장된 일정 목록은 RPC에 연결하지 않고 처리
+    if let Some(usage) = usage_only(command, args)? {
+        println!("{}", usage);
+        return Ok(());
+    }
+    let rpc_client = settings.client();
+    if command == "schedule" && args.positional(1) != Some("run") {
+        return schedule::run(&rpc_client, settings, args);
+    }
+    let cluster = connect(settings, &rpc_client, output)?;
+    if sends_funds(command, args) {
+        cluster::confirm_mainnet(&cluster, args)?;
+    }
+    match command {
+        "stake" => {
+            let sender_keypair = load_sender_keypair(settings, output)?;
+            let fee = settings.priority_fee(&rpc_client)?;
+            stake::run(&rpc_client, &sender_keypair, fee, args)
+        }
+        "history" => history::run(&rpc_client, args),
+        "schedule" => schedule::run(&rpc_client, settings, args),
+        "serve" => serve::run(rpc_client, settings, args),
+        "batch" => {
+            let sender_keypair = load_sender_keypair(settings, output)?;
+            let fee = settings.priority_fee(&rpc_client)?;
+            batch::run(&settings.rpc_url.value, sender_keypair, fee, args)
+        }
+        _ => watch::run(&rpc_client, &settings.ws_url.value, args),
+    }
+}
+
+// 사용 중인 프로필과 RPC URL 출력 (API 키 등은 가림) 후 제네시스 해시로 클러스터 감지
+fn connect(settings: &Settings, rpc_client: &RpcClient, output: OutputFormat) -> Result<Cluster> {
+    output.progress(format_args!("프로필: {}", settings.profile.value));
+    output.progress(format_args!("사용 중인 RPC URL: {}", config::redact_url(&settings.rpc_url.value)));
+    
+    let cluster = Cluster::detect(rpc_client)?;
+    output.progress(format_args!("클러스터: {}", cluster));
+    if !cluster.matches_profile(&settings.profile.value) {
+        output.progress(format_args!("주의: 프로필({})과 감지된 클러스터({})가 다릅니다.", settings.profile.value, cluster));
+    }
+    Ok(cluster)
+}
+
+// 인자가 없거나 잘못되어 사용법만 출력하는 하위 명령이면 그 사용법 (잘못된 stake 인자는 연결 전에 오류)
+fn usage_only(command: &str, args: &cli::Args) -> Result<Option<&'static str>> {
+    let usage = match command {
+        "stake" if StakeCommand::from_args(args)? == StakeCommand::Usage => stake::USAGE,
+        "history" if args.positional(1).is_none() => history::USAGE,
+        "batch" if matches!(args.positional(1), None | Some("help")) => batch::USAGE,
+        "serve" if args.positional(1) == Some("help") => serve::USAGE,
+        "watch" if args.values("address").is_empty() || !args.has("webhook") => watch::USAGE,
+        _ => return Ok(None),
+    };
+    Ok(Some(usage))
+}
+
+// 실제로 자금을 보내는 하위 명령인지 여부 (조회/도움말은 메인넷 확인 없이 실행)
+fn sends_funds(command: &str, args: &cli::Args) -> bool {
+    match command {
+        "stake" => matches!(
+            args.positional(1),
+            Some("create" | "delegate" | "deactivate" | "split" | "merge" | "withdraw")
+        ),
+        "batch" => !matches!(args.positional(1), None | Some("help")),
+        "serve" => args.positional(1) != Some("help"),
+        "schedule" => args.positional(1) == Some("run"),
+        _ => false,
+    }
+}
+
+// 설정된 키페어 소스에서 보내는 지갑 불러오기
+fn load_sender_keypair(settings: &Settings, output: OutputFormat) -> Result<Keypair> {
+    let sender_keypair = settings.load_keypair()?;
+    output.progress(format_args!("지갑 주소: {}", sender_keypair.pubkey()));
+    Ok(sender_keypair)
+}
+
+// 대화형 SOL 전송 (--output json|csv이면 종료할 때 전송 결과 목록 출력)
+fn interactive(settings: &Settings, args: &cli::Args) -> Result<()> {
+    let output = OutputFormat::from_args(args)?;
+    output.progress("솔라나 토큰 전송 봇이 시작되었습니다.");
     
-    let sender_keypair = create_keypair_from_base58(&private_key);
-    println!("지갑 주소: {}", sender_keypair.pubkey());
+    let rpc_client = settings.client();
+    let cluster = connect(settings, &rpc_client, output)?;
+    cluster::confirm_mainnet(&cluster, args)?;
+    let sender_keypair = load_sender_keypair(settings, output)?;
     
     // 전송할 SOL 양 입력 받기
     let amount_input = get_input("전송할 SOL 양을 입력하세요: ");
-    let amount: f64 = amount_input.trim().parse().expect("유효한 숫자를 입력하세요");
-    
-    // 우선순위 수수료 설정 (lamports per compute unit)
-    let default_fee = 1;
+    let amount: f64 = amount_input
+        .trim()
+        .parse()
+        .ok()
+        .filter(|amount: &f64| *amount > 0.0)
+        .ok_or_else(|| Error::Input(format!("유효한 SOL 양을 입력하세요: {}", amount_input.trim())))?;
+    
+    // 우선순위 수수료 설정 (lamports per compute unit, 기본값은 프로필의 수수료 전략)
+    let default_fee = settings.priority_fee(&rpc_client)?;
     let fee_input = get_input(format!("가스비를 lamports/compute unit 단위로 설정하세요 (기본값: {}): ", default_fee).as_str());
     let fee: u64 = if fee_input.trim().is_empty() {
         default_fee
     } else {
-        fee_input.trim().parse().expect("유효한 숫자를 입력하세요")
+        fee_input
+            .trim()
+            .parse()
+            .map_err(|_| Error::Input(format!("유효한 수수료를 입력하세요: {}", fee_input.trim())))?
     };
     
-    // RPC 클라이언트 초기화
-    println!("RPC 연결 중: {}", rpc_url);
-    let rpc_client = RpcClient::new_with_timeout(rpc_url, Duration::from_secs(30));
+    // 전송 결과 (기계가 읽는 출력용)
+    let mut records: Vec<TransferRecord> = Vec::new();
     
     // 전송 프로세스 시작
     loop {
         let recipient_input = get_input("전송받을 주소를 입력하세요 (종료하려면 'exit' 입력): ");
         
         if recipient_input.trim().to_lowercase() == "exit" {
-            println!("프로그램을 종료합니다.");
+            output.progress("프로그램을 종료합니다.");
             break;
         }
         
@@ -66,7 +170,7 @@ fn main() {
         let recipient_pubkey = match Pubkey::from_str(recipient_input.trim()) {
             Ok(pubkey) => pubkey,
             Err(_) => {
-                println!("오류: 유효하지 않은 솔라나 주소입니다.");
+                output.progress("오류: 유효하지 않은 솔라나 주소입니다.");
                 continue;
             }
         };
@@ -75,74 +179,50 @@ fn main() {
         let lamports = (amount * 1_000_000_000.0) as u64;
         
         // 전송 실행
-        match send_sol(&rpc_client, &sender_keypair, &recipient_pubkey, lamports, fee) {
+        let start_time = Instant::now();
+        match transfer::blocking::send_sol(&rpc_client, &sender_keypair, &recipient_pubkey, lamports, fee) {
             Ok(signature) => {
-                println!("전송 성공! 트랜잭션 서명: {}", signature);
-                println!("트랜잭션 확인: https://explorer.solana.com/tx/{}?cluster=mainnet", signature);
+                output.progress(format_args!("트랜잭션 처리 시간: {:?}", start_time.elapsed()));
+                output.progress(format_args!("전송 성공! 트랜잭션 서명: {}", signature));
+                output.progress(format_args!("트랜잭션 확인: {}", settings.explorer_url(&signature.to_string(), &cluster)));
+                // 처리된 슬롯 (조회 실패는 무시)
+                let slot = Signature::from_str(&signature)
+                    .ok()
+                    .and_then(|signature| rpc_client.get_signature_statuses(&[signature]).ok())
+                    .and_then(|response| response.value.into_iter().next().flatten())
+                    .map(|status| status.slot);
+                records.push(TransferRecord {
+                    id: (records.len() + 1).to_string(),
+                    signature: Some(signature),
+                    status: "confirmed".to_string(),
+                    fee,
+                    slot,
+                    error: None,
+                });
             }
             Err(err) => {
-                println!("전송 실패: {}", err);
+                // 확인 시간 초과는 실패가 아니라 처리 여부를 알 수 없는 상태
+                let (signature, status) = match &err {
+                    Error::Transaction { signature, .. } => (signature.map(|s| s.to_string()), "failed"),
+                    Error::ConfirmationTimeout { signature, .. } => (Some(signature.to_string()), "pending"),
+                    _ => (None, "failed"),
+                };
+                if status == "pending" {
+                    output.progress(format_args!("전송 결과 미확인: {}", err));
+                } else {
+                    output.progress(format_args!("전송 실패: {}", err));
+                }
+                records.push(TransferRecord {
+                    id: (records.len() + 1).to_string(),
+                    signature,
+                    status: status.to_string(),
+                    fee,
+                    slot: None,
+                    error: Some(err.to_string()),
+                });
             }
         }
     }
+    output.print(&records);
+    Ok(())
 }
-
-// 사용자 입력을 받는 함수
-fn get_input(prompt: &str) -> String {
-    print!("{}", prompt);
-    io::stdout().flush().expect("출력 실패");
-    
-    let mut input = String::new();
-    io::stdin().lock().read_line(&mut input).expect("입력 읽기 실패");
-    input
-}
-
-// Base58 인코딩된 개인키로부터 Keypair 생성
-fn create_keypair_from_base58(private_key: &str) -> Keypair {
-    let bytes = bs58::decode(private_key)
-        .into_vec()
-        .expect("유효하지 않은 개인키");
-    
-    Keypair::from_bytes(&bytes).expect("키페어 생성 실패")
-}
-
-// SOL 전송 함수
-fn send_sol(
-    rpc_client: &RpcClient,
-    sender: &Keypair,
-    recipient: &Pubkey,
-    lamports: u64,
-    fee: u64,
-) -> Result<String, Box<dyn std::error::Error>> {
-    // 최근 블록해시 가져오기
-    let recent_blockhash = rpc_client.get_latest_blockhash()?;
-    
-    // 명령어 벡터 생성
-    let mut instructions = vec![];
-    
-    // 우선순위 수수료 설정 (최신 SDK 방식)
-    if fee > 0 {
-        // 수수료 우선순위 설정 명령어 추가
-        let fee_instruction = ComputeBudgetInstruction::set_compute_unit_price(fee);
-        instructions.push(fee_instruction);
-    }
-    
-    // 전송 명령 추가
-    let transfer_instruction = system_instruction::transfer(&sender.pubkey(), recipient, lamports);
-    instructions.push(transfer_instruction);
-    
-    // 트랜잭션 생성 및 서명
-    let mut transaction = Transaction::new_with_payer(&instructions, Some(&sender.pubkey()));
-    transaction.sign(&[sender], recent_blockhash);
-    
-    // 트랜잭션 전송
-    let start_time = Instant::now();
-    let signature = rpc_client.send_and_confirm_transaction_with_spinner_and_commitment(
-        &transaction,
-        CommitmentConfig::confirmed(),
-    )?;
-    let elapsed = start_time.elapsed();
-    println!("트랜잭션 처리 시간: {:?}", elapsed);
-    
-    Ok(signature.to_string())
-} 
\ No newline at end of file
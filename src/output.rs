@@ -0,0 +1,893 @@
+//! 기계가 읽을 수 있는 결과 출력 (`--output json|csv|table`)
+//!
+//! 모든 바이너리는 `--output` 옵션으로 결과 형식을 고릅니다. 기본값인 `table`은 사람이 읽는 표와
+//! 안내 문구를 출력하고, `json`과 `csv`는 버전이 붙은 고정 스키마로 결과만 표준 출력에 씁니다.
+//! 이때 진행 상황 같은 안내 문구는 결과와 섞이지 않도록 표준 오류로 보냅니다.
+//!
+//! JSON은 `{"schema_version": 1, "kind": "latency", "records": [...]}` 형태의 문서 하나이고,
+//! CSV는 첫 열이 `schema_version`인 헤더와 레코드 줄입니다. 필드를 없애거나 의미를 바꾸면
+//! [`SCHEMA_VERSION`]을 올립니다 (필드 추가는 같은 버전에서 허용하며, CSV에서는 기존 열 뒤에 붙입니다).
+
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::failure::{Failure, FailureCounts, FailureKind};
+use crate::fee_sweep::SweepReport;
+use crate::freshness::{FreshnessReport, Lag};
+use crate::landing::Landing;
+use crate::load::{LoadReport, LoadWindow};
+use crate::probe::ProbeSample;
+use crate::stats::LatencySummary;
+use crate::subscription::SubscriptionReport;
+use crate::throughput::ThroughputReport;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// 출력 스키마 버전
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// 결과 출력 형식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// 사람이 읽는 표와 안내 문구
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            other => Err(Error::Input(format!("출력 형식 오류: {} (json, csv, table 중 하나)", other))),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+        })
+    }
+}
+
+impl OutputFormat {
+    /// `--output` 옵션 (없으면 표)
+    pub fn from_args(args: &Args) -> Result<OutputFormat> {
+        args.value("output").map_or(Ok(OutputFormat::Table), str::parse)
+    }
+
+    pub fn is_table(&self) -> bool {
+        *self == OutputFormat::Table
+    }
+
+    /// 안내 문구 출력 (JSON/CSV 결과와 섞이지 않도록 표 형식이 아니면 표준 오류로)
+    pub fn progress(&self, message: impl fmt::Display) {
+        if self.is_table() {
+            println!("{}", message);
+        } else {
+            eprintln!("{}", message);
+        }
+    }
+
+    /// 레코드를 JSON 문서나 CSV로 변환 (표 형식이면 `None` - 호출자가 표를 출력)
+    pub fn render<T: Record>(&self, records: &[T]) -> Option<String> {
+        match self {
+            OutputFormat::Table => None,
+            OutputFormat::Json => Some(to_json(records)),
+            OutputFormat::Csv => Some(to_csv(records)),
+        }
+    }
+
+    /// 레코드를 표준 출력에 쓰기 (표 형식이면 아무것도 하지 않음)
+    pub fn print<T: Record>(&self, records: &[T]) {
+        if let Some(rendered) = self.render(records) {
+            print!("{}", rendered);
+        }
+    }
+}
+
+/// 출력 레코드 종류
+pub trait Record: Serialize {
+    /// JSON 문서의 `kind` 값
+    const KIND: &'static str;
+    /// CSV 열 이름 (`schema_version` 제외)
+    const COLUMNS: &'static [&'static str];
+
+    /// CSV 한 줄의 값 (`COLUMNS` 순서, 값이 없으면 빈 문자열)
+    fn row(&self) -> Vec<String>;
+}
+
+/// JSON 출력 문서
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report<T> {
+    pub schema_version: u32,
+    pub kind: String,
+    pub records: Vec<T>,
+}
+
+/// 엔드포인트와 작업별 지연 시간 (rpc_benchmark, ping_test, tx_speed_test)
+///
+/// 시간은 밀리초(소수점 포함)이며, 성공한 측정이 없으면 통계 값은 `null`(CSV는 빈 칸)입니다.
+/// 실패는 종류별 횟수(`failures`, CSV는 종류마다 한 열)로도 기록합니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyRecord {
+    pub endpoint: String,
+    /// 측정한 작업 (RPC 메서드 이름, `ping_test`의 단계 `dns`/`tcp_connect`/`tls_handshake`/`ttfb`/`total`,
+    /// `tx_speed_test`의 커미트먼트 `processed`/`confirmed`/`finalized` 등)
+    pub operation: String,
+    /// 성공한 측정 수
+    pub samples: usize,
+    /// 실패한 측정 수
+    pub errors: usize,
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    pub p50_ms: Option<f64>,
+    pub p90_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    /// 표준편차
+    pub stddev_ms: Option<f64>,
+    /// 전체 시도 중 실패 비율 (0~1)
+    #[serde(default)]
+    pub error_rate: f64,
+    /// 종류별 실패 수 (실패가 있었던 종류만, 이름은 [`FailureKind::as_str`])
+    #[serde(default)]
+    pub failures: BTreeMap<String, usize>,
+    /// 측정한 주소 종류 (`ping_test`의 `ipv4`/`ipv6`, 그 밖의 도구는 `null`)
+    #[serde(default)]
+    pub ip_family: Option<String>,
+    /// 연속한 측정값 차이의 평균 (`ping_test`의 전체 시간, 그 밖에는 `null`)
+    #[serde(default)]
+    pub jitter_ms: Option<f64>,
+    /// 전송한 트랜잭션 중 블록에 포함된 비율 (`tx_speed_test`의 `confirmed` 레코드, 그 밖에는 `null`)
+    #[serde(default)]
+    pub landing_rate: Option<f64>,
+    /// 전송 슬롯부터 포함된 슬롯까지의 평균 슬롯 수 (`landing_rate`와 같은 레코드)
+    #[serde(default)]
+    pub slots_to_land: Option<f64>,
+    /// 측정 트랜잭션 메모에 담긴 실행 ID (`tx_speed_test`, 그 밖에는 `null`)
+    #[serde(default)]
+    pub run_id: Option<String>,
+}
+
+impl LatencyRecord {
+    pub fn new(
+        endpoint: &str,
+        operation: &str,
+        summary: Option<&LatencySummary>,
+        failures: &FailureCounts,
+    ) -> LatencyRecord {
+        let samples = summary.map_or(0, |s| s.count);
+        let ms = |pick: fn(&LatencySummary) -> Duration| summary.map(|s| millis(pick(s)));
+        LatencyRecord {
+            endpoint: endpoint.to_string(),
+            operation: operation.to_string(),
+            samples,
+            errors: failures.total(),
+            min_ms: ms(|s| s.min),
+            avg_ms: ms(|s| s.avg),
+            max_ms: ms(|s| s.max),
+            p50_ms: ms(|s| s.p50),
+            p90_ms: ms(|s| s.p90),
+            p95_ms: ms(|s| s.p95),
+            p99_ms: ms(|s| s.p99),
+            stddev_ms: ms(|s| s.stddev),
+            error_rate: round_rate(failures.rate(samples)),
+            failures: failure_map(failures),
+            ip_family: None,
+            jitter_ms: None,
+            landing_rate: None,
+            slots_to_land: None,
+            run_id: None,
+        }
+    }
+}
+
+impl Record for LatencyRecord {
+    const KIND: &'static str = "latency";
+    const COLUMNS: &'static [&'static str] = &[
+        "endpoint", "operation", "samples", "errors", "min_ms", "avg_ms", "max_ms", "p50_ms", "p90_ms", "p95_ms",
+        "p99_ms", "stddev_ms", "error_rate", "rate_limited", "http_4xx", "http_5xx", "timeout", "connection_refused",
+        "tls", "connection", "json_rpc", "other", "ip_family", "jitter_ms", "landing_rate", "slots_to_land", "run_id",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = vec![
+            self.endpoint.clone(),
+            self.operation.clone(),
+            self.samples.to_string(),
+            self.errors.to_string(),
+        ];
+        row.extend([
+            self.min_ms,
+            self.avg_ms,
+            self.max_ms,
+            self.p50_ms,
+            self.p90_ms,
+            self.p95_ms,
+            self.p99_ms,
+            self.stddev_ms,
+        ]
+        .map(optional));
+        row.push(self.error_rate.to_string());
+        row.extend(failure_columns(&self.failures));
+        row.push(self.ip_family.clone().unwrap_or_default());
+        row.push(optional(self.jitter_ms));
+        row.push(optional(self.landing_rate));
+        row.push(optional(self.slots_to_land));
+        row.push(self.run_id.clone().unwrap_or_default());
+        row
+    }
+}
+
+/// 부하 테스트의 구간별 결과와 전체 결과 (`rpc_benchmark --load`)
+///
+/// 엔드포인트마다 구간 레코드 뒤에 `window_start_s`가 `null`(CSV는 빈 칸)인 전체 레코드가 옵니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadRecord {
+    pub endpoint: String,
+    /// 실행 시작부터 구간 시작까지 (초, 전체 레코드는 `null`)
+    pub window_start_s: Option<f64>,
+    /// 구간 길이 (초, 전체 레코드는 실행 시간)
+    pub window_s: f64,
+    pub requests: usize,
+    pub succeeded: usize,
+    pub errors: usize,
+    pub timeouts: usize,
+    /// 초당 성공한 요청 수
+    pub throughput_rps: f64,
+    pub p50_ms: Option<f64>,
+    pub p90_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    /// 성능 저하가 처음 감지된 구간 (전체 레코드는 저하가 있었는지)
+    pub degraded: bool,
+    /// 종류별 실패 수 (실패가 있었던 종류만)
+    #[serde(default)]
+    pub failures: BTreeMap<String, usize>,
+}
+
+impl LoadRecord {
+    /// 엔드포인트 하나의 구간 레코드와 전체 레코드
+    pub fn from_report(endpoint: &str, report: &LoadReport) -> Vec<LoadRecord> {
+        let degradation = report.degradation();
+        let mut records: Vec<LoadRecord> = report
+            .windows
+            .iter()
+            .enumerate()
+            .map(|(index, window)| {
+                let degraded = degradation.is_some_and(|d| d.window == index);
+                LoadRecord::new(endpoint, Some(window.start), window, degraded)
+            })
+            .collect();
+        records.push(LoadRecord::new(endpoint, None, &report.total(), degradation.is_some()));
+        records
+    }
+
+    fn new(endpoint: &str, start: Option<Duration>, window: &LoadWindow, degraded: bool) -> LoadRecord {
+        let summary = window.latency.summary();
+        let ms = |pick: fn(&LatencySummary) -> Duration| summary.as_ref().map(|s| millis(pick(s)));
+        LoadRecord {
+            endpoint: endpoint.to_string(),
+            window_start_s: start.map(|start| start.as_secs_f64()),
+            window_s: window.length.as_secs_f64(),
+            requests: window.requests,
+            succeeded: window.latency.len(),
+            errors: window.errors(),
+            timeouts: window.timeouts(),
+            throughput_rps: (window.throughput() * 1000.0).round() / 1000.0,
+            p50_ms: ms(|s| s.p50),
+            p90_ms: ms(|s| s.p90),
+            p99_ms: ms(|s| s.p99),
+            max_ms: ms(|s| s.max),
+            degraded,
+            failures: failure_map(&window.failures),
+        }
+    }
+}
+
+impl Record for LoadRecord {
+    const KIND: &'static str = "load";
+    const COLUMNS: &'static [&'static str] = &[
+        "endpoint", "window_start_s", "window_s", "requests", "succeeded", "errors", "timeouts", "throughput_rps", "p50_ms",
+        "p90_ms", "p99_ms", "max_ms", "degraded", "rate_limited", "http_4xx", "http_5xx", "timeout", "connection_refused",
+        "tls", "connection", "json_rpc", "other",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = vec![
+            self.endpoint.clone(),
+            optional(self.window_start_s),
+            self.window_s.to_string(),
+            self.requests.to_string(),
+            self.succeeded.to_string(),
+            self.errors.to_string(),
+            self.timeouts.to_string(),
+            self.throughput_rps.to_string(),
+        ];
+        row.extend([self.p50_ms, self.p90_ms, self.p99_ms, self.max_ms].map(optional));
+        row.push(self.degraded.to_string());
+        row.extend(failure_columns(&self.failures));
+        row
+    }
+}
+
+/// `ping_test`의 측정 하나 (`--timeline`)
+///
+/// 손실된 측정은 `latency_ms`가 `null`이고 `failure`에 실패 종류가 들어갑니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeRecord {
+    pub endpoint: String,
+    /// `ipv4`/`ipv6` (`--icmp`는 `null`)
+    pub ip_family: Option<String>,
+    /// 1부터 시작하는 측정 번호
+    pub seq: usize,
+    /// 첫 측정 시작부터 이 측정 시작까지 (초)
+    pub offset_s: f64,
+    pub latency_ms: Option<f64>,
+    /// 실패 종류 ([`FailureKind::as_str`])
+    pub failure: Option<String>,
+}
+
+impl ProbeRecord {
+    /// 측정 기록 하나의 레코드
+    pub fn new(endpoint: &str, ip_family: Option<&str>, sample: &ProbeSample) -> ProbeRecord {
+        ProbeRecord {
+            endpoint: endpoint.to_string(),
+            ip_family: ip_family.map(str::to_string),
+            seq: sample.seq,
+            offset_s: millis(sample.offset) / 1000.0,
+            latency_ms: sample.result.ok().map(millis),
+            failure: sample.result.err().map(|kind| kind.as_str().to_string()),
+        }
+    }
+}
+
+impl Record for ProbeRecord {
+    const KIND: &'static str = "probe";
+    const COLUMNS: &'static [&'static str] = &["endpoint", "ip_family", "seq", "offset_s", "latency_ms", "failure"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.endpoint.clone(),
+            self.ip_family.clone().unwrap_or_default(),
+            self.seq.to_string(),
+            self.offset_s.to_string(),
+            optional(self.latency_ms),
+            self.failure.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// `tx_speed_test`의 트랜잭션 하나 (`--transactions`)
+///
+/// 시간은 전송 요청부터 각 커미트먼트에 처음 도달할 때까지이며, 도달하지 못했으면 `null`입니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LandingRecord {
+    pub endpoint: String,
+    /// 엔드포인트마다 1부터 시작하는 전송 번호
+    pub seq: usize,
+    /// 전송 요청이 실패했으면 `null`
+    pub signature: Option<String>,
+    /// 전송 직전의 processed 슬롯
+    pub sent_slot: Option<u64>,
+    /// 포함된 슬롯 (포함되지 않았으면 `null`)
+    pub landed_slot: Option<u64>,
+    pub slots_to_land: Option<u64>,
+    pub processed_ms: Option<f64>,
+    pub confirmed_ms: Option<f64>,
+    pub finalized_ms: Option<f64>,
+    /// 실패 종류 ([`FailureKind::as_str`], 포함되지 않았으면 `timeout`)
+    pub failure: Option<String>,
+    /// 실행 ID와 트랜잭션에 붙인 메모 (`rpc-bench:<실행 ID>:<순번>`)
+    #[serde(default)]
+    pub run_id: Option<String>,
+    #[serde(default)]
+    pub memo: Option<String>,
+}
+
+impl LandingRecord {
+    /// 측정 결과 하나의 레코드
+    pub fn new(endpoint: &str, seq: usize, result: &Result<Landing>) -> LandingRecord {
+        let landing = result.as_ref().ok();
+        let ms = |pick: fn(&Landing) -> Option<Duration>| landing.and_then(pick).map(millis);
+        let failure = match result {
+            Ok(landing) => landing.failure(),
+            Err(e) => Some(Failure::from_error(e)),
+        };
+        LandingRecord {
+            endpoint: endpoint.to_string(),
+            seq,
+            signature: landing.map(|landing| landing.signature.to_string()),
+            sent_slot: landing.and_then(|landing| landing.sent_slot),
+            landed_slot: landing.and_then(|landing| landing.landed_slot),
+            slots_to_land: landing.and_then(Landing::slots_to_land),
+            processed_ms: ms(|landing| landing.processed),
+            confirmed_ms: ms(|landing| landing.confirmed),
+            finalized_ms: ms(|landing| landing.finalized),
+            failure: failure.map(|failure| failure.kind.as_str().to_string()),
+            run_id: None,
+            memo: None,
+        }
+    }
+}
+
+impl Record for LandingRecord {
+    const KIND: &'static str = "landing";
+    const COLUMNS: &'static [&'static str] = &[
+        "endpoint", "seq", "signature", "sent_slot", "landed_slot", "slots_to_land", "processed_ms", "confirmed_ms",
+        "finalized_ms", "failure", "run_id", "memo",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = vec![
+            self.endpoint.clone(),
+            self.seq.to_string(),
+            self.signature.clone().unwrap_or_default(),
+        ];
+        row.extend([self.sent_slot, self.landed_slot, self.slots_to_land].map(optional));
+        row.extend([self.processed_ms, self.confirmed_ms, self.finalized_ms].map(optional));
+        row.push(self.failure.clone().unwrap_or_default());
+        row.push(self.run_id.clone().unwrap_or_default());
+        row.push(self.memo.clone().unwrap_or_default());
+        row
+    }
+}
+
+/// 수수료 스윕의 단계 하나 (`tx_speed_test --fee-sweep`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeSweepRecord {
+    pub endpoint: String,
+    /// compute unit 가격 (micro-lamports per CU)
+    pub cu_price: u64,
+    /// CU 한도 (한도 명령을 붙이지 않았으면 `null`)
+    pub cu_limit: Option<u32>,
+    pub sent: usize,
+    pub landed: usize,
+    /// 포함률 (0~1)
+    pub landing_rate: f64,
+    pub slots_to_land_avg: Option<f64>,
+    pub slots_to_land_max: Option<u64>,
+    /// confirmed까지 시간 (실행에 성공한 트랜잭션)
+    pub confirmed_p50_ms: Option<f64>,
+    pub confirmed_p90_ms: Option<f64>,
+    /// 트랜잭션 하나의 수수료 (lamports, 기본 수수료 + 우선순위 수수료)
+    pub fee_per_tx: u64,
+    /// 포함된 트랜잭션이 낸 수수료 합계 (lamports)
+    pub total_cost: u64,
+    /// 목표를 만족하는 가장 싼 단계인지
+    pub recommended: bool,
+    /// 종류별 실패 수 (실패가 있었던 종류만)
+    #[serde(default)]
+    pub failures: BTreeMap<String, usize>,
+    /// 측정 트랜잭션 메모에 담긴 실행 ID
+    #[serde(default)]
+    pub run_id: String,
+}
+
+impl FeeSweepRecord {
+    /// 스윕 결과의 단계별 레코드 (격자 순서)
+    pub fn from_report(endpoint: &str, report: &SweepReport) -> Vec<FeeSweepRecord> {
+        let recommended = report.recommended().map(|result| result.level);
+        report
+            .levels
+            .iter()
+            .map(|result| {
+                let stats = &result.stats;
+                let confirmed = stats.confirmed.summary();
+                FeeSweepRecord {
+                    endpoint: endpoint.to_string(),
+                    cu_price: result.level.price,
+                    cu_limit: result.level.cu_limit,
+                    sent: stats.sent,
+                    landed: stats.landed,
+                    landing_rate: round_rate(stats.landing_rate()),
+                    slots_to_land_avg: stats.average_slots(),
+                    slots_to_land_max: stats.max_slots(),
+                    confirmed_p50_ms: confirmed.as_ref().map(|s| millis(s.p50)),
+                    confirmed_p90_ms: confirmed.as_ref().map(|s| millis(s.p90)),
+                    fee_per_tx: result.level.cost(),
+                    total_cost: result.total_cost(),
+                    recommended: recommended == Some(result.level),
+                    failures: failure_map(&stats.failures),
+                    run_id: report.run_id.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Record for FeeSweepRecord {
+    const KIND: &'static str = "fee_sweep";
+    const COLUMNS: &'static [&'static str] = &[
+        "endpoint", "cu_price", "cu_limit", "sent", "landed", "landing_rate", "slots_to_land_avg", "slots_to_land_max",
+        "confirmed_p50_ms", "confirmed_p90_ms", "fee_per_tx", "total_cost", "recommended", "rate_limited", "http_4xx",
+        "http_5xx", "timeout", "connection_refused", "tls", "connection", "json_rpc", "other", "run_id",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = vec![
+            self.endpoint.clone(),
+            self.cu_price.to_string(),
+            optional(self.cu_limit),
+            self.sent.to_string(),
+            self.landed.to_string(),
+            self.landing_rate.to_string(),
+            optional(self.slots_to_land_avg),
+            optional(self.slots_to_land_max),
+            optional(self.confirmed_p50_ms),
+            optional(self.confirmed_p90_ms),
+            self.fee_per_tx.to_string(),
+            self.total_cost.to_string(),
+            self.recommended.to_string(),
+        ];
+        row.extend(failure_columns(&self.failures));
+        row.push(self.run_id.clone());
+        row
+    }
+}
+
+/// 엔드포인트 하나의 병렬 전송 처리량 (`tx_speed_test --throughput`)
+///
+/// 초당 수는 첫 전송 시작부터 센 값이며, `drop_rate`는 받아들여졌지만 블록에 포함되지 않은 비율입니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputRecord {
+    pub endpoint: String,
+    /// 트랜잭션을 나눠 보낸 테스트 계정 수
+    pub accounts: usize,
+    /// 목표 초당 전송 수
+    pub target_rate: f64,
+    pub sent: usize,
+    pub accepted: usize,
+    pub landed: usize,
+    /// 실제로 전송을 시도한 초당 수
+    pub send_rate: f64,
+    pub accepted_per_s: f64,
+    pub landed_per_s: f64,
+    /// 드롭률 (0~1)
+    pub drop_rate: f64,
+    /// 전송 요청의 응답 시간
+    pub send_p50_ms: Option<f64>,
+    pub send_p99_ms: Option<f64>,
+    /// 종류별 실패 수 (실패가 있었던 종류만)
+    #[serde(default)]
+    pub failures: BTreeMap<String, usize>,
+    /// 측정 트랜잭션 메모에 담긴 실행 ID
+    #[serde(default)]
+    pub run_id: String,
+}
+
+impl ThroughputRecord {
+    pub fn new(endpoint: &str, report: &ThroughputReport) -> ThroughputRecord {
+        let per_second = |rate: f64| (rate * 1000.0).round() / 1000.0;
+        let send_latency = report.send_latency.summary();
+        ThroughputRecord {
+            endpoint: endpoint.to_string(),
+            accounts: report.accounts,
+            target_rate: report.target_rate,
+            sent: report.sent,
+            accepted: report.accepted,
+            landed: report.landed,
+            send_rate: per_second(report.send_rate()),
+            accepted_per_s: per_second(report.accepted_per_second()),
+            landed_per_s: per_second(report.landed_per_second()),
+            drop_rate: round_rate(report.drop_rate()),
+            send_p50_ms: send_latency.as_ref().map(|s| millis(s.p50)),
+            send_p99_ms: send_latency.as_ref().map(|s| millis(s.p99)),
+            failures: failure_map(&report.failures),
+            run_id: report.run_id.clone(),
+        }
+    }
+}
+
+impl Record for ThroughputRecord {
+    const KIND: &'static str = "throughput";
+    const COLUMNS: &'static [&'static str] = &[
+        "endpoint", "accounts", "target_rate", "sent", "accepted", "landed", "send_rate", "accepted_per_s", "landed_per_s",
+        "drop_rate", "send_p50_ms", "send_p99_ms", "rate_limited", "http_4xx", "http_5xx", "timeout", "connection_refused",
+        "tls", "connection", "json_rpc", "other", "run_id",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = vec![
+            self.endpoint.clone(),
+            self.accounts.to_string(),
+            self.target_rate.to_string(),
+            self.sent.to_string(),
+            self.accepted.to_string(),
+            self.landed.to_string(),
+            self.send_rate.to_string(),
+            self.accepted_per_s.to_string(),
+            self.landed_per_s.to_string(),
+            self.drop_rate.to_string(),
+            optional(self.send_p50_ms),
+            optional(self.send_p99_ms),
+        ];
+        row.extend(failure_columns(&self.failures));
+        row.push(self.run_id.clone());
+        row
+    }
+}
+
+/// 엔드포인트 하나의 최신성 비교 결과 (`rpc_benchmark --freshness`)
+///
+/// `_lag_avg`/`_lag_max`는 같은 표본에서 가장 앞선 엔드포인트보다 뒤처진 슬롯(블록) 수이며,
+/// 비교한 표본이 없으면 `null`입니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FreshnessRecord {
+    pub endpoint: String,
+    /// 모은 표본 수
+    pub samples: usize,
+    pub processed_lag_avg: Option<f64>,
+    pub processed_lag_max: Option<u64>,
+    pub confirmed_lag_avg: Option<f64>,
+    pub confirmed_lag_max: Option<u64>,
+    pub finalized_lag_avg: Option<f64>,
+    pub finalized_lag_max: Option<u64>,
+    pub block_height_lag_avg: Option<f64>,
+    pub block_height_lag_max: Option<u64>,
+    /// 최근 블록해시의 `lastValidBlockHeight` 차이
+    pub blockhash_lag_avg: Option<f64>,
+    pub blockhash_lag_max: Option<u64>,
+    /// 다른 엔드포인트보다 오래된 블록해시를 돌려주는지
+    pub stale_blockhash: bool,
+    /// 종류별 실패 수 (실패가 있었던 종류만)
+    #[serde(default)]
+    pub failures: BTreeMap<String, usize>,
+}
+
+impl FreshnessRecord {
+    /// 엔드포인트별 레코드 (`endpoints`와 [`FreshnessReport::endpoints`]는 같은 순서)
+    pub fn from_report(endpoints: &[String], report: &FreshnessReport) -> Vec<FreshnessRecord> {
+        endpoints
+            .iter()
+            .zip(&report.endpoints)
+            .map(|(endpoint, freshness)| {
+                let average = |lag: &Lag| lag.average().map(|average| (average * 1000.0).round() / 1000.0);
+                let max = |lag: &Lag| (lag.samples > 0).then_some(lag.max);
+                let [processed, confirmed, finalized] = &freshness.slot_lag;
+                FreshnessRecord {
+                    endpoint: endpoint.clone(),
+                    samples: report.samples,
+                    processed_lag_avg: average(processed),
+                    processed_lag_max: max(processed),
+                    confirmed_lag_avg: average(confirmed),
+                    confirmed_lag_max: max(confirmed),
+                    finalized_lag_avg: average(finalized),
+                    finalized_lag_max: max(finalized),
+                    block_height_lag_avg: average(&freshness.block_height_lag),
+                    block_height_lag_max: max(&freshness.block_height_lag),
+                    blockhash_lag_avg: average(&freshness.blockhash_lag),
+                    blockhash_lag_max: max(&freshness.blockhash_lag),
+                    stale_blockhash: freshness.stale_blockhash(),
+                    failures: failure_map(&freshness.failures),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Record for FreshnessRecord {
+    const KIND: &'static str = "freshness";
+    const COLUMNS: &'static [&'static str] = &[
+        "endpoint", "samples", "processed_lag_avg", "processed_lag_max", "confirmed_lag_avg", "confirmed_lag_max",
+        "finalized_lag_avg", "finalized_lag_max", "block_height_lag_avg", "block_height_lag_max", "blockhash_lag_avg",
+        "blockhash_lag_max", "stale_blockhash", "rate_limited", "http_4xx", "http_5xx", "timeout", "connection_refused",
+        "tls", "connection", "json_rpc", "other",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = vec![
+            self.endpoint.clone(),
+            self.samples.to_string(),
+            optional(self.processed_lag_avg),
+            optional(self.processed_lag_max),
+            optional(self.confirmed_lag_avg),
+            optional(self.confirmed_lag_max),
+            optional(self.finalized_lag_avg),
+            optional(self.finalized_lag_max),
+            optional(self.block_height_lag_avg),
+            optional(self.block_height_lag_max),
+            optional(self.blockhash_lag_avg),
+            optional(self.blockhash_lag_max),
+            self.stale_blockhash.to_string(),
+        ];
+        row.extend(failure_columns(&self.failures));
+        row
+    }
+}
+
+/// 엔드포인트 하나의 웹소켓 구독 하나에 대한 알림 지연 (`rpc_benchmark --pubsub`)
+///
+/// 지연은 같은 이벤트를 가장 먼저 받은 엔드포인트보다 늦게 받은 시간이며, 받은 알림이 없으면 `null`입니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubscriptionRecord {
+    pub endpoint: String,
+    /// `slotSubscribe`, `accountSubscribe`, `signatureSubscribe`
+    pub subscription: String,
+    /// 비교한 이벤트 수
+    pub events: usize,
+    pub notifications: usize,
+    /// 가장 먼저 받은 이벤트 수
+    pub fastest: usize,
+    /// 다른 엔드포인트에는 왔지만 받지 못한 알림 수
+    pub missed: usize,
+    /// 구독 실패와 연결 끊김 횟수
+    pub dropped: usize,
+    pub avg_ms: Option<f64>,
+    pub p50_ms: Option<f64>,
+    pub p90_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+}
+
+impl SubscriptionRecord {
+    /// 엔드포인트와 구독마다 레코드 하나 (`endpoints`와 [`SubscriptionReport::endpoints`]는 같은 순서)
+    pub fn from_report(endpoints: &[String], report: &SubscriptionReport) -> Vec<SubscriptionRecord> {
+        let subscriptions = report.subscriptions();
+        let mut records = Vec::new();
+        for (endpoint, stats) in endpoints.iter().zip(&report.endpoints) {
+            for subscription in &subscriptions {
+                let Some(stats) = stats.get(subscription) else {
+                    continue;
+                };
+                let summary = stats.delay.summary();
+                let ms = |pick: fn(&LatencySummary) -> Duration| summary.as_ref().map(|s| millis(pick(s)));
+                records.push(SubscriptionRecord {
+                    endpoint: endpoint.clone(),
+                    subscription: subscription.method().to_string(),
+                    events: report.events.get(subscription).copied().unwrap_or(0),
+                    notifications: stats.notifications(),
+                    fastest: stats.fastest,
+                    missed: stats.missed,
+                    dropped: stats.dropped,
+                    avg_ms: ms(|s| s.avg),
+                    p50_ms: ms(|s| s.p50),
+                    p90_ms: ms(|s| s.p90),
+                    p99_ms: ms(|s| s.p99),
+                    max_ms: ms(|s| s.max),
+                });
+            }
+        }
+        records
+    }
+}
+
+impl Record for SubscriptionRecord {
+    const KIND: &'static str = "subscription";
+    const COLUMNS: &'static [&'static str] = &[
+        "endpoint", "subscription", "events", "notifications", "fastest", "missed", "dropped", "avg_ms", "p50_ms", "p90_ms",
+        "p99_ms", "max_ms",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let mut row = vec![
+            self.endpoint.clone(),
+            self.subscription.clone(),
+            self.events.to_string(),
+            self.notifications.to_string(),
+            self.fastest.to_string(),
+            self.missed.to_string(),
+            self.dropped.to_string(),
+        ];
+        row.extend([self.avg_ms, self.p50_ms, self.p90_ms, self.p99_ms, self.max_ms].map(optional));
+        row
+    }
+}
+
+/// 전송 하나의 결과 (전송 봇의 대화형 전송과 batch)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferRecord {
+    /// 전송 ID (batch는 지급 ID, 대화형 전송은 순번)
+    pub id: String,
+    pub signature: Option<String>,
+    /// `confirmed`, `failed`, `signed`(결과 미확인), `expired`
+    pub status: String,
+    /// 우선순위 수수료 (lamports per compute unit)
+    pub fee: u64,
+    /// 트랜잭션이 처리된 슬롯 (확인되지 않았으면 `null`)
+    pub slot: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl Record for TransferRecord {
+    const KIND: &'static str = "transfer";
+    const COLUMNS: &'static [&'static str] = &["id", "signature", "status", "fee", "slot", "error"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.signature.clone().unwrap_or_default(),
+            self.status.clone(),
+            self.fee.to_string(),
+            optional(self.slot),
+            self.error.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// 레코드를 JSON 문서로 변환
+pub fn to_json<T: Record>(records: &[T]) -> String {
+    let report = Report {
+        schema_version: SCHEMA_VERSION,
+        kind: T::KIND.to_string(),
+        records: records.iter().collect::<Vec<&T>>(),
+    };
+    let mut json = serde_json::to_string_pretty(&report).expect("출력 레코드는 항상 JSON으로 변환됨");
+    json.push('\n');
+    json
+}
+
+/// 레코드를 CSV로 변환 (헤더 포함)
+pub fn to_csv<T: Record>(records: &[T]) -> String {
+    let mut csv = format!("schema_version,{}\n", T::COLUMNS.join(","));
+    for record in records {
+        let fields: Vec<String> = record.row().iter().map(|field| csv_escape(field)).collect();
+        csv.push_str(&format!("{},{}\n", SCHEMA_VERSION, fields.join(",")));
+    }
+    csv
+}
+
+/// JSON 문서 파싱 (다른 바이너리의 출력을 합칠 때 사용)
+pub fn parse_json<T: Record + for<'de> Deserialize<'de>>(json: &str) -> Result<Vec<T>> {
+    let report: Report<T> =
+        serde_json::from_str(json).map_err(|e| Error::Input(format!("결과 JSON 형식 오류: {}", e)))?;
+    if report.schema_version != SCHEMA_VERSION || report.kind != T::KIND {
+        return Err(Error::Input(format!(
+            "지원하지 않는 결과 스키마입니다: {} v{} (필요: {} v{})",
+            report.kind,
+            report.schema_version,
+            T::KIND,
+            SCHEMA_VERSION
+        )));
+    }
+    Ok(report.records)
+}
+
+/// 시간을 밀리초(소수점 3자리)로
+pub fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+// 실패가 있었던 종류별 횟수
+fn failure_map(failures: &FailureCounts) -> BTreeMap<String, usize> {
+    failures.kinds().map(|(kind, count)| (kind.as_str().to_string(), count)).collect()
+}
+
+// 종류별 실패 수 CSV 열 ([`FailureKind::ALL`] 순서, 없으면 0)
+fn failure_columns(failures: &BTreeMap<String, usize>) -> Vec<String> {
+    FailureKind::ALL
+        .iter()
+        .map(|kind| failures.get(kind.as_str()).copied().unwrap_or(0).to_string())
+        .collect()
+}
+
+// 비율 (소수점 4자리)
+fn round_rate(rate: f64) -> f64 {
+    (rate * 10_000.0).round() / 10_000.0
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// CSV 값 이스케이프 (쉼표, 따옴표, 줄바꿈이 있는 값은 따옴표로 감쌈)
+pub fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,455 @@
+//! 네이티브 네트워크 프로브 (`ping_test`)
+//!
+//! 시스템 `ping` 명령 대신 RPC 엔드포인트에 직접 연결해 단계별 시간을 잽니다: DNS 조회, TCP 연결,
+//! TLS 핸드셰이크(https), JSON-RPC `getHealth` 요청을 보낸 뒤 첫 바이트까지(TTFB), 전체 시간.
+//! ICMP를 막는 CDN 뒤의 호스트나 `ping`이 없는 컨테이너에서도 동작하며, IPv4와 IPv6를 따로 측정합니다.
+//!
+//! 측정마다 DNS를 다시 조회하고 새 연결을 맺으므로, 결과는 연결을 재사용하지 않는 첫 요청이 겪는 시간입니다.
+//!
+//! 측정 순서는 [`Timeline`]에 남겨 손실률과 지터(연속한 측정값 차이의 평균)를 구합니다.
+
+use crate::benchmark::Rounds;
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::failure::{Failure, FailureCounts, FailureKind, HttpStatus};
+use crate::rpc;
+use crate::stats::{self, LatencyHistogram};
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::{self, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use tokio_rustls::TlsConnector;
+use url::{Host, Url};
+
+/// 프로브 하나의 기본 타임아웃 (DNS 조회부터 응답을 다 받을 때까지, `--timeout`)
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 측정 사이 기본 간격 (`--interval`)
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(200);
+
+// getHealth 요청 본문
+const HEALTH_REQUEST: &str = r#"{"jsonrpc":"2.0","id":1,"method":"getHealth"}"#;
+
+// 응답을 이만큼 넘게 받으면 더 읽지 않음 (getHealth 응답은 수십 바이트)
+const MAX_RESPONSE: usize = 64 * 1024;
+
+/// IP 주소 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IpFamily {
+    V4,
+    V6,
+}
+
+impl IpFamily {
+    pub const ALL: [IpFamily; 2] = [IpFamily::V4, IpFamily::V6];
+
+    /// 기계가 읽는 출력의 이름
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IpFamily::V4 => "ipv4",
+            IpFamily::V6 => "ipv6",
+        }
+    }
+
+    /// 출력용 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            IpFamily::V4 => "IPv4",
+            IpFamily::V6 => "IPv6",
+        }
+    }
+
+    pub fn matches(&self, address: &SocketAddr) -> bool {
+        match self {
+            IpFamily::V4 => address.is_ipv4(),
+            IpFamily::V6 => address.is_ipv6(),
+        }
+    }
+}
+
+/// `ping_test` 측정 옵션
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PingOptions {
+    pub rounds: Rounds,
+    /// 측정 사이 간격
+    pub interval: Duration,
+    /// 측정 하나의 타임아웃 (넘으면 손실)
+    pub timeout: Duration,
+}
+
+impl PingOptions {
+    /// `--count`(`--iterations`와 같음, 둘 다 주면 `--count`), `--warmup`, `--interval`/`--timeout`(밀리초)
+    pub fn from_args(args: &Args, default: Rounds) -> Result<PingOptions> {
+        let rounds = Rounds::from_args(args, default)?;
+        Ok(PingOptions {
+            rounds: Rounds {
+                iterations: args.parse_or("count", rounds.iterations)?.max(1),
+                ..rounds
+            },
+            interval: Duration::from_millis(args.parse_or("interval", DEFAULT_INTERVAL.as_millis() as u64)?),
+            timeout: Duration::from_millis(args.parse_or("timeout", PROBE_TIMEOUT.as_millis() as u64)?.max(1)),
+        })
+    }
+}
+
+/// 프로브 대상 (RPC URL에서 추출)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeTarget {
+    /// 호스트 이름 또는 IP 주소 (IPv6 주소는 대괄호 없이)
+    pub host: String,
+    pub port: u16,
+    /// https면 TLS 핸드셰이크도 측정
+    pub tls: bool,
+    /// `Host` 헤더 값 (기본 포트가 아니면 포트 포함)
+    pub authority: String,
+    /// 요청 경로 (URL에 API 키가 들어가는 RPC 제공자를 위해 쿼리 포함)
+    pub path: String,
+}
+
+impl ProbeTarget {
+    /// http/https RPC URL
+    pub fn parse(url: &str) -> Result<ProbeTarget> {
+        let parsed = Url::parse(url).map_err(|e| Error::Input(format!("유효하지 않은 URL: {} ({})", url, e)))?;
+        let tls = match parsed.scheme() {
+            "https" => true,
+            "http" => false,
+            other => return Err(Error::Input(format!("http/https URL이 아닙니다: {} ({})", url, other))),
+        };
+        let host = match parsed.host() {
+            Some(Host::Domain(domain)) => domain.to_string(),
+            Some(Host::Ipv4(address)) => address.to_string(),
+            Some(Host::Ipv6(address)) => address.to_string(),
+            None => return Err(Error::Input(format!("URL에 호스트가 없습니다: {}", url))),
+        };
+        let host_str = parsed.host_str().unwrap_or_default();
+        let authority = match parsed.port() {
+            Some(port) => format!("{}:{}", host_str, port),
+            None => host_str.to_string(),
+        };
+        let path = match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        };
+        Ok(ProbeTarget {
+            host,
+            port: parsed.port_or_known_default().unwrap_or(if tls { 443 } else { 80 }),
+            tls,
+            authority,
+            path,
+        })
+    }
+
+    // getHealth HTTP 요청
+    fn health_request(&self) -> String {
+        format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nUser-Agent: solana_transfer_bot\r\nConnection: close\r\n\r\n{}",
+            self.path,
+            self.authority,
+            HEALTH_REQUEST.len(),
+            HEALTH_REQUEST
+        )
+    }
+}
+
+/// 성공한 프로브 하나의 단계별 시간
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeTiming {
+    /// 연결한 주소
+    pub address: SocketAddr,
+    pub dns: Duration,
+    pub connect: Duration,
+    /// TLS 핸드셰이크 (http면 `None`)
+    pub tls: Option<Duration>,
+    /// 요청을 보내기 시작해서 응답의 첫 바이트를 받을 때까지
+    pub ttfb: Duration,
+    /// DNS 조회부터 응답을 다 받을 때까지
+    pub total: Duration,
+    /// `getHealth` 결과가 `ok`인지 (노드가 뒤처지면 JSON-RPC 오류로 응답)
+    pub healthy: bool,
+}
+
+/// 호스트의 주소 조회 (이름이 없거나 조회에 실패하면 연결 오류)
+pub async fn resolve(target: &ProbeTarget) -> std::result::Result<Vec<SocketAddr>, Failure> {
+    tokio::net::lookup_host((target.host.as_str(), target.port))
+        .await
+        .map(|addresses| addresses.collect())
+        .map_err(|e| Failure::from_io(&e, "DNS 조회 실패"))
+}
+
+/// 한 주소 종류로 프로브 한 번 (`timeout` 안에 끝나지 않으면 타임아웃)
+pub async fn probe(target: &ProbeTarget, family: IpFamily, timeout: Duration) -> std::result::Result<ProbeTiming, Failure> {
+    match tokio::time::timeout(timeout, probe_once(target, family)).await {
+        Ok(result) => result,
+        Err(_) => Err(Failure::timeout(timeout)),
+    }
+}
+
+async fn probe_once(target: &ProbeTarget, family: IpFamily) -> std::result::Result<ProbeTiming, Failure> {
+    let start = Instant::now();
+    let address = resolve(target)
+        .await?
+        .into_iter()
+        .find(|address| family.matches(address))
+        .ok_or_else(|| Failure::new(FailureKind::Connection, format!("{} 주소 없음: {}", family.label(), target.host)))?;
+    let dns = start.elapsed();
+
+    let phase = Instant::now();
+    let stream = TcpStream::connect(address).await.map_err(|e| Failure::from_io(&e, "TCP 연결 실패"))?;
+    // 요청을 한 번에 보내므로 Nagle 지연을 피함 (실패해도 측정에는 지장 없음)
+    let _ = stream.set_nodelay(true);
+    let connect = phase.elapsed();
+
+    let (tls, (ttfb, response)) = if target.tls {
+        let phase = Instant::now();
+        let server_name = ServerName::try_from(target.host.as_str())
+            .map_err(|e| Failure::new(FailureKind::Tls, format!("TLS 서버 이름 오류: {}", e)))?;
+        let stream = tls_connector()
+            .connect(server_name, stream)
+            .await
+            .map_err(|e| Failure::new(FailureKind::Tls, format!("TLS 핸드셰이크 실패: {}", e)))?;
+        let tls = phase.elapsed();
+        (Some(tls), exchange(stream, target).await?)
+    } else {
+        (None, exchange(stream, target).await?)
+    };
+    let total = start.elapsed();
+
+    let (status, headers, body) = parse_response(&response)?;
+    if !(200..300).contains(&status) {
+        let retry_after = headers
+            .lines()
+            .find_map(|line| line.split_once(':').filter(|(name, _)| name.eq_ignore_ascii_case("retry-after")))
+            .and_then(|(_, value)| rpc::parse_retry_after(value));
+        let http = HttpStatus { status, retry_after };
+        return Err(Failure::from_status(http, http.to_string()));
+    }
+    Ok(ProbeTiming {
+        address,
+        dns,
+        connect,
+        tls,
+        ttfb,
+        total,
+        healthy: String::from_utf8_lossy(body).contains(r#""result":"ok""#),
+    })
+}
+
+// 공개 루트 인증서로 검증하는 TLS 클라이언트 (프로세스에서 한 번 생성)
+fn tls_connector() -> TlsConnector {
+    static CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+    let config = CONFIG.get_or_init(|| {
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(anchor.subject, anchor.spki, anchor.name_constraints)
+        }));
+        Arc::new(
+            rustls::ClientConfig::builder()
+                .with_safe_defaults()
+                .with_root_certificates(roots)
+                .with_no_client_auth(),
+        )
+    });
+    TlsConnector::from(config.clone())
+}
+
+// 요청을 보내고 응답을 받음 (첫 바이트까지 시간, 응답 전체)
+async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(
+    mut stream: S,
+    target: &ProbeTarget,
+) -> std::result::Result<(Duration, Vec<u8>), Failure> {
+    let start = Instant::now();
+    stream
+        .write_all(target.health_request().as_bytes())
+        .await
+        .map_err(|e| Failure::from_io(&e, "요청 전송 실패"))?;
+
+    let mut response = Vec::new();
+    let mut buffer = [0u8; 4096];
+    let mut ttfb = None;
+    loop {
+        let read = match stream.read(&mut buffer).await {
+            Ok(read) => read,
+            // close_notify 없이 연결을 닫는 서버 (이미 받은 응답은 사용)
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof && !response.is_empty() => 0,
+            Err(e) => return Err(Failure::from_io(&e, "응답 수신 실패")),
+        };
+        if read == 0 {
+            break;
+        }
+        ttfb.get_or_insert_with(|| start.elapsed());
+        response.extend_from_slice(&buffer[..read]);
+        if response_complete(&response) || response.len() > MAX_RESPONSE {
+            break;
+        }
+    }
+    match ttfb {
+        Some(ttfb) => Ok((ttfb, response)),
+        None => Err(Failure::new(FailureKind::Connection, "응답 없이 연결이 끊김")),
+    }
+}
+
+// 헤더와 본문을 다 받았는지 (Content-Length 또는 chunked 마지막 조각 기준, 둘 다 없으면 연결이 닫힐 때까지)
+fn response_complete(response: &[u8]) -> bool {
+    let Some(end) = find(response, b"\r\n\r\n") else {
+        return false;
+    };
+    let headers = String::from_utf8_lossy(&response[..end]).to_ascii_lowercase();
+    let body = &response[end + 4..];
+    if let Some(length) = headers
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .and_then(|value| value.trim().parse::<usize>().ok())
+    {
+        return body.len() >= length;
+    }
+    headers.contains("transfer-encoding: chunked") && body.ends_with(b"0\r\n\r\n")
+}
+
+// 상태 코드, 헤더, 본문
+fn parse_response(response: &[u8]) -> std::result::Result<(u16, String, &[u8]), Failure> {
+    let invalid = || Failure::new(FailureKind::Other, "잘못된 HTTP 응답");
+    let end = find(response, b"\r\n\r\n").ok_or_else(invalid)?;
+    let headers = String::from_utf8_lossy(&response[..end]).into_owned();
+    let status = headers
+        .lines()
+        .next()
+        .filter(|line| line.starts_with("HTTP/"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(invalid)?;
+    Ok((status, headers, &response[end + 4..]))
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// 엔드포인트와 주소 종류 하나의 프로브 결과 집계
+#[derive(Debug, Clone, Default)]
+pub struct ProbeStats {
+    pub dns: LatencyHistogram,
+    pub connect: LatencyHistogram,
+    pub tls: LatencyHistogram,
+    pub ttfb: LatencyHistogram,
+    pub total: LatencyHistogram,
+    pub failures: FailureCounts,
+    /// 응답은 받았지만 `getHealth`가 `ok`가 아니었던 횟수
+    pub unhealthy: usize,
+    /// 마지막으로 연결한 주소
+    pub address: Option<SocketAddr>,
+}
+
+impl ProbeStats {
+    pub fn new() -> ProbeStats {
+        ProbeStats::default()
+    }
+
+    /// 프로브 결과 하나 기록
+    pub fn record(&mut self, result: &std::result::Result<ProbeTiming, Failure>) {
+        match result {
+            Ok(timing) => {
+                self.dns.record(timing.dns);
+                self.connect.record(timing.connect);
+                if let Some(tls) = timing.tls {
+                    self.tls.record(tls);
+                }
+                self.ttfb.record(timing.ttfb);
+                self.total.record(timing.total);
+                if !timing.healthy {
+                    self.unhealthy += 1;
+                }
+                self.address = Some(timing.address);
+            }
+            Err(failure) => self.failures.record(failure),
+        }
+    }
+
+    /// 단계별 (작업 이름, 출력용 이름, 히스토그램) - TLS를 측정하지 않았으면 TLS 단계 제외
+    pub fn phases(&self) -> Vec<(&'static str, &'static str, &LatencyHistogram)> {
+        let mut phases = vec![("dns", "DNS 조회", &self.dns), ("tcp_connect", "TCP 연결", &self.connect)];
+        if !self.tls.is_empty() {
+            phases.push(("tls_handshake", "TLS 핸드셰이크", &self.tls));
+        }
+        phases.push(("ttfb", "첫 바이트", &self.ttfb));
+        phases.push(("total", "전체", &self.total));
+        phases
+    }
+}
+
+/// 측정 하나 (측정 순서대로 번호가 붙음)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeSample {
+    /// 1부터 시작하는 측정 번호
+    pub seq: usize,
+    /// 첫 측정 시작부터 이 측정 시작까지
+    pub offset: Duration,
+    /// 응답 시간 또는 실패 종류 (손실)
+    pub result: std::result::Result<Duration, FailureKind>,
+}
+
+/// 측정 순서 기록 (손실률과 지터 계산)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timeline {
+    samples: Vec<ProbeSample>,
+}
+
+impl Timeline {
+    pub fn new() -> Timeline {
+        Timeline::default()
+    }
+
+    /// 다음 측정 기록
+    pub fn record(&mut self, offset: Duration, result: std::result::Result<Duration, FailureKind>) -> &ProbeSample {
+        let seq = self.samples.len() + 1;
+        self.samples.push(ProbeSample { seq, offset, result });
+        &self.samples[seq - 1]
+    }
+
+    pub fn samples(&self) -> &[ProbeSample] {
+        &self.samples
+    }
+
+    /// 보낸 측정 수
+    pub fn sent(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// 응답을 받지 못한 측정 수
+    pub fn lost(&self) -> usize {
+        self.samples.iter().filter(|sample| sample.result.is_err()).count()
+    }
+
+    /// 손실률 (0~1, 측정이 없으면 0)
+    pub fn loss(&self) -> f64 {
+        match self.sent() {
+            0 => 0.0,
+            sent => self.lost() as f64 / sent as f64,
+        }
+    }
+
+    /// 지터: 응답을 받은 연속한 측정의 응답 시간 차이 평균 (손실된 측정은 건너뜀)
+    pub fn jitter(&self) -> Option<Duration> {
+        let received: Vec<Duration> = self.samples.iter().filter_map(|sample| sample.result.ok()).collect();
+        stats::jitter(&received)
+    }
+}
+
+/// 시스템 `ping` 출력의 왕복 시간 (`--icmp`)
+///
+/// 언어와 운영체제에 상관없이 `=` 또는 `<` 바로 뒤의 `ms`가 붙은 숫자를 찾습니다
+/// (`time=0.045 ms`, `시간=3ms`, `Zeit=12ms`). 1ms 미만을 뜻하는 Windows의 `<1ms`는 1ms로 기록합니다.
+pub fn parse_ping_time(output: &str) -> Option<Duration> {
+    output.lines().find_map(|line| {
+        line.match_indices(['=', '<']).find_map(|(index, _)| {
+            let rest = &line[index + 1..];
+            let length = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+            let millis: f64 = rest[..length].parse().ok()?;
+            rest[length..]
+                .trim_start()
+                .starts_with("ms")
+                .then(|| Duration::from_secs_f64(millis / 1000.0))
+        })
+    })
+}
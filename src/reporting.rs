@@ -0,0 +1,430 @@
+//! 벤치마크 결과 출력
+
+use crate::failure::FailureCounts;
+use crate::fee_sweep::{SweepReport, MIN_LANDING_RATE};
+use crate::freshness::{FreshnessReport, Lag, COMMITMENTS, STALE_BLOCKHASH_LAG};
+use crate::landing::LandingStats;
+use crate::load::{DegradationCause, LoadReport, DEGRADATION_FACTOR, FAILURE_RATE_LIMIT};
+use crate::probe::Timeline;
+use crate::stats::{LatencyHistogram, LatencySummary};
+use crate::subscription::SubscriptionReport;
+use crate::throughput::ThroughputReport;
+
+// 히스토그램 막대 최대 길이
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// 결과 표에 사용할 문구
+pub struct TableLabels<'a> {
+    /// 표 제목 (예: "종합 결과")
+    pub title: &'a str,
+    /// 측정 항목 이름 (예: "응답시간")
+    pub metric: &'a str,
+    /// 측정값이 없을 때 표시할 문구 (예: "연결 오류")
+    pub failure: &'a str,
+    /// 가장 빠른 엔드포인트 안내 문구 (예: "가장 빠른 RPC 서버")
+    pub fastest: &'a str,
+}
+
+/// 엔드포인트별 평균/백분위/최소/최대/표준편차 표와 가장 빠른 엔드포인트 출력
+///
+/// `results`는 (엔드포인트, 통계) 목록이며 통계가 `None`이면 실패로 표시합니다.
+pub fn print_latency_table(labels: &TableLabels, results: &[(String, Option<LatencySummary>)]) {
+    println!("\n===== {} =====", labels.title);
+    println!(
+        "| RPC 엔드포인트 | 측정 수 | 평균 {m} | p50 | p95 | p99 | 최소 {m} | 최대 {m} | 표준편차 |",
+        m = labels.metric
+    );
+    println!("|----------------|---------|--------------|-----|-----|-----|--------------|--------------|----------|");
+
+    for (endpoint, summary) in results {
+        match summary {
+            Some(summary) => println!(
+                "| {:<14} | {} | {:?} | {:?} | {:?} | {:?} | {:?} | {:?} | {:?} |",
+                endpoint,
+                summary.count,
+                summary.avg,
+                summary.p50,
+                summary.p95,
+                summary.p99,
+                summary.min,
+                summary.max,
+                summary.stddev
+            ),
+            None => println!("| {:<14} | 0 | {:<12} | - | - | - | - | - | - |", endpoint, labels.failure),
+        }
+    }
+
+    // 가장 빠른 RPC 서버 확인
+    let fastest = results
+        .iter()
+        .filter_map(|(endpoint, summary)| summary.map(|s| (endpoint, s.avg)))
+        .min_by_key(|(_, avg)| *avg);
+    if let Some((endpoint, avg)) = fastest {
+        println!("\n🏆 {}: {} (평균: {:?})", labels.fastest, endpoint, avg);
+    }
+}
+
+/// 작업 하나의 통계 한 줄과 분포 막대 그래프 출력 (측정값이 없으면 통계 줄만)
+pub fn print_histogram(label: &str, histogram: &LatencyHistogram, errors: usize) {
+    match histogram.summary() {
+        Some(s) => println!(
+            "  {}: {}회 (오류 {}회), 평균 {:?}, p50 {:?}, p90 {:?}, p95 {:?}, p99 {:?}, 표준편차 {:?}",
+            label, s.count, errors, s.avg, s.p50, s.p90, s.p95, s.p99, s.stddev
+        ),
+        None => println!("  {}: 성공한 측정 없음 (오류 {}회)", label, errors),
+    }
+    for line in histogram.text_histogram(HISTOGRAM_WIDTH) {
+        println!("    {}", line);
+    }
+}
+
+/// 엔드포인트별 손실률과 지터 표 (`rows`는 (엔드포인트, 측정 기록))
+pub fn print_loss_and_jitter(rows: &[(String, &Timeline)]) {
+    println!("\n| RPC 엔드포인트 | 보냄 | 손실 | 손실률 | 지터 |");
+    println!("|----------------|------|------|--------|------|");
+    for (endpoint, timeline) in rows {
+        println!(
+            "| {} | {} | {} | {:.1}% | {} |",
+            endpoint,
+            timeline.sent(),
+            timeline.lost(),
+            timeline.loss() * 100.0,
+            // 지터는 1ms 미만인 경우가 많으므로 소수점 2자리
+            timeline.jitter().map_or("-".to_string(), |jitter| format!("{:.2}ms", jitter.as_secs_f64() * 1000.0))
+        );
+    }
+}
+
+/// 엔드포인트별 포함률, 포함까지 걸린 슬롯 수, 커미트먼트별 p50 표와 가장 빨리 포함시킨 엔드포인트
+pub fn print_landing(rows: &[(String, &LandingStats)]) {
+    println!("\n===== 트랜잭션 포함 결과 =====");
+    println!("| RPC 엔드포인트 | 전송 | 포함 | 포함률 | 평균 슬롯 | 최대 슬롯 | processed p50 | confirmed p50 | finalized p50 |");
+    println!("|----------------|------|------|--------|-----------|-----------|---------------|---------------|---------------|");
+    for (endpoint, stats) in rows {
+        let p50 = stats.phases().map(|(_, _, histogram)| histogram.summary().map_or("-".to_string(), |s| format_millis(s.p50)));
+        println!(
+            "| {} | {} | {} | {:.1}% | {} | {} | {} |",
+            endpoint,
+            stats.sent,
+            stats.landed,
+            stats.landing_rate() * 100.0,
+            stats.average_slots().map_or("-".to_string(), |slots| format!("{:.1}", slots)),
+            stats.max_slots().map_or("-".to_string(), |slots| slots.to_string()),
+            p50.join(" | ")
+        );
+    }
+
+    // 슬롯 수가 같으면 포함률이 높은 쪽
+    let fastest = rows
+        .iter()
+        .filter_map(|(endpoint, stats)| Some((endpoint, stats.average_slots()?, stats.landing_rate())))
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(b.2.total_cmp(&a.2)));
+    if let Some((endpoint, slots, _)) = fastest {
+        println!("\n🏆 가장 적은 슬롯 안에 포함시킨 RPC 서버: {} (평균 {:.1}슬롯)", endpoint, slots);
+    }
+}
+
+/// 수수료 단계별 포함률, 슬롯 수, confirmed까지 p50, 비용 표와 추천 가격
+pub fn print_fee_sweep(report: &SweepReport) {
+    println!("\n===== 우선순위 수수료 스윕 결과 =====");
+    println!("| CU 가격 (µL/CU) | CU 한도 | 전송 | 포함률 | 평균 슬롯 | confirmed p50 | 건당 수수료 | 총 비용 |");
+    println!("|-----------------|---------|------|--------|-----------|---------------|-------------|---------|");
+    let recommended = report.recommended().map(|result| result.level);
+    for result in &report.levels {
+        let stats = &result.stats;
+        println!(
+            "| {}{} | {} | {} | {:.1}% | {} | {} | {} | {} |",
+            result.level.price,
+            if recommended == Some(result.level) { " 💡" } else { "" },
+            result.level.cu_limit.map_or("-".to_string(), |limit| limit.to_string()),
+            stats.sent,
+            stats.landing_rate() * 100.0,
+            stats.average_slots().map_or("-".to_string(), |slots| format!("{:.1}", slots)),
+            stats.confirmed.summary().map_or("-".to_string(), |s| format_millis(s.p50)),
+            format_lamports(result.level.cost()),
+            format_lamports(result.total_cost())
+        );
+    }
+
+    let goal = format!("포함률 {:.0}% 이상, confirmed p50 {:?} 이내", MIN_LANDING_RATE * 100.0, report.target);
+    match report.recommended() {
+        Some(result) => println!(
+            "\n💡 추천 가격: {} (건당 {}, 기준: {})",
+            result.level,
+            format_lamports(result.level.cost()),
+            goal
+        ),
+        None => println!("\n⚠️ 목표({})를 만족하는 가격이 없습니다. 더 높은 가격을 포함해 다시 실행하세요.", goal),
+    }
+}
+
+/// 병렬 전송 처리량 표 (초당 전송 시도/수락/포함 수와 드롭률)
+pub fn print_throughput(rows: &[(String, &ThroughputReport)]) {
+    println!("\n===== 병렬 전송 처리량 결과 =====");
+    println!("| RPC 엔드포인트 | 전송 | 수락 | 포함 | 전송/초 | 수락/초 | 포함/초 | 드롭률 | 전송 응답 p50 |");
+    println!("|----------------|------|------|------|---------|---------|---------|--------|---------------|");
+    for (endpoint, report) in rows {
+        println!(
+            "| {} | {} | {} | {} | {:.1} | {:.1} | {:.1} | {:.1}% | {} |",
+            endpoint,
+            report.sent,
+            report.accepted,
+            report.landed,
+            report.send_rate(),
+            report.accepted_per_second(),
+            report.landed_per_second(),
+            report.drop_rate() * 100.0,
+            report.send_latency.summary().map_or("-".to_string(), |s| format_millis(s.p50))
+        );
+    }
+
+    if let Some((endpoint, report)) = rows.iter().max_by(|a, b| a.1.landed_per_second().total_cmp(&b.1.landed_per_second())) {
+        if report.landed > 0 {
+            println!("\n🏆 초당 가장 많이 포함시킨 RPC 서버: {} ({:.1}개/초)", endpoint, report.landed_per_second());
+        }
+    }
+    if let Some(report) = rows.first().map(|(_, report)| report) {
+        println!("(목표 {}개/초, 테스트 계정 {}개)", report.target_rate, report.accounts);
+    }
+}
+
+// lamports와 SOL 환산 (예: "15000 lamports (0.000015 SOL)")
+fn format_lamports(lamports: u64) -> String {
+    format!("{} lamports ({} SOL)", lamports, lamports as f64 / 1_000_000_000.0)
+}
+
+/// 단계별 p50/p90/p99/최대 표 (예: 네트워크 프로브의 DNS, TCP 연결, TLS, 첫 바이트)
+pub fn print_phases(phases: &[(&str, &LatencyHistogram)]) {
+    println!("  | 단계 | p50 | p90 | p99 | 최대 |");
+    println!("  |------|-----|-----|-----|------|");
+    for (label, histogram) in phases {
+        match histogram.summary() {
+            Some(s) => println!(
+                "  | {} | {} | {} | {} | {} |",
+                label,
+                format_millis(s.p50),
+                format_millis(s.p90),
+                format_millis(s.p99),
+                format_millis(s.max)
+            ),
+            None => println!("  | {} | - | - | - | - |", label),
+        }
+    }
+}
+
+/// 비교 표의 한 행: 메서드와 엔드포인트 순서의 (통계, 오류 수)
+pub type MethodRow<'a> = (&'a str, Vec<(Option<LatencySummary>, usize)>);
+
+/// 메서드(행)와 엔드포인트(열)별 p50/p99 비교 표와 메서드별 가장 빠른 엔드포인트 출력
+///
+/// 열은 번호로 표시하고 표 위에 번호별 엔드포인트를 출력합니다. 통계가 `None`이면 실패로 표시합니다.
+pub fn print_method_matrix(endpoints: &[String], rows: &[MethodRow]) {
+    println!("\n===== 메서드별 비교 (p50 / p99) =====");
+    for (index, endpoint) in endpoints.iter().enumerate() {
+        println!("  #{}: {}", index + 1, endpoint);
+    }
+
+    let columns: Vec<String> = (1..=endpoints.len()).map(|index| format!("#{}", index)).collect();
+    println!("\n| 메서드 | {} |", columns.join(" | "));
+    println!("|--------|{}", "------|".repeat(endpoints.len()));
+    for (method, cells) in rows {
+        let cells: Vec<String> = cells
+            .iter()
+            .map(|(summary, errors)| {
+                let cell = match summary {
+                    Some(s) => format!("{} / {}", format_millis(s.p50), format_millis(s.p99)),
+                    None => "실패".to_string(),
+                };
+                match errors {
+                    0 => cell,
+                    errors => format!("{} (오류 {})", cell, errors),
+                }
+            })
+            .collect();
+        println!("| {} | {} |", method, cells.join(" | "));
+    }
+
+    // 메서드별 p50이 가장 낮은 엔드포인트
+    println!();
+    for (method, cells) in rows {
+        let fastest = cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (summary, _))| summary.map(|s| (index, s.p50)))
+            .min_by_key(|(_, p50)| *p50);
+        if let Some((index, p50)) = fastest {
+            println!("🏆 {}: #{} {} (p50: {})", method, index + 1, endpoints[index], format_millis(p50));
+        }
+    }
+}
+
+/// 엔드포인트별 최신성 비교 표 (커미트먼트별 슬롯, 블록 높이, 블록해시가 뒤처진 정도의 평균 / 최대)
+pub fn print_freshness(endpoints: &[String], report: &FreshnessReport) {
+    println!("\n===== 엔드포인트 최신성 (표본 {}개) =====", report.samples);
+    if let Some(slot) = report.highest_slot {
+        println!("관찰한 가장 높은 슬롯: {}", slot);
+    }
+    println!("값은 같은 시점에 가장 앞선 엔드포인트보다 뒤처진 슬롯(블록) 수의 평균 / 최대입니다.\n");
+
+    let commitments: Vec<String> = COMMITMENTS.iter().map(|level| format!("{} 슬롯", level)).collect();
+    println!("| 엔드포인트 | {} | 블록 높이 | 블록해시 |", commitments.join(" | "));
+    println!("|------------|{}-----------|----------|", "------|".repeat(COMMITMENTS.len()));
+    for (endpoint, freshness) in endpoints.iter().zip(&report.endpoints) {
+        let mut cells: Vec<String> = freshness.slot_lag.iter().map(format_lag).collect();
+        cells.push(format_lag(&freshness.block_height_lag));
+        let blockhash = format_lag(&freshness.blockhash_lag);
+        cells.push(if freshness.stale_blockhash() { format!("{} ⚠️", blockhash) } else { blockhash });
+        println!("| {} | {} |", endpoint, cells.join(" | "));
+    }
+
+    println!();
+    for (endpoint, freshness) in endpoints.iter().zip(&report.endpoints) {
+        let attempts = report.samples * (COMMITMENTS.len() + 2);
+        print_failures(&format!("{} 실패", endpoint), attempts.saturating_sub(freshness.failures.total()), &freshness.failures);
+    }
+    let stale: Vec<(&String, f64)> = endpoints
+        .iter()
+        .zip(&report.endpoints)
+        .filter(|(_, freshness)| freshness.stale_blockhash())
+        .filter_map(|(endpoint, freshness)| Some((endpoint, freshness.blockhash_lag.average()?)))
+        .collect();
+    if stale.is_empty() {
+        println!("✅ 오래된 블록해시를 돌려주는 엔드포인트 없음 (기준: 평균 {}블록)", STALE_BLOCKHASH_LAG);
+    }
+    for (endpoint, lag) in stale {
+        println!("⚠️ {}: 다른 엔드포인트보다 평균 {:.1}블록 오래된 블록해시를 돌려줍니다", endpoint, lag);
+    }
+}
+
+/// 구독별 알림 지연 표와 엔드포인트별 지연 분포 (지연은 가장 먼저 받은 엔드포인트 기준)
+pub fn print_subscriptions(endpoints: &[String], report: &SubscriptionReport) {
+    for subscription in report.subscriptions() {
+        let events = report.events.get(&subscription).copied().unwrap_or(0);
+        println!("\n===== {} (이벤트 {}개) =====", subscription.method(), events);
+        println!("| 엔드포인트 | 알림 | 가장 빠름 | 놓침 | 끊김 | p50 | p90 | p99 | 최대 |");
+        println!("|------------|------|-----------|------|------|-----|-----|-----|------|");
+        for (endpoint, stats) in endpoints.iter().zip(&report.endpoints) {
+            let Some(stats) = stats.get(&subscription) else {
+                continue;
+            };
+            let delays = match stats.delay.summary() {
+                Some(s) => [s.p50, s.p90, s.p99, s.max].map(format_millis).join(" | "),
+                None => "- | - | - | -".to_string(),
+            };
+            println!(
+                "| {} | {} | {} | {} | {} | {} |",
+                endpoint,
+                stats.notifications(),
+                stats.fastest,
+                stats.missed,
+                stats.dropped,
+                delays
+            );
+        }
+
+        println!("\n알림 지연 분포:");
+        for (endpoint, stats) in endpoints.iter().zip(&report.endpoints) {
+            if let Some(stats) = stats.get(&subscription) {
+                print_histogram(endpoint, &stats.delay, stats.missed);
+            }
+        }
+    }
+}
+
+// 뒤처진 정도 (평균 / 최대, 비교한 표본이 없으면 "-")
+fn format_lag(lag: &Lag) -> String {
+    match lag.average() {
+        Some(average) => format!("{:.1} / {}", average, lag.max),
+        None => "-".to_string(),
+    }
+}
+
+// 밀리초 (소수점 1자리)
+fn format_millis(duration: std::time::Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// 부하 테스트 결과: 구간별 처리량과 백분위 표, 전체 요약, 성능 저하 지점
+pub fn print_load_report(endpoint: &str, report: &LoadReport) {
+    println!("\n===== 부하 테스트 결과: {} =====", endpoint);
+    println!("| 구간 | 요청/초 | 처리량/초 | p50 | p90 | p99 | 오류 | 타임아웃 |");
+    println!("|------|---------|-----------|-----|-----|-----|------|----------|");
+    let degradation = report.degradation();
+    for (index, window) in report.windows.iter().enumerate() {
+        let percentiles = match window.latency.is_empty() {
+            true => "- | - | -".to_string(),
+            false => [50.0, 90.0, 99.0].map(|p| format_millis(window.latency.percentile(p))).join(" | "),
+        };
+        println!(
+            "| {:.1}~{:.1}s{} | {:.1} | {:.1} | {} | {} | {} |",
+            window.start.as_secs_f64(),
+            (window.start + window.length).as_secs_f64(),
+            if degradation.is_some_and(|d| d.window == index) { " ⚠️" } else { "" },
+            window.offered_rps(),
+            window.throughput(),
+            percentiles,
+            window.errors(),
+            window.timeouts()
+        );
+    }
+
+    let total = report.total();
+    println!(
+        "\n요청 {}개, 처리량 {:.1}/초, 오류율 {:.1}%, 타임아웃 비율 {:.1}%",
+        total.requests,
+        total.throughput(),
+        ratio_percent(total.errors(), total.requests),
+        ratio_percent(total.timeouts(), total.requests)
+    );
+    if let Some(s) = total.latency.summary() {
+        println!("응답 시간: p50 {}, p90 {}, p99 {}, 최대 {}", format_millis(s.p50), format_millis(s.p90), format_millis(s.p99), format_millis(s.max));
+    }
+    print_failures("실패 종류", total.latency.len(), &total.failures);
+    match degradation {
+        Some(d) => {
+            let cause = match d.cause {
+                DegradationCause::Latency { baseline, p50 } => {
+                    format!("p50 {}가 기준 {}의 {}배 초과", format_millis(p50), format_millis(baseline), DEGRADATION_FACTOR)
+                }
+                DegradationCause::Failures { rate } => {
+                    format!("실패율 {:.1}%가 {}% 초과", rate * 100.0, FAILURE_RATE_LIMIT * 100.0)
+                }
+            };
+            println!("⚠️ 성능 저하 지점: {:.1}s 구간 (요청 {:.1}/초) - {}", d.start.as_secs_f64(), d.offered_rps, cause);
+        }
+        None => println!("✅ 실행 동안 성능 저하 없음"),
+    }
+}
+
+/// 종류별 실패 수와 비율, HTTP 상태/JSON-RPC 코드별 횟수, 마지막 오류 출력 (실패가 없으면 출력하지 않음)
+///
+/// `successes`는 성공한 측정 수이며 비율의 분모(전체 시도)를 구하는 데 씁니다.
+pub fn print_failures(label: &str, successes: usize, failures: &FailureCounts) {
+    if failures.is_empty() {
+        return;
+    }
+    let attempts = successes + failures.total();
+    println!("  {}: {}회 / {}회 ({:.1}%)", label, failures.total(), attempts, failures.rate(successes) * 100.0);
+    for (kind, count) in failures.kinds() {
+        println!("    {}: {}회 ({:.1}%)", kind.label(), count, ratio_percent(count, attempts));
+    }
+    let statuses: Vec<String> = failures.statuses().map(|(status, count)| format!("{} × {}", status, count)).collect();
+    if !statuses.is_empty() {
+        println!("    HTTP 상태: {}", statuses.join(", "));
+    }
+    let codes: Vec<String> = failures.codes().map(|(code, count)| format!("{} × {}", code, count)).collect();
+    if !codes.is_empty() {
+        println!("    JSON-RPC 코드: {}", codes.join(", "));
+    }
+    if let Some(error) = &failures.last_error {
+        println!("    마지막 오류: {}", error);
+    }
+}
+
+fn ratio_percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / total as f64
+    }
+}
@@ -0,0 +1,155 @@
+//! RPC 엔드포인트 설정
+//!
+//! 모든 도구는 설정(프로필, `--rpc-url`, `SOLANA_RPC_URL`, 설정 파일)에서 정한 RPC를 사용하며,
+//! 벤치마크는 프로필의 공식 RPC와 함께 측정합니다. `SOLANA_SKIP_OFFICIAL_RPC`를 설정하면 벤치마크에서
+//! 공식 RPC를 빼고 설정된 RPC만 측정합니다 (로컬 검증인 테스트처럼 메인넷에 연결하지 않아야 할 때).
+//!
+//! 벤치마크는 [`benchmark_client`]를 사용합니다. 기본 클라이언트는 429 응답을 `Retry-After`만큼
+//! 기다리며 최대 5번 조용히 재시도하므로, 한도 초과가 드러나지 않고 그 대기 시간이 응답 시간에 섞입니다.
+
+use crate::failure::HttpStatus;
+use async_trait::async_trait;
+use dotenv::dotenv;
+use reqwest::header::{CONTENT_TYPE, RETRY_AFTER};
+use solana_client::client_error::Result as ClientResult;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_client::{RpcClient, RpcClientConfig};
+use solana_client::rpc_request::{RpcError, RpcRequest, RpcResponseErrorData};
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::env;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 공식 RPC 엔드포인트
+pub const OFFICIAL_RPC: &str = "https://api.mainnet-beta.solana.com";
+
+/// RPC URL 환경 변수 이름
+pub const RPC_URL_ENV: &str = "SOLANA_RPC_URL";
+
+/// 벤치마크에서 공식 RPC를 빼는 환경 변수 이름 (`1`, `true`, `yes`)
+pub const SKIP_OFFICIAL_RPC_ENV: &str = "SOLANA_SKIP_OFFICIAL_RPC";
+
+/// RPC 요청 타임아웃
+pub const RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// .env 파일에서 환경 변수 로드
+pub fn load_env() {
+    dotenv().ok();
+}
+
+/// 환경 변수에 설정된 커스텀 RPC URL (없으면 `None`)
+pub fn custom_rpc_url() -> Option<String> {
+    env::var(RPC_URL_ENV).ok().filter(|url| !url.is_empty())
+}
+
+/// 벤치마크에서 공식 RPC를 빼도록 [`SKIP_OFFICIAL_RPC_ENV`]가 설정되었는지 여부
+///
+/// 대상 목록은 설정(`Settings::benchmark_endpoints`)에서 프로필과 RPC URL로 정합니다.
+pub fn skip_official_rpc() -> bool {
+    env::var(SKIP_OFFICIAL_RPC_ENV)
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// 기본 타임아웃을 적용한 RPC 클라이언트 생성
+pub fn client(url: &str) -> RpcClient {
+    RpcClient::new_with_timeout(url.to_string(), RPC_TIMEOUT)
+}
+
+/// 기본 타임아웃과 지정한 커미트먼트를 적용한 RPC 클라이언트 생성
+pub fn client_with_commitment(url: &str, commitment: CommitmentConfig) -> RpcClient {
+    RpcClient::new_with_timeout_and_commitment(url.to_string(), RPC_TIMEOUT, commitment)
+}
+
+/// 기본 타임아웃을 적용한 비동기 RPC 클라이언트 생성
+pub fn async_client(url: &str) -> AsyncRpcClient {
+    AsyncRpcClient::new_with_timeout(url.to_string(), RPC_TIMEOUT)
+}
+
+/// 기본 타임아웃과 지정한 커미트먼트를 적용한 비동기 RPC 클라이언트 생성
+pub fn async_client_with_commitment(url: &str, commitment: CommitmentConfig) -> AsyncRpcClient {
+    AsyncRpcClient::new_with_timeout_and_commitment(url.to_string(), RPC_TIMEOUT, commitment)
+}
+
+/// 벤치마크용 비동기 RPC 클라이언트 생성
+///
+/// HTTP 오류 상태를 재시도하지 않고 [`HttpStatus`](상태 코드와 `Retry-After`)로 반환하므로
+/// 측정 도구가 실패를 분류하고 `Retry-After`를 직접 따를 수 있습니다.
+pub fn benchmark_client(url: &str) -> AsyncRpcClient {
+    let sender = BenchmarkSender {
+        client: reqwest::Client::builder()
+            .timeout(RPC_TIMEOUT)
+            .build()
+            .expect("HTTP 클라이언트 설정은 고정값"),
+        url: url.to_string(),
+        request_id: AtomicU64::new(0),
+        stats: Mutex::new(RpcTransportStats::default()),
+    };
+    AsyncRpcClient::new_sender(sender, RpcClientConfig::with_commitment(CommitmentConfig::confirmed()))
+}
+
+// 재시도하지 않는 JSON-RPC 전송
+struct BenchmarkSender {
+    client: reqwest::Client,
+    url: String,
+    request_id: AtomicU64,
+    stats: Mutex<RpcTransportStats>,
+}
+
+#[async_trait]
+impl RpcSender for BenchmarkSender {
+    async fn send(&self, request: RpcRequest, params: serde_json::Value) -> ClientResult<serde_json::Value> {
+        let started = Instant::now();
+        let id = self.request_id.fetch_add(1, Ordering::Relaxed);
+        let result = self.post(request.build_request_json(id, params).to_string()).await;
+
+        let mut stats = self.stats.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        stats.request_count += 1;
+        stats.elapsed_time += started.elapsed();
+        result
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.stats.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+impl BenchmarkSender {
+    async fn post(&self, body: String) -> ClientResult<serde_json::Value> {
+        let response = self.client.post(&self.url).header(CONTENT_TYPE, "application/json").body(body).send().await?;
+        if !response.status().is_success() {
+            return Err(HttpStatus {
+                status: response.status().as_u16(),
+                retry_after: response.headers().get(RETRY_AFTER).and_then(|value| parse_retry_after(value.to_str().ok()?)),
+            }
+            .into());
+        }
+
+        let mut json: serde_json::Value = response.json().await?;
+        if json["error"].is_object() {
+            return Err(RpcError::RpcResponseError {
+                code: json["error"]["code"].as_i64().unwrap_or_default(),
+                message: json["error"]["message"].as_str().unwrap_or_default().to_string(),
+                data: RpcResponseErrorData::Empty,
+            }
+            .into());
+        }
+        Ok(json["result"].take())
+    }
+}
+
+/// `Retry-After` 헤더 값 (초 단위 숫자 또는 HTTP 날짜)
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().unwrap_or_default())
+}
@@ -0,0 +1,28 @@
+//! 공유 tokio 런타임
+//!
+//! 전송 경로는 비동기로 구현되어 있으므로, 대화형 모드나 `serve`/`schedule`/`stake`처럼
+//! 동기로 동작하는 코드는 이 런타임에서 비동기 함수를 실행합니다.
+
+use std::future::Future;
+use std::sync::OnceLock;
+use tokio::runtime::{Builder, Runtime};
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// 프로세스 전체에서 공유하는 멀티 스레드 런타임
+pub fn get() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("transfer-bot")
+            .build()
+            .expect("tokio 런타임 생성 실패")
+    })
+}
+
+/// 비동기 작업을 완료될 때까지 실행
+///
+/// 이미 tokio 런타임 안에서 실행 중인 코드에서 호출하면 안 됩니다.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    get().block_on(future)
+}
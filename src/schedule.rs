@@ -0,0 +1,495 @@
+//! 정기/반복 전송 스케줄러 (`schedule` 하위 명령)
+
+use crate::cli::Args;
+use crate::config::{FeeStrategy, Settings};
+use crate::error::{Error, Result};
+use crate::transfer::{self, ConfirmOptions};
+use crate::{rpc, runtime, state_file};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    native_token::sol_to_lamports,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+/// 한 번에 계산할 최대 실행 시점 수 (긴 중단 후 catch-up 폭주 방지)
+///
+/// `all` 정책은 한 번에 이만큼만 실행하고 나머지는 다음 확인 때 이어서 실행합니다.
+/// 가장 늦은 실행 시점은 [`latest_occurrence`]로 상한 없이 따로 계산합니다.
+pub const MAX_OCCURRENCES: usize = 1000;
+
+/// 사용법 안내 (연결 없이 출력할 수 있도록 공개)
+pub const USAGE: &str = "사용법:
+  schedule list [옵션]    다음 실행 예정 목록 표시 (전송하지 않음)
+  schedule run [옵션]     예정된 전송 실행
+옵션:
+  --file <파일>           정기 전송 정의 파일 (기본값: schedules.toml)
+  --state <파일>          마지막 실행 기록 파일 (기본값: schedule_state.json)
+  --catch-up <정책>       중단 중 놓친 실행 처리: skip | once | all (기본값: once)
+  --grace <초>            skip 정책에서 정시 실행으로 인정할 지연 시간 (기본값: 300)
+  --count <개수>          list에서 정의별로 표시할 실행 수 (기본값: 5)
+  --tick <초>             run에서 일정 확인 간격 (기본값: 30)
+  --once                  run에서 한 번만 확인하고 종료";
+
+// 정기 전송 정의 파일 형식
+#[derive(Debug, Deserialize)]
+struct ScheduleFile {
+    #[serde(default)]
+    payment: Vec<PaymentDefinition>,
+}
+
+/// 정기 전송 하나의 정의 (정의 파일의 `[[payment]]` 항목)
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaymentDefinition {
+    id: String,
+    recipient: String,
+    amount: f64,
+    // "SOL" 또는 토큰 민트 주소
+    #[serde(default = "default_token")]
+    token: String,
+    // 초 단위를 포함한 cron 표현식 (UTC), 예: "0 0 9 * * Mon"
+    cron: Option<String>,
+    // 고정 간격, 예: "30m", "12h", "7d"
+    interval: Option<String>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    fee: Option<u64>,
+    // 정의별 catch-up 정책 (없으면 --catch-up 값 사용)
+    catch_up: Option<CatchUp>,
+}
+
+impl PaymentDefinition {
+    /// 정의 검증 (전송 금액은 0보다 큰 유한한 값이어야 함)
+    ///
+    /// 음수나 NaN은 lamports로 바꾸면 0이 되어 수수료만 내는 빈 전송이 반복되므로 정의 파일을 읽을 때 거부합니다.
+    pub fn validate(&self) -> Result<()> {
+        if !(self.amount.is_finite() && self.amount > 0.0) {
+            return Err(Error::Config(format!("[{}] 전송 금액은 0보다 커야 합니다: {}", self.id, self.amount)));
+        }
+        Ok(())
+    }
+}
+
+fn default_token() -> String {
+    "SOL".to_string()
+}
+
+/// 중단 중 놓친 실행 처리 정책
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatchUp {
+    /// 놓친 실행은 건너뛰고 정시(유예 시간 이내) 실행만 수행
+    Skip,
+    /// 놓친 실행이 여러 번이어도 한 번만 수행
+    Once,
+    /// 놓친 실행을 모두 순서대로 수행
+    All,
+}
+
+impl FromStr for CatchUp {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(CatchUp::Skip),
+            "once" => Ok(CatchUp::Once),
+            "all" => Ok(CatchUp::All),
+            other => Err(format!("알 수 없는 catch-up 정책입니다: {}", other)),
+        }
+    }
+}
+
+// 정의별 실행 기록
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PaymentState {
+    // 처음 등록된 시각 (이전 일정은 실행하지 않음)
+    registered_at: DateTime<Utc>,
+    // 마지막으로 처리한 예정 시각
+    last_run: Option<DateTime<Utc>>,
+    last_signature: Option<String>,
+    last_error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ScheduleState {
+    payments: HashMap<String, PaymentState>,
+}
+
+/// 파싱된 실행 주기
+pub enum Recurrence {
+    Cron(Box<cron::Schedule>),
+    Interval(ChronoDuration),
+}
+
+struct Options {
+    catch_up: CatchUp,
+    grace: ChronoDuration,
+    // 정의에 fee가 없을 때 사용할 수수료 전략 (설정 프로필)
+    fee: FeeStrategy,
+}
+
+/// schedule 하위 명령 실행
+/// 보내는 지갑과 기본 수수료 전략은 설정(`settings`)에서 가져온다
+pub fn run(rpc_client: &RpcClient, settings: &Settings, args: &Args) -> Result<()> {
+    let file = args.value("file").unwrap_or("schedules.toml");
+    let state_path = args.value("state").unwrap_or("schedule_state.json");
+    let options = Options {
+        catch_up: args.parse_or("catch-up", CatchUp::Once)?,
+        grace: ChronoDuration::seconds(args.parse_or("grace", 300)?),
+        fee: settings.fee.value,
+    };
+
+    match args.positional(1) {
+        Some("list") => {
+            let definitions = load_definitions(file)?;
+            let state = state_file::load(state_path)?;
+            list(&definitions, &state, &options, args.parse_or("count", 5)?)
+        }
+        Some("run") => {
+            let sender = settings.load_keypair()?;
+            let tick = Duration::from_secs(args.parse_or("tick", 30)?);
+
+            loop {
+                // 실행 중에도 정의 파일 변경을 반영
+                let definitions = load_definitions(file)?;
+                let mut state = state_file::load(state_path)?;
+                run_due(rpc_client, &sender, &definitions, &mut state, state_path, &options)?;
+
+                if args.has("once") {
+                    return Ok(());
+                }
+                thread::sleep(tick);
+            }
+        }
+        _ => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    }
+}
+
+// 다음 실행 예정 목록과 놓친 실행 처리 계획 표시
+fn list(
+    definitions: &[PaymentDefinition],
+    state: &ScheduleState,
+    options: &Options,
+    count: usize,
+) -> Result<()> {
+    let now = Utc::now();
+
+    for definition in definitions {
+        let recurrence = parse_recurrence(definition)?;
+        let payment_state = state.payments.get(&definition.id);
+        let after = payment_state.map(baseline).unwrap_or(now);
+
+        println!(
+            "[{}] {} {} -> {}",
+            definition.id, definition.amount, definition.token, definition.recipient
+        );
+        if let Some(state) = payment_state {
+            match (&state.last_run, &state.last_signature, &state.last_error) {
+                (Some(run), Some(signature), _) => println!("  마지막 실행: {} (서명 {})", run, signature),
+                (Some(run), None, Some(error)) => println!("  마지막 실행: {} (실패: {})", run, error),
+                _ => println!("  등록 시각: {}", state.registered_at),
+            }
+        }
+
+        let missed = occurrences(definition, &recurrence, after, now, MAX_OCCURRENCES);
+        if let Some(latest) = latest_occurrence(definition, &recurrence, after, now) {
+            let policy = definition.catch_up.unwrap_or(options.catch_up);
+            let planned = plan(&missed, latest, policy, now, options.grace);
+            println!(
+                "  놓친 실행 {}회 ({:?} 정책: {}회 실행 예정)",
+                missed_count(&missed),
+                policy,
+                planned.len()
+            );
+        }
+
+        let upcoming = occurrences(definition, &recurrence, now, DateTime::<Utc>::MAX_UTC, count);
+        if upcoming.is_empty() {
+            println!("  예정된 실행 없음 (종료됨)");
+        }
+        for time in upcoming {
+            println!("  다음 실행: {}", time);
+        }
+    }
+
+    Ok(())
+}
+
+// 실행 시점이 된 전송을 catch-up 정책에 따라 실행
+fn run_due(
+    rpc_client: &RpcClient,
+    sender: &Keypair,
+    definitions: &[PaymentDefinition],
+    state: &mut ScheduleState,
+    state_path: &str,
+    options: &Options,
+) -> Result<()> {
+    let now = Utc::now();
+    let client = rpc::async_client_with_commitment(&rpc_client.url(), rpc_client.commitment());
+
+    for definition in definitions {
+        let recurrence = parse_recurrence(definition)?;
+
+        // 새 정의는 등록 시점부터 일정 계산
+        let after = baseline(state.payments.entry(definition.id.clone()).or_insert_with(|| PaymentState {
+            registered_at: now,
+            last_run: None,
+            last_signature: None,
+            last_error: None,
+        }));
+
+        let Some(latest) = latest_occurrence(definition, &recurrence, after, now) else {
+            continue;
+        };
+        let due = occurrences(definition, &recurrence, after, now, MAX_OCCURRENCES);
+
+        let policy = definition.catch_up.unwrap_or(options.catch_up);
+        let planned = plan(&due, latest, policy, now, options.grace);
+        if planned.len() < due.len() {
+            println!("[{}] 놓친 실행 {}회 중 {}회만 실행합니다 ({:?} 정책)", definition.id, missed_count(&due), planned.len(), policy);
+        }
+        // all 정책은 상한까지만 실행하고 나머지는 다음 확인 때 이어서 실행
+        let processed = match policy {
+            CatchUp::All => planned.last().copied().unwrap_or(latest),
+            CatchUp::Once | CatchUp::Skip => latest,
+        };
+
+        // 전송하지 못한 실행이 있으면 이후 실행과 건너뛴 실행을 기록하지 않고 다음 확인 때 다시 시도
+        let mut completed = true;
+        for time in planned {
+            println!("[{}] {} 예정 전송 실행: {} {} -> {}", definition.id, time, definition.amount, definition.token, definition.recipient);
+
+            // 서명 전의 실패(주소, 수수료 조회, 블록해시 등)는 전송되지 않았으므로 실행 기록을 남기지 않는다
+            let transaction = match prepare(rpc_client, &client, sender, definition, &options.fee) {
+                Ok(transaction) => transaction,
+                Err(err) => {
+                    println!("  전송 준비 실패, 다음 확인 때 다시 실행합니다: {}", err);
+                    state.payments.get_mut(&definition.id).unwrap().last_error = Some(err.to_string());
+                    state_file::save(state_path, state)?;
+                    completed = false;
+                    break;
+                }
+            };
+
+            // 전송 전에 기록을 먼저 저장하여 중단되더라도 같은 실행이 두 번 전송되지 않도록 한다
+            let payment_state = state.payments.get_mut(&definition.id).unwrap();
+            let previous_run = payment_state.last_run;
+            payment_state.last_run = Some(time);
+            payment_state.last_signature = Some(transaction.signatures[0].to_string());
+            payment_state.last_error = None;
+            state_file::save(state_path, state)?;
+
+            let result = runtime::block_on(transfer::send_and_confirm(&client, &transaction, &ConfirmOptions::default()));
+
+            let payment_state = state.payments.get_mut(&definition.id).unwrap();
+            match result {
+                Ok(signature) => println!("  전송 성공! 트랜잭션 서명: {}", signature),
+                // 자금이 옮겨지지 않은 것이 확실하면 기록을 되돌려 다시 실행
+                Err(err) if err.is_safe_to_resend() => {
+                    println!("  전송 실패, 다음 확인 때 다시 실행합니다: {}", err);
+                    payment_state.last_run = previous_run;
+                    payment_state.last_error = Some(err.to_string());
+                    state_file::save(state_path, state)?;
+                    completed = false;
+                    break;
+                }
+                // 처리 여부를 알 수 없으면 중복 전송을 막기 위해 실행한 것으로 두고 서명으로 확인하도록 남긴다
+                Err(err) => {
+                    println!("  전송 결과를 확인하지 못했습니다 (서명으로 확인하세요): {}", err);
+                    payment_state.last_error = Some(err.to_string());
+                }
+            }
+            state_file::save(state_path, state)?;
+        }
+
+        // 건너뛴 실행도 처리된 것으로 기록
+        if completed {
+            state.payments.get_mut(&definition.id).unwrap().last_run = Some(processed);
+        }
+    }
+
+    state_file::save(state_path, state)?;
+    Ok(())
+}
+
+// 기존 전송 경로와 같은 명령으로 트랜잭션을 만들어 서명 (전송하지 않음)
+fn prepare(
+    rpc_client: &RpcClient,
+    client: &AsyncRpcClient,
+    sender: &Keypair,
+    definition: &PaymentDefinition,
+    default_fee: &FeeStrategy,
+) -> Result<Transaction> {
+    let recipient = Pubkey::from_str(&definition.recipient)
+        .map_err(|_| Error::Config(format!("[{}] 유효하지 않은 솔라나 주소입니다: {}", definition.id, definition.recipient)))?;
+    let fee = match definition.fee {
+        Some(fee) => fee,
+        None => default_fee.resolve(rpc_client)?,
+    };
+
+    let instructions = if definition.token.eq_ignore_ascii_case("SOL") {
+        vec![system_instruction::transfer(&sender.pubkey(), &recipient, sol_to_lamports(definition.amount))]
+    } else {
+        let mint = Pubkey::from_str(&definition.token)
+            .map_err(|_| Error::Config(format!("[{}] 유효하지 않은 토큰 민트 주소입니다: {}", definition.id, definition.token)))?;
+        runtime::block_on(transfer::token_transfer_instructions(client, sender, &mint, &recipient, definition.amount))?
+    };
+    runtime::block_on(transfer::build_transaction(client, sender, &[], instructions, fee))
+}
+
+/// catch-up 정책에 따라 실제로 실행할 시점 선택
+///
+/// `due`는 [`occurrences`]로 구한 (상한이 있는) 놓친 실행 목록, `latest`는 [`latest_occurrence`]로 구한
+/// 가장 늦은 실행 시점입니다. `once`/`skip`은 목록이 상한에서 잘려도 `latest`만 봅니다.
+pub fn plan(
+    due: &[DateTime<Utc>],
+    latest: DateTime<Utc>,
+    policy: CatchUp,
+    now: DateTime<Utc>,
+    grace: ChronoDuration,
+) -> Vec<DateTime<Utc>> {
+    match policy {
+        CatchUp::All => due.to_vec(),
+        CatchUp::Once => vec![latest],
+        CatchUp::Skip if now - latest <= grace => vec![latest],
+        CatchUp::Skip => vec![],
+    }
+}
+
+// 놓친 실행 수 표시 (상한에 닿았으면 "1000 이상")
+fn missed_count(due: &[DateTime<Utc>]) -> String {
+    if due.len() >= MAX_OCCURRENCES {
+        format!("{} 이상", due.len())
+    } else {
+        due.len().to_string()
+    }
+}
+
+// 이 시각 이후의 실행만 대상 (마지막 실행 또는 등록 시각)
+fn baseline(state: &PaymentState) -> DateTime<Utc> {
+    state.last_run.unwrap_or(state.registered_at)
+}
+
+// 시작/종료일을 반영한 계산 구간
+fn window(definition: &PaymentDefinition, after: DateTime<Utc>, until: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let after = match definition.start {
+        // 시작일 당일 실행을 포함하기 위해 1초 앞에서 계산
+        Some(start) if start > after => start - ChronoDuration::seconds(1),
+        _ => after,
+    };
+    let until = match definition.end {
+        Some(end) if end < until => end,
+        _ => until,
+    };
+    (after, until)
+}
+
+/// (after, until] 구간의 실행 시점 (시작/종료일 반영, 앞에서부터 최대 `limit`개)
+pub fn occurrences(
+    definition: &PaymentDefinition,
+    recurrence: &Recurrence,
+    after: DateTime<Utc>,
+    until: DateTime<Utc>,
+    limit: usize,
+) -> Vec<DateTime<Utc>> {
+    let (after, until) = window(definition, after, until);
+
+    let times: Box<dyn Iterator<Item = DateTime<Utc>>> = match recurrence {
+        Recurrence::Cron(schedule) => Box::new(schedule.after(&after)),
+        Recurrence::Interval(interval) => {
+            // 간격 일정은 시작일(없으면 등록 시각 기준인 after)을 기준점으로 한다
+            let anchor = definition.start.unwrap_or(after);
+            let interval = *interval;
+            let skipped = if after > anchor {
+                ((after - anchor).num_seconds() / interval.num_seconds()) as i32
+            } else {
+                0
+            };
+            Box::new(
+                (skipped..)
+                    .map(move |n| anchor + interval * n)
+                    .filter(move |time| *time > after),
+            )
+        }
+    };
+
+    times.take_while(|time| *time <= until).take(limit).collect()
+}
+
+/// (after, until] 구간의 가장 늦은 실행 시점 (구간의 실행 수와 관계없이 계산)
+pub fn latest_occurrence(
+    definition: &PaymentDefinition,
+    recurrence: &Recurrence,
+    after: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let (after, until) = window(definition, after, until);
+
+    let latest = match recurrence {
+        // until 자체도 포함하도록 1초 뒤에서 거꾸로 찾는다
+        Recurrence::Cron(schedule) => schedule.after(&(until + ChronoDuration::seconds(1))).next_back(),
+        Recurrence::Interval(interval) => {
+            let anchor = definition.start.unwrap_or(after);
+            if until < anchor {
+                None
+            } else {
+                let n = (until - anchor).num_seconds() / interval.num_seconds();
+                Some(anchor + ChronoDuration::seconds(n * interval.num_seconds()))
+            }
+        }
+    };
+    latest.filter(|time| *time > after)
+}
+
+/// 정의의 cron 또는 interval 파싱
+pub fn parse_recurrence(definition: &PaymentDefinition) -> Result<Recurrence> {
+    match (&definition.cron, &definition.interval) {
+        (Some(expression), None) => {
+            let schedule = cron::Schedule::from_str(expression)
+                .map_err(|e| Error::Config(format!("[{}] cron 표현식 오류: {}", definition.id, e)))?;
+            Ok(Recurrence::Cron(Box::new(schedule)))
+        }
+        (None, Some(interval)) => Ok(Recurrence::Interval(parse_interval(interval)
+            .ok_or_else(|| Error::Config(format!("[{}] 간격 형식 오류: {} (예: 30m, 12h, 7d)", definition.id, interval)))?)),
+        _ => Err(Error::Config(format!("[{}] cron 또는 interval 중 하나만 지정해야 합니다", definition.id))),
+    }
+}
+
+/// "90s", "30m", "12h", "7d" 형식의 간격 파싱 (0 이하이거나 단위가 없으면 `None`)
+pub fn parse_interval(input: &str) -> Option<ChronoDuration> {
+    let input = input.trim();
+    let (number, unit) = input.split_at(input.len().checked_sub(1)?);
+    let number: i64 = number.parse().ok().filter(|n| *n > 0)?;
+
+    match unit {
+        "s" => Some(ChronoDuration::seconds(number)),
+        "m" => Some(ChronoDuration::minutes(number)),
+        "h" => Some(ChronoDuration::hours(number)),
+        "d" => Some(ChronoDuration::days(number)),
+        _ => None,
+    }
+}
+
+fn load_definitions(path: &str) -> Result<Vec<PaymentDefinition>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| Error::Config(format!("정의 파일을 읽을 수 없습니다 ({}): {}", path, e)))?;
+    let file: ScheduleFile = toml::from_str(&content)
+        .map_err(|e| Error::Config(format!("정의 파일 형식 오류 ({}): {}", path, e)))?;
+    for definition in &file.payment {
+        definition.validate()?;
+    }
+    Ok(file.payment)
+}
@@ -0,0 +1,309 @@
+//! 스테이크 계정 관리 (`stake` 하위 명령)
+
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::transfer::blocking::send_instructions;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    clock::Epoch,
+    feature,
+    feature_set,
+    native_token::{lamports_to_sol, sol_to_lamports},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    stake::{
+        self,
+        instruction as stake_instruction,
+        state::{Authorized, Lockup, StakeActivationStatus, StakeStateV2},
+    },
+    system_instruction,
+    sysvar::{self, stake_history::StakeHistory},
+};
+use std::str::FromStr;
+
+/// 사용법 안내 (연결 없이 출력할 수 있도록 공개)
+pub const USAGE: &str = "사용법:
+  stake create <SOL>                     새 스테이크 계정 생성
+  stake delegate <스테이크 계정> <투표 계정>   검증인에게 위임
+  stake deactivate <스테이크 계정>         위임 해제
+  stake split <스테이크 계정> <SOL>         일부 금액을 새 스테이크 계정으로 분할
+  stake merge <대상 계정> <원본 계정>        두 스테이크 계정 병합
+  stake withdraw <스테이크 계정> <SOL|all> [--to <주소>]  출금
+  stake status <스테이크 계정>             상태 및 에포크 정보 표시
+공통 옵션: --fee <lamports/compute unit 또는 recent[:백분위]> (기본값: 프로필 설정)";
+
+/// 스테이크 하위 명령
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StakeCommand {
+    Create { lamports: u64 },
+    Delegate { stake: Pubkey, vote: Pubkey },
+    Deactivate { stake: Pubkey },
+    Split { stake: Pubkey, lamports: u64 },
+    Merge { destination: Pubkey, source: Pubkey },
+    /// `lamports`가 `None`이면 전액, `to`가 `None`이면 보내는 지갑으로
+    Withdraw { stake: Pubkey, lamports: Option<u64>, to: Option<Pubkey> },
+    Status { stake: Pubkey },
+    /// 알 수 없는 하위 명령 (사용법 출력)
+    Usage,
+}
+
+impl StakeCommand {
+    /// `stake <하위 명령> ...` 인자 파싱 (위치 인자 0은 `stake`)
+    pub fn from_args(args: &Args) -> Result<StakeCommand> {
+        let pubkey = |index: usize, name: &str| args.require_positional(index, name).and_then(parse_pubkey);
+        let command = match args.positional(1) {
+            Some("create") => StakeCommand::Create {
+                lamports: parse_sol(args.require_positional(2, "SOL 양")?)?,
+            },
+            Some("delegate") => StakeCommand::Delegate {
+                stake: pubkey(2, "스테이크 계정")?,
+                vote: pubkey(3, "투표 계정")?,
+            },
+            Some("deactivate") => StakeCommand::Deactivate {
+                stake: pubkey(2, "스테이크 계정")?,
+            },
+            Some("split") => StakeCommand::Split {
+                stake: pubkey(2, "스테이크 계정")?,
+                lamports: parse_sol(args.require_positional(3, "SOL 양")?)?,
+            },
+            Some("merge") => StakeCommand::Merge {
+                destination: pubkey(2, "대상 계정")?,
+                source: pubkey(3, "원본 계정")?,
+            },
+            Some("withdraw") => {
+                let amount = args.require_positional(3, "SOL 양")?;
+                StakeCommand::Withdraw {
+                    stake: pubkey(2, "스테이크 계정")?,
+                    lamports: if amount.eq_ignore_ascii_case("all") { None } else { Some(parse_sol(amount)?) },
+                    to: args.value("to").map(parse_pubkey).transpose()?,
+                }
+            }
+            Some("status") => StakeCommand::Status {
+                stake: pubkey(2, "스테이크 계정")?,
+            },
+            _ => StakeCommand::Usage,
+        };
+        Ok(command)
+    }
+}
+
+/// 스테이크 하위 명령 실행
+/// 보내는 지갑이 스테이크/출금 권한자로 사용된다
+/// `fee`는 설정(`--fee`, 프로필)에서 결정된 우선순위 수수료
+pub fn run(rpc_client: &RpcClient, sender: &Keypair, fee: u64, args: &Args) -> Result<()> {
+    match StakeCommand::from_args(args)? {
+        StakeCommand::Create { lamports } => {
+            let (stake_pubkey, signature) = create(rpc_client, sender, lamports, fee)?;
+            println!("스테이크 계정 생성 완료: {}", stake_pubkey);
+            println!("트랜잭션 서명: {}", signature);
+        }
+        StakeCommand::Delegate { stake, vote } => {
+            let instruction = stake_instruction::delegate_stake(&stake, &sender.pubkey(), &vote);
+            let signature = send_instructions(rpc_client, sender, &[], vec![instruction], fee)?;
+            println!("위임 완료! 트랜잭션 서명: {}", signature);
+        }
+        StakeCommand::Deactivate { stake } => {
+            let instruction = stake_instruction::deactivate_stake(&stake, &sender.pubkey());
+            let signature = send_instructions(rpc_client, sender, &[], vec![instruction], fee)?;
+            println!("위임 해제 요청 완료! 트랜잭션 서명: {}", signature);
+        }
+        StakeCommand::Split { stake, lamports } => {
+            let (split_pubkey, signature) = split(rpc_client, sender, &stake, lamports, fee)?;
+            println!("분할 완료! 새 스테이크 계정: {}", split_pubkey);
+            println!("트랜잭션 서명: {}", signature);
+        }
+        StakeCommand::Merge { destination, source } => {
+            let instructions = stake_instruction::merge(&destination, &source, &sender.pubkey());
+            let signature = send_instructions(rpc_client, sender, &[], instructions, fee)?;
+            println!("병합 완료! 트랜잭션 서명: {}", signature);
+        }
+        StakeCommand::Withdraw { stake, lamports, to } => {
+            let to = to.unwrap_or_else(|| sender.pubkey());
+            let (lamports, signature) = withdraw(rpc_client, sender, &stake, lamports, &to, fee)?;
+            println!("{} SOL 출금 완료 ({} 으로)", lamports_to_sol(lamports), to);
+            println!("트랜잭션 서명: {}", signature);
+        }
+        StakeCommand::Status { stake } => status(rpc_client, &stake)?,
+        StakeCommand::Usage => println!("{}", USAGE),
+    }
+    Ok(())
+}
+
+/// 새 스테이크 계정 생성 (보내는 지갑에서 자금 충당, 권한자는 보내는 지갑)
+///
+/// 새 스테이크 계정 주소와 트랜잭션 서명을 반환합니다.
+pub fn create(rpc_client: &RpcClient, sender: &Keypair, lamports: u64, fee: u64) -> Result<(Pubkey, Signature)> {
+    let rent = rpc_client.get_minimum_balance_for_rent_exemption(StakeStateV2::size_of())?;
+    if lamports <= rent {
+        return Err(Error::Input(format!(
+            "스테이크 계정에는 렌트 면제 최소 금액({} SOL)보다 많은 SOL이 필요합니다",
+            lamports_to_sol(rent)
+        )));
+    }
+
+    let stake_keypair = Keypair::new();
+    let authorized = Authorized::auto(&sender.pubkey());
+    let instructions = stake_instruction::create_account(
+        &sender.pubkey(),
+        &stake_keypair.pubkey(),
+        &authorized,
+        &Lockup::default(),
+        lamports,
+    );
+
+    let signature = send_instructions(rpc_client, sender, &[&stake_keypair], instructions, fee)?;
+    Ok((stake_keypair.pubkey(), signature))
+}
+
+/// 스테이크 계정의 일부를 새 계정으로 분할하고 새 계정 주소와 트랜잭션 서명 반환
+pub fn split(
+    rpc_client: &RpcClient,
+    sender: &Keypair,
+    stake_pubkey: &Pubkey,
+    lamports: u64,
+    fee: u64,
+) -> Result<(Pubkey, Signature)> {
+    let split_keypair = Keypair::new();
+
+    // 분할 대상 계정은 렌트 면제 금액이 미리 들어 있어야 한다
+    let rent = rpc_client.get_minimum_balance_for_rent_exemption(StakeStateV2::size_of())?;
+    let mut instructions = vec![system_instruction::transfer(&sender.pubkey(), &split_keypair.pubkey(), rent)];
+    instructions.extend(stake_instruction::split(
+        stake_pubkey,
+        &sender.pubkey(),
+        lamports,
+        &split_keypair.pubkey(),
+    ));
+
+    let signature = send_instructions(rpc_client, sender, &[&split_keypair], instructions, fee)?;
+    Ok((split_keypair.pubkey(), signature))
+}
+
+/// 스테이크 계정에서 `to`로 출금 (`lamports`가 `None`이면 전액)하고 출금한 lamports와 트랜잭션 서명 반환
+pub fn withdraw(
+    rpc_client: &RpcClient,
+    sender: &Keypair,
+    stake_pubkey: &Pubkey,
+    lamports: Option<u64>,
+    to: &Pubkey,
+    fee: u64,
+) -> Result<(u64, Signature)> {
+    let lamports = match lamports {
+        Some(lamports) => lamports,
+        None => rpc_client.get_balance(stake_pubkey)?,
+    };
+
+    let instruction = stake_instruction::withdraw(stake_pubkey, &sender.pubkey(), to, lamports, None);
+    let signature = send_instructions(rpc_client, sender, &[], vec![instruction], fee)?;
+    Ok((lamports, signature))
+}
+
+// 스테이크 계정 상태 및 에포크 정보 표시
+fn status(rpc_client: &RpcClient, stake_pubkey: &Pubkey) -> Result<()> {
+    let account = rpc_client.get_account(stake_pubkey)?;
+    if account.owner != stake::program::id() {
+        return Err(Error::Input(format!("{}는 스테이크 계정이 아닙니다", stake_pubkey)));
+    }
+
+    let state: StakeStateV2 = account
+        .deserialize_data()
+        .map_err(|e| Error::Input(format!("스테이크 계정 데이터 오류: {}", e)))?;
+    let epoch_info = rpc_client.get_epoch_info()?;
+
+    println!("스테이크 계정: {}", stake_pubkey);
+    println!("잔액: {} SOL", lamports_to_sol(account.lamports));
+
+    match state {
+        StakeStateV2::Uninitialized => println!("상태: 초기화되지 않음"),
+        StakeStateV2::RewardsPool => println!("상태: 보상 풀"),
+        StakeStateV2::Initialized(meta) => {
+            println!("상태: 초기화됨 (위임 없음)");
+            println!("스테이크 권한자: {}", meta.authorized.staker);
+            println!("출금 권한자: {}", meta.authorized.withdrawer);
+        }
+        StakeStateV2::Stake(meta, stake, _) => {
+            let delegation = stake.delegation;
+            let stake_history = get_stake_history(rpc_client)?;
+            let new_rate_activation_epoch = get_new_rate_activation_epoch(rpc_client)?;
+            let activation = delegation.stake_activating_and_deactivating(
+                epoch_info.epoch,
+                &stake_history,
+                new_rate_activation_epoch,
+            );
+
+            println!("상태: {}", activation_label(&activation, delegation.deactivation_epoch, epoch_info.epoch));
+            println!("스테이크 권한자: {}", meta.authorized.staker);
+            println!("출금 권한자: {}", meta.authorized.withdrawer);
+            println!("위임 검증인 투표 계정: {}", delegation.voter_pubkey);
+            println!("위임량: {} SOL", lamports_to_sol(delegation.stake));
+            println!("  활성: {} SOL", lamports_to_sol(activation.effective));
+            println!("  활성화 중: {} SOL", lamports_to_sol(activation.activating));
+            println!("  비활성화 중: {} SOL", lamports_to_sol(activation.deactivating));
+            println!("활성화 에포크: {}", delegation.activation_epoch);
+            if delegation.deactivation_epoch != Epoch::MAX {
+                println!("비활성화 에포크: {}", delegation.deactivation_epoch);
+            }
+        }
+    }
+
+    let progress = epoch_info.slot_index as f64 / epoch_info.slots_in_epoch as f64 * 100.0;
+    println!(
+        "현재 에포크: {} (슬롯 {}/{}, {:.1}% 진행)",
+        epoch_info.epoch, epoch_info.slot_index, epoch_info.slots_in_epoch, progress
+    );
+    Ok(())
+}
+
+/// 활성화 상태를 사람이 읽을 수 있는 문자열로 변환
+pub fn activation_label(activation: &StakeActivationStatus, deactivation_epoch: Epoch, current_epoch: Epoch) -> &'static str {
+    if activation.deactivating > 0 {
+        "비활성화 중 (deactivating)"
+    } else if activation.activating > 0 {
+        "활성화 중 (activating)"
+    } else if activation.effective > 0 {
+        "활성 (active)"
+    } else if deactivation_epoch <= current_epoch {
+        "비활성 (inactive)"
+    } else {
+        "활성화 대기 (activating)"
+    }
+}
+
+// StakeHistory sysvar 조회
+fn get_stake_history(rpc_client: &RpcClient) -> Result<StakeHistory> {
+    let account = rpc_client.get_account(&sysvar::stake_history::id())?;
+    account
+        .deserialize_data()
+        .map_err(|e| Error::Rpc { code: None, message: format!("StakeHistory 데이터 오류: {}", e) })
+}
+
+// 스테이크 워밍업/쿨다운 속도 변경 기능이 활성화된 에포크 조회
+fn get_new_rate_activation_epoch(rpc_client: &RpcClient) -> Result<Option<Epoch>> {
+    let feature_id = feature_set::reduce_stake_warmup_cooldown::id();
+    let account: Option<Account> = rpc_client.get_multiple_accounts(&[feature_id])?.pop().flatten();
+
+    let activated_slot = account
+        .as_ref()
+        .and_then(feature::from_account)
+        .and_then(|feature| feature.activated_at);
+
+    match activated_slot {
+        Some(slot) => Ok(Some(rpc_client.get_epoch_schedule()?.get_epoch(slot))),
+        None => Ok(None),
+    }
+}
+
+fn parse_pubkey(input: &str) -> Result<Pubkey> {
+    Pubkey::from_str(input).map_err(|_| Error::Input(format!("유효하지 않은 솔라나 주소입니다: {}", input)))
+}
+
+fn parse_sol(input: &str) -> Result<u64> {
+    // 음수, NaN, 무한대는 sol_to_lamports에서 0이나 u64::MAX가 되므로 미리 거부
+    let amount = input
+        .parse::<f64>()
+        .ok()
+        .filter(|amount| amount.is_finite() && *amount > 0.0)
+        .ok_or_else(|| Error::Input(format!("유효한 SOL 양이 아닙니다: {}", input)))?;
+    Ok(sol_to_lamports(amount))
+}
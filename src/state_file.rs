@@ -0,0 +1,27 @@
+//! 실행 사이에 유지하는 JSON 상태 파일
+//!
+//! `watch`의 마지막 서명, `schedule`의 마지막 실행 시각, `history`의 커서처럼
+//! 재시작 후에도 이어서 진행하기 위한 상태를 읽고 씁니다.
+
+use crate::error::Result;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs::{self, File};
+use std::path::Path;
+
+/// 상태 파일 읽기 (파일이 없으면 기본값)
+pub fn load<T: DeserializeOwned + Default>(path: &str) -> Result<T> {
+    if !Path::new(path).exists() {
+        return Ok(T::default());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// 상태 파일 쓰기
+///
+/// 임시 파일에 쓴 뒤 교체하므로 쓰는 도중에 중단되어도 이전 상태가 깨지지 않습니다.
+pub fn save<T: Serialize>(path: &str, state: &T) -> Result<()> {
+    let tmp_path = format!("{}.tmp", path);
+    serde_json::to_writer_pretty(File::create(&tmp_path)?, state)?;
+    fs::rename(tmp_path, path)?;
+    Ok(())
+}
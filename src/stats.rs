@@ -0,0 +1,171 @@
+//! 지연 시간 통계
+//!
+//! 측정값은 [`LatencyHistogram`](HDR 히스토그램, 마이크로초 단위, 유효숫자 3자리)에 기록하므로
+//! 오래 실행해도 메모리 사용량이 측정 수와 무관하게 일정합니다. 최소/최대/평균/표준편차는 정확한
+//! 값이고, 백분위는 히스토그램에서 구한 근사값입니다 (상대 오차 0.1% 이내).
+
+use hdrhistogram::Histogram;
+use std::time::Duration;
+
+// 기록할 수 있는 최대 지연 시간 (더 큰 값은 이 값으로 기록)
+const MAX_TRACKABLE: Duration = Duration::from_secs(3600);
+
+// 백분위 유효숫자
+const SIGNIFICANT_DIGITS: u8 = 3;
+
+/// 측정값 목록의 최소/평균/최대, 백분위, 표준편차
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    /// 표준편차 (모집단)
+    pub stddev: Duration,
+}
+
+impl LatencySummary {
+    /// 측정값으로 통계 계산 (측정값이 없으면 `None`)
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut histogram = LatencyHistogram::new();
+        for sample in samples {
+            histogram.record(*sample);
+        }
+        histogram.summary()
+    }
+}
+
+/// 메모리 사용량이 일정한 지연 시간 히스토그램
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    histogram: Histogram<u64>,
+    min: Duration,
+    max: Duration,
+    sum: Duration,
+    // 마이크로초 제곱의 합 (표준편차 계산용)
+    sum_squares: f64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        LatencyHistogram::new()
+    }
+}
+
+impl LatencyHistogram {
+    pub fn new() -> LatencyHistogram {
+        LatencyHistogram {
+            histogram: Histogram::new_with_bounds(1, MAX_TRACKABLE.as_micros() as u64, SIGNIFICANT_DIGITS)
+                .expect("히스토그램 범위는 고정값"),
+            min: Duration::MAX,
+            max: Duration::ZERO,
+            sum: Duration::ZERO,
+            sum_squares: 0.0,
+        }
+    }
+
+    /// 측정값 하나 기록
+    pub fn record(&mut self, latency: Duration) {
+        let latency = latency.min(MAX_TRACKABLE);
+        self.histogram.saturating_record((latency.as_micros() as u64).max(1));
+        self.min = self.min.min(latency);
+        self.max = self.max.max(latency);
+        self.sum += latency;
+        let micros = latency.as_secs_f64() * 1_000_000.0;
+        self.sum_squares += micros * micros;
+    }
+
+    /// 다른 히스토그램의 측정값을 모두 합침
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        if other.is_empty() {
+            return;
+        }
+        self.histogram.add(&other.histogram).expect("같은 범위의 히스토그램");
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+        self.sum_squares += other.sum_squares;
+    }
+
+    /// 기록된 측정 수
+    pub fn len(&self) -> usize {
+        self.histogram.len() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.histogram.is_empty()
+    }
+
+    /// 백분위 (0~100, 기록이 없으면 0)
+    pub fn percentile(&self, percent: f64) -> Duration {
+        if self.is_empty() {
+            return Duration::ZERO;
+        }
+        let value = self.histogram.median_equivalent(self.histogram.value_at_quantile(percent / 100.0));
+        Duration::from_micros(value).clamp(self.min, self.max)
+    }
+
+    /// 통계 요약 (기록이 없으면 `None`)
+    pub fn summary(&self) -> Option<LatencySummary> {
+        if self.is_empty() {
+            return None;
+        }
+        let count = self.len();
+        let mean = self.sum.as_secs_f64() * 1_000_000.0 / count as f64;
+        let variance = (self.sum_squares / count as f64 - mean * mean).max(0.0);
+
+        Some(LatencySummary {
+            count,
+            min: self.min,
+            avg: self.sum / count as u32,
+            max: self.max,
+            p50: self.percentile(50.0),
+            p90: self.percentile(90.0),
+            p95: self.percentile(95.0),
+            p99: self.percentile(99.0),
+            stddev: Duration::from_secs_f64(variance.sqrt() / 1_000_000.0),
+        })
+    }
+
+    /// 2배씩 커지는 구간별 분포를 막대 그래프 줄로 (가장 많은 구간의 막대 길이가 `width`)
+    pub fn text_histogram(&self, width: usize) -> Vec<String> {
+        // 구간 k = [2^k, 2^(k+1)) 마이크로초
+        let mut buckets: Vec<u64> = vec![];
+        for value in self.histogram.iter_recorded() {
+            let bucket = value.value_iterated_to().max(1).ilog2() as usize;
+            if buckets.len() <= bucket {
+                buckets.resize(bucket + 1, 0);
+            }
+            buckets[bucket] += value.count_at_value();
+        }
+        let first = match buckets.iter().position(|&count| count > 0) {
+            Some(first) => first,
+            None => return vec![],
+        };
+        let peak = buckets.iter().copied().max().unwrap_or(1);
+
+        buckets[first..]
+            .iter()
+            .enumerate()
+            .map(|(offset, &count)| {
+                let low = Duration::from_micros(1 << (first + offset));
+                let high = Duration::from_micros(1 << (first + offset + 1));
+                let bar = "█".repeat((count as usize * width).div_ceil(peak as usize));
+                format!("{:>10} ~ {:<10} | {} {}", format!("{:?}", low), format!("{:?}", high), bar, count)
+            })
+            .collect()
+    }
+}
+
+/// 지터: 연속한 두 측정값 차이의 평균 (측정 순서대로 주어야 하며, 2개 미만이면 `None`)
+pub fn jitter(samples: &[Duration]) -> Option<Duration> {
+    if samples.len() < 2 {
+        return None;
+    }
+    let total: Duration = samples.windows(2).map(|pair| pair[0].abs_diff(pair[1])).sum();
+    Some(total / (samples.len() - 1) as u32)
+}
@@ -0,0 +1,430 @@
+//! 웹소켓 구독 알림 지연 비교 (`rpc_benchmark --pubsub`)
+//!
+//! 엔드포인트마다 웹소켓 URL로 `slotSubscribe`, `accountSubscribe`, `signatureSubscribe`를 열고,
+//! 같은 이벤트(슬롯, 계정이 바뀐 슬롯, 서명)가 가장 먼저 도착한 엔드포인트를 기준으로 다른 엔드포인트에
+//! 도착하기까지 걸린 시간을 잽니다. 가장 먼저 받은 엔드포인트의 지연은 0입니다.
+//!
+//! 측정 시간(`--duration`) 안에 처음 관찰된 이벤트만 비교하고, 늦게 오는 알림을 받기 위해
+//! [`SubscriptionOptions::grace`]만큼 더 기다립니다. 다른 엔드포인트에는 왔지만 끝내 오지 않은 알림은
+//! 놓친 알림으로, 구독 실패와 연결 끊김은 끊긴 구독으로 집계하며 끊긴 구독은 [`RECONNECT_DELAY`] 뒤
+//! 다시 엽니다.
+//!
+//! `signatureSubscribe`는 테스트 지갑에서 자기 자신에게 소액을 보내며 측정합니다. 각 전송에는
+//! `tx_speed_test`와 같은 `rpc-bench:<실행 ID>:<순번>` 메모를 붙여 탐색기에서 찾을 수 있습니다. 모든 엔드포인트가
+//! 구독을 연 뒤에 트랜잭션을 전송하므로 어느 엔드포인트도 알림을 미리 놓치지 않습니다.
+
+use crate::backend::RpcBackend;
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::landing::TestRun;
+use crate::stats::LatencyHistogram;
+use crate::transfer;
+use futures_util::{Stream, StreamExt};
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcSignatureSubscribeConfig};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    sysvar,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
+
+/// 기본 측정 시간
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(30);
+
+/// 측정 시간이 끝난 뒤 늦은 알림을 기다리는 기본 시간
+pub const DEFAULT_GRACE: Duration = Duration::from_secs(3);
+
+/// 기본 `signatureSubscribe` 측정 횟수 (테스트 지갑이 있을 때)
+pub const DEFAULT_SIGNATURES: usize = 3;
+
+/// 기본 `accountSubscribe` 대상 (매 슬롯 바뀌는 Clock sysvar)
+pub const DEFAULT_ACCOUNT: Pubkey = sysvar::clock::ID;
+
+/// 끊긴 구독을 다시 열기 전 대기 시간
+pub const RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+/// 서명 알림을 기다리는 최대 시간
+pub const SIGNATURE_TIMEOUT: Duration = Duration::from_secs(30);
+
+// 테스트 전송 금액 (lamports, 자기 자신에게 보내므로 수수료만 듦)
+const TEST_LAMPORTS: u64 = 1000;
+
+// 테스트 전송의 우선순위 수수료
+const TEST_PRIORITY_FEE: u64 = 5;
+
+// 가장 먼저 관찰할 수 있는 시점을 비교하기 위해 processed 커미트먼트로 구독
+const COMMITMENT: CommitmentConfig = CommitmentConfig::processed();
+
+/// 측정하는 구독 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Subscription {
+    Slot,
+    Account,
+    Signature,
+}
+
+impl Subscription {
+    pub const ALL: [Subscription; 3] = [Subscription::Slot, Subscription::Account, Subscription::Signature];
+
+    /// 웹소켓 메서드 이름
+    pub fn method(&self) -> &'static str {
+        match self {
+            Subscription::Slot => "slotSubscribe",
+            Subscription::Account => "accountSubscribe",
+            Subscription::Signature => "signatureSubscribe",
+        }
+    }
+}
+
+/// 구독 비교 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubscriptionOptions {
+    /// 이벤트를 비교하는 시간
+    pub duration: Duration,
+    /// 측정 시간이 끝난 뒤 늦은 알림을 기다리는 시간
+    pub grace: Duration,
+    /// `accountSubscribe` 대상 계정
+    pub account: Pubkey,
+    /// `signatureSubscribe`로 측정할 전송 수 (0이면 건너뜀)
+    pub signatures: usize,
+}
+
+impl Default for SubscriptionOptions {
+    fn default() -> Self {
+        SubscriptionOptions {
+            duration: DEFAULT_DURATION,
+            grace: DEFAULT_GRACE,
+            account: DEFAULT_ACCOUNT,
+            signatures: DEFAULT_SIGNATURES,
+        }
+    }
+}
+
+impl SubscriptionOptions {
+    /// 명령줄 옵션 (`--duration` 초, `--account`, `--signatures`)
+    pub fn from_args(args: &Args) -> Result<SubscriptionOptions> {
+        let duration: f64 = args.parse_or("duration", DEFAULT_DURATION.as_secs_f64())?;
+        if !duration.is_finite() || duration <= 0.0 {
+            return Err(Error::Input("--duration은 0보다 커야 합니다".to_string()));
+        }
+        let account = match args.value("account") {
+            Some(account) => Pubkey::from_str(account).map_err(|_| Error::Input(format!("계정 주소 오류: {}", account)))?,
+            None => DEFAULT_ACCOUNT,
+        };
+        Ok(SubscriptionOptions {
+            duration: Duration::from_secs_f64(duration),
+            account,
+            signatures: args.parse_or("signatures", DEFAULT_SIGNATURES)?,
+            ..SubscriptionOptions::default()
+        })
+    }
+}
+
+/// 엔드포인트 하나의 구독 하나에 대한 결과
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionStats {
+    /// 가장 먼저 받은 엔드포인트보다 늦게 받은 시간 (받은 알림마다)
+    pub delay: LatencyHistogram,
+    /// 가장 먼저 받은 이벤트 수 (동시에 받으면 모두)
+    pub fastest: usize,
+    /// 다른 엔드포인트에는 왔지만 받지 못한 알림 수
+    pub missed: usize,
+    /// 구독 실패와 연결 끊김 횟수
+    pub dropped: usize,
+}
+
+impl SubscriptionStats {
+    /// 받은 알림 수
+    pub fn notifications(&self) -> usize {
+        self.delay.len()
+    }
+}
+
+/// 모든 엔드포인트의 구독 비교 결과
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionReport {
+    /// 구독별로 비교한 이벤트 수
+    pub events: BTreeMap<Subscription, usize>,
+    /// 엔드포인트별 (설정한 순서) 구독별 결과
+    pub endpoints: Vec<BTreeMap<Subscription, SubscriptionStats>>,
+}
+
+impl SubscriptionReport {
+    /// 한 번이라도 이벤트나 끊김이 있었던 구독 (측정 순서)
+    pub fn subscriptions(&self) -> Vec<Subscription> {
+        Subscription::ALL
+            .into_iter()
+            .filter(|subscription| self.endpoints.iter().any(|stats| stats.contains_key(subscription)))
+            .collect()
+    }
+}
+
+/// 엔드포인트별 알림 도착 시각 기록
+///
+/// 시각은 측정 시작부터의 시간이며, 같은 이벤트가 한 엔드포인트에 여러 번 오면 처음 것만 씁니다.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationLog {
+    endpoints: usize,
+    arrivals: HashMap<(Subscription, u64), Vec<Option<Duration>>>,
+    dropped: HashMap<(usize, Subscription), usize>,
+}
+
+impl NotificationLog {
+    pub fn new(endpoints: usize) -> NotificationLog {
+        NotificationLog {
+            endpoints,
+            ..NotificationLog::default()
+        }
+    }
+
+    /// 알림 도착 (`key`는 슬롯, 계정이 바뀐 슬롯, 서명 순번처럼 엔드포인트끼리 같은 이벤트를 가리키는 값)
+    pub fn record(&mut self, endpoint: usize, subscription: Subscription, key: u64, at: Duration) {
+        let endpoints = self.endpoints;
+        let arrival = &mut self.arrivals.entry((subscription, key)).or_insert_with(|| vec![None; endpoints])[endpoint];
+        if arrival.is_none() {
+            *arrival = Some(at);
+        }
+    }
+
+    /// 구독 실패나 연결 끊김
+    pub fn record_drop(&mut self, endpoint: usize, subscription: Subscription) {
+        *self.dropped.entry((endpoint, subscription)).or_default() += 1;
+    }
+
+    /// 처음 관찰된 시각이 `window` 안인 이벤트로 엔드포인트별 지연 집계
+    pub fn report(&self, window: Duration) -> SubscriptionReport {
+        let mut report = SubscriptionReport {
+            endpoints: vec![BTreeMap::new(); self.endpoints],
+            ..SubscriptionReport::default()
+        };
+        for ((subscription, _), arrivals) in &self.arrivals {
+            let Some(first) = arrivals.iter().flatten().min().copied().filter(|first| *first < window) else {
+                continue;
+            };
+            *report.events.entry(*subscription).or_default() += 1;
+            for (endpoint, arrival) in arrivals.iter().enumerate() {
+                let stats = report.endpoints[endpoint].entry(*subscription).or_default();
+                match arrival {
+                    Some(at) => {
+                        stats.delay.record(*at - first);
+                        if *at == first {
+                            stats.fastest += 1;
+                        }
+                    }
+                    None => stats.missed += 1,
+                }
+            }
+        }
+        for ((endpoint, subscription), count) in &self.dropped {
+            report.endpoints[*endpoint].entry(*subscription).or_default().dropped += count;
+        }
+        report
+    }
+}
+
+/// `signatureSubscribe` 측정용 테스트 전송 (지갑에서 자기 자신에게 소액 전송)
+pub struct TestTransfers<B> {
+    /// 트랜잭션을 보낼 RPC
+    pub backend: B,
+    pub payer: Keypair,
+    /// 전송에 붙일 메모의 실행 ID와 순번
+    pub run: TestRun,
+}
+
+// 구독 작업이 수집기로 보내는 결과
+enum Event {
+    Notification {
+        endpoint: usize,
+        subscription: Subscription,
+        key: u64,
+        at: Instant,
+    },
+    Dropped {
+        endpoint: usize,
+        subscription: Subscription,
+    },
+}
+
+/// 모든 엔드포인트의 웹소켓 URL에 구독을 열고 측정 시간 동안 알림 지연 비교
+///
+/// `transfers`가 없거나 [`SubscriptionOptions::signatures`]가 0이면 `signatureSubscribe`는 건너뜁니다.
+pub async fn run_subscriptions<B: RpcBackend + 'static>(
+    ws_urls: &[String],
+    options: &SubscriptionOptions,
+    transfers: Option<TestTransfers<B>>,
+) -> SubscriptionReport {
+    let started = Instant::now();
+    let deadline = started + options.duration + options.grace;
+    let (sender, mut receiver) = mpsc::unbounded_channel::<Event>();
+
+    for (endpoint, url) in ws_urls.iter().enumerate() {
+        for subscription in [Subscription::Slot, Subscription::Account] {
+            let (url, sender, account) = (url.clone(), sender.clone(), options.account);
+            tokio::spawn(async move { follow(endpoint, &url, subscription, &account, &sender, deadline).await });
+        }
+    }
+    if let Some(transfers) = transfers.filter(|_| options.signatures > 0) {
+        let (urls, sender) = (ws_urls.to_vec(), sender.clone());
+        let (count, spacing) = (options.signatures, options.duration / options.signatures as u32);
+        tokio::spawn(async move { send_signatures(&urls, transfers, count, started, spacing, &sender).await });
+    }
+    drop(sender);
+
+    let mut log = NotificationLog::new(ws_urls.len());
+    while let Ok(Some(event)) = tokio::time::timeout_at(deadline.into(), receiver.recv()).await {
+        match event {
+            Event::Notification {
+                endpoint,
+                subscription,
+                key,
+                at,
+            } => log.record(endpoint, subscription, key, at - started),
+            Event::Dropped { endpoint, subscription } => log.record_drop(endpoint, subscription),
+        }
+    }
+    log.report(options.duration)
+}
+
+// 마감까지 구독을 유지하며 알림을 수집기로 전달 (끊기면 다시 구독)
+async fn follow(
+    endpoint: usize,
+    url: &str,
+    subscription: Subscription,
+    account: &Pubkey,
+    events: &mpsc::UnboundedSender<Event>,
+    deadline: Instant,
+) {
+    while Instant::now() < deadline {
+        let finished = match tokio::time::timeout_at(deadline.into(), PubsubClient::new(url)).await {
+            Err(_) => return,
+            Ok(Err(_)) => false,
+            Ok(Ok(client)) => match subscription {
+                Subscription::Slot => match client.slot_subscribe().await {
+                    Ok((stream, _)) => forward(stream.map(|info| info.slot), endpoint, subscription, events, deadline).await,
+                    Err(_) => false,
+                },
+                _ => {
+                    let config = RpcAccountInfoConfig {
+                        commitment: Some(COMMITMENT),
+                        ..RpcAccountInfoConfig::default()
+                    };
+                    match client.account_subscribe(account, Some(config)).await {
+                        Ok((stream, _)) => {
+                            let slots = stream.map(|response| response.context.slot);
+                            forward(slots, endpoint, subscription, events, deadline).await
+                        }
+                        Err(_) => false,
+                    }
+                }
+            },
+        };
+        if finished {
+            return;
+        }
+        if events.send(Event::Dropped { endpoint, subscription }).is_err() {
+            return;
+        }
+        tokio::time::sleep_until(deadline.min(Instant::now() + RECONNECT_DELAY).into()).await;
+    }
+}
+
+// 스트림이 끝나면 `false`, 마감까지 유지되면 `true`
+async fn forward(
+    mut keys: impl Stream<Item = u64> + Unpin,
+    endpoint: usize,
+    subscription: Subscription,
+    events: &mpsc::UnboundedSender<Event>,
+    deadline: Instant,
+) -> bool {
+    loop {
+        match tokio::time::timeout_at(deadline.into(), keys.next()).await {
+            Err(_) => return true,
+            Ok(None) => return false,
+            Ok(Some(key)) => {
+                let at = Instant::now();
+                if events.send(Event::Notification { endpoint, subscription, key, at }).is_err() {
+                    return true;
+                }
+            }
+        }
+    }
+}
+
+// `spacing` 간격으로 테스트 전송을 보내며 모든 엔드포인트에서 서명 알림 수신
+async fn send_signatures<B: RpcBackend>(
+    urls: &[String],
+    transfers: TestTransfers<B>,
+    count: usize,
+    started: Instant,
+    spacing: Duration,
+    events: &mpsc::UnboundedSender<Event>,
+) {
+    let mut clients: Vec<Option<Arc<PubsubClient>>> = Vec::with_capacity(urls.len());
+    for url in urls {
+        clients.push(PubsubClient::new(url).await.ok().map(Arc::new));
+    }
+    let payer = transfers.payer.pubkey();
+
+    for sequence in 0..count {
+        tokio::time::sleep_until((started + spacing * sequence as u32).into()).await;
+        // 순번이 담긴 메모 덕분에 같은 블록해시에서도 서명이 겹치지 않음
+        let (_, instructions) = transfers.run.next_transfer(&payer, TEST_LAMPORTS);
+        let transaction =
+            match transfer::build_transaction(&transfers.backend, &transfers.payer, &[], instructions, TEST_PRIORITY_FEE).await {
+                Ok(transaction) => transaction,
+                Err(_) => continue,
+            };
+        let signature = transaction.signatures[0];
+
+        let mut ready = Vec::new();
+        for (endpoint, client) in clients.iter().enumerate() {
+            let Some(client) = client.clone() else {
+                let _ = events.send(Event::Dropped {
+                    endpoint,
+                    subscription: Subscription::Signature,
+                });
+                continue;
+            };
+            let (subscribed, waiting) = oneshot::channel();
+            ready.push(waiting);
+            let events = events.clone();
+            tokio::spawn(async move {
+                let subscription = Subscription::Signature;
+                let config = RpcSignatureSubscribeConfig {
+                    commitment: Some(COMMITMENT),
+                    enable_received_notification: Some(false),
+                };
+                let (mut stream, _) = match client.signature_subscribe(&signature, Some(config)).await {
+                    Ok(subscribed) => subscribed,
+                    Err(_) => {
+                        let _ = events.send(Event::Dropped { endpoint, subscription });
+                        return;
+                    }
+                };
+                let _ = subscribed.send(());
+                let event = match tokio::time::timeout(SIGNATURE_TIMEOUT, stream.next()).await {
+                    Ok(Some(_)) => Event::Notification {
+                        endpoint,
+                        subscription,
+                        key: sequence as u64,
+                        at: Instant::now(),
+                    },
+                    Ok(None) => Event::Dropped { endpoint, subscription },
+                    // 아무 엔드포인트에도 오지 않았으면 비교에서 빠지고, 다른 곳에 왔으면 놓친 알림
+                    Err(_) => return,
+                };
+                let _ = events.send(event);
+            });
+        }
+        for waiting in ready {
+            let _ = tokio::time::timeout(SIGNATURE_TIMEOUT, waiting).await;
+        }
+        let _ = transfers.backend.send_transaction(&transaction).await;
+    }
+}
@@ -0,0 +1,404 @@
+//! 병렬 전송 처리량 테스트 (`tx_speed_test --throughput`)
+//!
+//! 트랜잭션을 하나씩 보내는 측정으로는 엔드포인트가 부하를 받을 때의 모습을 알 수 없습니다.
+//! 트랜잭션 묶음을 미리 서명해 두고 목표 초당 전송 수(`--rate`)에 맞춰 응답을 기다리지 않고 보낸 뒤,
+//! 받아들여진 전송과 블록에 포함된 트랜잭션의 초당 수, 받아들여졌지만 포함되지 않은 비율(드롭률)을 구합니다.
+//!
+//! 같은 계정을 쓰는 트랜잭션끼리는 쓰기 잠금을 두고 경쟁해 같은 블록에 들어가기 어려우므로,
+//! 트랜잭션을 여러 테스트 계정(`--accounts`)에 번갈아 나눠 각 계정이 자기 자신에게 보내게 합니다.
+//! 테스트 계정은 지갑 키에서 결정적으로 만들어지므로 ([`test_accounts`]) 실행이 끝나도 남은 잔액을
+//! 다음 실행에서 그대로 다시 쓰고, 부족한 만큼만 지갑에서 채웁니다 ([`fund_accounts`]). 남은 잔액은
+//! [`reclaim_accounts`]로 지갑에 돌려받습니다 (`tx_speed_test --throughput --reclaim`).
+//!
+//! 블록해시는 약 60~90초 뒤 만료되므로 트랜잭션은 [`BLOCKHASH_WINDOW`] 동안 보낼 만큼씩 나눠, 묶음마다
+//! 새 블록해시를 받아 보내기 직전에 서명합니다. 드롭률을 재기 위해 포함되지 않은 트랜잭션을 다시 보내지 않습니다.
+
+use crate::backend::RpcBackend;
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::failure::{Failure, FailureCounts, FailureKind};
+use crate::fee_sweep::FeeLevel;
+use crate::landing::TestRun;
+use crate::output::OutputFormat;
+use crate::stats::LatencyHistogram;
+use crate::transfer::{self, ConfirmOptions};
+use sha2::{Digest, Sha256};
+use solana_sdk::hash::Hash;
+use solana_sdk::signature::{keypair_from_seed, Keypair, Signature, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 기본 테스트 계정 수
+pub const DEFAULT_ACCOUNTS: usize = 4;
+
+/// 엔드포인트마다 보내는 기본 트랜잭션 수
+pub const DEFAULT_COUNT: usize = 40;
+
+/// 기본 목표 초당 전송 수
+pub const DEFAULT_RATE: f64 = 10.0;
+
+/// 기본 우선순위 수수료 (micro-lamports per CU)
+pub const DEFAULT_FEE: u64 = 5;
+
+/// 블록해시 하나로 서명한 트랜잭션을 보내는 최대 시간 (블록해시 수명의 절반 이하)
+pub const BLOCKHASH_WINDOW: Duration = Duration::from_secs(30);
+
+/// 데이터가 없는 시스템 계정의 렌트 면제 최소 잔액 (lamports)
+pub const RENT_EXEMPT_MINIMUM: u64 = 890_880;
+
+// 테스트 계정 키를 만들 때 지갑 키에 덧붙이는 값
+const ACCOUNT_SEED: &[u8] = b"rpc-bench-throughput";
+
+// 충전 트랜잭션 하나에 넣는 전송 명령 수
+const FUNDING_BATCH: usize = 10;
+
+// 회수 트랜잭션 하나에 넣는 전송 명령 수 (계정마다 서명이 하나씩 늘어 트랜잭션 크기 제한에 맞춤)
+const RECLAIM_BATCH: usize = 5;
+
+// getSignatureStatuses 한 번에 조회할 수 있는 서명 수
+const STATUS_BATCH: usize = 256;
+
+/// 처리량 테스트 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThroughputOptions {
+    /// 트랜잭션을 나눠 보낼 테스트 계정 수
+    pub accounts: usize,
+    /// 엔드포인트마다 보내는 트랜잭션 수
+    pub count: usize,
+    /// 목표 초당 전송 수
+    pub rate: f64,
+    /// 우선순위 수수료 (micro-lamports per CU)
+    pub fee: u64,
+}
+
+impl Default for ThroughputOptions {
+    fn default() -> Self {
+        ThroughputOptions {
+            accounts: DEFAULT_ACCOUNTS,
+            count: DEFAULT_COUNT,
+            rate: DEFAULT_RATE,
+            fee: DEFAULT_FEE,
+        }
+    }
+}
+
+impl ThroughputOptions {
+    /// 명령줄 옵션 (`--accounts`, `--count`, `--rate`, `--fee`)
+    pub fn from_args(args: &Args) -> Result<ThroughputOptions> {
+        let accounts: usize = args.parse_or("accounts", DEFAULT_ACCOUNTS)?;
+        let count: usize = args.parse_or("count", DEFAULT_COUNT)?;
+        let rate: f64 = args.parse_or("rate", DEFAULT_RATE)?;
+        if !(rate > 0.0 && rate.is_finite()) {
+            return Err(Error::Input("--rate는 0보다 커야 합니다".to_string()));
+        }
+        Ok(ThroughputOptions {
+            accounts: accounts.max(1),
+            count: count.max(1),
+            rate,
+            fee: args.parse_or("fee", DEFAULT_FEE)?,
+        })
+    }
+
+    /// 계정 하나가 엔드포인트마다 보내는 최대 트랜잭션 수
+    pub fn per_account(&self) -> usize {
+        self.count.div_ceil(self.accounts)
+    }
+
+    /// 블록해시 하나로 서명하는 트랜잭션 수 (목표 속도로 [`BLOCKHASH_WINDOW`] 동안 보내는 수, 최소 1)
+    pub fn per_blockhash(&self) -> usize {
+        ((self.rate * BLOCKHASH_WINDOW.as_secs_f64()) as usize).max(1)
+    }
+}
+
+/// 지갑 키에서 결정적으로 만든 테스트 계정 `count`개 (같은 지갑이면 실행마다 같은 계정)
+pub fn test_accounts(payer: &Keypair, count: usize) -> Vec<Keypair> {
+    (0..count as u32)
+        .map(|index| {
+            let seed = Sha256::new()
+                .chain_update(payer.to_bytes())
+                .chain_update(ACCOUNT_SEED)
+                .chain_update(index.to_le_bytes())
+                .finalize();
+            keypair_from_seed(&seed).expect("SHA-256 출력은 32바이트")
+        })
+        .collect()
+}
+
+/// `transactions`개를 `fee` (micro-lamports per CU)로 보내는 테스트 계정에 필요한 잔액 (렌트 면제 최소 잔액 포함)
+pub fn required_balance(transactions: usize, fee: u64) -> u64 {
+    let level = FeeLevel { price: fee, cu_limit: None };
+    RENT_EXEMPT_MINIMUM + level.cost() * transactions as u64
+}
+
+/// 잔액이 `minimum`보다 적은 테스트 계정을 `payer`의 지갑에서 `minimum`까지 채우고 보낸 lamports 합계 반환
+pub async fn fund_accounts<B: RpcBackend + ?Sized>(
+    backend: &B,
+    payer: &Keypair,
+    accounts: &[Keypair],
+    minimum: u64,
+    fee: u64,
+) -> Result<u64> {
+    let pubkeys: Vec<_> = accounts.iter().map(|account| account.pubkey()).collect();
+    let balances = backend.get_multiple_accounts(&pubkeys).await?;
+    let transfers: Vec<_> = pubkeys
+        .iter()
+        .zip(balances)
+        .filter_map(|(pubkey, account)| {
+            let balance = account.map_or(0, |account| account.lamports);
+            (balance < minimum).then(|| (pubkey, minimum - balance))
+        })
+        .collect();
+
+    let mut funded = 0;
+    for batch in transfers.chunks(FUNDING_BATCH) {
+        let instructions = batch
+            .iter()
+            .map(|(pubkey, lamports)| system_instruction::transfer(&payer.pubkey(), pubkey, *lamports))
+            .collect();
+        transfer::send_instructions(backend, payer, &[], instructions, fee).await?;
+        funded += batch.iter().map(|(_, lamports)| lamports).sum::<u64>();
+    }
+    Ok(funded)
+}
+
+/// 테스트 계정의 잔액을 모두 `payer`의 지갑으로 돌려보내고 돌려받은 lamports 합계 반환
+///
+/// 수수료는 지갑이 내므로 테스트 계정의 잔액은 0이 되어 계정이 정리됩니다.
+pub async fn reclaim_accounts<B: RpcBackend + ?Sized>(
+    backend: &B,
+    payer: &Keypair,
+    accounts: &[Keypair],
+    fee: u64,
+) -> Result<u64> {
+    let pubkeys: Vec<_> = accounts.iter().map(|account| account.pubkey()).collect();
+    let balances = backend.get_multiple_accounts(&pubkeys).await?;
+    let transfers: Vec<_> = accounts
+        .iter()
+        .zip(balances)
+        .filter_map(|(account, balance)| balance.filter(|balance| balance.lamports > 0).map(|balance| (account, balance.lamports)))
+        .collect();
+
+    let mut reclaimed = 0;
+    for batch in transfers.chunks(RECLAIM_BATCH) {
+        let instructions = batch
+            .iter()
+            .map(|(account, lamports)| system_instruction::transfer(&account.pubkey(), &payer.pubkey(), *lamports))
+            .collect();
+        let signers: Vec<&Keypair> = batch.iter().map(|(account, _)| *account).collect();
+        transfer::send_instructions(backend, payer, &signers, instructions, fee).await?;
+        reclaimed += batch.iter().map(|(_, lamports)| lamports).sum::<u64>();
+    }
+    Ok(reclaimed)
+}
+
+/// 블록해시 하나로 `indices` 순번의 측정 트랜잭션을 미리 서명
+///
+/// 순번에 따라 계정을 번갈아 사용하므로 묶음을 나눠 서명해도 계정마다 고르게 나뉘고, 각 계정이 자기 자신에게
+/// `lamports`를 보냅니다.
+pub fn presign(accounts: &[Keypair], run: &TestRun, indices: Range<usize>, lamports: u64, fee: u64, blockhash: Hash) -> Vec<Transaction> {
+    indices
+        .map(|index| {
+            let account = &accounts[index % accounts.len()];
+            let (_, instructions) = run.next_transfer(&account.pubkey(), lamports);
+            transfer::sign_transaction(account, &[], instructions, fee, blockhash)
+        })
+        .collect()
+}
+
+/// 엔드포인트 하나의 처리량 테스트 결과 (시간은 첫 전송을 시작한 시점부터)
+#[derive(Debug, Clone, Default)]
+pub struct ThroughputReport {
+    /// 측정 트랜잭션 메모에 담긴 실행 ID
+    pub run_id: String,
+    pub accounts: usize,
+    pub target_rate: f64,
+    /// 전송을 시도한 트랜잭션 수
+    pub sent: usize,
+    /// 전송 요청이 성공한 트랜잭션 수
+    pub accepted: usize,
+    /// 블록에 포함된 트랜잭션 수 (실행에 실패한 것 포함)
+    pub landed: usize,
+    /// 마지막 전송 요청이 끝날 때까지
+    pub submit_time: Duration,
+    /// 마지막으로 포함된 트랜잭션을 확인할 때까지 (상태 조회 간격만큼의 오차)
+    pub land_time: Duration,
+    /// 전송 요청의 응답 시간 (성공한 요청)
+    pub send_latency: LatencyHistogram,
+    /// 전송 실패, 실행 실패, 포함되지 않음(타임아웃)
+    pub failures: FailureCounts,
+}
+
+impl ThroughputReport {
+    /// 실제로 전송을 시도한 초당 수
+    pub fn send_rate(&self) -> f64 {
+        per_second(self.sent, self.submit_time)
+    }
+
+    /// 받아들여진 초당 전송 수
+    pub fn accepted_per_second(&self) -> f64 {
+        per_second(self.accepted, self.submit_time)
+    }
+
+    /// 블록에 포함된 초당 트랜잭션 수
+    pub fn landed_per_second(&self) -> f64 {
+        per_second(self.landed, self.land_time)
+    }
+
+    /// 받아들여진 전송 중 블록에 포함되지 않은 비율 (0~1, 받아들여진 전송이 없으면 0)
+    pub fn drop_rate(&self) -> f64 {
+        if self.accepted == 0 {
+            return 0.0;
+        }
+        self.accepted.saturating_sub(self.landed) as f64 / self.accepted as f64
+    }
+}
+
+fn per_second(count: usize, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        return 0.0;
+    }
+    count as f64 / elapsed.as_secs_f64()
+}
+
+// 전송 요청 하나의 결과
+struct Submission {
+    // 첫 전송 시작부터 요청이 끝날 때까지
+    finished: Duration,
+    result: std::result::Result<(Signature, Duration), Failure>,
+}
+
+/// 트랜잭션 묶음을 미리 서명해 목표 속도로 동시에 보내고, 포함 여부를 `track.timeout`까지 확인
+///
+/// [`ThroughputOptions::per_blockhash`]개마다 새 블록해시를 받아 그 묶음을 서명합니다. 상태는
+/// `track.poll_interval`마다 묶어서 조회하며, 마지막 블록해시가 만료되면 더 기다리지 않습니다
+/// (`track.resend_interval`은 사용하지 않음). 첫 블록해시를 받지 못한 경우만 오류를 반환하고,
+/// 이후 묶음의 블록해시를 받지 못하면 그 묶음은 전송 실패로 집계합니다.
+pub async fn run_throughput<B: RpcBackend + 'static>(
+    backend: Arc<B>,
+    accounts: &[Keypair],
+    run: &TestRun,
+    lamports: u64,
+    options: &ThroughputOptions,
+    track: &ConfirmOptions,
+    output: OutputFormat,
+) -> Result<ThroughputReport> {
+    let accounts = &accounts[..options.accounts.min(accounts.len())];
+    let per_blockhash = options.per_blockhash();
+    let mut blockhash = backend.get_latest_blockhash().await?;
+    output.progress(format_args!(
+        "  트랜잭션 {}개를 초당 {}개로 전송 (계정 {}개, 블록해시 하나에 최대 {}개)",
+        options.count,
+        options.rate,
+        accounts.len(),
+        per_blockhash
+    ));
+
+    // 응답을 기다리지 않고 일정한 간격으로 전송 시작
+    let started = Instant::now();
+    let interval = Duration::from_secs_f64(1.0 / options.rate);
+    let mut handles = Vec::with_capacity(options.count);
+    let mut unsent = Vec::new();
+    for chunk in (0..options.count).step_by(per_blockhash) {
+        let indices = chunk..(chunk + per_blockhash).min(options.count);
+        tokio::time::sleep_until((started + interval * chunk as u32).into()).await;
+        if chunk > 0 {
+            match backend.get_latest_blockhash().await {
+                Ok(latest) => blockhash = latest,
+                Err(e) => {
+                    let failure = Failure::from_client_error(&e);
+                    unsent.extend(indices.map(|_| Submission {
+                        finished: started.elapsed(),
+                        result: Err(failure.clone()),
+                    }));
+                    continue;
+                }
+            }
+        }
+        let transactions = presign(accounts, run, indices, lamports, options.fee, blockhash);
+        for (index, transaction) in (chunk..).zip(transactions) {
+            tokio::time::sleep_until((started + interval * index as u32).into()).await;
+            let backend = backend.clone();
+            handles.push(tokio::spawn(async move {
+                let begin = Instant::now();
+                let result = match backend.send_transaction(&transaction).await {
+                    Ok(signature) => Ok((signature, begin.elapsed())),
+                    Err(e) => Err(Failure::from_client_error(&e)),
+                };
+                Submission {
+                    finished: started.elapsed(),
+                    result,
+                }
+            }));
+        }
+    }
+
+    let mut report = ThroughputReport {
+        run_id: run.id().to_string(),
+        accounts: accounts.len(),
+        target_rate: options.rate,
+        ..ThroughputReport::default()
+    };
+    let mut pending = Vec::new();
+    let mut submissions = unsent;
+    for handle in handles {
+        submissions.push(handle.await.expect("전송 작업은 패닉하지 않음"));
+    }
+    for submission in submissions {
+        report.sent += 1;
+        report.submit_time = report.submit_time.max(submission.finished);
+        match submission.result {
+            Ok((signature, latency)) => {
+                report.accepted += 1;
+                report.send_latency.record(latency);
+                pending.push(signature);
+            }
+            Err(failure) => report.failures.record(&failure),
+        }
+    }
+    output.progress(format_args!(
+        "  전송 {}개 중 {}개 받아들여짐 ({:?}), 포함 확인 중...",
+        report.sent, report.accepted, report.submit_time
+    ));
+
+    // 남은 서명을 묶어서 조회 (상태 조회 중의 일시적인 오류는 무시하고 다음 조회에서 다시 확인)
+    let deadline = Instant::now() + track.timeout;
+    while !pending.is_empty() {
+        let expired = matches!(backend.is_blockhash_valid(&blockhash).await, Ok(false));
+        let mut remaining = Vec::with_capacity(pending.len());
+        for batch in pending.chunks(STATUS_BATCH) {
+            let statuses = match backend.get_signature_statuses(batch).await {
+                Ok(response) => response.value,
+                Err(_) => vec![None; batch.len()],
+            };
+            for (signature, status) in batch.iter().zip(statuses) {
+                match status {
+                    Some(status) => {
+                        report.landed += 1;
+                        report.land_time = started.elapsed();
+                        if let Some(error) = status.err {
+                            let error = Error::Transaction {
+                                signature: Some(*signature),
+                                error,
+                            };
+                            report.failures.record(&Failure::from_error(&error));
+                        }
+                    }
+                    None => remaining.push(*signature),
+                }
+            }
+        }
+        pending = remaining;
+        // 마지막 블록해시가 만료된 뒤에는 (그보다 먼저 받은 블록해시도 만료되었으므로) 더 포함될 수 없음
+        if pending.is_empty() || expired || Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(track.poll_interval).await;
+    }
+    for _ in &pending {
+        report.failures.record(&Failure::new(FailureKind::Timeout, "블록에 포함되지 않음"));
+    }
+    Ok(report)
+}
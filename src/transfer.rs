@@ -0,0 +1,307 @@
+//! SOL 및 SPL 토큰 전송
+//!
+//! 모든 전송은 우선순위 수수료(compute unit 가격) 명령을 앞에 붙여 서명한 뒤,
+//! `confirmed` 커미트먼트까지 확인하고 트랜잭션 서명을 반환합니다.
+//! RPC 호출은 [`RpcBackend`]를 거치므로 `RpcClient` 대신 가짜 백엔드로도 실행할 수 있습니다.
+//!
+//! 전송 함수는 모두 비동기이며, 동기 코드에서는 [`blocking`] 모듈의 래퍼를 사용합니다.
+
+use crate::backend::RpcBackend;
+use crate::error::{Error, Result};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use spl_associated_token_account::{
+    get_associated_token_address,
+    instruction::create_associated_token_account_idempotent,
+};
+use std::time::{Duration, Instant};
+
+/// 기본 우선순위 수수료 (lamports per compute unit)
+pub const DEFAULT_PRIORITY_FEE: u64 = 1;
+
+/// 트랜잭션 확인 대기 설정
+#[derive(Clone, Debug)]
+pub struct ConfirmOptions {
+    /// 서명 상태 조회 간격
+    pub poll_interval: Duration,
+    /// 아직 보이지 않는 트랜잭션을 다시 전송하는 간격
+    pub resend_interval: Duration,
+    /// 블록해시가 만료되지 않았는데도 확인을 포기하기까지의 최대 대기 시간
+    ///
+    /// 보통은 블록해시 만료(약 150블록)가 먼저 판단 기준이 되며, 이 시간은 RPC가 만료 여부를 답하지 못할 때의 상한입니다.
+    pub timeout: Duration,
+}
+
+impl Default for ConfirmOptions {
+    fn default() -> Self {
+        ConfirmOptions {
+            poll_interval: Duration::from_millis(500),
+            resend_interval: Duration::from_secs(2),
+            timeout: Duration::from_secs(180),
+        }
+    }
+}
+
+/// SOL 전송
+///
+/// `fee`는 우선순위 수수료이며 0이면 우선순위 수수료 명령을 붙이지 않습니다.
+pub async fn send_sol<B: RpcBackend + ?Sized>(
+    backend: &B,
+    sender: &Keypair,
+    recipient: &Pubkey,
+    lamports: u64,
+    fee: u64,
+) -> Result<String> {
+    // 전송 명령 생성
+    let transfer_instruction = system_instruction::transfer(&sender.pubkey(), recipient, lamports);
+    
+    let signature = send_instructions(backend, sender, &[], vec![transfer_instruction], fee).await?;
+    Ok(signature.to_string())
+}
+
+/// SPL 토큰 전송 (수신자 연관 토큰 계정이 없으면 함께 생성)
+///
+/// `amount`는 민트의 소수점 자릿수를 반영한 UI 단위입니다.
+pub async fn send_token(
+    rpc_client: &RpcClient,
+    sender: &Keypair,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    amount: f64,
+    fee: u64,
+) -> Result<String> {
+    let instructions = token_transfer_instructions(rpc_client, sender, mint, recipient, amount).await?;
+    
+    let signature = send_instructions(rpc_client, sender, &[], instructions, fee).await?;
+    Ok(signature.to_string())
+}
+
+/// SPL 토큰 전송 명령 생성 (수신자 연관 토큰 계정 생성 명령 포함, 전송하지 않음)
+///
+/// `amount`는 민트의 소수점 자릿수를 반영한 UI 단위입니다.
+pub async fn token_transfer_instructions(
+    rpc_client: &RpcClient,
+    sender: &Keypair,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    amount: f64,
+) -> Result<Vec<Instruction>> {
+    // 민트 계정에서 소수점 자릿수 조회
+    let mint_account = rpc_client.get_account(mint).await?;
+    let decimals = spl_token::state::Mint::unpack(&mint_account.data)?.decimals;
+    let raw_amount = spl_token::ui_amount_to_amount(amount, decimals);
+    
+    let source = get_associated_token_address(&sender.pubkey(), mint);
+    let destination = get_associated_token_address(recipient, mint);
+    
+    Ok(vec![
+        create_associated_token_account_idempotent(&sender.pubkey(), recipient, mint, &spl_token::id()),
+        spl_token::instruction::transfer_checked(
+            &spl_token::id(),
+            &source,
+            mint,
+            &destination,
+            &sender.pubkey(),
+            &[],
+            raw_amount,
+            decimals,
+        )?,
+    ])
+}
+
+/// 우선순위 수수료 명령을 앞에 붙여 트랜잭션을 서명하고 전송
+///
+/// `extra_signers`는 수수료 지불자 외에 서명이 필요한 키페어입니다 (예: 새로 생성하는 계정).
+pub async fn send_instructions<B: RpcBackend + ?Sized>(
+    backend: &B,
+    payer: &Keypair,
+    extra_signers: &[&Keypair],
+    instructions: Vec<Instruction>,
+    fee: u64,
+) -> Result<Signature> {
+    send_instructions_with_options(backend, payer, extra_signers, instructions, fee, &ConfirmOptions::default()).await
+}
+
+/// [`send_instructions`]와 같지만 확인 대기 설정을 직접 지정
+pub async fn send_instructions_with_options<B: RpcBackend + ?Sized>(
+    backend: &B,
+    payer: &Keypair,
+    extra_signers: &[&Keypair],
+    instructions: Vec<Instruction>,
+    fee: u64,
+    options: &ConfirmOptions,
+) -> Result<Signature> {
+    let transaction = build_transaction(backend, payer, extra_signers, instructions, fee).await?;
+    
+    // 트랜잭션 전송 및 확인
+    send_and_confirm(backend, &transaction, options).await
+}
+
+/// 최근 블록해시로 우선순위 수수료 명령을 붙인 트랜잭션을 만들어 서명 (전송하지 않음)
+///
+/// 서명은 전송 전에 이미 정해지므로, 전송 결과를 알 수 없게 되더라도 서명으로 상태를 추적할 수 있습니다.
+pub async fn build_transaction<B: RpcBackend + ?Sized>(
+    backend: &B,
+    payer: &Keypair,
+    extra_signers: &[&Keypair],
+    instructions: Vec<Instruction>,
+    fee: u64,
+) -> Result<Transaction> {
+    // 최근 블록해시 가져오기
+    let recent_blockhash = backend.get_latest_blockhash().await?;
+    
+    Ok(sign_transaction(payer, extra_signers, instructions, fee, recent_blockhash))
+}
+
+/// 주어진 블록해시로 우선순위 수수료 명령을 붙인 트랜잭션을 서명 (RPC 호출 없음)
+///
+/// 블록해시 하나로 여러 트랜잭션을 미리 서명해 둘 때 사용합니다.
+pub fn sign_transaction(
+    payer: &Keypair,
+    extra_signers: &[&Keypair],
+    instructions: Vec<Instruction>,
+    fee: u64,
+    recent_blockhash: Hash,
+) -> Transaction {
+    // 명령어 벡터 생성
+    let mut all_instructions = vec![];
+    
+    // 우선순위 수수료 설정 (최신 SDK 방식)
+    if fee > 0 {
+        // 수수료 우선순위 설정 명령어 추가
+        let fee_instruction = ComputeBudgetInstruction::set_compute_unit_price(fee);
+        all_instructions.push(fee_instruction);
+    }
+    
+    all_instructions.extend(instructions);
+    
+    // 트랜잭션 생성 및 서명
+    let mut signers: Vec<&Keypair> = vec![payer];
+    signers.extend_from_slice(extra_signers);
+    
+    let mut transaction = Transaction::new_with_payer(&all_instructions, Some(&payer.pubkey()));
+    transaction.sign(&signers, recent_blockhash);
+    transaction
+}
+
+/// 서명된 트랜잭션을 전송하고 `confirmed` 커미트먼트까지 기다림
+///
+/// 상태가 보이지 않으면 `resend_interval`마다 블록해시가 아직 유효한지 확인하고 다시 전송합니다.
+/// 블록해시가 만료될 때까지 상태가 보이지 않으면 처리될 수 없으므로 [`Error::BlockhashExpired`]를,
+/// 만료를 확인하지 못한 채 `timeout`이 지나면 처리 여부를 알 수 없다는 뜻으로 [`Error::ConfirmationTimeout`]을 반환합니다.
+/// 상태 조회 중의 일시적인 오류는 무시하고 계속 확인합니다.
+pub async fn send_and_confirm<B: RpcBackend + ?Sized>(
+    backend: &B,
+    transaction: &Transaction,
+    options: &ConfirmOptions,
+) -> Result<Signature> {
+    let signature = backend.send_transaction(transaction).await?;
+    let start = Instant::now();
+    let mut last_sent = start;
+    let mut last_error = None;
+    
+    loop {
+        match backend.get_signature_statuses(&[signature]).await {
+            Ok(response) => match response.value.into_iter().next().flatten() {
+                Some(status) => {
+                    if let Some(error) = status.err {
+                        return Err(Error::Transaction {
+                            signature: Some(signature),
+                            error,
+                        });
+                    }
+                    if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+                        return Ok(signature);
+                    }
+                }
+                None => {
+                    // 드롭되었을 수 있으므로 주기적으로 재전송 (이미 처리된 경우의 오류는 무시)
+                    if last_sent.elapsed() >= options.resend_interval {
+                        if let Ok(false) = backend.is_blockhash_valid(&transaction.message.recent_blockhash).await {
+                            // 만료 직전에 처리되었을 수 있으므로 상태를 한 번 더 확인 (조회 실패는 다음 확인에서 다시 판단)
+                            let recheck = backend.get_signature_statuses_with_history(&[signature]).await;
+                            if matches!(recheck, Ok(response) if response.value.first().is_some_and(Option::is_none)) {
+                                return Err(Error::BlockhashExpired);
+                            }
+                        } else {
+                            let _ = backend.send_transaction(transaction).await;
+                            last_sent = Instant::now();
+                        }
+                    }
+                }
+            },
+            Err(e) => last_error = Some(e),
+        }
+        
+        if start.elapsed() >= options.timeout {
+            return Err(Error::ConfirmationTimeout {
+                signature,
+                timeout: options.timeout,
+                last_error: last_error.map(|e| Error::from(e).to_string()),
+            });
+        }
+        tokio::time::sleep(options.poll_interval).await;
+    }
+}
+
+/// 동기 코드용 전송 래퍼
+///
+/// 동기 `RpcClient`의 URL과 커미트먼트로 비동기 클라이언트를 만들어 공유 런타임([`crate::runtime`])에서 실행합니다.
+/// tokio 런타임 안에서는 호출하지 마세요.
+pub mod blocking {
+    use crate::error::Result;
+    use crate::{rpc, runtime};
+    use solana_client::rpc_client::RpcClient;
+    use solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature},
+    };
+
+    /// [`super::send_sol`]의 동기 버전
+    pub fn send_sol(
+        rpc_client: &RpcClient,
+        sender: &Keypair,
+        recipient: &Pubkey,
+        lamports: u64,
+        fee: u64,
+    ) -> Result<String> {
+        let client = rpc::async_client_with_commitment(&rpc_client.url(), rpc_client.commitment());
+        runtime::block_on(super::send_sol(&client, sender, recipient, lamports, fee))
+    }
+
+    /// [`super::send_token`]의 동기 버전
+    pub fn send_token(
+        rpc_client: &RpcClient,
+        sender: &Keypair,
+        mint: &Pubkey,
+        recipient: &Pubkey,
+        amount: f64,
+        fee: u64,
+    ) -> Result<String> {
+        let client = rpc::async_client_with_commitment(&rpc_client.url(), rpc_client.commitment());
+        runtime::block_on(super::send_token(&client, sender, mint, recipient, amount, fee))
+    }
+
+    /// [`super::send_instructions`]의 동기 버전
+    pub fn send_instructions(
+        rpc_client: &RpcClient,
+        payer: &Keypair,
+        extra_signers: &[&Keypair],
+        instructions: Vec<Instruction>,
+        fee: u64,
+    ) -> Result<Signature> {
+        let client = rpc::async_client_with_commitment(&rpc_client.url(), rpc_client.commitment());
+        runtime::block_on(super::send_instructions(&client, payer, extra_signers, instructions, fee))
+    }
+}
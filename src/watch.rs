@@ -0,0 +1,516 @@
+//! 입금 감시 및 웹훅 알림 (`watch` 하위 명령)
+
+use crate::cli::Args;
+use crate::error::{Error, Result};
+use crate::history::{self, HistoryRecord};
+use crate::state_file;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use solana_client::{
+    pubsub_client::PubsubClient,
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::{RpcAccountInfoConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+    rpc_request::TokenAccountsFilter,
+    rpc_response::RpcConfirmedTransactionStatusWithSignature,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+use std::env;
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// 웹소켓 재연결 대기 시간
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+// 웹훅 재시도 간격 상한
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+// 중복 전달 방지를 위해 기억할 최근 서명 수
+const PROCESSED_CAPACITY: usize = 10_000;
+
+/// 사용법 안내 (연결 없이 출력할 수 있도록 공개)
+pub const USAGE: &str = "사용법:
+  watch --address <주소> [--address <주소> ...] --webhook <URL> [옵션]
+옵션:
+  --reference <주소>       Solana Pay 참조 키 (여러 번 지정 가능)
+  --memo <문자열>          매칭할 메모 (여러 번 지정 가능)
+  --only-matched           참조 키/메모가 일치하는 입금만 전달
+  --ws-url <URL>           웹소켓 URL (기본값: RPC URL에서 유추)
+  --no-ws                  웹소켓을 사용하지 않고 폴링만 사용
+  --poll-interval <초>     폴링 간격 (기본값: 10)
+  --refresh-interval <초>  지갑이 소유한 토큰 계정 목록을 다시 조회하는 간격 (기본값: 60)
+  --once                   모든 계정을 한 번만 확인하고 종료
+  --secret-env <이름>      웹훅 서명 비밀키 환경 변수 이름 (기본값: WEBHOOK_SECRET)
+  --max-retries <횟수>     웹훅 전송 재시도 횟수 (기본값: 5)
+  --state <파일>           마지막으로 처리한 서명 저장 파일 (기본값: watch_state.json)";
+
+type HmacSha256 = Hmac<Sha256>;
+
+// 웹훅으로 전달되는 입금 이벤트
+#[derive(Debug, Serialize)]
+struct DepositEvent<'a> {
+    event: &'static str,
+    address: String,
+    #[serde(flatten)]
+    record: &'a HistoryRecord,
+    matched_references: Vec<String>,
+    matched_memo: Option<String>,
+}
+
+// 감시 중인 계정별 마지막 처리 서명과 최근에 처리한 서명
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WatchState {
+    last_signatures: HashMap<String, String>,
+    // 재시작 후에도 다른 계정에서 다시 보이는 트랜잭션을 중복 전달하지 않도록 함께 저장
+    #[serde(default)]
+    processed: ProcessedSet,
+}
+
+// 감시 대상 계정 (지갑 자체 또는 지갑 소유 토큰 계정)
+#[derive(Debug, Clone)]
+struct WatchedAccount {
+    account: Pubkey,
+    owner: Pubkey,
+}
+
+// 입금과 매칭할 Solana Pay 참조 키와 메모
+struct DepositFilter {
+    references: Vec<String>,
+    memos: Vec<String>,
+    only_matched: bool,
+}
+
+struct Webhook {
+    client: reqwest::blocking::Client,
+    url: String,
+    secret: Option<String>,
+    max_retries: u32,
+}
+
+/// watch 하위 명령 실행
+/// `ws_url`은 설정(`--ws-url`, 프로필)에서 결정된 웹소켓 URL
+pub fn run(rpc_client: &RpcClient, ws_url: &str, args: &Args) -> Result<()> {
+    let addresses = args
+        .values("address")
+        .into_iter()
+        .map(|a| Pubkey::from_str(a).map_err(|_| Error::Input(format!("유효하지 않은 솔라나 주소입니다: {}", a))))
+        .collect::<Result<Vec<_>>>()?;
+    let webhook_url = args.value("webhook");
+
+    let webhook_url = match (addresses.is_empty(), webhook_url) {
+        (false, Some(url)) => url.to_string(),
+        _ => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+    };
+
+    let filter = DepositFilter {
+        references: args.values("reference").into_iter().map(String::from).collect(),
+        memos: args.values("memo").into_iter().map(String::from).collect(),
+        only_matched: args.has("only-matched"),
+    };
+    let poll_interval = Duration::from_secs(args.parse_or("poll-interval", 10)?);
+    let refresh_interval = Duration::from_secs(args.parse_or("refresh-interval", 60)?);
+    let once = args.has("once");
+    let use_ws = !args.has("no-ws");
+    let state_path = args.value("state").unwrap_or("watch_state.json").to_string();
+
+    let secret_env = args.value("secret-env").unwrap_or("WEBHOOK_SECRET");
+    let secret = env::var(secret_env).ok();
+    if secret.is_none() {
+        println!("경고: 환경변수 {}가 설정되지 않아 웹훅 요청에 서명하지 않습니다.", secret_env);
+    }
+
+    let webhook = Webhook {
+        client: reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| Error::Config(format!("웹훅 HTTP 클라이언트 생성 실패: {}", e)))?,
+        url: webhook_url,
+        secret,
+        max_retries: args.parse_or("max-retries", 5)?,
+    };
+
+    // 지갑과 지갑이 소유한 토큰 계정을 모두 감시 (토큰 입금은 토큰 계정에만 기록될 수 있음)
+    let mut watched: Vec<WatchedAccount> = addresses.iter().map(|owner| WatchedAccount { account: *owner, owner: *owner }).collect();
+    watched.extend(token_accounts(rpc_client, &addresses)?);
+
+    println!("입금 감시를 시작합니다. 감시 계정 {}개 (지갑 {}개)", watched.len(), addresses.len());
+    println!("웹훅 URL: {}", webhook.url);
+
+    let mut state: WatchState = state_file::load(&state_path)?;
+
+    // 처음 감시하는 계정은 현재 최신 서명부터 시작 (과거 내역은 전달하지 않음)
+    for watched_account in &watched {
+        if let Entry::Vacant(entry) = state.last_signatures.entry(watched_account.account.to_string()) {
+            if let Some(latest) = latest_signature(rpc_client, &watched_account.account)? {
+                entry.insert(latest);
+            }
+        }
+    }
+    state_file::save(&state_path, &state)?;
+
+    // 웹소켓 알림은 해당 계정의 즉시 확인을 요청하고, 폴링은 주기적으로 전체를 확인한다
+    let (sender, receiver) = mpsc::channel::<Pubkey>();
+    if use_ws {
+        println!("웹소켓 URL: {}", ws_url);
+        for watched_account in &watched {
+            spawn_logs_subscription(ws_url, watched_account.account, sender.clone());
+        }
+        for owner in &addresses {
+            spawn_account_subscription(ws_url, *owner, sender.clone());
+        }
+    }
+
+    let mut last_refresh = Instant::now();
+    let mut last_full_scan = Instant::now();
+    loop {
+        // 다음 전체 확인 시각까지만 알림을 기다린다
+        let notified: HashSet<Pubkey> = match once {
+            true => HashSet::new(),
+            false => match receiver.recv_timeout(poll_interval.saturating_sub(last_full_scan.elapsed())) {
+                Ok(account) => {
+                    // 대기 중인 알림을 모아 한 번에 처리
+                    let mut accounts: HashSet<Pubkey> = HashSet::from([account]);
+                    accounts.extend(receiver.try_iter());
+                    accounts
+                }
+                // 송신자를 이 함수가 계속 보유하므로 웹소켓이 없어도 폴링 간격만큼 대기한다
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => HashSet::new(),
+            },
+        };
+        // 알림이 자주 오는 계정이 있어도 폴링 간격마다 모든 계정을 확인 (구독이 조용히 끊긴 계정 대비)
+        let full_scan = once || last_full_scan.elapsed() >= poll_interval;
+        if full_scan {
+            last_full_scan = Instant::now();
+        }
+
+        // 감시를 시작한 뒤에 만들어진 토큰 계정도 감시 대상에 추가 (상태에 없는 새 계정은 첫 서명부터 확인)
+        if last_refresh.elapsed() >= refresh_interval {
+            last_refresh = Instant::now();
+            match token_accounts(rpc_client, &addresses) {
+                Ok(accounts) => {
+                    for account in accounts {
+                        if watched.iter().any(|w| w.account == account.account) {
+                            continue;
+                        }
+                        println!("새 토큰 계정 감시: {} (지갑 {})", account.account, account.owner);
+                        if use_ws {
+                            spawn_logs_subscription(ws_url, account.account, sender.clone());
+                        }
+                        watched.push(account);
+                    }
+                }
+                Err(err) => println!("토큰 계정 목록 조회 실패: {} (다음 주기에 재시도)", err),
+            }
+        }
+
+        let targets = watched
+            .iter()
+            .filter(|w| full_scan || notified.contains(&w.account));
+        for watched_account in targets {
+            let result = scan_account(rpc_client, watched_account, &mut state, &webhook, &filter);
+
+            if let Err(err) = result {
+                println!("계정 {} 확인 실패: {} (다음 주기에 재시도)", watched_account.account, err);
+            }
+            state_file::save(&state_path, &state)?;
+        }
+
+        if once {
+            return Ok(());
+        }
+    }
+}
+
+// 지갑들이 소유한 SPL 토큰 계정 목록
+fn token_accounts(rpc_client: &RpcClient, owners: &[Pubkey]) -> Result<Vec<WatchedAccount>> {
+    let mut accounts = vec![];
+    for owner in owners {
+        for token_account in rpc_client.get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(spl_token::id()))? {
+            accounts.push(WatchedAccount {
+                account: parse_response_pubkey(&token_account.pubkey)?,
+                owner: *owner,
+            });
+        }
+    }
+    Ok(accounts)
+}
+
+// 마지막 처리 서명 이후의 새 트랜잭션을 확인하고 입금을 웹훅으로 전달
+fn scan_account(
+    rpc_client: &RpcClient,
+    watched: &WatchedAccount,
+    state: &mut WatchState,
+    webhook: &Webhook,
+    filter: &DepositFilter,
+) -> Result<()> {
+    let key = watched.account.to_string();
+    let until = state.last_signatures.get(&key).cloned();
+
+    for status in new_signatures(rpc_client, &watched.account, until.as_deref())? {
+        if status.err.is_none() && !state.processed.contains(&status.signature) {
+            let transaction = history::fetch_transaction(rpc_client, &status.signature)?;
+            let account_keys = history::account_keys(&transaction);
+            let records = history::decode_transaction(&watched.owner, status.memo.clone(), &transaction);
+
+            let matched_references: Vec<String> = filter
+                .references
+                .iter()
+                .filter(|r| account_keys.contains(r))
+                .cloned()
+                .collect();
+            let matched_memo = status
+                .memo
+                .as_ref()
+                .and_then(|memo| filter.memos.iter().find(|m| memo.contains(m.as_str())).cloned());
+
+            for record in records.iter().filter(|r| is_incoming(r)) {
+                if filter.only_matched && matched_references.is_empty() && matched_memo.is_none() {
+                    continue;
+                }
+
+                let event = DepositEvent {
+                    event: "deposit",
+                    address: watched.owner.to_string(),
+                    record,
+                    matched_references: matched_references.clone(),
+                    matched_memo: matched_memo.clone(),
+                };
+                println!(
+                    "입금 감지: {} {} ({}) 서명 {}",
+                    record.change, record.asset, event.address, record.signature
+                );
+
+                // 전달에 실패하면 마지막 처리 서명을 갱신하지 않아 다음 주기에 다시 시도된다
+                let delivery_id = format!("{}:{}", record.signature, record.asset);
+                webhook.deliver(&delivery_id, &event)?;
+            }
+
+            state.processed.insert(status.signature.clone());
+        }
+
+        state.last_signatures.insert(key.clone(), status.signature);
+    }
+
+    Ok(())
+}
+
+fn is_incoming(record: &HistoryRecord) -> bool {
+    record.status == "success" && !record.change.starts_with('-') && record.change != "0"
+}
+
+// 주어진 서명 이후의 서명 목록 (오래된 순)
+fn new_signatures(
+    rpc_client: &RpcClient,
+    account: &Pubkey,
+    until: Option<&str>,
+) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+    let until = match until {
+        Some(signature) => Some(parse_response_signature(signature)?),
+        None => None,
+    };
+
+    let mut signatures = vec![];
+    let mut before = None;
+    loop {
+        let page = rpc_client.get_signatures_for_address_with_config(
+            account,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit: None,
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )?;
+
+        let last = match page.last() {
+            Some(last) => parse_response_signature(&last.signature)?,
+            None => break,
+        };
+        signatures.extend(page);
+
+        // 처음 감시하는 계정은 최신 페이지만 확인
+        if until.is_none() {
+            break;
+        }
+        before = Some(last);
+    }
+
+    signatures.reverse();
+    Ok(signatures)
+}
+
+fn latest_signature(rpc_client: &RpcClient, account: &Pubkey) -> Result<Option<String>> {
+    let page = rpc_client.get_signatures_for_address_with_config(
+        account,
+        GetConfirmedSignaturesForAddress2Config {
+            before: None,
+            until: None,
+            limit: Some(1),
+            commitment: Some(CommitmentConfig::confirmed()),
+        },
+    )?;
+    Ok(page.into_iter().next().map(|s| s.signature))
+}
+
+// logsSubscribe로 계정이 언급된 트랜잭션 알림 수신 (끊기면 재연결)
+fn spawn_logs_subscription(ws_url: &str, account: Pubkey, sender: Sender<Pubkey>) {
+    let ws_url = ws_url.to_string();
+    thread::spawn(move || loop {
+        let filter = RpcTransactionLogsFilter::Mentions(vec![account.to_string()]);
+        let config = RpcTransactionLogsConfig { commitment: Some(CommitmentConfig::confirmed()) };
+
+        match PubsubClient::logs_subscribe(&ws_url, filter, config) {
+            Ok((_subscription, receiver)) => {
+                while receiver.recv().is_ok() {
+                    if sender.send(account).is_err() {
+                        return;
+                    }
+                }
+                println!("logsSubscribe 연결 끊김 ({}), 폴링으로 대체하며 재연결합니다.", account);
+            }
+            Err(err) => println!("logsSubscribe 실패 ({}): {}, 폴링으로 대체합니다.", account, err),
+        }
+        thread::sleep(RECONNECT_DELAY);
+    });
+}
+
+// accountSubscribe로 지갑 잔액 변경 알림 수신 (끊기면 재연결)
+fn spawn_account_subscription(ws_url: &str, account: Pubkey, sender: Sender<Pubkey>) {
+    let ws_url = ws_url.to_string();
+    thread::spawn(move || loop {
+        let config = RpcAccountInfoConfig {
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..RpcAccountInfoConfig::default()
+        };
+
+        match PubsubClient::account_subscribe(&ws_url, &account, Some(config)) {
+            Ok((_subscription, receiver)) => {
+                while receiver.recv().is_ok() {
+                    if sender.send(account).is_err() {
+                        return;
+                    }
+                }
+                println!("accountSubscribe 연결 끊김 ({}), 폴링으로 대체하며 재연결합니다.", account);
+            }
+            Err(err) => println!("accountSubscribe 실패 ({}): {}, 폴링으로 대체합니다.", account, err),
+        }
+        thread::sleep(RECONNECT_DELAY);
+    });
+}
+
+impl Webhook {
+    // 서명된 JSON을 POST (실패 시 지수 백오프로 재시도)
+    fn deliver<T: Serialize>(&self, delivery_id: &str, payload: &T) -> Result<()> {
+        let body = serde_json::to_string(payload)?;
+        let mut delay = Duration::from_secs(1);
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|e| Error::Webhook(format!("시스템 시간 오류: {}", e)))?
+                .as_secs()
+                .to_string();
+            let mut request = self
+                .client
+                .post(&self.url)
+                .header("Content-Type", "application/json")
+                .header("X-Webhook-Id", delivery_id)
+                .header("X-Webhook-Timestamp", &timestamp);
+            if let Some(secret) = &self.secret {
+                request = request.header("X-Webhook-Signature", sign(secret, &timestamp, &body));
+            }
+
+            let error = match request.body(body.clone()).send() {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    // 429를 제외한 4xx는 바로 재시도해도 성공하지 않지만 (URL, 비밀키 설정 오류 등)
+                    // 전달된 것으로 보면 입금 이벤트를 잃으므로 실패로 반환해 다음 주기에 다시 시도한다
+                    if status.is_client_error() && status.as_u16() != 429 {
+                        return Err(Error::Webhook(format!("웹훅이 요청을 거부했습니다 (HTTP {}), 웹훅 URL과 비밀키를 확인하세요", status)));
+                    }
+                    format!("HTTP {}", status)
+                }
+                Err(err) => err.to_string(),
+            };
+
+            if attempt > self.max_retries {
+                return Err(Error::Webhook(format!("{}회 시도 후 실패: {}", attempt, error)));
+            }
+            println!("웹훅 전송 실패 ({}), {:?} 후 재시도합니다.", error, delay);
+            thread::sleep(delay);
+            delay = (delay * 2).min(MAX_RETRY_DELAY);
+        }
+    }
+}
+
+/// HMAC-SHA256("{timestamp}.{body}") 서명 헤더 값
+pub fn sign(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC 키 길이 제한 없음");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+// 최근에 처리한 서명 (지갑과 토큰 계정에 같은 트랜잭션이 보이므로 중복 전달 방지)
+// 상태 파일에는 처리한 순서대로 서명 목록으로 저장한다
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "Vec<String>", into = "Vec<String>")]
+struct ProcessedSet {
+    order: VecDeque<String>,
+    set: HashSet<String>,
+}
+
+impl ProcessedSet {
+    fn contains(&self, signature: &str) -> bool {
+        self.set.contains(signature)
+    }
+
+    fn insert(&mut self, signature: String) {
+        if self.set.insert(signature.clone()) {
+            self.order.push_back(signature);
+            if self.order.len() > PROCESSED_CAPACITY {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.set.remove(&oldest);
+                }
+            }
+        }
+    }
+}
+
+impl From<Vec<String>> for ProcessedSet {
+    fn from(signatures: Vec<String>) -> Self {
+        let mut processed = ProcessedSet::default();
+        for signature in signatures {
+            processed.insert(signature);
+        }
+        processed
+    }
+}
+
+impl From<ProcessedSet> for Vec<String> {
+    fn from(processed: ProcessedSet) -> Self {
+        processed.order.into()
+    }
+}
+
+// RPC 응답(또는 상태 파일)에 들어 있는 주소/서명 파싱
+fn parse_response_pubkey(input: &str) -> Result<Pubkey> {
+    Pubkey::from_str(input).map_err(|_| Error::Rpc { code: None, message: format!("응답의 주소 형식 오류: {}", input) })
+}
+
+fn parse_response_signature(input: &str) -> Result<Signature> {
+    Signature::from_str(input).map_err(|_| Error::Rpc { code: None, message: format!("서명 형식 오류: {}", input) })
+}
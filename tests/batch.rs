@@ -0,0 +1,262 @@
+// 대량 지급의 동시 실행, 저널 재개, 중단 동작 검증 (가짜 RPC 사용)
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use solana_transfer_bot::batch::{self, BatchOptions, BatchSummary, Interrupt, Journal, Payout, PayoutStatus};
+use solana_transfer_bot::fake_rpc::{FakeRpc, Method};
+use solana_transfer_bot::output::OutputFormat;
+use solana_transfer_bot::transfer::ConfirmOptions;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+const AMOUNT: u64 = 1_000_000;
+
+// 테스트마다 다른 임시 저널 경로
+fn journal_path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("batch-test-{}-{}.jsonl", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn payouts(count: usize) -> Vec<Payout> {
+    (0..count)
+        .map(|i| Payout {
+            key: format!("p{}", i),
+            recipient: Pubkey::new_unique(),
+            lamports: AMOUNT,
+        })
+        .collect()
+}
+
+fn options(concurrency: usize) -> BatchOptions {
+    BatchOptions {
+        concurrency,
+        fee: 0,
+        retry_failed: false,
+        confirm: ConfirmOptions {
+            poll_interval: Duration::from_millis(2),
+            resend_interval: Duration::from_secs(60),
+            timeout: Duration::from_millis(100),
+        },
+        output: OutputFormat::Table,
+    }
+}
+
+async fn run(
+    fake: &Arc<FakeRpc>,
+    payer: &Arc<Keypair>,
+    payouts: &[Payout],
+    path: &Path,
+    options: &BatchOptions,
+    interrupt: watch::Receiver<Interrupt>,
+) -> BatchSummary {
+    let journal = Arc::new(Journal::open(path).unwrap());
+    batch::run_payouts(fake.clone(), payer.clone(), payouts.to_vec(), journal, options, interrupt).await
+}
+
+fn setup() -> (Arc<FakeRpc>, Arc<Keypair>) {
+    let payer = Keypair::new();
+    let fake = FakeRpc::new().with_balance(&payer.pubkey(), LAMPORTS_PER_SOL);
+    (Arc::new(fake), Arc::new(payer))
+}
+
+#[tokio::test]
+async fn pays_everyone_concurrently() {
+    let (fake, payer) = setup();
+    let list = payouts(8);
+    let path = journal_path("concurrent");
+    fake.set_latency(Method::SendTransaction, Duration::from_millis(50));
+    let (_tx, rx) = watch::channel(Interrupt::Running);
+
+    let start = Instant::now();
+    let summary = run(&fake, &payer, &list, &path, &options(4), rx).await;
+
+    assert_eq!(summary.confirmed, 8);
+    assert!(start.elapsed() < Duration::from_millis(8 * 50), "{:?}", start.elapsed());
+    for payout in &list {
+        assert_eq!(fake.balance(&payout.recipient), AMOUNT);
+    }
+    let journal = Journal::open(&path).unwrap();
+    assert!(journal.entries().iter().all(|e| e.status == PayoutStatus::Confirmed));
+}
+
+#[tokio::test]
+async fn rerun_skips_confirmed_payouts() {
+    let (fake, payer) = setup();
+    let list = payouts(3);
+    let path = journal_path("rerun");
+    let (_tx, rx) = watch::channel(Interrupt::Running);
+
+    run(&fake, &payer, &list, &path, &options(2), rx.clone()).await;
+    let summary = run(&fake, &payer, &list, &path, &options(2), rx).await;
+
+    assert_eq!(summary.skipped, 3);
+    assert_eq!(summary.confirmed, 0);
+    assert_eq!(fake.sent_transactions().len(), 3);
+}
+
+#[tokio::test]
+async fn dropped_payouts_are_resent_only_after_blockhash_expiry() {
+    let (fake, payer) = setup();
+    let list = payouts(2);
+    let path = journal_path("dropped");
+    let (_tx, rx) = watch::channel(Interrupt::Running);
+    fake.drop_next_sends(2);
+
+    // 전송이 드롭되고 블록해시가 아직 유효하면 결과 미확인으로 남음
+    let first = run(&fake, &payer, &list, &path, &options(2), rx.clone()).await;
+    assert_eq!(first.unresolved, 2);
+    let journal = Journal::open(&path).unwrap();
+    assert!(journal.entries().iter().all(|e| e.status == PayoutStatus::Signed && e.signature.is_some()));
+
+    // 블록해시가 유효한 동안 다시 실행해도 새로 전송하지 않음
+    let second = run(&fake, &payer, &list, &path, &options(2), rx.clone()).await;
+    assert_eq!(second.unresolved, 2);
+    assert_eq!(fake.sent_transactions().len(), 2);
+
+    // 블록해시가 만료되면 한 번만 다시 전송
+    fake.expire_blockhashes();
+    let third = run(&fake, &payer, &list, &path, &options(2), rx).await;
+    assert_eq!(third.confirmed, 2);
+    assert_eq!(fake.sent_transactions().len(), 4);
+    for payout in &list {
+        assert_eq!(fake.balance(&payout.recipient), AMOUNT);
+    }
+}
+
+#[tokio::test]
+async fn drain_finishes_in_flight_and_leaves_rest_unstarted() {
+    let (fake, payer) = setup();
+    let list = payouts(6);
+    let path = journal_path("drain");
+    fake.set_latency(Method::SendTransaction, Duration::from_millis(100));
+    let (tx, rx) = watch::channel(Interrupt::Running);
+
+    let stopper = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        tx.send(Interrupt::Drain).unwrap();
+        tx
+    });
+    let summary = run(&fake, &payer, &list, &path, &options(2), rx).await;
+    drop(stopper.await);
+
+    assert_eq!(summary.confirmed, 2);
+    assert_eq!(summary.not_started, 4);
+    let journal = Journal::open(&path).unwrap();
+    assert_eq!(journal.entries().len(), 2);
+}
+
+#[tokio::test]
+async fn abort_leaves_signed_entries_that_resolve_without_resending() {
+    let (fake, payer) = setup();
+    let list = payouts(2);
+    let path = journal_path("abort");
+    // 전송은 바로 처리되지만 상태 확인이 느려 확인 단계에서 중단됨
+    fake.set_latency(Method::GetSignatureStatuses, Duration::from_millis(500));
+    let (tx, rx) = watch::channel(Interrupt::Running);
+
+    let stopper = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        tx.send(Interrupt::Abort).unwrap();
+        tx
+    });
+    let summary = run(&fake, &payer, &list, &path, &options(2), rx).await;
+    drop(stopper.await);
+
+    assert_eq!(summary.unresolved, 2);
+    let journal = Journal::open(&path).unwrap();
+    assert!(journal.entries().iter().all(|e| e.status == PayoutStatus::Signed));
+
+    // 다시 실행하면 서명 상태로 확인하고 새로 전송하지 않음
+    fake.set_latency(Method::GetSignatureStatuses, Duration::ZERO);
+    let (_tx, rx) = watch::channel(Interrupt::Running);
+    let resumed = run(&fake, &payer, &list, &path, &options(2), rx).await;
+
+    assert_eq!(resumed.confirmed, 2);
+    assert_eq!(fake.sent_transactions().len(), 2);
+    for payout in &list {
+        assert_eq!(fake.balance(&payout.recipient), AMOUNT);
+    }
+}
+
+#[tokio::test]
+async fn resume_finds_landed_payouts_evicted_from_the_status_cache() {
+    let (fake, payer) = setup();
+    let list = payouts(2);
+    let path = journal_path("evicted");
+    // 전송은 처리되었지만 확인 단계에서 중단되어 signed 상태로 남음
+    fake.set_latency(Method::GetSignatureStatuses, Duration::from_millis(500));
+    let (tx, rx) = watch::channel(Interrupt::Running);
+    let stopper = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        tx.send(Interrupt::Abort).unwrap();
+        tx
+    });
+    assert_eq!(run(&fake, &payer, &list, &path, &options(2), rx).await.unresolved, 2);
+    drop(stopper.await);
+
+    // 한참 뒤 재개: 블록해시는 만료되었고 최근 상태 캐시에서도 밀려남
+    fake.set_latency(Method::GetSignatureStatuses, Duration::ZERO);
+    fake.expire_blockhashes();
+    fake.evict_status_cache();
+    let (_tx, rx) = watch::channel(Interrupt::Running);
+    let resumed = run(&fake, &payer, &list, &path, &options(2), rx).await;
+
+    assert_eq!(resumed.confirmed, 2);
+    assert_eq!(fake.sent_transactions().len(), 2);
+    for payout in &list {
+        assert_eq!(fake.balance(&payout.recipient), AMOUNT);
+    }
+    let journal = Journal::open(&path).unwrap();
+    assert!(journal.entries().iter().all(|e| e.status == PayoutStatus::Confirmed));
+}
+
+#[tokio::test]
+async fn transfer_records_follow_payout_order_with_landed_slots() {
+    let (fake, payer) = setup();
+    let list = payouts(3);
+    let path = journal_path("records");
+    let (_tx, rx) = watch::channel(Interrupt::Running);
+    fake.drop_next_sends(1);
+
+    run(&fake, &payer, &list, &path, &options(1), rx).await;
+    let journal = Journal::open(&path).unwrap();
+    let records = batch::transfer_records(fake.as_ref(), &list, &journal, 7).await;
+
+    let ids: Vec<&str> = records.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, ["p0", "p1", "p2"]);
+    // 첫 전송은 드롭되어 결과 미확인
+    assert_eq!(records[0].status, "signed");
+    assert!(records[0].signature.is_some());
+    assert_eq!(records[0].slot, None);
+    for record in &records[1..] {
+        assert_eq!(record.status, "confirmed");
+        assert!(record.slot.is_some());
+        assert_eq!(record.fee, 7);
+    }
+}
+
+#[test]
+fn parses_payout_csv() {
+    let recipient = Pubkey::new_unique();
+    let csv = format!(
+        "recipient,amount,id\n# 주석\n{r},0.5,alice\n\n{r},1\n",
+        r = recipient
+    );
+
+    let list = batch::parse_payouts(&csv).unwrap();
+
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].key, "alice");
+    assert_eq!(list[0].lamports, LAMPORTS_PER_SOL / 2);
+    assert_eq!(list[1].key, "line-5");
+    assert!(batch::parse_payouts("not-an-address,1").is_err());
+    assert!(batch::parse_payouts(&format!("{},0", recipient)).is_err());
+    assert!(batch::parse_payouts(&format!("{r},1,a\n{r},2,a", r = recipient)).is_err());
+}
@@ -0,0 +1,82 @@
+// 명령줄 인자 파싱 (위치 인자, `--이름 값`, `--이름=값`, 값을 받지 않는 옵션)과 연결 없이 실행되는 하위 명령 검증
+use solana_transfer_bot::cli::{Args, FLAGS};
+use std::env;
+use std::fs;
+use std::process::Command;
+
+fn args(list: &[&str]) -> Args {
+    Args::parse(list.iter().map(|s| s.to_string()))
+}
+
+#[test]
+fn options_take_the_next_token_as_their_value() {
+    let parsed = args(&["batch", "payouts.csv", "--concurrency", "4", "--fee=recent:75", "--to", "a", "--to", "b"]);
+
+    assert_eq!((parsed.positional(0), parsed.positional(1), parsed.positional(2)), (Some("batch"), Some("payouts.csv"), None));
+    assert_eq!(parsed.value("concurrency"), Some("4"));
+    assert_eq!(parsed.value("fee"), Some("recent:75"));
+    assert_eq!((parsed.value("to"), parsed.values("to")), (Some("b"), vec!["a", "b"]));
+    // 값이 없으면 "true"
+    assert_eq!(args(&["--verbose"]).value("verbose"), Some("true"));
+    assert_eq!(args(&["--output", "--yes"]).value("output"), Some("true"));
+}
+
+#[test]
+fn flags_never_swallow_positionals() {
+    let parsed = args(&["--yes", "stake", "create", "1"]);
+    assert!(parsed.has("yes"));
+    assert_eq!((parsed.positional(0), parsed.positional(1), parsed.positional(2)), (Some("stake"), Some("create"), Some("1")));
+
+    for flag in FLAGS {
+        let parsed = args(&[&format!("--{}", flag), "schedule", "run"]);
+        assert_eq!((parsed.value(flag), parsed.positional(0)), (Some("true"), Some("schedule")), "--{}", flag);
+    }
+    assert_eq!(args(&["watch", "--no-ws", "addr", "--only-matched", "--interval", "5"]).positional(1), Some("addr"));
+    // 값을 주려면 `--이름=값`
+    assert_eq!(args(&["--yes=false"]).value("yes"), Some("false"));
+}
+
+#[test]
+fn parse_or_reports_invalid_values() {
+    let parsed = args(&["--count", "ten"]);
+    assert_eq!(parsed.parse_or("count", 1_usize).unwrap_err().kind(), "input");
+    assert_eq!(parsed.parse_or("missing", 7_usize).unwrap(), 7);
+    assert_eq!(parsed.require_positional(0, "주소").unwrap_err().kind(), "input");
+}
+
+#[test]
+fn usage_and_schedule_list_run_without_connecting() {
+    // 연결할 수 없는 RPC로 실행해도 사용법과 일정 목록은 성공
+    let file = env::temp_dir().join(format!("offline-schedule-{}.toml", std::process::id()));
+    fs::write(&file, "[[payment]]\nid = \"rent\"\nrecipient = \"11111111111111111111111111111111\"\namount = 0.1\ninterval = \"1h\"\n").unwrap();
+    let state = env::temp_dir().join(format!("offline-schedule-state-{}.json", std::process::id()));
+    let schedule = ["schedule", "list", "--file", file.to_str().unwrap(), "--state", state.to_str().unwrap()];
+
+    for (command, expected) in [
+        (vec!["stake"], "stake create"),
+        (vec!["stake", "help"], "stake create"),
+        (vec!["history"], "사용법"),
+        (vec!["batch"], "사용법"),
+        (vec!["serve", "help"], "SERVE_API_TOKEN"),
+        (vec!["watch"], "사용법"),
+        (schedule.to_vec(), "[rent]"),
+    ] {
+        let output = Command::new(env!("CARGO_BIN_EXE_solana_transfer_bot"))
+            .args(&command)
+            .args(["--rpc-url", "http://127.0.0.1:1", "--config", "/dev/null"])
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{:?}: {}", command, String::from_utf8_lossy(&output.stderr));
+        assert!(stdout.contains(expected), "{:?}: {}", command, stdout);
+    }
+
+    // 잘못된 stake 인자는 연결하기 전에 입력 오류
+    let output = Command::new(env!("CARGO_BIN_EXE_solana_transfer_bot"))
+        .args(["stake", "create", "-1", "--rpc-url", "http://127.0.0.1:1", "--config", "/dev/null"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("유효한 SOL 양이 아닙니다"));
+    fs::remove_file(file).unwrap();
+}
@@ -0,0 +1,133 @@
+// 오류 분류 검증 (RPC 클라이언트 오류 → 오류 종류, 벤치마크 실패 종류, 개인키/입력 오류)
+use solana_client::{
+    client_error::ClientError,
+    rpc_request::{RpcError, RpcResponseErrorData},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Keypair,
+    transaction::TransactionError,
+};
+use solana_transfer_bot::failure::{self, Failure, FailureKind, HttpStatus};
+use solana_transfer_bot::fake_rpc;
+use solana_transfer_bot::{cli::Args, keys, rpc, Error};
+use std::time::Duration;
+
+fn simulation(err: Option<TransactionError>) -> RpcSimulateTransactionResult {
+    RpcSimulateTransactionResult {
+        err,
+        logs: Some(vec!["Program log: Error: insufficient funds".to_string()]),
+        accounts: None,
+        units_consumed: None,
+        return_data: None,
+        inner_instructions: None,
+    }
+}
+
+fn preflight_failure(err: TransactionError) -> Error {
+    let client_error: solana_client::client_error::ClientError = RpcError::RpcResponseError {
+        code: -32002,
+        message: format!("Transaction simulation failed: {}", err),
+        data: RpcResponseErrorData::SendTransactionPreflightFailure(simulation(Some(err))),
+    }
+    .into();
+    client_error.into()
+}
+
+#[test]
+fn classifies_rpc_client_errors() {
+    assert_eq!(Error::from(fake_rpc::transport_error("connection refused")).kind(), "rpc_transport");
+    assert_eq!(Error::from(fake_rpc::rpc_error(429, "Too many requests")).kind(), "rate_limited");
+    assert_eq!(Error::from(fake_rpc::rpc_error(-32429, "rate limit")).kind(), "rate_limited");
+
+    match Error::from(fake_rpc::rpc_error(-32005, "Node is behind")) {
+        Error::Rpc { code, message } => {
+            assert_eq!(code, Some(-32005));
+            assert_eq!(message, "Node is behind");
+        }
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn preflight_failures_keep_program_logs() {
+    let error = preflight_failure(TransactionError::InstructionError(1, InstructionError::Custom(6001)));
+
+    match &error {
+        Error::Simulation { logs, .. } => assert_eq!(logs.len(), 1),
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(error.instruction_error(), Some((1, &InstructionError::Custom(6001))));
+    assert!(error.to_string().contains("insufficient funds"), "{}", error);
+}
+
+#[test]
+fn expired_blockhash_is_its_own_kind() {
+    assert!(matches!(preflight_failure(TransactionError::BlockhashNotFound), Error::BlockhashExpired));
+    let rejected: solana_client::client_error::ClientError = TransactionError::BlockhashNotFound.into();
+    assert!(matches!(Error::from(rejected), Error::BlockhashExpired));
+}
+
+#[test]
+fn simulation_results_convert_only_on_failure() {
+    assert!(Error::from_simulation(&simulation(None)).is_none());
+    let error = Error::from_simulation(&simulation(Some(TransactionError::AccountNotFound))).unwrap();
+    assert_eq!(error.kind(), "simulation");
+    assert!(error.instruction_error().is_none());
+}
+
+#[test]
+fn invalid_private_keys_are_key_errors() {
+    assert!(matches!(keys::create_keypair_from_base58("0OIl"), Err(Error::Key(_))));
+    assert!(matches!(keys::create_keypair_from_base58("3yZe7d"), Err(Error::Key(_))));
+
+    let keypair = Keypair::new();
+    let encoded = bs58::encode(keypair.to_bytes()).into_string();
+    assert_eq!(keys::create_keypair_from_base58(&encoded).unwrap().to_bytes(), keypair.to_bytes());
+}
+
+#[test]
+fn bad_arguments_are_input_errors() {
+    let args = Args::parse(["batch".to_string(), "--concurrency".to_string(), "many".to_string()]);
+
+    assert!(matches!(args.parse_or("concurrency", 4usize), Err(Error::Input(_))));
+    assert!(matches!(args.require_positional(1, "목록 파일"), Err(Error::Input(_))));
+}
+
+#[test]
+fn classifies_benchmark_failures() {
+    let rate_limited = Failure::from_client_error(&HttpStatus { status: 429, retry_after: Some(Duration::from_secs(2)) }.into());
+    assert_eq!(rate_limited.kind, FailureKind::RateLimited);
+    assert_eq!(rate_limited.status, Some(429));
+    assert_eq!(rate_limited.backoff(), Some(Duration::from_secs(2)));
+
+    let unavailable = Failure::from_client_error(&HttpStatus { status: 503, retry_after: None }.into());
+    assert_eq!((unavailable.kind, unavailable.backoff()), (FailureKind::ServerError, None));
+    let forbidden = Failure::from_client_error(&HttpStatus { status: 403, retry_after: None }.into());
+    assert_eq!(forbidden.kind, FailureKind::HttpStatus);
+
+    // JSON-RPC 요청 한도 초과는 Retry-After가 없으므로 기본 대기 시간
+    let json_rate_limited = Failure::from_client_error(&fake_rpc::rpc_error(-32429, "rate limit"));
+    assert_eq!(json_rate_limited.backoff(), Some(failure::RATE_LIMIT_PAUSE));
+    let behind = Failure::from_client_error(&fake_rpc::rpc_error(-32005, "Node is behind"));
+    assert_eq!((behind.kind, behind.code), (FailureKind::JsonRpc, Some(-32005)));
+
+    let refused = Failure::from_client_error(&fake_rpc::transport_error("connection refused"));
+    assert_eq!(refused.kind, FailureKind::ConnectionRefused);
+    assert_eq!(Failure::timeout(Duration::from_secs(5)).kind, FailureKind::Timeout);
+
+    // 벤치마크용 클라이언트의 HTTP 429도 라이브러리 오류에서는 요청 한도 초과
+    let error = Error::from(ClientError::from(HttpStatus { status: 429, retry_after: None }));
+    assert_eq!(error.kind(), "rate_limited");
+    assert_eq!(Failure::from_error(&error).kind, FailureKind::RateLimited);
+}
+
+#[test]
+fn parses_retry_after_header() {
+    assert_eq!(rpc::parse_retry_after("3"), Some(Duration::from_secs(3)));
+    assert_eq!(rpc::parse_retry_after(" 0 "), Some(Duration::ZERO));
+    // 지난 날짜는 바로 다시 요청
+    assert_eq!(rpc::parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::ZERO));
+    assert_eq!(rpc::parse_retry_after("soon"), None);
+}
@@ -0,0 +1,379 @@
+// 가짜 RPC 백엔드로 전송/벤치마크 흐름 검증 (네트워크 불필요)
+use solana_sdk::{
+    hash::Hash,
+    instruction::InstructionError,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use solana_transfer_bot::backend::RpcBackend;
+use solana_transfer_bot::benchmark::{self, Operation};
+use solana_transfer_bot::failure::{FailureKind, HttpStatus};
+use solana_transfer_bot::fake_rpc::{self, FakeRpc, Method, LAMPORTS_PER_SIGNATURE};
+use solana_transfer_bot::transfer::{self, ConfirmOptions};
+use solana_transfer_bot::Error;
+use std::time::{Duration, Instant};
+
+// 테스트가 빨리 끝나도록 짧은 확인 대기 설정
+fn fast_confirm() -> ConfirmOptions {
+    ConfirmOptions {
+        poll_interval: Duration::from_millis(1),
+        resend_interval: Duration::from_millis(5),
+        timeout: Duration::from_millis(200),
+    }
+}
+
+fn funded(lamports: u64) -> (FakeRpc, Keypair) {
+    let sender = Keypair::new();
+    let fake = FakeRpc::new().with_balance(&sender.pubkey(), lamports);
+    (fake, sender)
+}
+
+async fn send_with(fake: &FakeRpc, sender: &Keypair, recipient: &Pubkey, lamports: u64, options: &ConfirmOptions) -> Result<String, Error> {
+    let instruction = system_instruction::transfer(&sender.pubkey(), recipient, lamports);
+    transfer::send_instructions_with_options(fake, sender, &[], vec![instruction], 0, options)
+        .await
+        .map(|signature| signature.to_string())
+}
+
+#[tokio::test]
+async fn send_sol_moves_lamports_and_charges_fee() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    let recipient = Pubkey::new_unique();
+
+    let signature = transfer::send_sol(&fake, &sender, &recipient, 1_000, 0).await.unwrap();
+
+    assert_eq!(fake.balance(&recipient), 1_000);
+    assert_eq!(fake.balance(&sender.pubkey()), LAMPORTS_PER_SOL - 1_000 - LAMPORTS_PER_SIGNATURE);
+    assert_eq!(fake.sent_transactions()[0].signatures[0].to_string(), signature);
+    assert_eq!(fake.landed_count(), 1);
+}
+
+#[tokio::test]
+async fn priority_fee_adds_compute_budget_instruction() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    let recipient = Pubkey::new_unique();
+
+    transfer::send_sol(&fake, &sender, &recipient, 1_000, 0).await.unwrap();
+    transfer::send_sol(&fake, &sender, &recipient, 1_000, transfer::DEFAULT_PRIORITY_FEE).await.unwrap();
+
+    let sent = fake.sent_transactions();
+    assert_eq!(sent[0].message.instructions.len(), 1);
+    assert_eq!(sent[1].message.instructions.len(), 2);
+    assert_eq!(fake.balance(&recipient), 2_000);
+}
+
+#[tokio::test]
+async fn blockhash_error_aborts_before_sending() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    fake.fail_next(Method::GetLatestBlockhash, fake_rpc::transport_error("connection refused"));
+
+    let error = transfer::send_sol(&fake, &sender, &Pubkey::new_unique(), 1_000, 0).await.unwrap_err();
+
+    assert!(matches!(error, Error::Transport(_)), "{:?}", error);
+    assert!(error.is_retryable());
+    assert!(error.to_string().contains("connection refused"));
+    assert_eq!(fake.call_count(Method::SendTransaction), 0);
+}
+
+#[tokio::test]
+async fn rate_limited_send_is_reported() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    fake.fail_next(Method::SendTransaction, fake_rpc::rpc_error(429, "Too many requests"));
+
+    let error = send_with(&fake, &sender, &Pubkey::new_unique(), 1_000, &fast_confirm()).await.unwrap_err();
+
+    assert!(matches!(error, Error::RateLimited(_)), "{:?}", error);
+    assert!(error.to_string().contains("Too many requests"), "{}", error);
+    assert_eq!(fake.landed_count(), 0);
+    assert_eq!(fake.balance(&sender.pubkey()), LAMPORTS_PER_SOL);
+}
+
+#[tokio::test]
+async fn preflight_rejects_insufficient_funds() {
+    let (fake, sender) = funded(10_000);
+    let recipient = Pubkey::new_unique();
+
+    let error = send_with(&fake, &sender, &recipient, LAMPORTS_PER_SOL, &fast_confirm()).await.unwrap_err();
+
+    match &error {
+        Error::Simulation { logs, .. } => assert!(logs.iter().any(|log| log.contains("failed")), "{:?}", logs),
+        other => panic!("시뮬레이션 실패가 아님: {:?}", other),
+    }
+    assert_eq!(error.instruction_error(), Some((0, &InstructionError::Custom(1))));
+    assert!(error.to_string().contains("프로그램 로그"), "{}", error);
+    assert_eq!(fake.balance(&sender.pubkey()), 10_000);
+    assert_eq!(fake.balance(&recipient), 0);
+}
+
+#[tokio::test]
+async fn on_chain_failure_still_charges_fee() {
+    let (fake, sender) = funded(10_000);
+    fake.set_preflight(false);
+
+    let error = send_with(&fake, &sender, &Pubkey::new_unique(), LAMPORTS_PER_SOL, &fast_confirm()).await.unwrap_err();
+
+    match &error {
+        Error::Transaction { signature, error } => {
+            assert_eq!(signature.as_ref(), Some(&fake.sent_transactions()[0].signatures[0]));
+            assert_eq!(*error, TransactionError::InstructionError(0, InstructionError::Custom(1)));
+        }
+        other => panic!("온체인 실패가 아님: {:?}", other),
+    }
+    assert!(error.to_string().contains("트랜잭션 실패"), "{}", error);
+    assert_eq!(fake.balance(&sender.pubkey()), 10_000 - LAMPORTS_PER_SIGNATURE);
+}
+
+#[tokio::test]
+async fn dropped_transaction_lands_after_resend() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    let recipient = Pubkey::new_unique();
+    fake.drop_next_sends(1);
+
+    send_with(&fake, &sender, &recipient, 1_000, &fast_confirm()).await.unwrap();
+
+    assert_eq!(fake.sent_transactions().len(), 2);
+    assert_eq!(fake.balance(&recipient), 1_000);
+}
+
+#[tokio::test]
+async fn dropped_transaction_times_out() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    let recipient = Pubkey::new_unique();
+    fake.drop_next_sends(usize::MAX / 2);
+
+    let error = send_with(&fake, &sender, &recipient, 1_000, &fast_confirm()).await.unwrap_err();
+
+    assert!(matches!(error, Error::ConfirmationTimeout { .. }), "{:?}", error);
+    assert_eq!(fake.landed_count(), 0);
+    assert_eq!(fake.balance(&recipient), 0);
+}
+
+#[tokio::test]
+async fn dropped_transaction_stops_waiting_when_the_blockhash_expires() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    let recipient = Pubkey::new_unique();
+    fake.drop_next_sends(usize::MAX / 2);
+    let options = ConfirmOptions {
+        timeout: Duration::from_secs(5),
+        ..fast_confirm()
+    };
+
+    // 만료 전에는 확인 시간 초과가 아니라 계속 기다리며 재전송
+    let started = Instant::now();
+    let (result, _) = tokio::join!(send_with(&fake, &sender, &recipient, 1_000, &options), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        fake.expire_blockhashes();
+    });
+
+    assert!(matches!(result, Err(Error::BlockhashExpired)), "{:?}", result);
+    assert!(started.elapsed() < Duration::from_secs(1), "{:?}", started.elapsed());
+    assert!(fake.sent_transactions().len() > 1);
+    assert_eq!(fake.balance(&recipient), 0);
+}
+
+#[tokio::test]
+async fn evicted_status_is_found_only_with_history() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    let signature = transfer::send_sol(&fake, &sender, &Pubkey::new_unique(), 1_000, 0).await.unwrap().parse().unwrap();
+    fake.evict_status_cache();
+
+    assert_eq!(fake.get_signature_statuses(&[signature]).await.unwrap().value, vec![None]);
+    let status = fake.get_signature_statuses_with_history(&[signature]).await.unwrap().value;
+    assert!(status[0].as_ref().is_some_and(|status| status.err.is_none()), "{:?}", status);
+}
+
+#[tokio::test]
+async fn waits_until_confirmed() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    fake.set_confirmation_polls(3);
+
+    send_with(&fake, &sender, &Pubkey::new_unique(), 1_000, &fast_confirm()).await.unwrap();
+
+    assert_eq!(fake.call_count(Method::GetSignatureStatuses), 4);
+}
+
+#[tokio::test]
+async fn status_poll_errors_are_retried() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    fake.fail_next(Method::GetSignatureStatuses, fake_rpc::rpc_error(429, "Too many requests"));
+    fake.fail_next(Method::GetSignatureStatuses, fake_rpc::transport_error("timed out"));
+
+    send_with(&fake, &sender, &Pubkey::new_unique(), 1_000, &fast_confirm()).await.unwrap();
+
+    assert_eq!(fake.call_count(Method::GetSignatureStatuses), 3);
+}
+
+#[tokio::test]
+async fn expired_blockhash_is_rejected() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    let blockhash = fake.get_latest_blockhash().await.unwrap();
+    let instruction = system_instruction::transfer(&sender.pubkey(), &Pubkey::new_unique(), 1_000);
+    let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&sender.pubkey()), &[&sender], blockhash);
+    fake.expire_blockhashes();
+
+    let error = transfer::send_and_confirm(&fake, &transaction, &fast_confirm()).await.unwrap_err();
+
+    assert!(matches!(error, Error::BlockhashExpired), "{:?}", error);
+    assert!(!error.is_retryable());
+    assert_ne!(fake.get_latest_blockhash().await.unwrap(), blockhash);
+}
+
+#[tokio::test]
+async fn duplicate_transaction_is_rejected() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    let blockhash = fake.get_latest_blockhash().await.unwrap();
+    let instruction = system_instruction::transfer(&sender.pubkey(), &Pubkey::new_unique(), 1_000);
+    let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&sender.pubkey()), &[&sender], blockhash);
+
+    transfer::send_and_confirm(&fake, &transaction, &fast_confirm()).await.unwrap();
+    assert!(fake.send_transaction(&transaction).await.is_err());
+    assert_eq!(fake.landed_count(), 1);
+}
+
+#[tokio::test]
+async fn unsigned_transaction_is_rejected() {
+    let (fake, sender) = funded(LAMPORTS_PER_SOL);
+    let instruction = system_instruction::transfer(&sender.pubkey(), &Pubkey::new_unique(), 1_000);
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&sender.pubkey()));
+    transaction.message.recent_blockhash = Hash::new_unique();
+
+    assert!(fake.send_transaction(&transaction).await.is_err());
+    assert!(fake.sent_transactions().is_empty());
+}
+
+#[tokio::test]
+async fn simulation_reports_failure_without_changing_balances() {
+    let (fake, sender) = funded(10_000);
+    let blockhash = fake.get_latest_blockhash().await.unwrap();
+    let instruction = system_instruction::transfer(&sender.pubkey(), &Pubkey::new_unique(), LAMPORTS_PER_SOL);
+    let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&sender.pubkey()), &[&sender], blockhash);
+
+    let result = fake.simulate_transaction(&transaction).await.unwrap().value;
+
+    assert!(result.err.is_some());
+    assert!(result.logs.unwrap().iter().any(|log| log.contains("failed")));
+    assert_eq!(fake.balance(&sender.pubkey()), 10_000);
+}
+
+#[tokio::test]
+async fn benchmark_measures_injected_latency() {
+    let fake = FakeRpc::new();
+    fake.set_latency(Method::GetSlot, Duration::from_millis(30));
+
+    let slot = benchmark::measure(&fake, &Operation::Slot).await.unwrap();
+    let version = benchmark::measure(&fake, &Operation::Version).await.unwrap();
+
+    assert!(slot >= Duration::from_millis(30));
+    assert!(version < Duration::from_millis(30));
+}
+
+#[tokio::test]
+async fn benchmark_reports_errors_per_operation() {
+    let fake = FakeRpc::new();
+    fake.fail_next(Method::GetBalance, fake_rpc::rpc_error(-32005, "Node is behind"));
+
+    let results = benchmark::run_suite(&fake, &benchmark::standard_suite()).await;
+
+    assert_eq!(results.len(), 4);
+    assert!(results[..3].iter().all(|(_, result)| result.is_ok()));
+    assert_eq!(results[3].0, Operation::Balance(benchmark::LARGE_WALLET));
+    assert!(results[3].1.as_ref().unwrap_err().to_string().contains("Node is behind"));
+    assert_eq!(fake.call_count(Method::GetBalance), 1);
+}
+
+#[tokio::test]
+async fn benchmark_rounds_exclude_warmup_from_stats() {
+    let fake = FakeRpc::new();
+    // 첫 오류는 예열 라운드에서, 두 번째 오류는 첫 측정 라운드에서 발생
+    fake.fail_next(Method::GetBalance, fake_rpc::rpc_error(-32005, "Node is behind"));
+    fake.fail_next(Method::GetBalance, fake_rpc::rpc_error(-32005, "Node is behind"));
+
+    let rounds = benchmark::Rounds { iterations: 3, warmup: 1 };
+    let stats = benchmark::run_rounds(&fake, &benchmark::standard_suite(), rounds).await;
+
+    assert_eq!(fake.call_count(Method::GetSlot), 4);
+    assert_eq!(stats.len(), 4);
+    assert!(stats[..3].iter().all(|s| s.latency.len() == 3 && s.failures.is_empty()));
+    assert_eq!(stats[3].latency.len(), 2);
+    assert_eq!(stats[3].failures.count(FailureKind::JsonRpc), 1);
+    assert_eq!(stats[3].failures.codes().collect::<Vec<_>>(), [(-32005, 1)]);
+    assert!(stats[3].failures.last_error.as_ref().unwrap().contains("Node is behind"));
+}
+
+#[tokio::test]
+async fn benchmark_waits_for_retry_after_on_rate_limit() {
+    let fake = FakeRpc::new();
+    let retry_after = Duration::from_millis(200);
+    fake.fail_next(Method::GetSlot, HttpStatus { status: 429, retry_after: Some(retry_after) }.into());
+
+    let rounds = benchmark::Rounds { iterations: 2, warmup: 0 };
+    let started = std::time::Instant::now();
+    let stats = benchmark::run_rounds(&fake, &[Operation::Slot], rounds).await;
+
+    // 기다린 시간은 통계에 들어가지 않고, 실패는 요청 한도 초과로 분류됨
+    assert!(started.elapsed() >= retry_after);
+    assert_eq!(stats[0].latency.len(), 1);
+    assert!(stats[0].latency.percentile(100.0) < retry_after);
+    assert_eq!(stats[0].failures.count(FailureKind::RateLimited), 1);
+    assert_eq!(stats[0].failures.statuses().collect::<Vec<_>>(), [(429, 1)]);
+}
+
+#[tokio::test]
+async fn full_benchmark_suite_covers_every_method() {
+    let fake = FakeRpc::new().with_balance(&benchmark::LARGE_WALLET, LAMPORTS_PER_SOL);
+    fake.advance_slot(10);
+
+    let suite = benchmark::full_suite();
+    let results = benchmark::run_suite(&fake, &suite).await;
+
+    let methods: Vec<&str> = results.iter().map(|(operation, _)| operation.method()).collect();
+    assert_eq!(
+        methods,
+        [
+            "getVersion",
+            "getLatestBlockhash",
+            "getSlot",
+            "getBalance",
+            "getAccountInfo",
+            "getMultipleAccounts",
+            "getProgramAccounts",
+            "getSignatureStatuses",
+            "getBlock",
+            "simulateTransaction",
+            "getRecentPrioritizationFees",
+        ]
+    );
+    for (operation, result) in &results {
+        assert!(result.is_ok(), "{}: {:?}", operation.method(), result);
+    }
+    assert_eq!(fake.call_count(Method::GetBlock), 1);
+    assert_eq!(fake.call_count(Method::SimulateTransaction), 1);
+}
+
+#[tokio::test]
+async fn benchmark_excludes_request_preparation_from_timing() {
+    let fake = FakeRpc::new();
+    fake.set_latency(Method::GetSlot, Duration::from_millis(50));
+
+    // 블록 조회 전 슬롯 조회는 측정에 포함되지 않음
+    let block = benchmark::measure(&fake, &Operation::Block).await.unwrap();
+    assert!(block < Duration::from_millis(50));
+
+    // 준비 단계가 실패하면 해당 작업의 오류
+    fake.fail_next(Method::GetLatestBlockhash, fake_rpc::transport_error("연결 거부"));
+    let simulation = Operation::SimulateTransaction(benchmark::LARGE_WALLET);
+    assert!(benchmark::measure(&fake, &simulation).await.is_err());
+    assert_eq!(fake.call_count(Method::SimulateTransaction), 0);
+}
+
+#[test]
+fn benchmark_methods_are_selected_by_rpc_name() {
+    let suite = benchmark::parse_suite("getSlot, getBlock,getSlot").unwrap();
+    assert_eq!(suite, vec![Operation::Slot, Operation::Block]);
+    assert_eq!(benchmark::parse_suite("all").unwrap(), benchmark::full_suite());
+    assert_eq!(benchmark::parse_suite("getFoo").unwrap_err().kind(), "input");
+    assert_eq!(benchmark::parse_suite(",").unwrap_err().kind(), "input");
+}
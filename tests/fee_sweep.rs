@@ -0,0 +1,141 @@
+// 우선순위 수수료 스윕의 격자 옵션, 단계별 비용, 추천 가격과 라운드 실행 검증 (가짜 RPC 백엔드 사용)
+use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::fake_rpc::FakeRpc;
+use solana_transfer_bot::fee_sweep::{self, FeeLevel, LevelResult, SweepOptions, SweepReport};
+use solana_transfer_bot::landing::{Landing, LandingStats, TestRun};
+use solana_transfer_bot::output::{FeeSweepRecord, OutputFormat};
+use solana_transfer_bot::transfer::ConfirmOptions;
+use std::time::Duration;
+
+fn level(price: u64, cu_limit: Option<u32>) -> FeeLevel {
+    FeeLevel { price, cu_limit }
+}
+
+// `landed`개는 `confirmed_ms`에 확인되고 나머지는 포함되지 않은 단계
+fn result(price: u64, landed: usize, sent: usize, confirmed_ms: u64) -> LevelResult {
+    let mut stats = LandingStats::new();
+    for index in 0..sent {
+        let at = (index < landed).then(|| Duration::from_millis(confirmed_ms));
+        stats.record(&Ok(Landing {
+            signature: Signature::new_unique(),
+            sent_slot: Some(100),
+            landed_slot: at.map(|_| 102),
+            processed: at,
+            confirmed: at,
+            finalized: at,
+            err: None,
+        }));
+    }
+    LevelResult {
+        level: level(price, None),
+        stats,
+    }
+}
+
+#[test]
+fn parses_the_price_and_limit_grid() {
+    let args = |list: &[&str]| Args::parse(list.iter().map(|s| s.to_string()));
+
+    let options = SweepOptions::from_args(&args(&[])).unwrap();
+    assert_eq!(options, SweepOptions::default());
+    assert_eq!(options.levels.len(), fee_sweep::DEFAULT_PRICES.len());
+    assert!(options.levels.iter().all(|level| level.cu_limit.is_none()));
+
+    let options =
+        SweepOptions::from_args(&args(&["--prices", "0, 5000,5000", "--cu-limits", "1000,300", "--repeat", "2", "--target", "800"]))
+            .unwrap();
+    assert_eq!(
+        options.levels,
+        [level(0, Some(1000)), level(0, Some(300)), level(5000, Some(1000)), level(5000, Some(300))]
+    );
+    assert_eq!((options.repeat, options.target), (2, Duration::from_millis(800)));
+
+    for bad in [&["--prices", "cheap"][..], &["--prices", ","], &["--cu-limits", "0"], &["--repeat", "-1"]] {
+        assert_eq!(SweepOptions::from_args(&args(bad)).unwrap_err().kind(), "input", "{:?}", bad);
+    }
+}
+
+#[test]
+fn cost_is_the_base_fee_plus_price_times_limit() {
+    // 한도 명령이 없으면 메모와 전송 명령마다 200,000 CU
+    assert_eq!(level(10_000, None).compute_units(), 400_000);
+    assert_eq!(level(10_000, None).priority_fee(), 4_000);
+    assert_eq!(level(10_000, None).cost(), 9_000);
+    assert_eq!(level(1_000, Some(1_000)).priority_fee(), 1);
+    // 1 lamport 미만은 올림
+    assert_eq!(level(1, Some(300)).priority_fee(), 1);
+    assert_eq!(level(0, None).cost(), fee_sweep::BASE_FEE);
+}
+
+#[test]
+fn recommends_the_cheapest_level_that_meets_the_target() {
+    let report = SweepReport {
+        run_id: "run".to_string(),
+        target: Duration::from_secs(2),
+        levels: vec![
+            // 포함률 부족
+            result(0, 2, 4, 900),
+            // 목표 시간 초과
+            result(1_000, 4, 4, 3_000),
+            result(10_000, 4, 4, 1_500),
+            result(100_000, 4, 4, 800),
+        ],
+    };
+
+    assert_eq!(report.recommended().unwrap().level, level(10_000, None));
+    assert_eq!(report.levels[0].total_cost(), 2 * fee_sweep::BASE_FEE);
+
+    let records = FeeSweepRecord::from_report("a", &report);
+    assert_eq!(records.iter().map(|r| r.recommended).collect::<Vec<_>>(), [false, false, true, false]);
+    assert_eq!((records[0].landing_rate, records[0].failures.get("timeout")), (0.5, Some(&2)));
+    assert_eq!((records[2].fee_per_tx, records[2].total_cost), (9_000, 36_000));
+    assert_eq!(records[3].confirmed_p50_ms, Some(800.0));
+    assert_eq!(records[3].run_id, "run");
+
+    let slow = SweepReport {
+        target: Duration::from_millis(500),
+        ..report
+    };
+    assert!(slow.recommended().is_none());
+}
+
+#[tokio::test]
+async fn sends_every_level_in_each_round() {
+    let payer = Keypair::new();
+    let fake = FakeRpc::new().with_balance(&payer.pubkey(), 1_000_000_000);
+    let options = SweepOptions {
+        levels: vec![level(0, None), level(5_000, Some(1_000))],
+        repeat: 2,
+        target: Duration::from_secs(1),
+    };
+    let track = ConfirmOptions {
+        poll_interval: Duration::from_millis(10),
+        ..ConfirmOptions::default()
+    };
+    let run = TestRun::with_id("sweep");
+
+    let report = fee_sweep::run_sweep(&fake, &payer, &run, 1_000, &options, &track, OutputFormat::Json).await;
+
+    // 같은 블록해시로 만든 라운드끼리도 메모 순번이 달라 모두 포함됨
+    assert_eq!(fake.landed_count(), 4);
+    for result in &report.levels {
+        assert_eq!((result.stats.sent, result.stats.landed, result.stats.landing_rate()), (2, 2, 1.0));
+        assert_eq!(result.stats.slots_to_land.len(), 2);
+    }
+    // 가격과 한도는 compute budget 명령으로 붙음
+    let limited: Vec<_> = fake
+        .sent_transactions()
+        .into_iter()
+        .filter(|transaction| {
+            let message = &transaction.message;
+            message.instructions.iter().any(|instruction| {
+                message.account_keys[instruction.program_id_index as usize] == compute_budget::id()
+                    && instruction.data == ComputeBudgetInstruction::set_compute_unit_limit(1_000).data
+            })
+        })
+        .collect();
+    assert_eq!(limited.len(), 2);
+    assert_eq!(report.recommended().unwrap().level, level(0, None));
+}
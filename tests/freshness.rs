@@ -0,0 +1,119 @@
+// 엔드포인트 최신성 비교의 슬롯/블록 높이 차이 집계와 오래된 블록해시 감지 검증 (가짜 RPC 백엔드 사용)
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::failure::FailureKind;
+use solana_transfer_bot::fake_rpc::{self, FakeRpc, Method};
+use solana_transfer_bot::freshness::{self, FreshnessOptions, FreshnessReport, Lag, Observation};
+use solana_transfer_bot::output::FreshnessRecord;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn options(samples: u32) -> FreshnessOptions {
+    FreshnessOptions {
+        duration: Duration::from_millis(20) * samples,
+        interval: Duration::from_millis(20),
+        timeout: Duration::from_millis(100),
+    }
+}
+
+#[test]
+fn lag_is_measured_against_the_highest_value_in_each_sample() {
+    let observation = |slot: u64, last_valid: Option<u64>| Observation {
+        slots: [Some(slot), Some(slot - 2), None],
+        block_height: Some(slot - 10),
+        last_valid_block_height: last_valid,
+    };
+    let mut report = FreshnessReport::new(3);
+    report.record(&[observation(100, Some(240)), observation(97, Some(240)), observation(100, Some(200))]);
+    report.record(&[observation(104, Some(244)), observation(103, None), observation(101, Some(200))]);
+
+    assert_eq!(report.samples, 2);
+    assert_eq!(report.highest_slot, Some(104));
+    let [fresh, behind, stale] = &report.endpoints[..] else { unreachable!() };
+    assert_eq!(fresh.slot_lag[0], Lag { samples: 2, total: 0, max: 0 });
+    assert_eq!(behind.slot_lag[1], Lag { samples: 2, total: 4, max: 3 });
+    assert_eq!(stale.block_height_lag.average(), Some(1.5));
+    // 어느 엔드포인트도 값을 주지 않은 항목은 비교하지 않음
+    assert_eq!(fresh.slot_lag[2].average(), None);
+
+    // 블록해시를 돌려주지 않은 표본은 비교에서 빠짐
+    assert_eq!(behind.blockhash_lag, Lag { samples: 1, total: 0, max: 0 });
+    assert_eq!(stale.blockhash_lag, Lag { samples: 2, total: 84, max: 44 });
+    assert!(stale.stale_blockhash());
+    assert!(!fresh.stale_blockhash() && !behind.stale_blockhash());
+}
+
+#[tokio::test]
+async fn compares_endpoints_polled_at_the_same_time() {
+    let endpoint = |slots: u64, new_blockhash: bool| {
+        let fake = Arc::new(FakeRpc::new());
+        fake.advance_slot(slots);
+        fake.set_commitment_lag(2, 20);
+        if new_blockhash {
+            fake.expire_blockhashes();
+        }
+        fake
+    };
+    let fresh = endpoint(30, true);
+    let behind = endpoint(25, true);
+    // 슬롯은 따라오지만 블록해시는 처음 것을 계속 돌려줌
+    let stale = endpoint(30, false);
+
+    let report = freshness::run_freshness(&[fresh.clone(), behind.clone(), stale.clone()], &options(3)).await;
+
+    assert_eq!(report.samples, 3);
+    assert_eq!(report.highest_slot, Some(31));
+    assert_eq!(fresh.call_count(Method::GetSlot), 9);
+    assert_eq!(fresh.call_count(Method::GetBlockHeight), 3);
+    assert_eq!(fresh.call_count(Method::GetLatestBlockhash), 3);
+
+    let [fresh, behind, stale] = &report.endpoints[..] else { unreachable!() };
+    assert_eq!(fresh.slot_lag.map(|lag| lag.max), [0, 0, 0]);
+    assert_eq!(behind.slot_lag.map(|lag| lag.average()), [Some(5.0); 3]);
+    assert_eq!(behind.block_height_lag, Lag { samples: 3, total: 15, max: 5 });
+    assert_eq!(stale.block_height_lag.max, 0);
+    // 뒤처진 엔드포인트도 자기 슬롯의 블록해시는 최신
+    assert_eq!(behind.blockhash_lag.average(), Some(5.0));
+    assert_eq!(stale.blockhash_lag.average(), Some(30.0));
+    assert!(stale.stale_blockhash());
+    assert!(!fresh.stale_blockhash() && !behind.stale_blockhash());
+}
+
+#[tokio::test]
+async fn failed_requests_are_counted_and_left_out_of_the_comparison() {
+    let healthy = Arc::new(FakeRpc::new());
+    healthy.advance_slot(10);
+    let flaky = Arc::new(FakeRpc::new());
+    flaky.fail_next(Method::GetSlot, fake_rpc::rpc_error(429, "Too many requests"));
+    flaky.set_latency(Method::GetBlockHeight, Duration::from_secs(5));
+
+    let report = freshness::run_freshness(&[healthy, flaky], &options(2)).await;
+
+    let flaky = &report.endpoints[1];
+    assert_eq!(flaky.failures.count(FailureKind::RateLimited), 1);
+    assert_eq!(flaky.failures.count(FailureKind::Timeout), 2);
+    assert_eq!(flaky.block_height_lag.samples, 0);
+    assert_eq!(flaky.slot_lag.iter().map(|lag| lag.samples).sum::<usize>(), 5);
+    assert_eq!(flaky.blockhash_lag.max, 0);
+
+    let records = FreshnessRecord::from_report(&["a".to_string(), "b".to_string()], &report);
+    assert_eq!(records[1].block_height_lag_avg, None);
+    assert_eq!(records[1].confirmed_lag_max, Some(10));
+    assert!(!records[1].stale_blockhash);
+    assert_eq!(records[1].failures.get("timeout"), Some(&2));
+}
+
+#[test]
+fn parses_freshness_options() {
+    let args = |list: &[&str]| Args::parse(list.iter().map(|s| s.to_string()));
+
+    let options = FreshnessOptions::from_args(&args(&[])).unwrap();
+    assert_eq!(options, FreshnessOptions::default());
+    assert_eq!(options.samples(), 10);
+
+    let options = FreshnessOptions::from_args(&args(&["--duration", "3", "--interval", "400", "--timeout", "800"])).unwrap();
+    assert_eq!((options.interval, options.timeout), (Duration::from_millis(400), Duration::from_millis(800)));
+    assert_eq!(options.samples(), 8);
+
+    assert_eq!(FreshnessOptions::from_args(&args(&["--interval", "0"])).unwrap_err().kind(), "input");
+    assert_eq!(FreshnessOptions::from_args(&args(&["--duration", "soon"])).unwrap_err().kind(), "input");
+}
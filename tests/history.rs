@@ -0,0 +1,258 @@
+// 내역 내보내기의 잔액 변동 해석(수수료 분리, 상대 주소, 주소 조회 테이블, 토큰), 금액 표기,
+// 공통 --output 형식과 --file 경로, 커서 재개와 잘라내기 검증 (로컬 JSON-RPC 스텁 사용)
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use solana_transaction_status::{Encodable, EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::history::{self, HistoryRecord};
+use solana_transfer_bot::output::OutputFormat;
+use std::env;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Response, Server};
+
+const FEE: u64 = 5_000;
+
+fn args(list: &[&str]) -> Args {
+    Args::parse(["history", "wallet"].iter().chain(list).map(|s| s.to_string()))
+}
+
+// 보내는 지갑이 수수료를 내고 다른 계정에 SOL을 보내는 트랜잭션
+fn transfer(sender: &Keypair, recipient: &Pubkey, lamports: u64) -> Transaction {
+    let instruction = system_instruction::transfer(&sender.pubkey(), recipient, lamports);
+    Transaction::new_signed_with_payer(&[instruction], Some(&sender.pubkey()), &[sender], Hash::new_unique())
+}
+
+// getTransaction 응답 형식의 JSON
+fn confirmed_json(slot: u64, transaction: &Transaction, meta: Value) -> Value {
+    json!({ "slot": slot, "blockTime": 1_700_000_000 + slot as i64, "transaction": transaction.encode(UiTransactionEncoding::Base64), "meta": meta })
+}
+
+fn confirmed(transaction: &Transaction, meta: Value) -> EncodedConfirmedTransactionWithStatusMeta {
+    serde_json::from_value(confirmed_json(7, transaction, meta)).unwrap()
+}
+
+fn meta(pre: &[u64], post: &[u64]) -> Value {
+    json!({ "err": null, "status": { "Ok": null }, "fee": FEE, "preBalances": pre, "postBalances": post })
+}
+
+fn token_balance(index: usize, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Value {
+    json!({
+        "accountIndex": index,
+        "mint": mint.to_string(),
+        "owner": owner.to_string(),
+        "programId": spl_token::id().to_string(),
+        "uiTokenAmount": { "amount": amount.to_string(), "decimals": 6, "uiAmount": null, "uiAmountString": "" }
+    })
+}
+
+#[test]
+fn formats_amounts_with_decimals_and_sign() {
+    assert_eq!(history::format_amount(-1_500_000, 9), "-0.0015");
+    assert_eq!(history::format_amount(1_000_000_000, 9), "1");
+    assert_eq!(history::format_amount(1_000_000_001, 9), "1.000000001");
+    assert_eq!(history::format_amount(-2_500_000, 6), "-2.5");
+    assert_eq!(history::format_amount(0, 9), "0");
+    assert_eq!(history::format_amount(123, 2), "1.23");
+    assert_eq!(history::format_amount(-42, 0), "-42");
+}
+
+#[test]
+fn sol_change_excludes_the_fee_and_names_the_counterparty() {
+    let sender = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let transaction = transfer(&sender, &recipient, 1_000_000);
+    // 보내는 쪽은 전송액과 수수료만큼 줄어듦 (마지막 계정은 시스템 프로그램)
+    let confirmed = confirmed(&transaction, meta(&[10_000_000, 0, 1], &[8_995_000, 1_000_000, 1]));
+
+    let sent = history::decode_transaction(&sender.pubkey(), Some("rent".to_string()), &confirmed);
+    assert_eq!(sent.len(), 1);
+    let record = &sent[0];
+    assert_eq!((record.asset.as_str(), record.change.as_str()), ("SOL", "-0.001"));
+    assert_eq!((record.fee_lamports, record.fee_payer), (FEE, true));
+    assert_eq!(record.counterparty, Some(recipient.to_string()));
+    assert_eq!((record.signature.clone(), record.slot, record.status.as_str()), (transaction.signatures[0].to_string(), 7, "success"));
+    assert_eq!(record.memo.as_deref(), Some("rent"));
+
+    // 받는 쪽은 수수료를 내지 않고, 상대는 수수료를 되돌린 변동 기준으로 보낸 지갑
+    let received = history::decode_transaction(&recipient, None, &confirmed);
+    assert_eq!(received[0].change, "0.001");
+    assert_eq!((received[0].fee_lamports, received[0].fee_payer), (0, false));
+    assert_eq!(received[0].counterparty, Some(sender.pubkey().to_string()));
+}
+
+#[test]
+fn failed_and_unchanged_transactions_keep_a_fee_row() {
+    let sender = Keypair::new();
+    let transaction = transfer(&sender, &Pubkey::new_unique(), 1_000_000);
+    let mut failed = meta(&[10_000_000, 0, 1], &[9_995_000, 0, 1]);
+    failed["err"] = json!({ "InstructionError": [0, { "Custom": 1 }] });
+    failed["status"] = json!({ "Err": { "InstructionError": [0, { "Custom": 1 }] } });
+
+    let records = history::decode_transaction(&sender.pubkey(), None, &confirmed(&transaction, failed));
+    assert_eq!(records.len(), 1);
+    assert_eq!((records[0].status.as_str(), records[0].change.as_str(), records[0].fee_lamports), ("failed", "0", FEE));
+    assert_eq!(records[0].counterparty, None);
+}
+
+#[test]
+fn wallet_found_through_loaded_addresses() {
+    let sender = Keypair::new();
+    let looked_up = Pubkey::new_unique();
+    let transaction = transfer(&sender, &Pubkey::new_unique(), 1);
+    // 주소 조회 테이블로 불러온 계정은 정적 계정 키 뒤에 온다
+    let mut meta = meta(&[10_000_000, 0, 1, 0], &[9_495_000, 0, 1, 500_000]);
+    meta["loadedAddresses"] = json!({ "writable": [looked_up.to_string()], "readonly": [] });
+    let confirmed = confirmed(&transaction, meta);
+
+    let keys = history::account_keys(&confirmed);
+    assert_eq!(keys.len(), 4);
+    assert_eq!(keys[3], looked_up.to_string());
+    let records = history::decode_transaction(&looked_up, None, &confirmed);
+    assert_eq!(records[0].change, "0.0005");
+    assert_eq!(records[0].counterparty, Some(sender.pubkey().to_string()));
+}
+
+#[test]
+fn token_changes_follow_owned_accounts() {
+    let (payer, wallet, other) = (Keypair::new(), Pubkey::new_unique(), Pubkey::new_unique());
+    let (mint, other_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let transaction = transfer(&payer, &Pubkey::new_unique(), 1);
+    let mut meta = meta(&[10_000_000, 0, 1], &[9_995_000, 0, 1]);
+    // 계정 1: 지갑의 기존 토큰 계정, 계정 2: 상대의 토큰 계정, 계정 3: 이 트랜잭션에서 만든 지갑의 다른 민트 계정
+    meta["preTokenBalances"] = json!([token_balance(1, &mint, &wallet, 500_000), token_balance(2, &mint, &other, 5_000_000)]);
+    meta["postTokenBalances"] = json!([
+        token_balance(1, &mint, &wallet, 3_000_000),
+        token_balance(2, &mint, &other, 2_500_000),
+        token_balance(3, &other_mint, &wallet, 7),
+    ]);
+    let confirmed = confirmed(&transaction, meta);
+
+    let mut records: Vec<HistoryRecord> = history::decode_transaction(&wallet, None, &confirmed);
+    records.sort_by(|a, b| a.change.cmp(&b.change));
+    assert_eq!(records.len(), 2, "{:?}", records);
+    assert_eq!((records[0].asset.clone(), records[0].change.as_str()), (other_mint.to_string(), "0.000007"));
+    assert_eq!(records[0].counterparty, None);
+    assert_eq!((records[1].asset.clone(), records[1].change.as_str()), (mint.to_string(), "2.5"));
+    assert_eq!(records[1].counterparty, Some(other.to_string()));
+    // 수수료를 내지 않은 지갑
+    assert!(records.iter().all(|record| record.fee_lamports == 0 && !record.fee_payer));
+
+    let sent = history::decode_transaction(&other, None, &confirmed);
+    assert_eq!((sent[0].change.as_str(), sent[0].counterparty.clone()), ("-2.5", Some(wallet.to_string())));
+}
+
+#[test]
+fn output_selects_format_and_file_is_a_separate_path() {
+    assert_eq!(history::format(&args(&[])).unwrap(), OutputFormat::Table);
+    assert_eq!(history::format(&args(&["--file", "history.csv"])).unwrap(), OutputFormat::Csv);
+    assert_eq!(history::format(&args(&["--output", "json"])).unwrap(), OutputFormat::Json);
+    assert_eq!(history::format(&args(&["--output", "json", "--file", "json"])).unwrap(), OutputFormat::Json);
+
+    // 파일 경로를 --output에 넘기거나 표를 파일로 내보내면 오류
+    assert_eq!(history::format(&args(&["--output", "history.csv"])).unwrap_err().kind(), "input");
+    assert_eq!(history::format(&args(&["--output", "table", "--file", "history.txt"])).unwrap_err().kind(), "input");
+}
+
+// 지갑 하나의 서명 목록(최신순)과 트랜잭션을 돌려주는 JSON-RPC 스텁 (URL 반환)
+fn rpc_stub(signatures: Vec<String>, transactions: Vec<Value>) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let chain = Arc::new(Mutex::new((signatures, transactions)));
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let request_json: Value = serde_json::from_str(&body).unwrap();
+            let params = &request_json["params"];
+            let (signatures, transactions) = &*chain.lock().unwrap();
+            let result = match request_json["method"].as_str().unwrap() {
+                "getSignaturesForAddress" => {
+                    let config = &params[1];
+                    let start = match config["before"].as_str() {
+                        Some(before) => signatures.iter().position(|s| s == before).map_or(signatures.len(), |i| i + 1),
+                        None => 0,
+                    };
+                    let limit = config["limit"].as_u64().unwrap_or(1000) as usize;
+                    let page: Vec<Value> = signatures[start..]
+                        .iter()
+                        .take(limit)
+                        .map(|signature| {
+                            let slot = transactions.iter().find(|t| signature_in(t) == *signature).unwrap()["slot"].clone();
+                            json!({ "signature": signature, "slot": slot, "err": null, "memo": null, "blockTime": null, "confirmationStatus": "finalized" })
+                        })
+                        .collect();
+                    json!(page)
+                }
+                "getTransaction" => transactions.iter().find(|t| signature_in(t) == params[0].as_str().unwrap()).unwrap().clone(),
+                "getVersion" => json!({ "solana-core": "1.18.26", "feature-set": 0 }),
+                other => panic!("예상하지 못한 RPC 메서드: {}", other),
+            };
+            let reply = json!({ "jsonrpc": "2.0", "result": result, "id": request_json["id"] });
+            let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+            let _ = request.respond(Response::from_string(reply.to_string()).with_header(header));
+        }
+    });
+    url
+}
+
+// 스텁에 저장한 getTransaction 응답의 서명
+fn signature_in(transaction: &Value) -> String {
+    let confirmed: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_value(transaction.clone()).unwrap();
+    confirmed.transaction.transaction.decode().unwrap().signatures[0].to_string()
+}
+
+#[test]
+fn cursor_resumes_after_the_last_saved_transaction_and_truncates_later_rows() {
+    let sender = Keypair::new();
+    let wallet = sender.pubkey().to_string();
+    // 최신순 트랜잭션 4개
+    let transactions: Vec<Transaction> = (0..4).map(|_| transfer(&sender, &Pubkey::new_unique(), 1_000)).collect();
+    let signatures: Vec<String> = transactions.iter().map(|t| t.signatures[0].to_string()).collect();
+    let responses: Vec<Value> = transactions
+        .iter()
+        .enumerate()
+        .map(|(i, t)| confirmed_json(100 - i as u64, t, meta(&[10_000_000, 0, 1], &[9_994_000, 1_000, 1])))
+        .collect();
+    let rpc_client = RpcClient::new(rpc_stub(signatures.clone(), responses));
+
+    let dir = env::temp_dir();
+    let file = dir.join(format!("history-test-{}.csv", std::process::id()));
+    let cursor = dir.join(format!("history-test-{}.cursor", std::process::id()));
+    let (file, cursor) = (file.to_str().unwrap().to_string(), cursor.to_str().unwrap().to_string());
+    let _ = (fs::remove_file(&file), fs::remove_file(&cursor));
+    let run = |extra: &[&str]| {
+        let mut list = vec!["history", &wallet, "--output", "csv", "--file", &file, "--cursor", &cursor, "--page-size", "1"];
+        list.extend_from_slice(extra);
+        history::run(&rpc_client, &Args::parse(list.iter().map(|s| s.to_string()))).unwrap();
+    };
+
+    // 첫 실행은 두 개만 가져오고 커서에 마지막 서명을 남김
+    run(&["--limit", "2"]);
+    let saved: Value = serde_json::from_str(&fs::read_to_string(&cursor).unwrap()).unwrap();
+    assert_eq!(saved["last_signature"], signatures[1]);
+    assert_eq!(saved["output_len"], fs::metadata(&file).unwrap().len());
+
+    // 커서를 저장하기 전에 중단된 것처럼 다음 트랜잭션의 줄을 이미 써 둠
+    fs::write(&file, fs::read_to_string(&file).unwrap() + &format!("{},99,partial\n", signatures[2])).unwrap();
+    run(&[]);
+
+    let content = fs::read_to_string(&file).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert!(lines[0].starts_with("signature,slot"), "{}", content);
+    let rows: Vec<&str> = lines[1..].iter().map(|line| line.split(',').next().unwrap()).collect();
+    assert_eq!(rows, signatures.iter().map(String::as_str).collect::<Vec<_>>(), "{}", content);
+    assert!(!content.contains("partial"));
+
+    let saved: Value = serde_json::from_str(&fs::read_to_string(&cursor).unwrap()).unwrap();
+    assert_eq!(saved["last_signature"], signatures[3]);
+    let _ = (fs::remove_file(&file), fs::remove_file(&cursor));
+}
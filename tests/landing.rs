@@ -0,0 +1,173 @@
+// 트랜잭션 포함 측정(메모로 구분되는 측정 전송, 전송/포함 슬롯, 커미트먼트별 도달 시간, 재전송, 포함률 집계) 검증 (가짜 RPC 백엔드 사용)
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_transfer_bot::failure::FailureKind;
+use solana_transfer_bot::fake_rpc::{self, FakeRpc, Method};
+use solana_transfer_bot::landing::{self, Landing, LandingStats, TestRun};
+use solana_transfer_bot::output::LandingRecord;
+use solana_transfer_bot::transfer::{self, ConfirmOptions};
+use solana_transfer_bot::Error;
+use std::time::Duration;
+
+fn options() -> ConfirmOptions {
+    ConfirmOptions {
+        poll_interval: Duration::from_millis(10),
+        resend_interval: Duration::from_millis(30),
+        timeout: Duration::from_secs(2),
+    }
+}
+
+async fn signed_transfer(fake: &FakeRpc, payer: &Keypair) -> Transaction {
+    let instruction = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1_000);
+    transfer::build_transaction(fake, payer, &[], vec![instruction], 5).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_transfers_are_unique_within_and_across_runs() {
+    let payer = Keypair::new();
+    let fake = FakeRpc::new().with_balance(&payer.pubkey(), 1_000_000);
+    let run = TestRun::with_id("run-a");
+
+    // 같은 블록해시, 같은 금액이어도 순번이 달라 서명이 다름
+    for expected in 1..=2 {
+        let (sequence, instructions) = run.next_transfer(&payer.pubkey(), 1_000);
+        assert_eq!(sequence, expected);
+        transfer::send_instructions(&fake, &payer, &[], instructions, 5).await.unwrap();
+    }
+    assert_eq!(fake.landed_count(), 2);
+    let memos: Vec<String> = fake
+        .sent_transactions()
+        .iter()
+        .flat_map(|transaction| {
+            let message = &transaction.message;
+            message
+                .instructions
+                .iter()
+                .filter(move |instruction| message.account_keys[instruction.program_id_index as usize] == spl_memo::id())
+                .map(|instruction| String::from_utf8(instruction.data.clone()).unwrap())
+        })
+        .collect();
+    assert_eq!(memos, ["rpc-bench:run-a:1", "rpc-bench:run-a:2"]);
+    // 자기 자신에게 보내므로 수수료만 나감
+    assert_eq!(fake.balance(&payer.pubkey()), 1_000_000 - 2 * fake_rpc::LAMPORTS_PER_SIGNATURE);
+
+    // 다른 기기의 실행은 다른 ID를 받으므로 같은 순번도 겹치지 않음
+    let (first, second) = (TestRun::new(), TestRun::new());
+    assert_ne!(first.id(), second.id());
+    assert_ne!(first.next_transfer(&payer.pubkey(), 1_000).1, second.next_transfer(&payer.pubkey(), 1_000).1);
+}
+
+fn landing(sent_slot: u64, landed_slot: Option<u64>, confirmed_ms: u64) -> Landing {
+    let at = landed_slot.map(|_| Duration::from_millis(confirmed_ms));
+    Landing {
+        signature: Signature::new_unique(),
+        sent_slot: Some(sent_slot),
+        landed_slot,
+        processed: at.map(|at| at / 2),
+        confirmed: at,
+        finalized: at.map(|at| at * 10),
+        err: None,
+    }
+}
+
+#[tokio::test]
+async fn measures_slots_to_land_and_each_commitment() {
+    let payer = Keypair::new();
+    let fake = FakeRpc::new().with_balance(&payer.pubkey(), 1_000_000);
+    fake.advance_slot(10);
+    fake.set_confirmation_polls(2);
+    fake.set_finalization_polls(3);
+    let transaction = signed_transfer(&fake, &payer).await;
+
+    let landing = landing::track(&fake, &transaction, &options()).await.unwrap();
+
+    assert_eq!(landing.signature, transaction.signatures[0]);
+    assert_eq!((landing.sent_slot, landing.landed_slot, landing.slots_to_land()), (Some(11), Some(12), Some(1)));
+    let (processed, confirmed, finalized) = (landing.processed.unwrap(), landing.confirmed.unwrap(), landing.finalized.unwrap());
+    // 첫 조회에서 processed, 세 번째에서 confirmed, 여섯 번째에서 finalized
+    assert!(processed < confirmed && confirmed < finalized, "{:?}", landing);
+    assert!(finalized >= Duration::from_millis(50), "{:?}", landing);
+    assert_eq!(fake.call_count(Method::GetSignatureStatuses), 6);
+    assert_eq!(landing.failure(), None);
+}
+
+#[tokio::test]
+async fn dropped_transactions_are_resent_and_land_in_a_later_slot() {
+    let payer = Keypair::new();
+    let fake = FakeRpc::new().with_balance(&payer.pubkey(), 1_000_000);
+    fake.drop_next_sends(1);
+    let transaction = signed_transfer(&fake, &payer).await;
+
+    // 재전송 전에 슬롯이 진행됨
+    let options = options();
+    let (landing, _) = tokio::join!(landing::track(&fake, &transaction, &options), async {
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        fake.advance_slot(3);
+    });
+    let landing = landing.unwrap();
+
+    assert_eq!(fake.sent_transactions().len(), 2);
+    assert_eq!((landing.sent_slot, landing.slots_to_land()), (Some(1), Some(4)));
+    assert!(landing.processed.unwrap() >= Duration::from_millis(30));
+}
+
+#[tokio::test]
+async fn stops_when_the_blockhash_expires_before_landing() {
+    let payer = Keypair::new();
+    let fake = FakeRpc::new().with_balance(&payer.pubkey(), 1_000_000);
+    fake.drop_next_sends(1);
+    let transaction = signed_transfer(&fake, &payer).await;
+    let options = options();
+    let (landing, _) = tokio::join!(landing::track(&fake, &transaction, &options), async {
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        fake.expire_blockhashes();
+    });
+    let landing = landing.unwrap();
+
+    assert!(!landing.landed());
+    assert_eq!((landing.processed, landing.slots_to_land()), (None, None));
+    // 타임아웃까지 기다리지 않고 재전송하려던 시점에 멈춤
+    assert_eq!(fake.sent_transactions().len(), 1);
+    assert!(fake.call_count(Method::GetSignatureStatuses) < 10);
+    assert_eq!(landing.failure().unwrap().kind, FailureKind::Timeout);
+
+    // 전송 요청 자체의 실패는 오류
+    let payer = Keypair::new();
+    let fake = FakeRpc::new();
+    let transaction = signed_transfer(&fake, &payer).await;
+    assert!(landing::track(&fake, &transaction, &options).await.is_err());
+}
+
+#[test]
+fn stats_report_landing_rate_and_slots_to_land() {
+    let mut stats = LandingStats::new();
+    stats.record(&Ok(landing(100, Some(101), 400)));
+    stats.record(&Ok(landing(100, Some(104), 800)));
+    stats.record(&Ok(landing(100, None, 0)));
+    let failed = Landing {
+        err: Some(TransactionError::InsufficientFundsForFee),
+        ..landing(100, Some(101), 500)
+    };
+    stats.record(&Ok(failed.clone()));
+    stats.record(&Err(Error::Transaction {
+        signature: None,
+        error: TransactionError::BlockhashNotFound,
+    }));
+
+    assert_eq!((stats.sent, stats.landed), (5, 3));
+    assert_eq!(stats.landing_rate(), 0.6);
+    assert_eq!((stats.average_slots(), stats.max_slots()), (Some(2.0), Some(4)));
+    // 실행에 실패한 트랜잭션은 포함되었지만 시간 통계에서는 빠짐
+    assert_eq!(stats.confirmed.len(), 2);
+    assert_eq!(stats.confirmed.summary().unwrap().max, Duration::from_millis(800));
+    assert_eq!(stats.failures.total(), 3);
+    assert_eq!(stats.failures.count(FailureKind::Timeout), 1);
+    assert_eq!(LandingStats::new().landing_rate(), 0.0);
+
+    let record = LandingRecord::new("a", 4, &Ok(failed));
+    assert_eq!((record.seq, record.slots_to_land, record.confirmed_ms), (4, Some(1), Some(500.0)));
+    assert_eq!(record.failure.as_deref(), Some("other"));
+    let record = LandingRecord::new("a", 3, &Ok(landing(100, None, 0)));
+    assert_eq!((record.landed_slot, record.processed_ms, record.failure.as_deref()), (None, None, Some("timeout")));
+}
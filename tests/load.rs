@@ -0,0 +1,157 @@
+// 동시 부하 테스트의 부하 형태, 구간 집계, 성능 저하 지점 검증 (가짜 RPC 백엔드 사용)
+use solana_transfer_bot::benchmark::Operation;
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::failure::{Failure, FailureCounts, FailureKind};
+use solana_transfer_bot::fake_rpc::{self, FakeRpc, Method};
+use solana_transfer_bot::load::{self, DegradationCause, LoadOptions, LoadReport, LoadShape, LoadWindow};
+use solana_transfer_bot::output::LoadRecord;
+use solana_transfer_bot::stats::LatencyHistogram;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn options(shape: LoadShape, mix: Vec<(Operation, u32)>) -> LoadOptions {
+    LoadOptions {
+        shape,
+        duration: Duration::from_millis(300),
+        timeout: Duration::from_millis(100),
+        window: Duration::from_millis(100),
+        mix,
+    }
+}
+
+fn args(list: &[&str]) -> Args {
+    Args::parse(list.iter().map(|s| s.to_string()))
+}
+
+#[tokio::test]
+async fn fixed_concurrency_keeps_workers_busy() {
+    let fake = Arc::new(FakeRpc::new());
+    fake.set_latency(Method::GetSlot, Duration::from_millis(10));
+    fake.set_latency(Method::GetVersion, Duration::from_millis(10));
+    let mix = vec![(Operation::Slot, 3), (Operation::Version, 1)];
+
+    let report = load::run_load(fake.clone(), &options(LoadShape::Concurrency(4), mix)).await;
+    let total = report.total();
+
+    assert_eq!(report.windows.len(), 3);
+    // 워커 4개가 요청 하나에 10ms 이상 걸리는 메서드를 3:1 비율로 호출
+    assert!((40..=120).contains(&total.requests), "{}", total.requests);
+    assert!(total.failures.is_empty());
+    let (slots, versions) = (fake.call_count(Method::GetSlot), fake.call_count(Method::GetVersion));
+    assert_eq!(slots + versions, total.requests);
+    assert!(slots >= versions * 2, "{} {}", slots, versions);
+}
+
+#[tokio::test]
+async fn target_rate_starts_requests_without_waiting_for_responses() {
+    let fake = Arc::new(FakeRpc::new());
+    // 응답이 보내는 간격보다 훨씬 느려도 요청 수는 목표 속도를 따름
+    fake.set_latency(Method::GetSlot, Duration::from_millis(50));
+
+    let rate = LoadShape::Rate { from: 100.0, to: 100.0 };
+    let report = load::run_load(fake.clone(), &options(rate, vec![(Operation::Slot, 1)])).await;
+    let total = report.total();
+
+    assert!((25..=31).contains(&total.requests), "{}", total.requests);
+    assert_eq!(total.latency.len(), total.requests);
+    assert!(report.windows.iter().all(|w| w.requests >= 7), "{:?}", report.windows);
+}
+
+#[tokio::test]
+async fn errors_and_timeouts_are_counted_separately() {
+    let fake = Arc::new(FakeRpc::new());
+    fake.set_latency(Method::GetSlot, Duration::from_millis(10));
+    fake.set_latency(Method::GetBalance, Duration::from_millis(500));
+    fake.fail_next(Method::GetSlot, fake_rpc::rpc_error(429, "Too many requests"));
+    let mix = vec![(Operation::Slot, 1), (Operation::Balance(solana_sdk::pubkey::Pubkey::new_unique()), 1)];
+
+    let report = load::run_load(fake.clone(), &options(LoadShape::Concurrency(2), mix)).await;
+    let total = report.total();
+
+    assert_eq!(total.errors(), 1);
+    assert_eq!(total.failures.count(FailureKind::RateLimited), 1);
+    assert_eq!(total.timeouts(), fake.call_count(Method::GetBalance));
+    assert!(total.failures.last_error.is_some());
+    // 절반이 타임아웃이므로 첫 구간부터 성능 저하
+    assert_eq!(report.degradation().unwrap().window, 0);
+}
+
+// 구간마다 같은 응답 시간의 성공 `count`개
+fn window(index: u64, latency_ms: u64, count: usize, failures: usize) -> LoadWindow {
+    let mut latency = LatencyHistogram::new();
+    for _ in 0..count {
+        latency.record(Duration::from_millis(latency_ms));
+    }
+    let mut counts = FailureCounts::new();
+    for _ in 0..failures {
+        counts.record(&Failure::new(FailureKind::ServerError, "HTTP 503"));
+    }
+    LoadWindow {
+        start: Duration::from_secs(index),
+        length: Duration::from_secs(1),
+        requests: count + failures,
+        latency,
+        failures: counts,
+    }
+}
+
+#[test]
+fn degradation_is_the_first_window_much_slower_than_baseline() {
+    let report = LoadReport {
+        duration: Duration::from_secs(5),
+        windows: vec![
+            // 성공이 적은 구간은 기준이 되지 않음
+            window(0, 5, 2, 0),
+            window(1, 20, 50, 0),
+            window(2, 35, 100, 0),
+            window(3, 45, 150, 1),
+            window(4, 90, 200, 20),
+        ],
+    };
+
+    let degradation = report.degradation().unwrap();
+    assert_eq!(degradation.window, 3);
+    assert_eq!(degradation.offered_rps, 151.0);
+    match degradation.cause {
+        DegradationCause::Latency { baseline, p50 } => {
+            assert_eq!(baseline, Duration::from_millis(20));
+            assert_eq!(p50, Duration::from_millis(45));
+        }
+        other => panic!("{:?}", other),
+    }
+
+    // 구간 레코드 뒤에 전체 레코드
+    let records = LoadRecord::from_report("https://rpc.example.com", &report);
+    assert_eq!(records.len(), 6);
+    assert!(records[3].degraded && !records[2].degraded);
+    assert_eq!(records[5].window_start_s, None);
+    assert_eq!(records[5].requests, 523);
+    assert_eq!(records[5].errors, 21);
+    assert_eq!(records[5].failures["http_5xx"], 21);
+
+    let steady = LoadReport {
+        windows: vec![window(0, 20, 50, 0), window(1, 30, 50, 2)],
+        ..report
+    };
+    assert!(steady.degradation().is_none());
+}
+
+#[test]
+fn parses_load_options() {
+    let defaults = LoadOptions::from_args(&args(&["--load"])).unwrap();
+    assert_eq!(defaults.shape, LoadShape::Concurrency(load::DEFAULT_CONCURRENCY));
+    assert_eq!(defaults.duration, load::DEFAULT_DURATION);
+    assert_eq!(defaults.mix.len(), 4);
+
+    let ramp = LoadOptions::from_args(&args(&[
+        "--rps", "500", "--ramp-from", "50", "--duration", "10", "--timeout", "2000", "--methods", "getSlot:3,getBlock,getSlot",
+    ]))
+    .unwrap();
+    assert_eq!(ramp.shape, LoadShape::Rate { from: 50.0, to: 500.0 });
+    assert_eq!(ramp.timeout, Duration::from_secs(2));
+    assert_eq!(ramp.mix, vec![(Operation::Slot, 4), (Operation::Block, 1)]);
+
+    for invalid in [&["--rps", "10", "--concurrency", "4"][..], &["--rps", "0"], &["--methods", "getSlot:0"], &["--methods", "getFoo"]] {
+        assert_eq!(LoadOptions::from_args(&args(invalid)).unwrap_err().kind(), "input", "{:?}", invalid);
+    }
+}
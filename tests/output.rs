@@ -0,0 +1,108 @@
+// 기계가 읽는 출력 형식(JSON/CSV) 스키마 검증
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::failure::{Failure, FailureCounts, FailureKind};
+use solana_transfer_bot::output::{self, LatencyRecord, OutputFormat, TransferRecord, SCHEMA_VERSION};
+use solana_transfer_bot::stats::LatencySummary;
+use std::time::Duration;
+
+fn latency_records() -> Vec<LatencyRecord> {
+    let samples: Vec<Duration> = (1..=10).map(|i| Duration::from_micros(i * 100)).collect();
+    let summary = LatencySummary::from_samples(&samples);
+    let failures = |kinds: &[FailureKind]| {
+        let mut counts = FailureCounts::new();
+        for kind in kinds {
+            counts.record(&Failure::new(*kind, kind.label()));
+        }
+        counts
+    };
+    let refused = [FailureKind::ConnectionRefused; 3];
+    vec![
+        LatencyRecord::new(
+            "https://rpc.example.com",
+            "getSlot",
+            summary.as_ref(),
+            &failures(&[FailureKind::RateLimited, FailureKind::Timeout]),
+        ),
+        LatencyRecord {
+            ip_family: Some("ipv6".to_string()),
+            jitter_ms: Some(0.25),
+            ..LatencyRecord::new("https://down.example.com", "total", None, &failures(&refused))
+        },
+        LatencyRecord {
+            landing_rate: Some(0.75),
+            slots_to_land: Some(2.5),
+            run_id: Some("20240501093000-1a2b3c4d".to_string()),
+            ..LatencyRecord::new("https://rpc.example.com", "confirmed", summary.as_ref(), &failures(&[FailureKind::Timeout]))
+        },
+    ]
+}
+
+#[test]
+fn parses_output_option() {
+    let args = |list: &[&str]| Args::parse(list.iter().map(|s| s.to_string()));
+
+    assert_eq!(OutputFormat::from_args(&args(&[])).unwrap(), OutputFormat::Table);
+    assert_eq!(OutputFormat::from_args(&args(&["--output", "json"])).unwrap(), OutputFormat::Json);
+    assert_eq!(OutputFormat::from_args(&args(&["--output=csv"])).unwrap(), OutputFormat::Csv);
+    assert_eq!(OutputFormat::from_args(&args(&["--output", "xml"])).unwrap_err().kind(), "input");
+    assert_eq!(OutputFormat::Table.render(&latency_records()), None);
+}
+
+#[test]
+fn json_output_is_versioned_and_round_trips() {
+    let records = latency_records();
+    let json = OutputFormat::Json.render(&records).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(value["schema_version"], SCHEMA_VERSION);
+    assert_eq!(value["kind"], "latency");
+    assert_eq!(value["records"][0]["samples"], 10);
+    assert_eq!(value["records"][0]["errors"], 2);
+    assert_eq!(value["records"][0]["avg_ms"], 0.55);
+    assert_eq!(value["records"][0]["p90_ms"], 0.9);
+    assert_eq!(value["records"][0]["stddev_ms"], 0.287);
+    assert!(value["records"][1]["min_ms"].is_null());
+    assert_eq!(value["records"][0]["error_rate"], 0.1667);
+    assert_eq!(value["records"][0]["failures"], serde_json::json!({"rate_limited": 1, "timeout": 1}));
+    assert_eq!(value["records"][1]["error_rate"], 1.0);
+    assert!(value["records"][0]["ip_family"].is_null());
+    assert_eq!(value["records"][1]["ip_family"], "ipv6");
+    assert!(value["records"][0]["landing_rate"].is_null());
+    assert_eq!(value["records"][2]["slots_to_land"], 2.5);
+
+    assert_eq!(output::parse_json::<LatencyRecord>(&json).unwrap(), records);
+    // 다른 종류의 문서는 거부
+    let transfers = output::to_json::<TransferRecord>(&[]);
+    assert!(output::parse_json::<LatencyRecord>(&transfers).is_err());
+}
+
+#[test]
+fn csv_output_has_schema_column_and_escapes_values() {
+    let csv = OutputFormat::Csv.render(&latency_records()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    // 백분위 열은 p50/p90/p95/p99 순서이고, 나중에 추가된 열(stddev_ms, 실패 비율과 종류별 실패 수, ip_family,
+    // jitter_ms, landing_rate, slots_to_land, run_id)은 기존 열 뒤에 붙음
+    assert_eq!(
+        lines[0],
+        "schema_version,endpoint,operation,samples,errors,min_ms,avg_ms,max_ms,p50_ms,p90_ms,p95_ms,p99_ms,stddev_ms,\
+         error_rate,rate_limited,http_4xx,http_5xx,timeout,connection_refused,tls,connection,json_rpc,other,ip_family,jitter_ms,\
+         landing_rate,slots_to_land,run_id"
+    );
+    assert_eq!(lines[1], "1,https://rpc.example.com,getSlot,10,2,0.1,0.55,1,0.5,0.9,1,1,0.287,0.1667,1,0,0,1,0,0,0,0,0,,,,,");
+    assert_eq!(lines[2], "1,https://down.example.com,total,0,3,,,,,,,,,1,0,0,0,0,3,0,0,0,0,ipv6,0.25,,,");
+    assert!(lines[3].ends_with(",0.75,2.5,20240501093000-1a2b3c4d"), "{}", lines[3]);
+
+    let transfer = TransferRecord {
+        id: "alice".to_string(),
+        signature: None,
+        status: "failed".to_string(),
+        fee: 5,
+        slot: None,
+        error: Some("insufficient funds, \"retry\"".to_string()),
+    };
+    let csv = output::to_csv(&[transfer]);
+    assert_eq!(
+        csv,
+        "schema_version,id,signature,status,fee,slot,error\n1,alice,,failed,5,,\"insufficient funds, \"\"retry\"\"\"\n"
+    );
+}
@@ -0,0 +1,174 @@
+// 네이티브 네트워크 프로브, 측정 기록(손실률, 지터), 시스템 ping 출력 해석 검증 (로컬 HTTP 스텁 서버 사용)
+use solana_transfer_bot::benchmark::Rounds;
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::failure::FailureKind;
+use solana_transfer_bot::probe::{self, IpFamily, PingOptions, ProbeStats, ProbeTarget, Timeline};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+// 요청마다 `response`를 돌려주고 연결을 닫는 서버 (`delay`만큼 늦게 응답)
+async fn stub_server(bind: &str, response: &'static str, delay: Duration) -> Option<SocketAddr> {
+    let listener = TcpListener::bind(bind).await.ok()?;
+    let address = listener.local_addr().ok()?;
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut buffer = [0u8; 4096];
+                let _ = stream.read(&mut buffer).await;
+                tokio::time::sleep(delay).await;
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+    Some(address)
+}
+
+const HEALTHY: &str = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 38\r\n\r\n{\"jsonrpc\":\"2.0\",\"result\":\"ok\",\"id\":1}";
+
+fn target(scheme: &str, address: SocketAddr) -> ProbeTarget {
+    ProbeTarget::parse(&format!("{}://{}/", scheme, address)).unwrap()
+}
+
+#[test]
+fn parses_probe_targets() {
+    let target = ProbeTarget::parse("https://rpc.example.com/?api-key=abc").unwrap();
+    assert_eq!((target.host.as_str(), target.port, target.tls), ("rpc.example.com", 443, true));
+    assert_eq!(target.authority, "rpc.example.com");
+    assert_eq!(target.path, "/?api-key=abc");
+
+    let target = ProbeTarget::parse("http://[::1]:8899").unwrap();
+    assert_eq!((target.host.as_str(), target.port, target.tls), ("::1", 8899, false));
+    assert_eq!(target.authority, "[::1]:8899");
+
+    assert_eq!(ProbeTarget::parse("ws://rpc.example.com").unwrap_err().kind(), "input");
+    assert_eq!(ProbeTarget::parse("not a url").unwrap_err().kind(), "input");
+}
+
+#[tokio::test]
+async fn measures_each_phase_of_a_health_request() {
+    let address = stub_server("127.0.0.1:0", HEALTHY, Duration::from_millis(30)).await.unwrap();
+    let target = target("http", address);
+
+    let timing = probe::probe(&target, IpFamily::V4, probe::PROBE_TIMEOUT).await.unwrap();
+
+    assert_eq!(timing.address, address);
+    assert!(timing.healthy);
+    assert_eq!(timing.tls, None);
+    // 서버가 늦게 응답한 시간은 첫 바이트까지 시간에 들어감
+    assert!(timing.ttfb >= Duration::from_millis(30), "{:?}", timing);
+    assert!(timing.connect < timing.ttfb);
+    assert!(timing.total >= timing.dns + timing.connect + timing.ttfb);
+
+    // 주소가 없는 종류는 연결 오류
+    let failure = probe::probe(&target, IpFamily::V6, probe::PROBE_TIMEOUT).await.unwrap_err();
+    assert_eq!(failure.kind, FailureKind::Connection);
+}
+
+#[tokio::test]
+async fn probes_over_ipv6_when_available() {
+    // IPv6가 없는 환경에서는 건너뜀
+    let Some(address) = stub_server("[::1]:0", HEALTHY, Duration::ZERO).await else {
+        return;
+    };
+    let timing = probe::probe(&target("http", address), IpFamily::V6, probe::PROBE_TIMEOUT).await.unwrap();
+    assert!(timing.address.is_ipv6());
+}
+
+#[tokio::test]
+async fn classifies_probe_failures() {
+    // 닫힌 포트
+    let closed = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+    let failure = probe::probe(&target("http", closed), IpFamily::V4, probe::PROBE_TIMEOUT).await.unwrap_err();
+    assert_eq!(failure.kind, FailureKind::ConnectionRefused);
+
+    // 요청 한도 초과 응답의 Retry-After
+    let limited = "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 2\r\nContent-Length: 0\r\n\r\n";
+    let address = stub_server("127.0.0.1:0", limited, Duration::ZERO).await.unwrap();
+    let failure = probe::probe(&target("http", address), IpFamily::V4, probe::PROBE_TIMEOUT).await.unwrap_err();
+    assert_eq!((failure.kind, failure.status), (FailureKind::RateLimited, Some(429)));
+    assert_eq!(failure.retry_after, Some(Duration::from_secs(2)));
+
+    // TLS가 아닌 서버에 https로 연결
+    let address = stub_server("127.0.0.1:0", HEALTHY, Duration::ZERO).await.unwrap();
+    let failure = probe::probe(&target("https", address), IpFamily::V4, probe::PROBE_TIMEOUT).await.unwrap_err();
+    assert_eq!(failure.kind, FailureKind::Tls, "{:?}", failure);
+
+    // 응답이 늦으면 타임아웃
+    let address = stub_server("127.0.0.1:0", HEALTHY, Duration::from_secs(5)).await.unwrap();
+    let failure = probe::probe(&target("http", address), IpFamily::V4, Duration::from_millis(100)).await.unwrap_err();
+    assert_eq!(failure.kind, FailureKind::Timeout);
+}
+
+#[tokio::test]
+async fn stats_report_unhealthy_nodes_and_failures() {
+    let behind = "HTTP/1.1 200 OK\r\nContent-Length: 87\r\n\r\n{\"jsonrpc\":\"2.0\",\"error\":{\"code\":-32005,\"message\":\"Node is behind by 42 slots\"},\"id\":1}";
+    let address = stub_server("127.0.0.1:0", behind, Duration::ZERO).await.unwrap();
+    let target = target("http", address);
+
+    let mut stats = ProbeStats::new();
+    for _ in 0..3 {
+        stats.record(&probe::probe(&target, IpFamily::V4, probe::PROBE_TIMEOUT).await);
+    }
+    stats.record(&probe::probe(&target, IpFamily::V6, probe::PROBE_TIMEOUT).await);
+
+    assert_eq!(stats.total.len(), 3);
+    assert_eq!(stats.unhealthy, 3);
+    assert_eq!(stats.failures.total(), 1);
+    // http이므로 TLS 단계 없음
+    let phases: Vec<&str> = stats.phases().iter().map(|(operation, _, _)| *operation).collect();
+    assert_eq!(phases, ["dns", "tcp_connect", "ttfb", "total"]);
+}
+
+#[test]
+fn timeline_reports_loss_and_jitter() {
+    let mut timeline = Timeline::new();
+    let ms = Duration::from_millis;
+    timeline.record(ms(0), Ok(ms(20)));
+    timeline.record(ms(200), Err(FailureKind::Timeout));
+    let sample = *timeline.record(ms(400), Ok(ms(26)));
+    timeline.record(ms(600), Ok(ms(22)));
+
+    assert_eq!((sample.seq, sample.offset), (3, ms(400)));
+    assert_eq!((timeline.sent(), timeline.lost()), (4, 1));
+    assert_eq!(timeline.loss(), 0.25);
+    // 손실된 측정은 건너뛰고 |26-20|, |22-26|의 평균
+    assert_eq!(timeline.jitter(), Some(ms(5)));
+    assert_eq!(Timeline::new().loss(), 0.0);
+}
+
+#[test]
+fn parses_system_ping_output_with_sub_millisecond_times() {
+    let linux = "PING 127.0.0.1 (127.0.0.1) 56(84) bytes of data.\n64 bytes from 127.0.0.1: icmp_seq=1 ttl=64 time=0.045 ms\n\n--- 127.0.0.1 ping statistics ---\nrtt min/avg/max/mdev = 0.045/0.045/0.045/0.000 ms";
+    assert_eq!(probe::parse_ping_time(linux), Some(Duration::from_micros(45)));
+
+    let windows = "Ping 1.1.1.1 32바이트 데이터 사용:\r\n1.1.1.1의 응답: 바이트=32 시간=12ms TTL=57\r\n";
+    assert_eq!(probe::parse_ping_time(windows), Some(Duration::from_millis(12)));
+    assert_eq!(probe::parse_ping_time("Reply from 10.0.0.1: bytes=32 time<1ms TTL=128"), Some(Duration::from_millis(1)));
+    assert_eq!(probe::parse_ping_time("Antwort von 1.1.1.1: Bytes=32 Zeit=7ms TTL=57"), Some(Duration::from_millis(7)));
+
+    // 응답이 없으면 통계 줄에 ms가 있어도 시간 없음
+    let lost = "1 packets transmitted, 0 received, 100% packet loss, time 0ms";
+    assert_eq!(probe::parse_ping_time(lost), None);
+}
+
+#[test]
+fn parses_ping_options() {
+    let args = |list: &[&str]| Args::parse(list.iter().map(|s| s.to_string()));
+    let default = Rounds { iterations: 10, warmup: 1 };
+
+    let options = PingOptions::from_args(&args(&[]), default).unwrap();
+    assert_eq!(options.rounds, default);
+    assert_eq!((options.interval, options.timeout), (probe::DEFAULT_INTERVAL, probe::PROBE_TIMEOUT));
+
+    let options = PingOptions::from_args(&args(&["--count", "50", "--interval", "1000", "--timeout", "800"]), default).unwrap();
+    assert_eq!(options.rounds, Rounds { iterations: 50, warmup: 1 });
+    assert_eq!((options.interval, options.timeout), (Duration::from_secs(1), Duration::from_millis(800)));
+    // --count가 --iterations보다 우선
+    let options = PingOptions::from_args(&args(&["--iterations", "5", "--count", "7"]), default).unwrap();
+    assert_eq!(options.rounds.iterations, 7);
+
+    assert_eq!(PingOptions::from_args(&args(&["--interval", "soon"]), default).unwrap_err().kind(), "input");
+}
@@ -0,0 +1,212 @@
+// 정기 전송 간격 파싱, 실행 시점 계산, catch-up 계획 검증 (skip/once/all, 유예 시간, 상한을 넘는 긴 중단),
+// 금액 검증과 전송되지 않은 실행의 재시도
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Keypair};
+use solana_transaction_status::{EncodedTransaction, TransactionBinaryEncoding};
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::config::Settings;
+use solana_transfer_bot::schedule::{self, CatchUp, PaymentDefinition, MAX_OCCURRENCES};
+use solana_transfer_bot::Error;
+use std::env;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Response, Server};
+
+fn time(input: &str) -> DateTime<Utc> {
+    input.parse().unwrap()
+}
+
+fn definition(extra: &str) -> PaymentDefinition {
+    let toml = format!("id = \"rent\"\nrecipient = \"{}\"\namount = 0.1\n{}", solana_sdk::pubkey::Pubkey::new_unique(), extra);
+    toml::from_str(&toml).unwrap()
+}
+
+fn definition_with_cron(expression: &str) -> PaymentDefinition {
+    definition(&format!("cron = \"{}\"", expression))
+}
+
+#[test]
+fn parses_intervals() {
+    assert_eq!(schedule::parse_interval("90s"), Some(Duration::seconds(90)));
+    assert_eq!(schedule::parse_interval("30m"), Some(Duration::minutes(30)));
+    assert_eq!(schedule::parse_interval(" 12h "), Some(Duration::hours(12)));
+    assert_eq!(schedule::parse_interval("7d"), Some(Duration::days(7)));
+
+    for bad in ["", "m", "0m", "-5m", "5", "5w", "1.5h"] {
+        assert_eq!(schedule::parse_interval(bad), None, "{:?}", bad);
+    }
+}
+
+#[test]
+fn occurrences_follow_start_end_and_limit() {
+    let definition = definition("interval = \"1h\"\nstart = \"2024-01-01T00:00:00Z\"\nend = \"2024-01-01T02:30:00Z\"");
+    let recurrence = schedule::parse_recurrence(&definition).unwrap();
+    let after = time("2023-12-31T12:00:00Z");
+
+    // 시작 시각 자체를 포함하고 종료 시각 이후는 제외
+    let all = schedule::occurrences(&definition, &recurrence, after, time("2024-01-02T00:00:00Z"), 10);
+    assert_eq!(all, vec![time("2024-01-01T00:00:00Z"), time("2024-01-01T01:00:00Z"), time("2024-01-01T02:00:00Z")]);
+    assert_eq!(schedule::occurrences(&definition, &recurrence, after, time("2024-01-02T00:00:00Z"), 2), all[..2]);
+    // 마지막 실행 이후만
+    assert_eq!(schedule::occurrences(&definition, &recurrence, all[0], time("2024-01-01T01:30:00Z"), 10), all[1..2]);
+
+    let cron = definition_with_cron("0 0 9 * * *");
+    let recurrence = schedule::parse_recurrence(&cron).unwrap();
+    let days = schedule::occurrences(&cron, &recurrence, time("2024-01-01T09:00:00Z"), time("2024-01-03T12:00:00Z"), 10);
+    assert_eq!(days, vec![time("2024-01-02T09:00:00Z"), time("2024-01-03T09:00:00Z")]);
+}
+
+#[test]
+fn latest_occurrence_is_not_capped() {
+    // 하루 동안 멈춰 있던 1분 간격 일정: 놓친 실행이 상한보다 많다
+    let after = time("2024-01-01T00:00:00Z");
+    let now = time("2024-01-02T00:00:30Z");
+
+    for definition in [definition("interval = \"1m\"\nstart = \"2024-01-01T00:00:00Z\""), definition_with_cron("0 * * * * *")] {
+        let recurrence = schedule::parse_recurrence(&definition).unwrap();
+        let due = schedule::occurrences(&definition, &recurrence, after, now, MAX_OCCURRENCES);
+        assert_eq!(due.len(), MAX_OCCURRENCES);
+        assert_eq!(due.last(), Some(&time("2024-01-01T16:40:00Z")));
+
+        assert_eq!(schedule::latest_occurrence(&definition, &recurrence, after, now), Some(time("2024-01-02T00:00:00Z")));
+    }
+
+    // 구간에 실행이 없거나 이미 끝난 일정
+    let definition = definition("interval = \"1d\"\nstart = \"2024-01-01T00:00:00Z\"\nend = \"2024-01-05T00:00:00Z\"");
+    let recurrence = schedule::parse_recurrence(&definition).unwrap();
+    assert_eq!(schedule::latest_occurrence(&definition, &recurrence, time("2024-01-02T00:00:00Z"), time("2024-01-02T12:00:00Z")), None);
+    assert_eq!(
+        schedule::latest_occurrence(&definition, &recurrence, time("2024-01-03T00:00:00Z"), time("2024-02-01T00:00:00Z")),
+        Some(time("2024-01-05T00:00:00Z"))
+    );
+    assert_eq!(schedule::latest_occurrence(&definition, &recurrence, time("2024-01-05T00:00:00Z"), time("2024-02-01T00:00:00Z")), None);
+}
+
+#[test]
+fn plans_catch_up_by_policy_and_grace() {
+    let due: Vec<DateTime<Utc>> = (0..MAX_OCCURRENCES as i64).map(|n| time("2024-01-01T00:00:00Z") + Duration::minutes(n)).collect();
+    let latest = time("2024-01-02T00:00:00Z");
+    let grace = Duration::minutes(5);
+
+    // all은 상한까지만, once는 잘린 목록의 끝이 아니라 실제로 가장 늦은 시점 한 번
+    assert_eq!(schedule::plan(&due, latest, CatchUp::All, latest, grace), due);
+    assert_eq!(schedule::plan(&due, latest, CatchUp::Once, latest + Duration::hours(1), grace), vec![latest]);
+
+    // skip은 가장 늦은 시점이 유예 시간 이내일 때만 실행
+    assert_eq!(schedule::plan(&due, latest, CatchUp::Skip, latest + Duration::minutes(5), grace), vec![latest]);
+    assert_eq!(schedule::plan(&due, latest, CatchUp::Skip, latest + Duration::minutes(6), grace), Vec::<DateTime<Utc>>::new());
+    assert_eq!(schedule::plan(&[latest], latest, CatchUp::Skip, latest + Duration::seconds(30), grace), vec![latest]);
+}
+
+#[test]
+fn rejects_non_positive_amounts() {
+    for amount in ["0", "-1", "nan", "inf"] {
+        let toml = format!("id = \"rent\"\nrecipient = \"{}\"\namount = {}\ninterval = \"1h\"", Pubkey::new_unique(), amount);
+        let definition: PaymentDefinition = toml::from_str(&toml).unwrap();
+        assert!(matches!(definition.validate(), Err(Error::Config(_))), "{}", amount);
+    }
+    assert!(definition("interval = \"1h\"").validate().is_ok());
+
+    // 정의 파일을 읽을 때 거부 (list도 전송 없이 실패)
+    let file = env::temp_dir().join(format!("schedule-amount-{}.toml", std::process::id()));
+    fs::write(&file, format!("[[payment]]\nid = \"rent\"\nrecipient = \"{}\"\namount = 0\ninterval = \"1h\"\n", Pubkey::new_unique())).unwrap();
+    let args = Args::parse(["schedule", "list", "--file", file.to_str().unwrap()].map(String::from));
+    let result = schedule::run(&RpcClient::new("http://127.0.0.1:1".to_string()), &settings(), &args);
+    assert!(matches!(result, Err(Error::Config(_))), "{:?}", result);
+    fs::remove_file(file).unwrap();
+}
+
+#[test]
+fn unsent_run_is_retried_on_the_next_check() {
+    let blockhash_fails = Arc::new(AtomicBool::new(true));
+    let (url, sent) = rpc_stub(blockhash_fails.clone());
+    let dir = env::temp_dir().join(format!("schedule-retry-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("schedules.toml");
+    let state_path = dir.join("state.json");
+    fs::write(
+        &file,
+        format!("[[payment]]\nid = \"rent\"\nrecipient = \"{}\"\namount = 0.1\ninterval = \"1d\"\nstart = \"2024-01-01T00:00:00Z\"\nfee = 0\n", Pubkey::new_unique()),
+    )
+    .unwrap();
+    fs::write(
+        &state_path,
+        json!({ "payments": { "rent": { "registered_at": "2024-01-01T00:00:00Z", "last_run": null, "last_signature": null, "last_error": null } } }).to_string(),
+    )
+    .unwrap();
+
+    let args = Args::parse(
+        ["schedule", "run", "--once", "--file", file.to_str().unwrap(), "--state", state_path.to_str().unwrap()].map(String::from),
+    );
+    let rpc_client = RpcClient::new(url);
+    let state = || -> Value { serde_json::from_str(&fs::read_to_string(&state_path).unwrap()).unwrap() };
+
+    // 블록해시 조회 실패: 전송하지 않았으므로 실행 기록 없이 오류만 남김
+    schedule::run(&rpc_client, &settings(), &args).unwrap();
+    let rent = &state()["payments"]["rent"];
+    assert_eq!(rent["last_run"], Value::Null);
+    assert!(rent["last_error"].as_str().is_some());
+    assert!(sent.lock().unwrap().is_empty());
+
+    // 다음 확인에서 같은 실행을 다시 전송
+    blockhash_fails.store(false, Ordering::SeqCst);
+    schedule::run(&rpc_client, &settings(), &args).unwrap();
+    let rent = &state()["payments"]["rent"];
+    assert!(rent["last_run"].as_str().is_some());
+    assert_eq!(rent["last_error"], Value::Null);
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(rent["last_signature"].as_str(), Some(sent[0].as_str()));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+fn settings() -> Settings {
+    let args = Args::parse(["--keypair".to_string(), format!("base58:{}", Keypair::new().to_base58_string())]);
+    Settings::resolve(&args, &|_| None, None).unwrap()
+}
+
+// 블록해시 조회를 실패시킬 수 있는 JSON-RPC 스텁 (전송된 서명을 기록하고 바로 confirmed로 응답)
+fn rpc_stub(blockhash_fails: Arc<AtomicBool>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let recorded = sent.clone();
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let request_json: Value = serde_json::from_str(&body).unwrap();
+            let params = &request_json["params"];
+            let mut reply = match request_json["method"].as_str().unwrap() {
+                "getLatestBlockhash" if blockhash_fails.load(Ordering::SeqCst) => {
+                    json!({ "error": { "code": -32005, "message": "Node is behind" } })
+                }
+                "getLatestBlockhash" => {
+                    json!({ "result": { "context": { "slot": 1 }, "value": { "blockhash": Hash::new_unique().to_string(), "lastValidBlockHeight": 1000 } } })
+                }
+                "sendTransaction" => {
+                    let encoded = EncodedTransaction::Binary(params[0].as_str().unwrap().to_string(), TransactionBinaryEncoding::Base64);
+                    let signature = encoded.decode().unwrap().signatures[0].to_string();
+                    recorded.lock().unwrap().push(signature.clone());
+                    json!({ "result": signature })
+                }
+                "getSignatureStatuses" => {
+                    let status = json!({ "slot": 1, "confirmations": null, "err": null, "status": { "Ok": null }, "confirmationStatus": "confirmed" });
+                    json!({ "result": { "context": { "slot": 1 }, "value": [status] } })
+                }
+                "getVersion" => json!({ "result": { "solana-core": "1.18.26", "feature-set": 0 } }),
+                other => panic!("예상하지 못한 RPC 메서드: {}", other),
+            };
+            reply["jsonrpc"] = json!("2.0");
+            reply["id"] = request_json["id"].clone();
+            let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+            let _ = request.respond(Response::from_string(reply.to_string()).with_header(header));
+        }
+    });
+    (url, sent)
+}
@@ -0,0 +1,300 @@
+// 전송 API 서버의 인증, 멱등성 키, 한도(오버플로 포함), pending 전송 확인, 재시작 후 기록 복원 검증
+// (로컬 JSON-RPC 스텁에 연결한 서버를 HTTP로 호출)
+use reqwest::blocking::{Client, RequestBuilder};
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+};
+use solana_transaction_status::{EncodedTransaction, TransactionBinaryEncoding};
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::config::Settings;
+use solana_transfer_bot::serve;
+use std::env;
+use std::fs;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Response, Server};
+
+const TOKEN: &str = "serve-test-token";
+
+// 스텁 RPC 노드의 상태
+struct Chain {
+    // 전송된 트랜잭션 서명
+    sent: Vec<String>,
+    // sendTransaction에 503으로 응답 (노드가 받았는지 알 수 없는 전송)
+    send_fails: bool,
+    // 모든 서명이 confirmed로 보이는지 (false면 아직 보이지 않음)
+    confirmed: bool,
+    blockhash_valid: bool,
+    // searchTransactionHistory를 켠 상태 조회 횟수
+    history_lookups: usize,
+}
+
+fn rpc_stub() -> (String, Arc<Mutex<Chain>>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let chain = Arc::new(Mutex::new(Chain { sent: vec![], send_fails: false, confirmed: true, blockhash_valid: true, history_lookups: 0 }));
+    let state = chain.clone();
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let request_json: Value = serde_json::from_str(&body).unwrap();
+            let params = &request_json["params"];
+            let mut chain = state.lock().unwrap();
+            let result = match request_json["method"].as_str().unwrap() {
+                "getLatestBlockhash" => {
+                    json!({ "context": { "slot": 1 }, "value": { "blockhash": Hash::new_unique().to_string(), "lastValidBlockHeight": 1000 } })
+                }
+                "sendTransaction" if chain.send_fails => {
+                    let _ = request.respond(Response::from_string("").with_status_code(503));
+                    continue;
+                }
+                "sendTransaction" => {
+                    let encoded = EncodedTransaction::Binary(params[0].as_str().unwrap().to_string(), TransactionBinaryEncoding::Base64);
+                    let signature = encoded.decode().unwrap().signatures[0].to_string();
+                    chain.sent.push(signature.clone());
+                    json!(signature)
+                }
+                "getSignatureStatuses" => {
+                    if params[1]["searchTransactionHistory"] == json!(true) {
+                        chain.history_lookups += 1;
+                    }
+                    let status = json!({ "slot": 1, "confirmations": null, "err": null, "status": { "Ok": null }, "confirmationStatus": "confirmed" });
+                    let statuses: Vec<Value> = params[0]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|_| if chain.confirmed { status.clone() } else { Value::Null })
+                        .collect();
+                    json!({ "context": { "slot": 1 }, "value": statuses })
+                }
+                "isBlockhashValid" => json!({ "context": { "slot": 1 }, "value": chain.blockhash_valid }),
+                "getVersion" => json!({ "solana-core": "1.18.26", "feature-set": 0 }),
+                other => panic!("예상하지 못한 RPC 메서드: {}", other),
+            };
+            let reply = json!({ "jsonrpc": "2.0", "result": result, "id": request_json["id"] });
+            let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+            let _ = request.respond(Response::from_string(reply.to_string()).with_header(header));
+        }
+    });
+    (url, chain)
+}
+
+fn journal(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("serve-{}-{}.jsonl", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+// 서버를 띄우고 /health가 응답할 때까지 기다린 뒤 주소 반환
+fn start(rpc_url: &str, journal: &Path, options: &[&str]) -> String {
+    env::set_var("SERVE_API_TOKEN", TOKEN);
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let bind = format!("127.0.0.1:{}", port);
+
+    let mut list = vec!["serve".to_string(), "--bind".to_string(), bind.clone(), "--journal".to_string(), journal.display().to_string()];
+    list.extend(options.iter().map(|s| s.to_string()));
+    let args = Args::parse(list);
+    let keypair = Args::parse(["--keypair".to_string(), format!("base58:{}", Keypair::new().to_base58_string())]);
+    let settings = Settings::resolve(&keypair, &|_| None, None).unwrap();
+    let rpc_client = RpcClient::new(rpc_url.to_string());
+    thread::spawn(move || serve::run(rpc_client, &settings, &args).unwrap());
+
+    let base = format!("http://{}", bind);
+    for _ in 0..100 {
+        if Client::new().get(format!("{}/health", base)).send().is_ok_and(|r| r.status() == 200) {
+            return base;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    panic!("서버가 시작되지 않았습니다");
+}
+
+fn authorized(request: RequestBuilder) -> RequestBuilder {
+    request.header("Authorization", format!("Bearer {}", TOKEN))
+}
+
+fn transfer(base: &str, key: &str, amount: f64, fee: u64) -> (u16, Value) {
+    let body = json!({ "recipient": Pubkey::new_unique().to_string(), "amount": amount, "fee": fee });
+    let response = authorized(Client::new().post(format!("{}/v1/transfers", base)))
+        .header("Idempotency-Key", key)
+        .json(&body)
+        .send()
+        .unwrap();
+    (response.status().as_u16(), response.json().unwrap())
+}
+
+fn get(base: &str, path: &str) -> (u16, Value) {
+    let response = authorized(Client::new().get(format!("{}{}", base, path))).send().unwrap();
+    (response.status().as_u16(), response.json().unwrap())
+}
+
+// 기록 파일에서 전송 ID의 마지막 상태
+fn journaled_status(journal: &Path, id: &str) -> String {
+    let content = fs::read_to_string(journal).unwrap();
+    let record: Value = content
+        .lines()
+        .rev()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .find(|record| record["id"] == id)
+        .unwrap();
+    record["status"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn rejects_requests_without_the_api_token() {
+    let (rpc_url, _) = rpc_stub();
+    let journal = journal("auth");
+    let base = start(&rpc_url, &journal, &[]);
+    let client = Client::new();
+    let path = format!("{}/v1/transfers/tx-missing", base);
+
+    assert_eq!(client.get(format!("{}/health", base)).send().unwrap().status(), 200);
+    assert_eq!(client.get(&path).send().unwrap().status(), 401);
+    for header in ["Bearer wrong-token", "Bearer serve-test-toke", "Bearer serve-test-token-longer", TOKEN] {
+        assert_eq!(client.get(&path).header("Authorization", header).send().unwrap().status(), 401, "{}", header);
+    }
+    // 인증을 통과하면 경로 처리 (없는 기록)
+    assert_eq!(get(&base, "/v1/transfers/tx-missing").0, 404);
+    // 전송이 없었으므로 기록 파일도 생기지 않음
+    assert!(!journal.exists());
+}
+
+#[test]
+fn idempotency_key_returns_the_first_result() {
+    let (rpc_url, chain) = rpc_stub();
+    let journal = journal("idempotency");
+    let base = start(&rpc_url, &journal, &[]);
+
+    let response = authorized(Client::new().post(format!("{}/v1/transfers", base)))
+        .json(&json!({ "recipient": Pubkey::new_unique().to_string(), "amount": 0.1, "fee": 0 }))
+        .send()
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    let (status, first) = transfer(&base, "order-1", 0.1, 0);
+    assert_eq!(status, 201);
+    assert_eq!(first["status"], "confirmed");
+    let (status, again) = transfer(&base, "order-1", 0.2, 0);
+    assert_eq!(status, 200);
+    assert_eq!(again, first);
+    assert_eq!(chain.lock().unwrap().sent, vec![first["signature"].as_str().unwrap().to_string()]);
+
+    let (status, found) = get(&base, &format!("/v1/transfers?signature={}", first["signature"].as_str().unwrap()));
+    assert_eq!(status, 200);
+    assert_eq!(found["id"], first["id"]);
+    fs::remove_file(journal).unwrap();
+}
+
+#[test]
+fn enforces_limits_and_rejects_overflowing_totals() {
+    let (rpc_url, chain) = rpc_stub();
+    let journal_a = journal("limits-a");
+    let base = start(&rpc_url, &journal_a, &["--max-amount", "1", "--max-fee", "10"]);
+    assert_eq!(transfer(&base, "too-much", 2.0, 0).0, 422);
+    assert_eq!(transfer(&base, "fee-too-high", 0.5, 11).0, 422);
+    assert_eq!(transfer(&base, "zero", 0.0, 0).0, 400);
+    assert_eq!(transfer(&base, "ok", 1.0, 10).0, 201);
+    // 우선순위 수수료 계산이 넘치면 RPC 호출 전에 400
+    assert_eq!(get(&base, &format!("/v1/quote?amount=0.1&fee={}", u64::MAX)).0, 400);
+
+    let journal_b = journal("limits-b");
+    let base = start(&rpc_url, &journal_b, &["--daily-limit", "1.5"]);
+    assert_eq!(transfer(&base, "first", 1.0, 0).0, 201);
+    let (status, body) = transfer(&base, "second", 1.0, 0);
+    assert_eq!(status, 422);
+    assert!(body["error"].as_str().unwrap().contains("일일 전송 한도"));
+    // 오늘 사용한 금액과 합치면 u64를 넘는 금액
+    assert_eq!(transfer(&base, "overflow", 1e20, 0).0, 400);
+
+    assert_eq!(chain.lock().unwrap().sent.len(), 2);
+    fs::remove_file(journal_a).unwrap();
+    fs::remove_file(journal_b).unwrap();
+}
+
+#[test]
+fn pending_transfer_is_resolved_on_status_reads() {
+    let (rpc_url, chain) = rpc_stub();
+    let journal = journal("pending");
+    let base = start(&rpc_url, &journal, &[]);
+
+    // 전송 결과를 알 수 없으면 서명과 함께 pending
+    {
+        let mut chain = chain.lock().unwrap();
+        chain.send_fails = true;
+        chain.confirmed = false;
+    }
+    let (status, pending) = transfer(&base, "unknown", 0.1, 0);
+    assert_eq!(status, 202);
+    assert_eq!(pending["status"], "pending");
+    assert!(pending["signature"].as_str().is_some());
+    let id = pending["id"].as_str().unwrap();
+
+    // 아직 보이지 않고 블록해시도 유효하면 그대로 pending
+    assert_eq!(get(&base, &format!("/v1/transfers/{}", id)).1["status"], "pending");
+    assert!(chain.lock().unwrap().history_lookups > 0);
+
+    // 원장 기록에서 확인되면 confirmed로 기록
+    chain.lock().unwrap().confirmed = true;
+    let (status, resolved) = get(&base, &format!("/v1/transfers/{}", id));
+    assert_eq!(status, 200);
+    assert_eq!(resolved["status"], "confirmed");
+    assert_eq!(resolved["error"], Value::Null);
+    assert_eq!(journaled_status(&journal, id), "confirmed");
+
+    // 보이지 않은 채 블록해시가 만료되면 failed로 기록
+    chain.lock().unwrap().confirmed = false;
+    let (_, expired) = transfer(&base, "expired", 0.1, 0);
+    chain.lock().unwrap().blockhash_valid = false;
+    let (_, resolved) = get(&base, &format!("/v1/transfers?signature={}", expired["signature"].as_str().unwrap()));
+    assert_eq!(resolved["status"], "failed");
+    assert_eq!(resolved["error_kind"], "blockhash_expired");
+    assert_eq!(journaled_status(&journal, expired["id"].as_str().unwrap()), "failed");
+    fs::remove_file(journal).unwrap();
+}
+
+#[test]
+fn journal_replay_restores_transfers_and_resolves_pending() {
+    let (rpc_url, chain) = rpc_stub();
+    let journal = journal("replay");
+    let base = start(&rpc_url, &journal, &["--daily-limit", "1.5"]);
+    let (status, first) = transfer(&base, "before-restart", 1.0, 0);
+    assert_eq!(status, 201);
+
+    // 결과를 확인하기 전에 중단된 전송
+    let pending = json!({
+        "id": "tx-0-99",
+        "idempotency_key": "interrupted",
+        "recipient": Pubkey::new_unique().to_string(),
+        "lamports": 100_000_000u64,
+        "fee": 0,
+        "status": "pending",
+        "signature": Signature::new_unique().to_string(),
+        "blockhash": Hash::new_unique().to_string(),
+        "error": null,
+        "error_kind": null,
+        "created_at": "2024-01-01T00:00:00Z",
+    });
+    let mut content = fs::read_to_string(&journal).unwrap();
+    content.push_str(&format!("{}\n", pending));
+    fs::write(&journal, content).unwrap();
+
+    // 같은 기록 파일로 다시 시작하면 시작할 때 pending 전송을 확인
+    let base = start(&rpc_url, &journal, &["--daily-limit", "1.5"]);
+    assert_eq!(journaled_status(&journal, "tx-0-99"), "confirmed");
+
+    // 멱등성 키와 일일 한도가 유지됨
+    let (status, again) = transfer(&base, "before-restart", 1.0, 0);
+    assert_eq!(status, 200);
+    assert_eq!(again, first);
+    assert_eq!(transfer(&base, "after-restart", 1.0, 0).0, 422);
+    assert_eq!(chain.lock().unwrap().sent.len(), 1);
+    fs::remove_file(journal).unwrap();
+}
@@ -0,0 +1,97 @@
+// 스테이크 하위 명령의 인자 파싱과 활성화 상태 표시 검증 (온체인 동작은 tests/test_validator.rs)
+use solana_sdk::clock::Epoch;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::stake::state::StakeActivationStatus;
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::stake::{self, StakeCommand};
+
+fn command(list: &[&str]) -> solana_transfer_bot::Result<StakeCommand> {
+    StakeCommand::from_args(&Args::parse(list.iter().map(|s| s.to_string())))
+}
+
+#[test]
+fn parses_each_subcommand() {
+    let (stake, vote) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (stake_s, vote_s) = (stake.to_string(), vote.to_string());
+
+    assert_eq!(command(&["stake", "create", "1.5"]).unwrap(), StakeCommand::Create { lamports: 3 * LAMPORTS_PER_SOL / 2 });
+    assert_eq!(command(&["stake", "delegate", &stake_s, &vote_s]).unwrap(), StakeCommand::Delegate { stake, vote });
+    assert_eq!(command(&["stake", "deactivate", &stake_s]).unwrap(), StakeCommand::Deactivate { stake });
+    assert_eq!(
+        command(&["stake", "split", &stake_s, "0.25"]).unwrap(),
+        StakeCommand::Split {
+            stake,
+            lamports: LAMPORTS_PER_SOL / 4
+        }
+    );
+    assert_eq!(
+        command(&["stake", "merge", &stake_s, &vote_s]).unwrap(),
+        StakeCommand::Merge {
+            destination: stake,
+            source: vote
+        }
+    );
+    assert_eq!(command(&["stake", "status", &stake_s]).unwrap(), StakeCommand::Status { stake });
+    assert_eq!(command(&["stake"]).unwrap(), StakeCommand::Usage);
+    assert_eq!(command(&["stake", "unknown"]).unwrap(), StakeCommand::Usage);
+}
+
+#[test]
+fn withdraw_accepts_all_and_a_destination() {
+    let (stake, to) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (stake_s, to_s) = (stake.to_string(), to.to_string());
+
+    assert_eq!(
+        command(&["stake", "withdraw", &stake_s, "ALL"]).unwrap(),
+        StakeCommand::Withdraw { stake, lamports: None, to: None }
+    );
+    assert_eq!(
+        command(&["stake", "withdraw", &stake_s, "2", "--to", &to_s]).unwrap(),
+        StakeCommand::Withdraw {
+            stake,
+            lamports: Some(2 * LAMPORTS_PER_SOL),
+            to: Some(to)
+        }
+    );
+    // 불리언 옵션이 앞에 와도 위치 인자가 밀리지 않음
+    assert_eq!(
+        command(&["--yes", "stake", "withdraw", &stake_s, "all"]).unwrap(),
+        StakeCommand::Withdraw { stake, lamports: None, to: None }
+    );
+}
+
+#[test]
+fn rejects_missing_or_invalid_arguments() {
+    let stake = Pubkey::new_unique().to_string();
+    for bad in [
+        &["stake", "create"][..],
+        &["stake", "create", "lots"],
+        &["stake", "delegate", &stake],
+        &["stake", "deactivate", "not-a-key"],
+        &["stake", "withdraw", &stake, "1", "--to", "nope"],
+        // 0 이하이거나 유한하지 않은 양
+        &["stake", "create", "0"],
+        &["stake", "split", &stake, "-1"],
+        &["stake", "split", &stake, "NaN"],
+        &["stake", "withdraw", &stake, "inf"],
+    ] {
+        assert_eq!(command(bad).unwrap_err().kind(), "input", "{:?}", bad);
+    }
+}
+
+#[test]
+fn labels_activation_state() {
+    let status = |effective, activating, deactivating| StakeActivationStatus {
+        effective,
+        activating,
+        deactivating,
+    };
+
+    assert_eq!(stake::activation_label(&status(0, 10, 0), Epoch::MAX, 5), "활성화 중 (activating)");
+    assert_eq!(stake::activation_label(&status(10, 0, 0), Epoch::MAX, 5), "활성 (active)");
+    assert_eq!(stake::activation_label(&status(10, 0, 10), 5, 5), "비활성화 중 (deactivating)");
+    assert_eq!(stake::activation_label(&status(0, 0, 0), 4, 5), "비활성 (inactive)");
+    // 위임한 에포크에는 아직 활성화가 시작되지 않음
+    assert_eq!(stake::activation_label(&status(0, 0, 0), Epoch::MAX, 5), "활성화 대기 (activating)");
+}
@@ -0,0 +1,33 @@
+// 상태 파일의 기본값, 왕복 저장, 임시 파일 정리 검증
+use serde::{Deserialize, Serialize};
+use solana_transfer_bot::state_file;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct State {
+    last: HashMap<String, String>,
+}
+
+#[test]
+fn missing_file_loads_default_and_saves_round_trip() {
+    let path = env::temp_dir().join(format!("state-file-test-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    let _ = fs::remove_file(path);
+
+    assert_eq!(state_file::load::<State>(path).unwrap(), State::default());
+
+    let state = State {
+        last: HashMap::from([("wallet".to_string(), "sig".to_string())]),
+    };
+    state_file::save(path, &state).unwrap();
+    assert_eq!(state_file::load::<State>(path).unwrap(), state);
+    assert!(!Path::new(&format!("{}.tmp", path)).exists());
+
+    // 깨진 파일은 기본값으로 덮지 않고 오류
+    fs::write(path, "{").unwrap();
+    assert!(state_file::load::<State>(path).is_err());
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,83 @@
+// 지연 시간 히스토그램 통계(백분위, 표준편차, 분포 그래프)와 지터 검증
+use solana_transfer_bot::stats::{self, LatencyHistogram, LatencySummary};
+use std::time::Duration;
+
+// 백분위는 유효숫자 3자리 근사값
+fn assert_close(actual: Duration, expected: Duration) {
+    let error = actual.abs_diff(expected).as_secs_f64() / expected.as_secs_f64();
+    assert!(error <= 0.001, "{:?} != {:?}", actual, expected);
+}
+
+#[test]
+fn summary_reports_exact_extremes_and_approximate_percentiles() {
+    let samples: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+    let summary = LatencySummary::from_samples(&samples).unwrap();
+
+    assert_eq!(summary.count, 100);
+    assert_eq!(summary.min, Duration::from_millis(1));
+    assert_eq!(summary.max, Duration::from_millis(100));
+    assert_eq!(summary.avg, Duration::from_micros(50_500));
+    assert_close(summary.p50, Duration::from_millis(50));
+    assert_close(summary.p90, Duration::from_millis(90));
+    assert_close(summary.p95, Duration::from_millis(95));
+    assert_close(summary.p99, Duration::from_millis(99));
+    // 1..=100의 모집단 표준편차 28.866
+    assert_close(summary.stddev, Duration::from_micros(28_866));
+
+    // 측정값이 하나면 모든 백분위가 그 값
+    let single = LatencySummary::from_samples(&[Duration::from_micros(1500)]).unwrap();
+    assert_eq!(single.p99, Duration::from_micros(1500));
+    assert_eq!(single.stddev, Duration::ZERO);
+    assert!(LatencySummary::from_samples(&[]).is_none());
+}
+
+#[test]
+fn long_runs_keep_constant_memory_and_merge() {
+    let mut first = LatencyHistogram::new();
+    let mut second = LatencyHistogram::new();
+    for i in 0..200_000u64 {
+        first.record(Duration::from_micros(1000 + i % 1000));
+        second.record(Duration::from_micros(5000 + i % 1000));
+    }
+    let size = std::mem::size_of_val(&first);
+
+    first.merge(&second);
+    first.merge(&LatencyHistogram::new());
+
+    assert_eq!(first.len(), 400_000);
+    assert_eq!(std::mem::size_of_val(&first), size);
+    let summary = first.summary().unwrap();
+    assert_eq!(summary.min, Duration::from_micros(1000));
+    assert_eq!(summary.max, Duration::from_micros(5999));
+    assert_close(summary.p50, Duration::from_micros(1999));
+}
+
+#[test]
+fn text_histogram_uses_doubling_buckets() {
+    let mut histogram = LatencyHistogram::new();
+    assert!(histogram.text_histogram(10).is_empty());
+
+    for micros in [1100, 1200, 1300, 1400, 5000] {
+        histogram.record(Duration::from_micros(micros));
+    }
+    let lines = histogram.text_histogram(8);
+
+    // [1.024ms, 2.048ms), [2.048ms, 4.096ms), [4.096ms, 8.192ms)
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("1.024ms") && lines[0].ends_with("████████ 4"), "{}", lines[0]);
+    assert!(lines[1].ends_with("|  0"), "{}", lines[1]);
+    assert!(lines[2].ends_with("██ 1"), "{}", lines[2]);
+}
+
+#[test]
+fn jitter_is_mean_difference_between_consecutive_samples() {
+    let ms = |values: &[u64]| values.iter().map(|v| Duration::from_micros(v * 1000)).collect::<Vec<_>>();
+
+    // |12-10| + |9-12| + |9-9| = 5, 3개 구간
+    assert_close(stats::jitter(&ms(&[10, 12, 9, 9])).unwrap(), Duration::from_micros(1667));
+    assert_eq!(stats::jitter(&ms(&[10, 10, 10])), Some(Duration::ZERO));
+    assert_eq!(stats::jitter(&ms(&[10])), None);
+    // 1ms 미만도 그대로
+    let fast = [Duration::from_micros(450), Duration::from_micros(520)];
+    assert_eq!(stats::jitter(&fast), Some(Duration::from_micros(70)));
+}
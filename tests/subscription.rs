@@ -0,0 +1,173 @@
+// 웹소켓 구독 알림 지연 비교의 집계(가장 빠른 엔드포인트 기준 지연, 놓친 알림, 끊긴 구독) 검증 (로컬 웹소켓 스텁 서버 사용)
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::fake_rpc::FakeRpc;
+use solana_transfer_bot::output::SubscriptionRecord;
+use solana_transfer_bot::subscription::{self, NotificationLog, Subscription, SubscriptionOptions, TestTransfers};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+// 스텁 서버가 슬롯 알림을 보내는 간격과 슬롯 수
+const TICK: Duration = Duration::from_millis(40);
+const SLOTS: u64 = 15;
+
+// 구독 요청마다 슬롯 `n`의 알림을 `base + n * TICK + delay`에 보내는 웹소켓 서버
+// (이미 지난 슬롯은 건너뜀, `close_after`를 주면 구독 종류마다 첫 연결은 그만큼 보낸 뒤 끊음)
+async fn ws_stub(base: Instant, delay: Duration, close_after: Option<usize>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let closed: Arc<Mutex<HashSet<String>>> = Arc::default();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let closed = closed.clone();
+            tokio::spawn(async move {
+                let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await else {
+                    return;
+                };
+                let Some(Ok(Message::Text(text))) = ws.next().await else {
+                    return;
+                };
+                let request: Value = serde_json::from_str(&text).unwrap();
+                let method = request["method"].as_str().unwrap().replace("Subscribe", "Notification");
+                let close_after = close_after.filter(|_| closed.lock().unwrap().insert(method.clone()));
+                let reply = json!({ "jsonrpc": "2.0", "result": 7, "id": request["id"] });
+                ws.send(Message::Text(reply.to_string())).await.unwrap();
+
+                let mut sent = 0;
+                for slot in 1..=SLOTS {
+                    let at = base + TICK * slot as u32 + delay;
+                    if at < Instant::now() {
+                        continue;
+                    }
+                    tokio::time::sleep_until(at.into()).await;
+                    let result = match method.as_str() {
+                        "slotNotification" => json!({ "slot": slot, "parent": slot - 1, "root": 0 }),
+                        _ => json!({
+                            "context": { "slot": slot },
+                            "value": {
+                                "lamports": 1, "data": ["", "base64"], "owner": Pubkey::default().to_string(),
+                                "executable": false, "rentEpoch": 0, "space": 0
+                            }
+                        }),
+                    };
+                    let notification = json!({ "jsonrpc": "2.0", "method": method, "params": { "result": result, "subscription": 7 } });
+                    if ws.send(Message::Text(notification.to_string())).await.is_err() {
+                        return;
+                    }
+                    sent += 1;
+                    if Some(sent) == close_after {
+                        return;
+                    }
+                }
+                while ws.next().await.is_some() {}
+            });
+        }
+    });
+    url
+}
+
+#[test]
+fn delays_are_measured_from_the_first_arrival() {
+    let ms = Duration::from_millis;
+    let mut log = NotificationLog::new(3);
+    log.record(0, Subscription::Slot, 10, ms(100));
+    log.record(1, Subscription::Slot, 10, ms(130));
+    log.record(2, Subscription::Slot, 10, ms(100));
+    log.record(1, Subscription::Slot, 11, ms(500));
+    log.record(0, Subscription::Slot, 11, ms(540));
+    // 같은 이벤트가 다시 오면 처음 도착 시각 유지
+    log.record(0, Subscription::Slot, 11, ms(545));
+    // 측정 시간 뒤에 처음 관찰된 이벤트는 비교하지 않음
+    log.record(0, Subscription::Slot, 12, ms(1200));
+    log.record(1, Subscription::Account, 10, ms(120));
+    log.record_drop(2, Subscription::Account);
+    log.record_drop(2, Subscription::Account);
+
+    let report = log.report(Duration::from_secs(1));
+
+    assert_eq!(report.events[&Subscription::Slot], 2);
+    assert_eq!(report.subscriptions(), [Subscription::Slot, Subscription::Account]);
+    let slot = |endpoint: usize| &report.endpoints[endpoint][&Subscription::Slot];
+    assert_eq!((slot(0).notifications(), slot(0).fastest, slot(0).missed), (2, 1, 0));
+    assert_eq!(slot(0).delay.summary().unwrap().max, ms(40));
+    assert_eq!((slot(1).fastest, slot(1).delay.summary().unwrap().max), (1, ms(30)));
+    // 동시에 받으면 둘 다 가장 빠름
+    assert_eq!((slot(2).notifications(), slot(2).fastest, slot(2).missed), (1, 1, 1));
+
+    let account = &report.endpoints[2][&Subscription::Account];
+    assert_eq!((account.missed, account.dropped), (1, 2));
+    assert_eq!(report.endpoints[0][&Subscription::Account].missed, 1);
+}
+
+#[tokio::test]
+async fn compares_notification_arrival_across_websocket_endpoints() {
+    let base = Instant::now() + Duration::from_millis(200);
+    let urls = vec![
+        ws_stub(base, Duration::ZERO, None).await,
+        ws_stub(base, Duration::from_millis(25), None).await,
+        // 세 번째 알림 뒤 연결이 끊겼다가 다시 구독
+        ws_stub(base, Duration::ZERO, Some(3)).await,
+    ];
+    let options = SubscriptionOptions {
+        duration: Duration::from_millis(900),
+        grace: Duration::from_millis(300),
+        signatures: 0,
+        ..SubscriptionOptions::default()
+    };
+
+    let report = subscription::run_subscriptions(&urls, &options, None::<TestTransfers<FakeRpc>>).await;
+
+    assert_eq!(report.subscriptions(), [Subscription::Slot, Subscription::Account]);
+    for subscription in [Subscription::Slot, Subscription::Account] {
+        assert_eq!(report.events[&subscription], SLOTS as usize, "{:?}", subscription);
+        let [steady, slow, flaky] = &report.endpoints[..] else { unreachable!() };
+        let (steady, slow, flaky) = (&steady[&subscription], &slow[&subscription], &flaky[&subscription]);
+
+        assert_eq!((steady.notifications(), steady.missed, steady.dropped), (SLOTS as usize, 0, 0));
+        assert!(steady.delay.summary().unwrap().p50 < Duration::from_millis(15), "{:?}", steady.delay.summary());
+        assert_eq!((slow.notifications(), slow.fastest), (SLOTS as usize, 0));
+        assert!(slow.delay.summary().unwrap().p50 >= Duration::from_millis(15), "{:?}", slow.delay.summary());
+        assert!(flaky.dropped >= 1);
+        assert!(flaky.missed >= 1 && flaky.notifications() + flaky.missed == SLOTS as usize);
+    }
+
+    let records = SubscriptionRecord::from_report(&["a".to_string(), "b".to_string(), "c".to_string()], &report);
+    assert_eq!(records.len(), 6);
+    assert_eq!((records[0].endpoint.as_str(), records[0].subscription.as_str()), ("a", "slotSubscribe"));
+    assert_eq!(records[1].subscription, "accountSubscribe");
+    assert!(records[5].dropped >= 1);
+}
+
+#[tokio::test]
+async fn unreachable_endpoints_count_as_dropped_subscriptions() {
+    let closed = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+    let options = SubscriptionOptions {
+        duration: Duration::from_millis(200),
+        grace: Duration::ZERO,
+        ..SubscriptionOptions::default()
+    };
+
+    let report = subscription::run_subscriptions(&[format!("ws://{}", closed)], &options, None::<TestTransfers<FakeRpc>>).await;
+
+    assert!(report.events.is_empty());
+    assert!(report.endpoints[0][&Subscription::Slot].dropped >= 1);
+    assert!(report.endpoints[0][&Subscription::Account].dropped >= 1);
+}
+
+#[test]
+fn parses_subscription_options() {
+    let args = |list: &[&str]| Args::parse(list.iter().map(|s| s.to_string()));
+
+    assert_eq!(SubscriptionOptions::from_args(&args(&[])).unwrap(), SubscriptionOptions::default());
+    let account = Pubkey::new_unique();
+    let options =
+        SubscriptionOptions::from_args(&args(&["--duration", "5", "--account", &account.to_string(), "--signatures", "0"])).unwrap();
+    assert_eq!((options.duration, options.account, options.signatures), (Duration::from_secs(5), account, 0));
+
+    assert_eq!(SubscriptionOptions::from_args(&args(&["--account", "nope"])).unwrap_err().kind(), "input");
+}
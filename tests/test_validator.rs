@@ -0,0 +1,352 @@
+// 로컬 solana-test-validator를 띄워 전송과 벤치마크 바이너리를 끝까지 검증
+//
+// solana-test-validator가 PATH에 없으면 (또는 SOLANA_TEST_VALIDATOR로 경로를 지정하지 않으면)
+// 테스트는 메시지만 출력하고 통과합니다. 검증인 기동 비용이 크므로 검증인 하나를 띄운 뒤
+// 시나리오들을 순서대로 실행합니다.
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    stake::state::StakeStateV2,
+    system_instruction,
+};
+use solana_transfer_bot::batch::{self, BatchOptions, Interrupt, Journal, Payout};
+use solana_transfer_bot::output::{LatencyRecord, Report};
+use solana_transfer_bot::{rpc, runtime, stake, transfer};
+use spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account_idempotent};
+use std::env;
+use std::fs;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+// 검증인 기동 대기 시간
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(90);
+
+// 서명 하나당 기본 수수료
+const SIGNATURE_FEE: u64 = 5_000;
+
+// 실행 중인 로컬 검증인 (drop 시 프로세스 종료 및 원장 삭제)
+struct TestValidator {
+    child: Child,
+    ledger: PathBuf,
+    url: String,
+}
+
+impl TestValidator {
+    // 검증인 실행 파일이 없으면 None
+    fn start() -> Option<TestValidator> {
+        let program = env::var("SOLANA_TEST_VALIDATOR").unwrap_or_else(|_| "solana-test-validator".to_string());
+        if Command::new(&program).arg("--version").output().is_err() {
+            eprintln!("{}를 찾을 수 없어 검증인 통합 테스트를 건너뜁니다.", program);
+            return None;
+        }
+
+        let rpc_port = free_port();
+        let ledger = env::temp_dir().join(format!("solana_transfer_bot-ledger-{}-{}", std::process::id(), rpc_port));
+        let child = Command::new(&program)
+            .arg("--ledger")
+            .arg(&ledger)
+            .args(["--reset", "--quiet", "--bind-address", "127.0.0.1"])
+            .args(["--rpc-port", &rpc_port.to_string()])
+            .args(["--faucet-port", &free_port().to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("solana-test-validator 실행 실패");
+
+        let validator = TestValidator {
+            child,
+            ledger,
+            url: format!("http://127.0.0.1:{}", rpc_port),
+        };
+        validator.wait_until_ready();
+        Some(validator)
+    }
+
+    fn client(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.url.clone(), CommitmentConfig::confirmed())
+    }
+
+    fn wait_until_ready(&self) {
+        let client = self.client();
+        let start = Instant::now();
+        while start.elapsed() < STARTUP_TIMEOUT {
+            if client.get_health().is_ok() && client.get_slot().map(|slot| slot > 0).unwrap_or(false) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+        panic!("solana-test-validator가 {:?} 안에 준비되지 않았습니다", STARTUP_TIMEOUT);
+    }
+}
+
+impl Drop for TestValidator {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_dir_all(&self.ledger);
+    }
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .expect("빈 포트 찾기 실패")
+}
+
+// 에어드롭 후 확인될 때까지 대기
+fn airdrop(client: &RpcClient, pubkey: &Pubkey, lamports: u64) {
+    let signature = client.request_airdrop(pubkey, lamports).expect("에어드롭 요청 실패");
+    let start = Instant::now();
+    while !client.confirm_transaction(&signature).unwrap_or(false) {
+        assert!(start.elapsed() < Duration::from_secs(30), "에어드롭 확인 시간 초과");
+        thread::sleep(Duration::from_millis(200));
+    }
+}
+
+fn funded_keypair(client: &RpcClient, sol: u64) -> Keypair {
+    let keypair = Keypair::new();
+    airdrop(client, &keypair.pubkey(), sol * LAMPORTS_PER_SOL);
+    keypair
+}
+
+// 바이너리를 검증인 RPC(공식 메인넷 RPC 제외)와 주어진 인자, 환경 변수로 실행
+fn run_binary(path: &str, validator: &TestValidator, args: &[&str], envs: &[(&str, String)]) -> Output {
+    let mut command = Command::new(path);
+    command
+        .args(args)
+        .env(rpc::RPC_URL_ENV, &validator.url)
+        .env(rpc::SKIP_OFFICIAL_RPC_ENV, "1")
+        .env_remove("SOLANA_PRIVATE_KEY");
+    for (key, value) in envs {
+        command.env(key, value);
+    }
+    command.output().expect("바이너리 실행 실패")
+}
+
+#[test]
+fn end_to_end_against_test_validator() {
+    let validator = match TestValidator::start() {
+        Some(validator) => validator,
+        None => return,
+    };
+    let client = validator.client();
+
+    sol_transfer(&client);
+    priority_fee(&client);
+    batch_sends(&client);
+    token_transfer(&client);
+    stake_lifecycle(&client);
+    rpc_benchmark_binary(&validator);
+    tx_speed_test_binary(&validator);
+    ping_test_binary(&validator);
+}
+
+// SOL 전송 후 양쪽 잔액 확인
+fn sol_transfer(client: &RpcClient) {
+    let sender = funded_keypair(client, 2);
+    let recipient = Pubkey::new_unique();
+
+    transfer::blocking::send_sol(client, &sender, &recipient, LAMPORTS_PER_SOL / 2, 0).expect("SOL 전송 실패");
+
+    assert_eq!(client.get_balance(&recipient).unwrap(), LAMPORTS_PER_SOL / 2);
+    assert_eq!(
+        client.get_balance(&sender.pubkey()).unwrap(),
+        2 * LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 2 - SIGNATURE_FEE
+    );
+}
+
+// 우선순위 수수료가 붙으면 기본 수수료보다 많이 차감됨
+fn priority_fee(client: &RpcClient) {
+    let sender = funded_keypair(client, 1);
+    let recipient = Pubkey::new_unique();
+    let amount = LAMPORTS_PER_SOL / 10;
+
+    let signature = transfer::blocking::send_sol(client, &sender, &recipient, amount, 1_000_000).expect("우선순위 수수료 전송 실패");
+
+    let charged = LAMPORTS_PER_SOL - amount - client.get_balance(&sender.pubkey()).unwrap();
+    assert!(charged > SIGNATURE_FEE, "우선순위 수수료가 반영되지 않음: {} lamports", charged);
+    assert_eq!(client.get_balance(&recipient).unwrap(), amount);
+
+    let transaction = solana_transfer_bot::history::fetch_transaction(client, &signature).expect("트랜잭션 조회 실패");
+    let fee = transaction.transaction.meta.expect("트랜잭션 메타 없음").fee;
+    assert_eq!(fee, charged);
+}
+
+// 배치 지급으로 여러 수신자에게 동시에 전송
+fn batch_sends(client: &RpcClient) {
+    let sender = funded_keypair(client, 1);
+    let amount = LAMPORTS_PER_SOL / 100;
+    let payouts: Vec<Payout> = (0..5)
+        .map(|i| Payout {
+            key: format!("p{}", i),
+            recipient: Pubkey::new_unique(),
+            lamports: amount,
+        })
+        .collect();
+    let journal_path = env::temp_dir().join(format!("solana_transfer_bot-batch-{}.jsonl", std::process::id()));
+    let _ = fs::remove_file(&journal_path);
+    let journal = Arc::new(Journal::open(&journal_path).unwrap());
+
+    let (_interrupt_tx, interrupt_rx) = watch::channel(Interrupt::Running);
+    let summary = runtime::block_on(batch::run_payouts(
+        Arc::new(rpc::async_client(&client.url())),
+        Arc::new(sender.insecure_clone()),
+        payouts.clone(),
+        journal,
+        &BatchOptions {
+            concurrency: 3,
+            fee: 0,
+            ..BatchOptions::default()
+        },
+        interrupt_rx,
+    ));
+    let _ = fs::remove_file(&journal_path);
+
+    assert_eq!(summary.confirmed, payouts.len(), "{:?}", summary);
+    for payout in &payouts {
+        assert_eq!(client.get_balance(&payout.recipient).unwrap(), amount);
+    }
+    let spent = payouts.len() as u64 * (amount + SIGNATURE_FEE);
+    assert_eq!(client.get_balance(&sender.pubkey()).unwrap(), LAMPORTS_PER_SOL - spent);
+}
+
+// 새 민트를 만들어 발행한 뒤 수신자 연관 토큰 계정으로 전송
+fn token_transfer(client: &RpcClient) {
+    let sender = funded_keypair(client, 1);
+    let recipient = Pubkey::new_unique();
+    let mint = Keypair::new();
+    let source = get_associated_token_address(&sender.pubkey(), &mint.pubkey());
+
+    let rent = client
+        .get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)
+        .unwrap();
+    let instructions = vec![
+        system_instruction::create_account(
+            &sender.pubkey(),
+            &mint.pubkey(),
+            rent,
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint.pubkey(), &sender.pubkey(), None, 6).unwrap(),
+        create_associated_token_account_idempotent(&sender.pubkey(), &sender.pubkey(), &mint.pubkey(), &spl_token::id()),
+        spl_token::instruction::mint_to(&spl_token::id(), &mint.pubkey(), &source, &sender.pubkey(), &[], 10_000_000).unwrap(),
+    ];
+    transfer::blocking::send_instructions(client, &sender, &[&mint], instructions, 0).expect("민트 생성 실패");
+
+    transfer::blocking::send_token(client, &sender, &mint.pubkey(), &recipient, 1.5, 0).expect("토큰 전송 실패");
+
+    let destination = get_associated_token_address(&recipient, &mint.pubkey());
+    assert_eq!(client.get_token_account_balance(&destination).unwrap().amount, "1500000");
+    assert_eq!(client.get_token_account_balance(&source).unwrap().amount, "8500000");
+}
+
+// 스테이크 계정 생성, 분할, 분할한 계정 전액 출금, 검증인에게 위임, 위임 해제
+fn stake_lifecycle(client: &RpcClient) {
+    let sender = funded_keypair(client, 5);
+    let state = |pubkey: &Pubkey| -> StakeStateV2 { client.get_account(pubkey).unwrap().deserialize_data().unwrap() };
+
+    let (stake_pubkey, _) = stake::create(client, &sender, 2 * LAMPORTS_PER_SOL, 0).expect("스테이크 계정 생성 실패");
+    assert_eq!(client.get_balance(&stake_pubkey).unwrap(), 2 * LAMPORTS_PER_SOL);
+    assert!(matches!(state(&stake_pubkey), StakeStateV2::Initialized(meta) if meta.authorized.withdrawer == sender.pubkey()));
+
+    let (split_pubkey, _) = stake::split(client, &sender, &stake_pubkey, LAMPORTS_PER_SOL / 2, 0).expect("분할 실패");
+    assert_eq!(client.get_balance(&stake_pubkey).unwrap(), 3 * LAMPORTS_PER_SOL / 2);
+    let (withdrawn, _) = stake::withdraw(client, &sender, &split_pubkey, None, &sender.pubkey(), 0).expect("출금 실패");
+    assert!(withdrawn > LAMPORTS_PER_SOL / 2);
+    assert_eq!(client.get_balance(&split_pubkey).unwrap(), 0);
+
+    let vote = client.get_vote_accounts().unwrap().current[0].vote_pubkey.parse().unwrap();
+    let delegate = solana_sdk::stake::instruction::delegate_stake(&stake_pubkey, &sender.pubkey(), &vote);
+    transfer::blocking::send_instructions(client, &sender, &[], vec![delegate], 0).expect("위임 실패");
+    let delegation = match state(&stake_pubkey) {
+        StakeStateV2::Stake(_, stake, _) => stake.delegation,
+        other => panic!("위임되지 않음: {:?}", other),
+    };
+    assert_eq!((delegation.voter_pubkey, delegation.deactivation_epoch), (vote, u64::MAX));
+
+    let deactivate = solana_sdk::stake::instruction::deactivate_stake(&stake_pubkey, &sender.pubkey());
+    transfer::blocking::send_instructions(client, &sender, &[], vec![deactivate], 0).expect("위임 해제 실패");
+    match state(&stake_pubkey) {
+        StakeStateV2::Stake(_, stake, _) => assert_ne!(stake.delegation.deactivation_epoch, u64::MAX),
+        other => panic!("스테이크 상태가 아님: {:?}", other),
+    }
+}
+
+// rpc_benchmark 비교 표에 검증인 엔드포인트의 메서드별 측정값이 나와야 함
+fn rpc_benchmark_binary(validator: &TestValidator) {
+    let output = run_binary(env!("CARGO_BIN_EXE_rpc_benchmark"), validator, &[], &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{}", stdout);
+    // 열 번호 목록 ("  #2: http://127.0.0.1:...")
+    let column: usize = stdout
+        .lines()
+        .find_map(|line| line.trim().strip_suffix(validator.url.as_str())?.strip_prefix('#')?.strip_suffix(": ")?.parse().ok())
+        .unwrap_or_else(|| panic!("비교 표에 검증인 열이 없습니다:\n{}", stdout));
+    let rows: Vec<&str> = stdout.lines().filter(|line| line.starts_with("| get")).collect();
+    assert_eq!(rows.len(), 4, "{}", stdout);
+    for row in rows {
+        let cell = row.split('|').nth(column + 1).unwrap();
+        assert!(!cell.contains("실패") && !cell.contains("오류"), "{}", row);
+    }
+    assert!(stdout.contains("🏆 getSlot"), "{}", stdout);
+}
+
+// tx_speed_test가 검증인에서 트랜잭션 처리 시간을 보고해야 함
+fn tx_speed_test_binary(validator: &TestValidator) {
+    let client = validator.client();
+    let sender = funded_keypair(&client, 1);
+    let before = client.get_balance(&sender.pubkey()).unwrap();
+
+    let private_key = bs58::encode(sender.to_bytes()).into_string();
+    let output = run_binary(
+        env!("CARGO_BIN_EXE_tx_speed_test"),
+        validator,
+        &[],
+        &[("SOLANA_PRIVATE_KEY", private_key)],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{}", stdout);
+    let row = stdout
+        .lines()
+        .find(|line| line.starts_with('|') && line.contains(&validator.url))
+        .unwrap_or_else(|| panic!("결과 표에 검증인 행이 없습니다:\n{}", stdout));
+    assert!(!row.contains("테스트 실패"), "{}", row);
+
+    // 자기 자신에게 보내므로 수수료만 차감됨
+    let spent = before - client.get_balance(&sender.pubkey()).unwrap();
+    assert!(spent > 0 && spent < LAMPORTS_PER_SOL / 100, "{} lamports", spent);
+}
+
+// ping_test가 검증인 엔드포인트만 측정하고 모든 측정이 성공해야 함
+fn ping_test_binary(validator: &TestValidator) {
+    let output = run_binary(
+        env!("CARGO_BIN_EXE_ping_test"),
+        validator,
+        &["--output", "json", "--count", "3", "--warmup", "0", "--interval", "50", "--ipv4"],
+        &[],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+
+    let report: Report<LatencyRecord> = serde_json::from_str(&stdout).unwrap_or_else(|e| panic!("{}: {}", e, stdout));
+    assert!(report.records.iter().all(|record| record.endpoint == validator.url), "{}", stdout);
+    let total = report
+        .records
+        .iter()
+        .find(|record| record.operation == "total")
+        .unwrap_or_else(|| panic!("total 레코드가 없습니다:\n{}", stdout));
+    assert_eq!((total.samples, total.errors), (3, 0), "{:?}", total);
+}
@@ -0,0 +1,160 @@
+// 병렬 전송 처리량 테스트의 옵션, 테스트 계정 생성/충전, 목표 속도 전송과 초당 수락/포함 수, 드롭률 집계 검증 (가짜 RPC 백엔드 사용)
+use solana_sdk::signature::{Keypair, Signer};
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::failure::FailureKind;
+use solana_transfer_bot::fake_rpc::{self, FakeRpc, Method};
+use solana_transfer_bot::landing::TestRun;
+use solana_transfer_bot::output::{OutputFormat, ThroughputRecord};
+use solana_transfer_bot::throughput::{self, ThroughputOptions};
+use solana_transfer_bot::transfer::ConfirmOptions;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn track(timeout: Duration) -> ConfirmOptions {
+    ConfirmOptions {
+        poll_interval: Duration::from_millis(10),
+        timeout,
+        ..ConfirmOptions::default()
+    }
+}
+
+#[test]
+fn parses_throughput_options() {
+    let args = |list: &[&str]| Args::parse(list.iter().map(|s| s.to_string()));
+
+    assert_eq!(ThroughputOptions::from_args(&args(&[])).unwrap(), ThroughputOptions::default());
+    let options = ThroughputOptions::from_args(&args(&["--accounts", "3", "--count", "10", "--rate", "2.5", "--fee", "0"])).unwrap();
+    assert_eq!(
+        options,
+        ThroughputOptions {
+            accounts: 3,
+            count: 10,
+            rate: 2.5,
+            fee: 0,
+        }
+    );
+    // 10개를 3개 계정에 나누면 가장 많이 보내는 계정이 4개
+    assert_eq!(options.per_account(), 4);
+    assert_eq!(throughput::required_balance(4, 0), throughput::RENT_EXEMPT_MINIMUM + 4 * 5_000);
+    // 블록해시 하나로는 목표 속도로 30초 동안 보낼 만큼만 서명
+    assert_eq!(options.per_blockhash(), 75);
+    assert_eq!(ThroughputOptions { rate: 0.01, ..options }.per_blockhash(), 1);
+
+    for bad in [&["--rate", "0"][..], &["--rate", "fast"], &["--count", "-1"]] {
+        assert_eq!(ThroughputOptions::from_args(&args(bad)).unwrap_err().kind(), "input", "{:?}", bad);
+    }
+}
+
+#[tokio::test]
+async fn test_accounts_are_derived_from_the_wallet_and_topped_up() {
+    let payer = Keypair::new();
+    let accounts = throughput::test_accounts(&payer, 3);
+    let again = throughput::test_accounts(&payer, 3);
+    let other = throughput::test_accounts(&Keypair::new(), 3);
+
+    // 같은 지갑이면 같은 계정, 계정끼리와 다른 지갑과는 겹치지 않음
+    let pubkeys: Vec<_> = accounts.iter().map(|account| account.pubkey()).collect();
+    assert_eq!(pubkeys, again.iter().map(|account| account.pubkey()).collect::<Vec<_>>());
+    assert_eq!(pubkeys.iter().collect::<HashSet<_>>().len(), 3);
+    assert!(other.iter().all(|account| !pubkeys.contains(&account.pubkey())));
+
+    let minimum = throughput::required_balance(4, 5);
+    let fake = FakeRpc::new().with_balance(&payer.pubkey(), 1_000_000_000).with_balance(&pubkeys[1], minimum - 100);
+    let funded = throughput::fund_accounts(&fake, &payer, &accounts, minimum, 5).await.unwrap();
+
+    // 부족한 만큼만 한 트랜잭션으로 채움
+    assert_eq!(funded, 2 * minimum + 100);
+    assert_eq!(fake.landed_count(), 1);
+    assert!(pubkeys.iter().all(|pubkey| fake.balance(pubkey) == minimum));
+    assert_eq!(throughput::fund_accounts(&fake, &payer, &accounts, minimum, 5).await.unwrap(), 0);
+
+    // 남은 잔액은 지갑이 수수료를 내며 모두 돌려받음
+    let before = fake.balance(&payer.pubkey());
+    assert_eq!(throughput::reclaim_accounts(&fake, &payer, &accounts, 0).await.unwrap(), 3 * minimum);
+    assert!(pubkeys.iter().all(|pubkey| fake.balance(pubkey) == 0));
+    assert_eq!(fake.balance(&payer.pubkey()), before + 3 * minimum - 4 * fake_rpc::LAMPORTS_PER_SIGNATURE);
+    assert_eq!(throughput::reclaim_accounts(&fake, &payer, &accounts, 0).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn sends_at_the_target_rate_and_reports_drops() {
+    let payer = Keypair::new();
+    let fake = Arc::new(FakeRpc::new().with_balance(&payer.pubkey(), 1_000_000_000));
+    let options = ThroughputOptions {
+        accounts: 3,
+        count: 10,
+        rate: 100.0,
+        fee: 5,
+    };
+    let accounts = throughput::test_accounts(&payer, options.accounts);
+    let minimum = throughput::required_balance(options.per_account(), options.fee);
+    throughput::fund_accounts(fake.as_ref(), &payer, &accounts, minimum, options.fee).await.unwrap();
+    // 첫 전송은 요청 한도 초과, 그다음 두 개는 받아들여졌지만 드롭
+    fake.fail_next(Method::SendTransaction, fake_rpc::rpc_error(429, "Too many requests"));
+    fake.drop_next_sends(2);
+    let run = TestRun::with_id("throughput");
+
+    let report = throughput::run_throughput(
+        fake.clone(),
+        &accounts,
+        &run,
+        1_000,
+        &options,
+        &track(Duration::from_millis(200)),
+        OutputFormat::Json,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!((report.sent, report.accepted, report.landed), (10, 9, 7));
+    assert_eq!(report.drop_rate(), 2.0 / 9.0);
+    assert_eq!(report.failures.count(FailureKind::RateLimited), 1);
+    assert_eq!(report.failures.count(FailureKind::Timeout), 2);
+    assert_eq!(report.send_latency.len(), 9);
+    // 10ms 간격으로 보냄
+    assert!(report.submit_time >= Duration::from_millis(90), "{:?}", report.submit_time);
+    assert!(report.send_rate() <= 112.0, "{}", report.send_rate());
+    assert!(report.landed_per_second() > 0.0);
+
+    // 블록해시는 충전 트랜잭션과 측정 묶음에서 한 번씩만 받고, 각 계정이 자기 자신에게 보냄
+    assert_eq!(fake.call_count(Method::GetLatestBlockhash), 2);
+    let sent = fake.sent_transactions();
+    let payers: HashSet<_> = sent[1..].iter().map(|transaction| transaction.message.account_keys[0]).collect();
+    assert_eq!(payers, accounts.iter().map(|account| account.pubkey()).collect());
+
+    let record = ThroughputRecord::new("a", &report);
+    assert_eq!((record.accounts, record.target_rate, record.drop_rate), (3, 100.0, 0.2222));
+    assert_eq!((record.failures.get("rate_limited"), record.run_id.as_str()), (Some(&1), "throughput"));
+}
+
+#[tokio::test]
+async fn stops_waiting_when_the_blockhash_expires() {
+    let payer = Keypair::new();
+    let fake = Arc::new(FakeRpc::new().with_balance(&payer.pubkey(), 1_000_000_000));
+    let options = ThroughputOptions {
+        accounts: 2,
+        count: 4,
+        rate: 1_000.0,
+        fee: 0,
+    };
+    let accounts = throughput::test_accounts(&payer, options.accounts);
+    throughput::fund_accounts(fake.as_ref(), &payer, &accounts, throughput::required_balance(2, 0), 0).await.unwrap();
+    fake.drop_next_sends(4);
+    let run = TestRun::with_id("expired");
+    let track = track(Duration::from_secs(5));
+
+    let started = Instant::now();
+    let (report, _) = tokio::join!(
+        throughput::run_throughput(fake.clone(), &accounts, &run, 1_000, &options, &track, OutputFormat::Json),
+        async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            fake.expire_blockhashes();
+        }
+    );
+    let report = report.unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(1), "{:?}", started.elapsed());
+    assert_eq!((report.accepted, report.landed, report.drop_rate()), (4, 0, 1.0));
+    assert_eq!(report.landed_per_second(), 0.0);
+}
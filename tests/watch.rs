@@ -0,0 +1,300 @@
+// 입금 감시의 토큰 계정 재조회, 재시작 후 중복 전달 방지, 웹훅 거부 시 재전달 검증 (로컬 JSON-RPC 스텁과 웹훅 스텁 사용)
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use solana_transaction_status::{Encodable, UiTransactionEncoding};
+use solana_transfer_bot::cli::Args;
+use solana_transfer_bot::watch;
+use spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Response, Server};
+
+// 스텁 RPC 노드의 상태
+#[derive(Default)]
+struct Chain {
+    // 주소별 서명 목록 (최신순, (서명, 슬롯))
+    signatures: HashMap<String, Vec<(String, u64)>>,
+    // 서명별 getTransaction 응답
+    transactions: HashMap<String, Value>,
+    token_accounts: Vec<String>,
+    // getTokenAccountsByOwner가 이 횟수만큼은 빈 목록을 돌려줌
+    hide_token_accounts: usize,
+}
+
+impl Chain {
+    // 트랜잭션을 기록하고 언급된 주소들의 서명 목록 맨 앞에 추가
+    fn land(&mut self, slot: u64, transaction: &Transaction, meta: Value) {
+        let signature = transaction.signatures[0].to_string();
+        for key in &transaction.message.account_keys {
+            self.signatures.entry(key.to_string()).or_default().insert(0, (signature.clone(), slot));
+        }
+        let encoded = transaction.encode(UiTransactionEncoding::Base64);
+        self.transactions.insert(
+            signature,
+            json!({ "slot": slot, "blockTime": null, "transaction": encoded, "meta": meta }),
+        );
+    }
+
+    fn handle(&mut self, method: &str, params: &Value) -> Value {
+        match method {
+            "getTokenAccountsByOwner" => {
+                let accounts: Vec<Value> = match self.hide_token_accounts {
+                    0 => self.token_accounts.iter().map(|pubkey| token_account(pubkey)).collect(),
+                    _ => {
+                        self.hide_token_accounts -= 1;
+                        vec![]
+                    }
+                };
+                json!({ "context": { "slot": 10 }, "value": accounts })
+            }
+            "getSignaturesForAddress" => {
+                let config = &params[1];
+                let list = self.signatures.get(params[0].as_str().unwrap()).cloned().unwrap_or_default();
+                let start = match config["before"].as_str() {
+                    Some(before) => list.iter().position(|(s, _)| s == before).map_or(list.len(), |i| i + 1),
+                    None => 0,
+                };
+                let limit = config["limit"].as_u64().unwrap_or(1000) as usize;
+                let page: Vec<Value> = list[start..]
+                    .iter()
+                    .take_while(|(s, _)| Some(s.as_str()) != config["until"].as_str())
+                    .take(limit)
+                    .map(|(signature, slot)| {
+                        json!({ "signature": signature, "slot": slot, "err": null, "memo": null, "blockTime": null, "confirmationStatus": "confirmed" })
+                    })
+                    .collect();
+                json!(page)
+            }
+            "getTransaction" => self.transactions[params[0].as_str().unwrap()].clone(),
+            // 클라이언트가 요청 형식을 고르기 위해 조회
+            "getVersion" => json!({ "solana-core": "1.18.26", "feature-set": 0 }),
+            other => panic!("예상하지 못한 RPC 메서드: {}", other),
+        }
+    }
+}
+
+fn token_account(pubkey: &str) -> Value {
+    json!({
+        "pubkey": pubkey,
+        "account": { "lamports": 2_039_280, "data": ["", "base64"], "owner": spl_token::id().to_string(), "executable": false, "rentEpoch": 0, "space": 165 }
+    })
+}
+
+// 지갑이 소유한 토큰 계정의 잔액 전후 값 (raw 단위)
+fn token_meta(transaction: &Transaction, account: &Pubkey, owner: &Pubkey, mint: &Pubkey, pre: u64, post: u64) -> Value {
+    let index = transaction.message.account_keys.iter().position(|key| key == account).unwrap();
+    let keys = transaction.message.account_keys.len();
+    let balance = |amount: u64| {
+        json!([{
+            "accountIndex": index,
+            "mint": mint.to_string(),
+            "owner": owner.to_string(),
+            "programId": spl_token::id().to_string(),
+            "uiTokenAmount": { "amount": amount.to_string(), "decimals": 6, "uiAmount": null, "uiAmountString": amount.to_string() }
+        }])
+    };
+    json!({
+        "err": null, "status": { "Ok": null }, "fee": 5000,
+        "preBalances": vec![1_000_000_000u64; keys], "postBalances": vec![1_000_000_000u64; keys],
+        "preTokenBalances": balance(pre), "postTokenBalances": balance(post)
+    })
+}
+
+// JSON-RPC 스텁 서버 (URL 반환)
+fn rpc_stub(chain: Arc<Mutex<Chain>>) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let request_json: Value = serde_json::from_str(&body).unwrap();
+            let result = chain.lock().unwrap().handle(request_json["method"].as_str().unwrap(), &request_json["params"]);
+            let reply = json!({ "jsonrpc": "2.0", "result": result, "id": request_json["id"] });
+            let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+            let _ = request.respond(Response::from_string(reply.to_string()).with_header(header));
+        }
+    });
+    url
+}
+
+// 받은 웹훅의 (X-Webhook-Id, 본문)
+type Deliveries = Arc<Mutex<Vec<(String, Value)>>>;
+
+// 받은 웹훅을 모으는 서버 (`status`로 응답 상태 코드를 바꿀 수 있음, 거부한 요청은 모으지 않음)
+fn webhook_stub(status: Arc<AtomicU16>) -> (String, Deliveries) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hooks", server.server_addr().to_ip().unwrap());
+    let received: Deliveries = Arc::default();
+    let sink = received.clone();
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let id = request
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("X-Webhook-Id"))
+                .map(|h| h.value.to_string())
+                .unwrap_or_default();
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let status = status.load(Ordering::SeqCst);
+            if status == 200 {
+                sink.lock().unwrap().push((id, serde_json::from_str(&body).unwrap()));
+            }
+            let _ = request.respond(Response::empty(status));
+        }
+    });
+    (url, received)
+}
+
+#[test]
+fn relists_token_accounts_and_delivers_each_deposit_once_across_restarts() {
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let token_account = get_associated_token_address(&wallet, &mint);
+    let sender = Keypair::new();
+    let source = Pubkey::new_unique();
+    let signed = |instructions: &[_]| Transaction::new_signed_with_payer(instructions, Some(&sender.pubkey()), &[&sender], Hash::new_unique());
+    let token_transfer = |amount| spl_token::instruction::transfer(&spl_token::id(), &source, &token_account, &sender.pubkey(), &[], amount).unwrap();
+
+    let chain: Arc<Mutex<Chain>> = Arc::default();
+    let (old_wallet_tx, old_token_tx, sol_deposit, ata_deposit, token_deposit);
+    {
+        let mut chain = chain.lock().unwrap();
+        chain.token_accounts = vec![token_account.to_string()];
+        // 첫 실행의 시작 시 조회와 두 번째 실행의 시작 시 조회에서는 토큰 계정이 보이지 않음
+        chain.hide_token_accounts = 2;
+
+        // 이전 실행에서 이미 처리한 트랜잭션
+        old_wallet_tx = signed(&[system_instruction::transfer(&sender.pubkey(), &wallet, 1)]);
+        chain.land(1, &old_wallet_tx, json!({ "err": null, "status": { "Ok": null }, "fee": 5000, "preBalances": [10, 0, 1], "postBalances": [9, 1, 1] }));
+        old_token_tx = signed(&[token_transfer(1)]);
+        chain.land(2, &old_token_tx, token_meta(&old_token_tx, &token_account, &wallet, &mint, 0, 1));
+
+        // SOL 입금 (지갑만 언급)
+        sol_deposit = signed(&[system_instruction::transfer(&sender.pubkey(), &wallet, 1_000_000)]);
+        chain.land(
+            3,
+            &sol_deposit,
+            json!({ "err": null, "status": { "Ok": null }, "fee": 5000, "preBalances": [1_000_000_000, 0, 1], "postBalances": [998_995_000, 1_000_000, 1] }),
+        );
+        // 토큰 계정을 만들며 입금 (지갑과 토큰 계정 모두 언급)
+        ata_deposit = signed(&[create_associated_token_account(&sender.pubkey(), &wallet, &mint, &spl_token::id()), token_transfer(5)]);
+        chain.land(4, &ata_deposit, token_meta(&ata_deposit, &token_account, &wallet, &mint, 1, 6));
+        // 기존 토큰 계정으로 입금 (토큰 계정만 언급)
+        token_deposit = signed(&[token_transfer(7)]);
+        chain.land(5, &token_deposit, token_meta(&token_deposit, &token_account, &wallet, &mint, 6, 13));
+    }
+
+    let rpc_client = RpcClient::new(rpc_stub(chain.clone()));
+    let (webhook_url, received) = webhook_stub(Arc::new(AtomicU16::new(200)));
+    let state_path = env::temp_dir().join(format!("watch_state_{}.json", wallet));
+    let state_path = state_path.to_str().unwrap().to_string();
+    // 두 계정 모두 이전에 감시하던 상태 (토큰 계정은 지난 실행에서 확인까지 마침)
+    fs::write(
+        &state_path,
+        json!({ "last_signatures": {
+            wallet.to_string(): old_wallet_tx.signatures[0].to_string(),
+            token_account.to_string(): old_token_tx.signatures[0].to_string(),
+        } })
+        .to_string(),
+    )
+    .unwrap();
+
+    let run = |extra: &[&str]| {
+        let wallet = wallet.to_string();
+        let mut list = vec!["watch", "--address", &wallet, "--webhook", &webhook_url, "--state", &state_path, "--no-ws", "--once"];
+        list.extend_from_slice(extra);
+        watch::run(&rpc_client, "ws://127.0.0.1:1", &Args::parse(list.iter().map(|s| s.to_string()))).unwrap();
+    };
+
+    // 첫 실행: 토큰 계정 목록이 비어 지갑만 확인 (SOL 입금과 토큰 계정 생성 입금 전달)
+    run(&[]);
+    let ids: Vec<String> = received.lock().unwrap().iter().map(|(id, _)| id.clone()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            format!("{}:SOL", sol_deposit.signatures[0]),
+            format!("{}:{}", ata_deposit.signatures[0], mint),
+        ]
+    );
+    let state: Value = serde_json::from_str(&fs::read_to_string(&state_path).unwrap()).unwrap();
+    assert_eq!(state["processed"].as_array().unwrap().len(), 2);
+
+    // 두 번째 실행: 재조회로 토큰 계정을 찾고, 이미 전달한 입금은 상태 파일의 처리 목록으로 건너뜀
+    run(&["--refresh-interval", "0"]);
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 3);
+    assert_eq!(received[2].0, format!("{}:{}", token_deposit.signatures[0], mint));
+    assert_eq!(received[2].1["change"], "0.000007");
+    assert_eq!(received[2].1["address"], wallet.to_string());
+
+    let state: Value = serde_json::from_str(&fs::read_to_string(&state_path).unwrap()).unwrap();
+    assert_eq!(state["last_signatures"][token_account.to_string()], token_deposit.signatures[0].to_string());
+    fs::remove_file(&state_path).unwrap();
+}
+
+#[test]
+fn rejected_webhook_keeps_the_deposit_for_the_next_cycle() {
+    let wallet = Pubkey::new_unique();
+    let sender = Keypair::new();
+    let chain: Arc<Mutex<Chain>> = Arc::default();
+    let deposit = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(&sender.pubkey(), &wallet, 1_000_000)],
+        Some(&sender.pubkey()),
+        &[&sender],
+        Hash::new_unique(),
+    );
+    let old = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(&sender.pubkey(), &wallet, 1)],
+        Some(&sender.pubkey()),
+        &[&sender],
+        Hash::new_unique(),
+    );
+    {
+        let mut chain = chain.lock().unwrap();
+        chain.land(1, &old, json!({ "err": null, "status": { "Ok": null }, "fee": 5000, "preBalances": [10, 0, 1], "postBalances": [4, 1, 1] }));
+        chain.land(
+            2,
+            &deposit,
+            json!({ "err": null, "status": { "Ok": null }, "fee": 5000, "preBalances": [1_000_000_000, 0, 1], "postBalances": [998_995_000, 1_000_000, 1] }),
+        );
+    }
+
+    let rpc_client = RpcClient::new(rpc_stub(chain));
+    let status = Arc::new(AtomicU16::new(401));
+    let (webhook_url, received) = webhook_stub(status.clone());
+    let state_path = env::temp_dir().join(format!("watch_state_{}.json", wallet));
+    let state_path = state_path.to_str().unwrap().to_string();
+    fs::write(&state_path, json!({ "last_signatures": { wallet.to_string(): old.signatures[0].to_string() } }).to_string()).unwrap();
+    let run = || {
+        let wallet = wallet.to_string();
+        let list = ["watch", "--address", &wallet, "--webhook", &webhook_url, "--state", &state_path, "--no-ws", "--once"];
+        watch::run(&rpc_client, "ws://127.0.0.1:1", &Args::parse(list.iter().map(|s| s.to_string()))).unwrap();
+    };
+
+    // 비밀키나 URL 설정 오류로 거부되면 전달한 것으로 보지 않음
+    run();
+    assert!(received.lock().unwrap().is_empty());
+    let state: Value = serde_json::from_str(&fs::read_to_string(&state_path).unwrap()).unwrap();
+    assert_eq!(state["last_signatures"][wallet.to_string()], old.signatures[0].to_string());
+
+    // 설정을 고친 뒤 다음 주기에 전달
+    status.store(200, Ordering::SeqCst);
+    run();
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].0, format!("{}:SOL", deposit.signatures[0]));
+    fs::remove_file(&state_path).unwrap();
+}